@@ -0,0 +1,664 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+
+//! analyzes Structured Text documents for editors, the analysis answers the questions of the
+//! language server (`rusty-lsp`) with locations and byte-offsets of the analyzed documents
+
+use crate::{
+    ast::{
+        self, get_children, AstStatement, CompilationUnit, DataType, DataTypeDeclaration, PouType,
+        SourceRange, Variable,
+    },
+    builtins,
+    index::{self, Index},
+    lexer::{self, IdProvider},
+    parser,
+    resolver::{self, AnnotationMap, StatementAnnotation, TypeAnnotator},
+    stdlib,
+    typesystem::DataTypeInformation,
+    validation::{Linter, Validator},
+    Diagnostic, Severity, SourceCode,
+};
+
+/// a range in one of the analyzed documents
+#[derive(Debug, PartialEq, Clone)]
+pub struct DocumentRange {
+    /// the position of the document in the analyzed documents
+    pub document: usize,
+    pub range: SourceRange,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SymbolKind {
+    Program,
+    Function,
+    FunctionBlock,
+    Class,
+    Method,
+    Action,
+    Interface,
+    Type,
+    Variable,
+}
+
+/// a POU, type or global variable declared in a document
+#[derive(Debug, PartialEq, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub range: SourceRange,
+}
+
+/// a member offered after typing `.`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Completion {
+    pub name: String,
+    /// the member's type, or the return type of a method
+    pub type_name: String,
+    pub kind: SymbolKind,
+}
+
+/// the parsed, indexed, annotated and validated documents of a workspace
+pub struct Analysis {
+    index: Index,
+    sources: Vec<String>,
+    units: Vec<(CompilationUnit, AnnotationMap)>,
+    diagnostics: Vec<Vec<Diagnostic>>,
+}
+
+impl Analysis {
+    /// analyzes the given documents together, so they can reference each other's declarations
+    pub fn new(documents: &[SourceCode]) -> Analysis {
+        let id_provider = IdProvider::new();
+        let mut index = Index::new();
+        index.import(index::visitor::visit(&builtins::parse_built_ins(
+            id_provider.clone(),
+        )));
+        index.import(index::visitor::visit(&stdlib::parse_standard_library(
+            id_provider.clone(),
+        )));
+
+        let mut parsed_units = Vec::new();
+        for document in documents {
+            let (mut unit, syntax_errors) = parser::parse(lexer::lex_with_ids(
+                document.source.as_str(),
+                id_provider.clone(),
+            ));
+            ast::pre_process(&mut unit);
+            index.import(index::visitor::visit(&unit));
+            parsed_units.push((unit, syntax_errors));
+        }
+        let (index, _unresolvables) = resolver::const_evaluator::evaluate_constants(index);
+
+        let mut units = Vec::new();
+        let mut diagnostics = Vec::new();
+        for ((unit, mut document_diagnostics), document) in parsed_units.into_iter().zip(documents)
+        {
+            let annotations = TypeAnnotator::visit_unit(&index, &unit);
+            let mut validator = Validator::new();
+            validator.visit_unit(&annotations, &index, &unit);
+            document_diagnostics.append(&mut validator.diagnostics());
+            let mut linter = Linter::new();
+            linter.visit_unit(&annotations, &index, &unit);
+            document_diagnostics.extend(
+                linter
+                    .diagnostics
+                    .into_iter()
+                    .filter(|it| !is_suppressed(it, &unit, &document.source)),
+            );
+
+            diagnostics.push(document_diagnostics);
+            units.push((unit, annotations));
+        }
+
+        Analysis {
+            index,
+            sources: documents.iter().map(|it| it.source.clone()).collect(),
+            units,
+            diagnostics,
+        }
+    }
+
+    /// returns the syntax errors, validation errors and lints of the given document
+    pub fn get_diagnostics(&self, document: usize) -> &[Diagnostic] {
+        self.diagnostics
+            .get(document)
+            .map(|it| it.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// returns the declaration of the variable, POU or type referenced at the given offset
+    pub fn find_definition(&self, document: usize, offset: usize) -> Option<DocumentRange> {
+        let (unit, annotations) = self.units.get(document)?;
+        if let Some(type_name) = find_type_reference_at(unit, offset) {
+            return self.find_type_declaration(type_name);
+        }
+
+        match annotations.get(find_statement_at(unit, offset)?)? {
+            StatementAnnotation::Variable { qualified_name, .. } => {
+                self.find_variable_declaration(qualified_name)
+            }
+            StatementAnnotation::Function { qualified_name, .. }
+            | StatementAnnotation::Program { qualified_name } => {
+                self.find_pou_declaration(qualified_name)
+            }
+            StatementAnnotation::Type { type_name } => self.find_type_declaration(type_name),
+            StatementAnnotation::Value { .. } => None,
+        }
+    }
+
+    /// describes the expression at the given offset with its type
+    pub fn get_hover(&self, document: usize, offset: usize) -> Option<String> {
+        let (unit, annotations) = self.units.get(document)?;
+        let statement = find_statement_at(unit, offset)?;
+        let hover = match annotations.get(statement)? {
+            StatementAnnotation::Variable {
+                qualified_name,
+                resulting_type,
+                constant,
+            } => format!(
+                "{}{} : {}",
+                if *constant { "CONSTANT " } else { "" },
+                qualified_name,
+                resulting_type
+            ),
+            StatementAnnotation::Function {
+                qualified_name,
+                return_type,
+            } => format!("{} : {}", qualified_name, return_type),
+            StatementAnnotation::Program { qualified_name } => {
+                format!("PROGRAM {}", qualified_name)
+            }
+            StatementAnnotation::Type { type_name } => format!("TYPE {}", type_name),
+            StatementAnnotation::Value { resulting_type } => resulting_type.clone(),
+        };
+        Some(hover)
+    }
+
+    /// returns the members of the variable or program written in front of the `.` that
+    /// precedes the given offset (e.g. `fb.` or `prg.st.`)
+    pub fn complete_members(&self, document: usize, offset: usize) -> Vec<Completion> {
+        let qualifier = match self
+            .sources
+            .get(document)
+            .and_then(|it| get_qualifier_before(it, offset))
+        {
+            Some(qualifier) => qualifier,
+            None => return Vec::new(),
+        };
+        let segments: Vec<&str> = qualifier.split('.').collect();
+        let pou = self
+            .units
+            .get(document)
+            .and_then(|(unit, _)| {
+                unit.implementations
+                    .iter()
+                    .find(|it| contains(&it.location, offset))
+            })
+            .map(|it| it.type_name.as_str());
+
+        let container = match self.index.find_variable(pou, &segments) {
+            Some(variable) => self.get_container_name(variable.get_type_name()),
+            //a program's variables can be accessed from everywhere
+            None if segments.len() == 1 => self
+                .index
+                .find_pou(segments[0])
+                .filter(|it| it.get_pou_type() == &PouType::Program)
+                .map(|it| it.get_name()),
+            None => None,
+        };
+        let container = match container {
+            Some(container) => container,
+            None => return Vec::new(),
+        };
+
+        let variables = self
+            .index
+            .find_local_members(container)
+            .into_iter()
+            //skip the compiler's hidden members (e.g. the method table)
+            .filter(|it| !it.get_name().starts_with("__"))
+            .map(|it| Completion {
+                name: it.get_name().to_string(),
+                type_name: it.get_type_name().to_string(),
+                kind: SymbolKind::Variable,
+            });
+        let methods = self
+            .index
+            .find_declared_methods(container)
+            .into_iter()
+            .map(|it| Completion {
+                name: it
+                    .get_name()
+                    .rsplit('.')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                type_name: self
+                    .index
+                    .find_return_type(it.get_name())
+                    .map(|it| it.get_name().to_string())
+                    .unwrap_or_default(),
+                kind: SymbolKind::Method,
+            });
+        variables.chain(methods).collect()
+    }
+
+    /// returns the POUs, types and global variables declared in the given document
+    pub fn get_symbols(&self, document: usize) -> Vec<Symbol> {
+        let unit = match self.units.get(document) {
+            Some((unit, _)) => unit,
+            None => return Vec::new(),
+        };
+
+        let pous = unit.units.iter().map(|it| Symbol {
+            name: it.name.clone(),
+            kind: match it.pou_type {
+                PouType::Program => SymbolKind::Program,
+                PouType::Function => SymbolKind::Function,
+                PouType::FunctionBlock => SymbolKind::FunctionBlock,
+                PouType::Action => SymbolKind::Action,
+                PouType::Class => SymbolKind::Class,
+                PouType::Method { .. } => SymbolKind::Method,
+                PouType::Interface => SymbolKind::Interface,
+            },
+            range: it.location.clone(),
+        });
+        //inline types (e.g. `x : ARRAY[0..2] OF INT`) get generated names starting with `__`
+        let types = unit.types.iter().filter_map(|it| {
+            it.data_type
+                .get_name()
+                .filter(|name| !name.starts_with("__"))
+                .map(|name| Symbol {
+                    name: name.to_string(),
+                    kind: SymbolKind::Type,
+                    range: it.location.clone(),
+                })
+        });
+        let globals = unit
+            .global_vars
+            .iter()
+            .flat_map(|it| it.variables.iter())
+            .map(|it| Symbol {
+                name: it.name.clone(),
+                kind: SymbolKind::Variable,
+                range: it.location.clone(),
+            });
+        pous.chain(types).chain(globals).collect()
+    }
+
+    /// returns the name of the POU or struct whose members are accessed through a variable
+    /// of the given type, auto-deref pointers (e.g. VAR_IN_OUT) are dereferenced
+    fn get_container_name<'a>(&'a self, type_name: &'a str) -> Option<&'a str> {
+        match self
+            .index
+            .find_effective_type_by_name(type_name)?
+            .get_type_information()
+        {
+            DataTypeInformation::Struct { name, .. } => Some(name.as_str()),
+            DataTypeInformation::Pointer {
+                inner_type_name,
+                auto_deref: true,
+                ..
+            } => self.get_container_name(inner_type_name),
+            _ => None,
+        }
+    }
+
+    /// finds the declaration of a global (`x`) or of a member (`prg.x`) variable
+    fn find_variable_declaration(&self, qualified_name: &str) -> Option<DocumentRange> {
+        let (container, name) = match qualified_name.rsplit_once('.') {
+            Some((container, name)) => (Some(container), name),
+            None => (None, qualified_name),
+        };
+        self.find_in_units(|unit| match container {
+            Some(container) => {
+                let pou_variables = unit
+                    .units
+                    .iter()
+                    .filter(|it| it.name.eq_ignore_ascii_case(container))
+                    .flat_map(|it| it.variable_blocks.iter())
+                    .flat_map(|it| it.variables.iter());
+                let struct_members = get_struct_members(unit, Some(container));
+                pou_variables
+                    .chain(struct_members)
+                    .find(|it| it.name.eq_ignore_ascii_case(name))
+                    .map(|it| it.location.clone())
+                    //enum elements are declared by their enum type
+                    .or_else(|| find_type_location(unit, container))
+            }
+            None => unit
+                .global_vars
+                .iter()
+                .flat_map(|it| it.variables.iter())
+                .find(|it| it.name.eq_ignore_ascii_case(name))
+                .map(|it| it.location.clone()),
+        })
+    }
+
+    fn find_pou_declaration(&self, name: &str) -> Option<DocumentRange> {
+        self.find_in_units(|unit| {
+            unit.units
+                .iter()
+                .find(|it| it.name.eq_ignore_ascii_case(name))
+                .map(|it| it.location.clone())
+                .or_else(|| {
+                    unit.implementations
+                        .iter()
+                        .find(|it| it.name.eq_ignore_ascii_case(name))
+                        .map(|it| it.location.clone())
+                })
+        })
+    }
+
+    fn find_type_declaration(&self, name: &str) -> Option<DocumentRange> {
+        self.find_in_units(|unit| find_type_location(unit, name))
+            .or_else(|| self.find_pou_declaration(name))
+    }
+
+    /// returns the first range found by `find` in the analyzed documents
+    fn find_in_units<F>(&self, find: F) -> Option<DocumentRange>
+    where
+        F: Fn(&CompilationUnit) -> Option<SourceRange>,
+    {
+        self.units
+            .iter()
+            .enumerate()
+            .find_map(|(document, (unit, _))| {
+                find(unit).map(|range| DocumentRange { document, range })
+            })
+    }
+}
+
+/// converts a byte-offset into a zero-based line and a column counted in UTF-16 code units,
+/// the positions used by the language server protocol
+pub fn get_line_and_column(source: &str, offset: usize) -> (u32, u32) {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |it| it + 1);
+    let line = before.matches('\n').count();
+    let column: usize = before[line_start..].chars().map(char::len_utf16).sum();
+    (line as u32, column as u32)
+}
+
+/// converts a zero-based line and a column counted in UTF-16 code units into a byte-offset
+pub fn get_offset(source: &str, line: u32, column: u32) -> usize {
+    let line_start = if line == 0 {
+        0
+    } else {
+        match source.match_indices('\n').nth(line as usize - 1) {
+            Some((index, _)) => index + 1,
+            None => return source.len(),
+        }
+    };
+    let mut remaining = column as usize;
+    for (index, c) in source[line_start..].char_indices() {
+        if remaining == 0 || c == '\n' {
+            return line_start + index;
+        }
+        remaining = remaining.saturating_sub(c.len_utf16());
+    }
+    source.len()
+}
+
+/// returns true if an `{allow(...)}` pragma on the same or the previous line suppresses the lint
+fn is_suppressed(diagnostic: &Diagnostic, unit: &CompilationUnit, source: &str) -> bool {
+    if diagnostic.get_severity() != Severity::Warning {
+        return false;
+    }
+    let code = format!("{:?}", diagnostic.get_err_no());
+    let (line, _) = get_line_and_column(source, diagnostic.get_location().get_start());
+    unit.suppressions.iter().any(|it| {
+        let (pragma_line, _) = get_line_and_column(source, it.location.get_start());
+        (pragma_line == line || pragma_line + 1 == line)
+            && it.codes.iter().any(|it| it.eq_ignore_ascii_case(&code))
+    })
+}
+
+fn contains(range: &SourceRange, offset: usize) -> bool {
+    range.get_start() <= offset && offset <= range.get_end()
+}
+
+/// returns the qualifier written in front of the `.` that precedes the given offset
+fn get_qualifier_before(source: &str, offset: usize) -> Option<&str> {
+    let before = source.get(..offset)?.strip_suffix('.')?;
+    let start = before
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .map_or(0, |it| it + 1);
+    let qualifier = before[start..].trim_matches('.');
+    if qualifier.is_empty() {
+        None
+    } else {
+        Some(qualifier)
+    }
+}
+
+fn find_type_location(unit: &CompilationUnit, name: &str) -> Option<SourceRange> {
+    unit.types
+        .iter()
+        .find(|it| {
+            it.data_type
+                .get_name()
+                .map_or(false, |it| it.eq_ignore_ascii_case(name))
+        })
+        .map(|it| it.location.clone())
+}
+
+/// returns the members of the unit's structs, or of the struct with the given name
+fn get_struct_members<'a>(
+    unit: &'a CompilationUnit,
+    struct_name: Option<&'a str>,
+) -> impl Iterator<Item = &'a Variable> {
+    unit.types
+        .iter()
+        .filter_map(move |it| match &it.data_type {
+            DataType::StructType {
+                name: Some(name),
+                variables,
+            } if struct_name.map_or(true, |it| it.eq_ignore_ascii_case(name)) => Some(variables),
+            _ => None,
+        })
+        .flatten()
+}
+
+/// returns the name of the type referenced by a variable-declaration at the given offset
+fn find_type_reference_at(unit: &CompilationUnit, offset: usize) -> Option<&str> {
+    let blocks = unit
+        .units
+        .iter()
+        .flat_map(|it| it.variable_blocks.iter())
+        .chain(unit.global_vars.iter());
+    blocks
+        .flat_map(|it| it.variables.iter())
+        .chain(get_struct_members(unit, None))
+        .find_map(|it| match &it.data_type {
+            DataTypeDeclaration::DataTypeReference {
+                referenced_type,
+                location,
+            } if contains(location, offset) => Some(referenced_type.as_str()),
+            _ => None,
+        })
+}
+
+/// returns the innermost statement of the unit's implementations at the given offset
+fn find_statement_at(unit: &CompilationUnit, offset: usize) -> Option<&AstStatement> {
+    unit.implementations
+        .iter()
+        .filter(|it| contains(&it.location, offset))
+        .flat_map(|it| it.statements.iter())
+        .find_map(|it| find_innermost_statement(it, offset))
+}
+
+fn find_innermost_statement(statement: &AstStatement, offset: usize) -> Option<&AstStatement> {
+    if !contains(&statement.get_location(), offset) {
+        return None;
+    }
+    get_children(statement)
+        .into_iter()
+        .find_map(|it| find_innermost_statement(it, offset))
+        .or(Some(statement))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{get_line_and_column, get_offset, Analysis, Completion, Symbol, SymbolKind};
+    use crate::SourceCode;
+
+    fn analyze(sources: &[&str]) -> Analysis {
+        let documents: Vec<SourceCode> = sources
+            .iter()
+            .enumerate()
+            .map(|(i, source)| SourceCode {
+                path: format!("file{}.st", i),
+                source: source.to_string(),
+            })
+            .collect();
+        Analysis::new(&documents)
+    }
+
+    #[test]
+    fn diagnostics_are_reported_per_document() {
+        let analysis = analyze(&[
+            "VAR_GLOBAL g : INT; END_VAR",
+            "PROGRAM prg g := 1; y := 2; END_PROGRAM",
+        ]);
+
+        assert!(analysis.get_diagnostics(0).is_empty());
+        assert_eq!(
+            analysis.get_diagnostics(1),
+            &[crate::Diagnostic::unrseolved_reference(
+                "y",
+                (20..21).into()
+            )]
+        );
+    }
+
+    #[test]
+    fn definitions_are_found_in_all_documents() {
+        let globals =
+            "VAR_GLOBAL g : INT; END_VAR TYPE MyStruct : STRUCT a : INT; END_STRUCT END_TYPE";
+        let program = "PROGRAM prg VAR s : MyStruct; END_VAR g := s.a; END_PROGRAM";
+        let analysis = analyze(&[globals, program]);
+
+        //the global variable
+        let definition = analysis
+            .find_definition(1, program.find("g :=").unwrap())
+            .unwrap();
+        assert_eq!(definition.document, 0);
+        assert_eq!(definition.range.get_start(), globals.find("g :").unwrap());
+
+        //the struct member
+        let definition = analysis
+            .find_definition(1, program.find("a;").unwrap())
+            .unwrap();
+        assert_eq!(definition.document, 0);
+        assert_eq!(definition.range.get_start(), globals.find("a :").unwrap());
+
+        //the type of a declared variable
+        let definition = analysis
+            .find_definition(1, program.find("MyStruct").unwrap() + 2)
+            .unwrap();
+        let type_name = globals.find("MyStruct").unwrap();
+        assert_eq!(definition.document, 0);
+        assert!(definition.range.get_start() <= type_name);
+        assert!(type_name <= definition.range.get_end());
+
+        //nothing is declared at a literal
+        assert_eq!(analysis.find_definition(1, program.len()), None);
+    }
+
+    #[test]
+    fn hover_describes_the_type_of_the_expression() {
+        let program = "PROGRAM prg VAR x : INT; END_VAR VAR CONSTANT c : DINT := 1; END_VAR x := c; END_PROGRAM";
+        let analysis = analyze(&[program]);
+
+        assert_eq!(
+            analysis.get_hover(0, program.find("x :=").unwrap()),
+            Some("prg.x : INT".to_string())
+        );
+        assert_eq!(
+            analysis.get_hover(0, program.find("c;").unwrap()),
+            Some("CONSTANT prg.c : DINT".to_string())
+        );
+    }
+
+    #[test]
+    fn members_and_methods_are_completed_after_a_dot() {
+        let program = "
+            CLASS MyClass
+                VAR x : INT; END_VAR
+                METHOD inc : DINT END_METHOD
+            END_CLASS
+
+            PROGRAM prg
+                VAR inst : MyClass; END_VAR
+                inst.x := 1;
+            END_PROGRAM
+        ";
+        let analysis = analyze(&[program]);
+
+        assert_eq!(
+            analysis.complete_members(0, program.find("inst.x").unwrap() + 5),
+            vec![
+                Completion {
+                    name: "x".into(),
+                    type_name: "INT".into(),
+                    kind: SymbolKind::Variable,
+                },
+                Completion {
+                    name: "inc".into(),
+                    type_name: "DINT".into(),
+                    kind: SymbolKind::Method,
+                },
+            ]
+        );
+        //nothing is completed without a dot
+        assert_eq!(
+            analysis.complete_members(0, program.find("inst.x").unwrap() + 4),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn pous_types_and_globals_are_document_symbols() {
+        let source = "
+            TYPE MyStruct : STRUCT a : INT; END_STRUCT END_TYPE
+            VAR_GLOBAL g : INT; END_VAR
+            FUNCTION foo : INT VAR_INPUT arr : ARRAY[0..2] OF INT; END_VAR END_FUNCTION
+        ";
+        let analysis = analyze(&[source]);
+
+        let symbols: Vec<(String, SymbolKind)> = analysis
+            .get_symbols(0)
+            .into_iter()
+            .map(|Symbol { name, kind, .. }| (name, kind))
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![
+                ("foo".to_string(), SymbolKind::Function),
+                ("MyStruct".to_string(), SymbolKind::Type),
+                ("g".to_string(), SymbolKind::Variable),
+            ]
+        );
+    }
+
+    #[test]
+    fn offsets_are_converted_to_utf16_lines_and_columns() {
+        let source = "ab\näx := 1;\n𝄞c";
+
+        assert_eq!(get_line_and_column(source, 0), (0, 0));
+        assert_eq!(get_line_and_column(source, 3), (1, 0));
+        //ä takes two bytes but one UTF-16 code unit
+        assert_eq!(get_line_and_column(source, 5), (1, 1));
+        //𝄞 takes four bytes and two UTF-16 code units
+        assert_eq!(get_line_and_column(source, 17), (2, 2));
+
+        assert_eq!(get_offset(source, 1, 1), 5);
+        assert_eq!(get_offset(source, 2, 2), 17);
+        //columns beyond the end of the line stop at the line's end
+        assert_eq!(get_offset(source, 1, 100), 12);
+        assert_eq!(get_offset(source, 5, 0), source.len());
+    }
+}
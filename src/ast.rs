@@ -18,9 +18,15 @@ pub struct Pou {
     pub return_type: Option<DataTypeDeclaration>,
     pub location: SourceRange,
     pub poly_mode: Option<PolymorphismMode>,
+    /// the name of the POU this POU EXTENDS (only classes and function blocks)
+    pub super_class: Option<String>,
+    /// the names of the INTERFACEs this POU IMPLEMENTS (only classes and function blocks)
+    pub interfaces: Vec<String>,
+    /// `@EXTERNAL` POUs are only declared, they are implemented by another object
+    pub linkage: LinkageType,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum PolymorphismMode {
     None,
     Abstract,
@@ -33,6 +39,7 @@ pub enum DirectAccessType {
     Byte,
     Word,
     DWord,
+    LWord,
 }
 
 impl DirectAccessType {
@@ -58,6 +65,7 @@ impl DirectAccessType {
             DirectAccessType::Byte => 8,
             DirectAccessType::Word => 16,
             DirectAccessType::DWord => 32,
+            DirectAccessType::LWord => 64,
         }
     }
 }
@@ -99,13 +107,15 @@ pub struct Implementation {
 pub enum LinkageType {
     Internal,
     External,
+    /// a builtin function that is generated inline by the compiler
+    BuiltIn,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Copy, PartialEq, Clone)]
 pub enum AccessModifier {
     Private,
-    Public,
-    Protected, // default
+    Public, // default
+    Protected,
     Internal,
 }
 
@@ -117,6 +127,7 @@ pub enum PouType {
     Action,
     Class,
     Method { owner_class: String },
+    Interface,
 }
 
 impl PouType {
@@ -136,12 +147,74 @@ pub struct CompilationUnit {
     pub units: Vec<Pou>,
     pub implementations: Vec<Implementation>,
     pub types: Vec<UserTypeDeclaration>,
+    pub configurations: Vec<Configuration>,
+    /// the `{allow(...)}` pragmas of this unit
+    pub suppressions: Vec<Suppression>,
+    /// the comments and pragmas of this unit
+    pub comments: Vec<Comment>,
+}
+
+/// an `{allow(<code>, ...)}` pragma, it suppresses the lints with the given codes
+/// on the line of the pragma and on the line following it
+#[derive(Debug, PartialEq, Clone)]
+pub struct Suppression {
+    pub codes: Vec<String>,
+    pub location: SourceRange,
+}
+
+/// a comment or a pragma as it was written in the source, comments are not part of
+/// the ast but they are kept to reproduce them when formatting a unit
+#[derive(Debug, PartialEq, Clone)]
+pub struct Comment {
+    pub text: String,
+    pub location: SourceRange,
+}
+
+/// a CONFIGURATION with its RESOURCEs
+///
+/// the global variables declared in a configuration or resource are
+/// part of the CompilationUnit's `global_vars`
+#[derive(Debug, PartialEq)]
+pub struct Configuration {
+    pub name: String,
+    pub resources: Vec<Resource>,
+    pub location: SourceRange,
+}
+
+/// a RESOURCE with its TASKs and the PROGRAM instances they run
+#[derive(Debug, PartialEq)]
+pub struct Resource {
+    pub name: String,
+    /// the name of the processing unit the resource runs ON
+    pub target: String,
+    pub tasks: Vec<Task>,
+    pub programs: Vec<ProgramConfiguration>,
+    pub location: SourceRange,
+}
+
+/// a cyclic TASK, e.g. `TASK t(INTERVAL := T#10ms, PRIORITY := 1)`
+#[derive(Debug, PartialEq)]
+pub struct Task {
+    pub name: String,
+    pub interval: Option<AstStatement>,
+    pub priority: Option<AstStatement>,
+    pub location: SourceRange,
+}
+
+/// a PROGRAM instance of a resource, e.g. `PROGRAM p WITH t : MyProg`
+#[derive(Debug, PartialEq)]
+pub struct ProgramConfiguration {
+    pub instance_name: String,
+    /// the task running the program instance
+    pub task_name: Option<String>,
+    pub type_name: String,
+    pub location: SourceRange,
 }
 
 impl CompilationUnit {
     /// imports all elements of the other CompilationUnit into this CompilationUnit
     ///
-    /// this will import all global_vars, units, implementations and types. The imported
+    /// this will import all global_vars, units, implementations, types and configurations. The imported
     /// structs are moved from the other unit into this unit
     /// # Arguments
     /// `other` the other CompilationUnit to import the elements from.
@@ -150,6 +223,9 @@ impl CompilationUnit {
         self.units.extend(other.units);
         self.implementations.extend(other.implementations);
         self.types.extend(other.types);
+        self.configurations.extend(other.configurations);
+        self.suppressions.extend(other.suppressions);
+        self.comments.extend(other.comments);
     }
 }
 
@@ -160,6 +236,9 @@ impl Default for CompilationUnit {
             units: Vec::new(),
             implementations: Vec::new(),
             types: Vec::new(),
+            configurations: Vec::new(),
+            suppressions: Vec::new(),
+            comments: Vec::new(),
         }
     }
 }
@@ -182,6 +261,8 @@ pub struct VariableBlock {
     pub variables: Vec<Variable>,
     pub variable_block_type: VariableBlockType,
     pub location: SourceRange,
+    /// the variables of an `@EXTERNAL VAR_GLOBAL` block are defined by another object
+    pub linkage: LinkageType,
 }
 
 impl Debug for VariableBlock {
@@ -199,25 +280,103 @@ pub struct Variable {
     pub data_type: DataTypeDeclaration,
     pub initializer: Option<AstStatement>,
     pub location: SourceRange,
+    /// the address of a located variable (e.g. `x AT %IX1.2 : BOOL`)
+    pub address: Option<HardwareAddress>,
 }
 
 impl Debug for Variable {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut debug = f.debug_struct("Variable");
+        debug
+            .field("name", &self.name)
+            .field("data_type", &self.data_type);
         if self.initializer.is_some() {
-            f.debug_struct("Variable")
-                .field("name", &self.name)
-                .field("data_type", &self.data_type)
-                .field("initializer", &self.initializer)
-                .finish()
-        } else {
-            f.debug_struct("Variable")
-                .field("name", &self.name)
-                .field("data_type", &self.data_type)
-                .finish()
+            debug.field("initializer", &self.initializer);
+        }
+        if self.address.is_some() {
+            debug.field("address", &self.address);
+        }
+        debug.finish()
+    }
+}
+
+/// the memory area addressed by a located variable
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HardwareAccessType {
+    /// `%I`, the input process image
+    Input,
+    /// `%Q`, the output process image
+    Output,
+    /// `%M`, the memory process image
+    Memory,
+}
+
+/// the address of a located variable, e.g. `%IX1.2` or `%QW4`
+///
+/// bits are addressed by byte and bit (`%IX<byte>.<bit>`), all other sizes
+/// by their index in the process image (`%QW4` starts at byte 8)
+#[derive(Clone, PartialEq)]
+pub struct HardwareAddress {
+    pub area: HardwareAccessType,
+    pub access: DirectAccessType,
+    pub address: Vec<u32>,
+    pub location: SourceRange,
+}
+
+impl HardwareAddress {
+    /// returns the offset of the first byte of the addressed element in its process image
+    pub fn get_byte_offset(&self) -> u32 {
+        let index = self.address.first().copied().unwrap_or_default();
+        match self.access {
+            DirectAccessType::Bit => index,
+            _ => index * self.get_byte_size(),
+        }
+    }
+
+    /// returns the number of bytes occupied by the addressed element
+    pub fn get_byte_size(&self) -> u32 {
+        ((self.access.get_bit_width() + 7) / 8) as u32
+    }
+
+    /// returns the addressed bit within its byte, `0` for all sizes but bits
+    pub fn get_bit_offset(&self) -> u32 {
+        match self.access {
+            DirectAccessType::Bit => self.address.get(1).copied().unwrap_or_default(),
+            _ => 0,
         }
     }
 }
 
+impl Display for HardwareAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let area = match self.area {
+            HardwareAccessType::Input => "I",
+            HardwareAccessType::Output => "Q",
+            HardwareAccessType::Memory => "M",
+        };
+        let access = match self.access {
+            DirectAccessType::Bit => "X",
+            DirectAccessType::Byte => "B",
+            DirectAccessType::Word => "W",
+            DirectAccessType::DWord => "D",
+            DirectAccessType::LWord => "L",
+        };
+        let address = self
+            .address
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(".");
+        write!(f, "%{}{}{}", area, access, address)
+    }
+}
+
+impl Debug for HardwareAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
 impl Variable {
     pub fn replace_data_type_with_reference_to(
         &mut self,
@@ -320,6 +479,8 @@ pub struct UserTypeDeclaration {
     pub data_type: DataType,
     pub initializer: Option<AstStatement>,
     pub location: SourceRange,
+    /// the elements of an `@EXTERNAL` enum are defined by another object
+    pub linkage: LinkageType,
 }
 
 impl Debug for UserTypeDeclaration {
@@ -348,12 +509,13 @@ pub enum DataType {
     },
     ArrayType {
         name: Option<String>,
-        bounds: AstStatement,
+        bounds: AstStatement, //the `*` dimensions of a variable-length array are EmptyStatements
         referenced_type: Box<DataTypeDeclaration>,
     },
     PointerType {
         name: Option<String>,
         referenced_type: Box<DataTypeDeclaration>,
+        auto_deref: bool, //REFERENCE TO
     },
     StringType {
         name: Option<String>,
@@ -401,10 +563,12 @@ impl Debug for DataType {
             DataType::PointerType {
                 name,
                 referenced_type,
+                auto_deref,
             } => f
                 .debug_struct("PointerType")
                 .field("name", name)
                 .field("referenced_type", referenced_type)
+                .field("auto_deref", auto_deref)
                 .finish(),
             DataType::StringType {
                 name,
@@ -639,6 +803,12 @@ pub enum AstStatement {
         right: Box<AstStatement>,
         id: AstId,
     },
+    // RefAssignment, binds the reference on the left to the variable on the right
+    RefAssignment {
+        left: Box<AstStatement>,
+        right: Box<AstStatement>,
+        id: AstId,
+    },
     //Call Statement
     CallStatement {
         operator: Box<AstStatement>,
@@ -832,6 +1002,11 @@ impl Debug for AstStatement {
                 .field("left", left)
                 .field("right", right)
                 .finish(),
+            AstStatement::RefAssignment { left, right, .. } => f
+                .debug_struct("RefAssignment")
+                .field("left", left)
+                .field("right", right)
+                .finish(),
             AstStatement::CallStatement {
                 operator,
                 parameters,
@@ -993,6 +1168,11 @@ impl AstStatement {
                 let right_loc = right.get_location();
                 SourceRange::new(left_loc.range.start..right_loc.range.end)
             }
+            AstStatement::RefAssignment { left, right, .. } => {
+                let left_loc = left.get_location();
+                let right_loc = right.get_location();
+                SourceRange::new(left_loc.range.start..right_loc.range.end)
+            }
             AstStatement::CallStatement { location, .. } => location.clone(),
             AstStatement::IfStatement { location, .. } => location.clone(),
             AstStatement::ForLoopStatement { location, .. } => location.clone(),
@@ -1042,6 +1222,7 @@ impl AstStatement {
             AstStatement::RangeStatement { id, .. } => *id,
             AstStatement::Assignment { id, .. } => *id,
             AstStatement::OutputAssignment { id, .. } => *id,
+            AstStatement::RefAssignment { id, .. } => *id,
             AstStatement::CallStatement { id, .. } => *id,
             AstStatement::IfStatement { id, .. } => *id,
             AstStatement::ForLoopStatement { id, .. } => *id,
@@ -1085,7 +1266,17 @@ impl Display for Operator {
             Operator::Multiplication => "*",
             Operator::Division => "/",
             Operator::Equal => "=",
-            _ => unimplemented!(),
+            Operator::NotEqual => "<>",
+            Operator::Modulo => "MOD",
+            Operator::Less => "<",
+            Operator::Greater => ">",
+            Operator::LessOrEqual => "<=",
+            Operator::GreaterOrEqual => ">=",
+            Operator::Not => "NOT",
+            Operator::And => "AND",
+            Operator::Or => "OR",
+            Operator::Xor => "XOR",
+            Operator::Address => "&",
         };
         f.write_str(symbol)
     }
@@ -1112,6 +1303,87 @@ pub fn flatten_expression_list(condition: &AstStatement) -> Vec<&AstStatement> {
     }
 }
 
+/// returns the statements nested in the given statement in the order they appear in the source
+pub fn get_children(statement: &AstStatement) -> Vec<&AstStatement> {
+    match statement {
+        AstStatement::LiteralArray {
+            elements: Some(elements),
+            ..
+        } => vec![elements.as_ref()],
+        AstStatement::CastStatement { target, .. } => vec![target.as_ref()],
+        AstStatement::MultipliedStatement { element, .. } => vec![element.as_ref()],
+        AstStatement::QualifiedReference { elements, .. } => elements.iter().collect(),
+        AstStatement::ArrayAccess {
+            reference, access, ..
+        } => vec![reference.as_ref(), access.as_ref()],
+        AstStatement::PointerAccess { reference, .. } => vec![reference.as_ref()],
+        AstStatement::DirectAccess { index, .. } => vec![index.as_ref()],
+        AstStatement::BinaryExpression { left, right, .. }
+        | AstStatement::Assignment { left, right, .. }
+        | AstStatement::OutputAssignment { left, right, .. }
+        | AstStatement::RefAssignment { left, right, .. } => vec![left.as_ref(), right.as_ref()],
+        AstStatement::UnaryExpression { value, .. } => vec![value.as_ref()],
+        AstStatement::ExpressionList { expressions, .. } => expressions.iter().collect(),
+        AstStatement::RangeStatement { start, end, .. } => vec![start.as_ref(), end.as_ref()],
+        AstStatement::CallStatement {
+            operator,
+            parameters,
+            ..
+        } => {
+            let mut children = vec![operator.as_ref()];
+            if let Some(parameters) = &**parameters {
+                children.push(parameters);
+            }
+            children
+        }
+        AstStatement::IfStatement {
+            blocks, else_block, ..
+        } => blocks
+            .iter()
+            .flat_map(|it| std::iter::once(it.condition.as_ref()).chain(it.body.iter()))
+            .chain(else_block.iter())
+            .collect(),
+        AstStatement::CaseStatement {
+            selector,
+            case_blocks,
+            else_block,
+            ..
+        } => std::iter::once(selector.as_ref())
+            .chain(
+                case_blocks
+                    .iter()
+                    .flat_map(|it| std::iter::once(it.condition.as_ref()).chain(it.body.iter())),
+            )
+            .chain(else_block.iter())
+            .collect(),
+        AstStatement::CaseCondition { condition, .. } => vec![condition.as_ref()],
+        AstStatement::ForLoopStatement {
+            counter,
+            start,
+            end,
+            by_step,
+            body,
+            ..
+        } => vec![counter.as_ref(), start.as_ref(), end.as_ref()]
+            .into_iter()
+            .chain(by_step.as_deref())
+            .chain(body.iter())
+            .collect(),
+        AstStatement::WhileLoopStatement {
+            condition, body, ..
+        } => std::iter::once(condition.as_ref())
+            .chain(body.iter())
+            .collect(),
+        AstStatement::RepeatLoopStatement {
+            condition, body, ..
+        } => body
+            .iter()
+            .chain(std::iter::once(condition.as_ref()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 pub fn pre_process(unit: &mut CompilationUnit) {
     pre_processor::pre_process(unit)
 }
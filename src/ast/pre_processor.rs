@@ -2,7 +2,7 @@
 
 use super::{
     super::ast::{CompilationUnit, DataType, DataTypeDeclaration, UserTypeDeclaration, Variable},
-    Pou, SourceRange,
+    LinkageType, Pou, SourceRange,
 };
 use std::vec;
 
@@ -16,7 +16,7 @@ pub fn pre_process(unit: &mut CompilationUnit) {
             .filter(|it| should_generate_implicit_type(it));
 
         for var in all_variables {
-            pre_process_variable_data_type(pou.name.as_str(), var, &mut unit.types)
+            pre_process_variable_data_type(pou.name.as_str(), var, pou.linkage, &mut unit.types)
         }
 
         //Generate implicit type for returns
@@ -24,20 +24,23 @@ pub fn pre_process(unit: &mut CompilationUnit) {
     }
 
     //process all variables from GVLs
-    let all_variables = unit
-        .global_vars
-        .iter_mut()
-        .flat_map(|gv| gv.variables.iter_mut())
-        .filter(|it| should_generate_implicit_type(it));
+    for block in unit.global_vars.iter_mut() {
+        let linkage = block.linkage;
+        let all_variables = block
+            .variables
+            .iter_mut()
+            .filter(|it| should_generate_implicit_type(it));
 
-    for var in all_variables {
-        pre_process_variable_data_type("global", var, &mut unit.types)
+        for var in all_variables {
+            pre_process_variable_data_type("global", var, linkage, &mut unit.types)
+        }
     }
 
     //process all variables in dataTypes
     let mut new_types = vec![];
     for dt in unit.types.iter_mut() {
         {
+            let linkage = dt.linkage;
             match &mut dt.data_type {
                 DataType::StructType { name, variables } => {
                     variables
@@ -47,6 +50,7 @@ pub fn pre_process(unit: &mut CompilationUnit) {
                             pre_process_variable_data_type(
                                 name.as_ref().unwrap().as_str(),
                                 var,
+                                linkage,
                                 &mut new_types,
                             )
                         });
@@ -75,11 +79,18 @@ pub fn pre_process(unit: &mut CompilationUnit) {
                     } = *datatype
                     {
                         data_type.set_name(type_name);
-                        add_nested_datatypes(name, &mut data_type, &mut new_types, &location);
+                        add_nested_datatypes(
+                            name,
+                            &mut data_type,
+                            linkage,
+                            &mut new_types,
+                            &location,
+                        );
                         let data_type = UserTypeDeclaration {
                             data_type,
                             initializer: None,
                             location,
+                            linkage,
                         };
                         new_types.push(data_type);
                     }
@@ -106,11 +117,18 @@ fn preprocess_return_type(pou: &mut Pou, types: &mut Vec<UserTypeDeclaration>) {
             }) = datatype
             {
                 data_type.set_name(type_name);
-                add_nested_datatypes(pou.name.as_str(), &mut data_type, types, &location);
+                add_nested_datatypes(
+                    pou.name.as_str(),
+                    &mut data_type,
+                    pou.linkage,
+                    types,
+                    &location,
+                );
                 let data_type = UserTypeDeclaration {
                     data_type,
                     initializer: None,
                     location,
+                    linkage: pou.linkage,
                 };
                 types.push(data_type);
             }
@@ -136,6 +154,7 @@ fn should_generate_implicit_type(variable: &Variable) -> bool {
 fn pre_process_variable_data_type(
     container_name: &str,
     variable: &mut Variable,
+    linkage: LinkageType,
     types: &mut Vec<UserTypeDeclaration>,
 ) {
     let new_type_name = format!("__{}_{}", container_name, variable.name);
@@ -145,12 +164,19 @@ fn pre_process_variable_data_type(
     } = variable.replace_data_type_with_reference_to(new_type_name.clone())
     {
         // create index entry
-        add_nested_datatypes(new_type_name.as_str(), &mut data_type, types, &location);
+        add_nested_datatypes(
+            new_type_name.as_str(),
+            &mut data_type,
+            linkage,
+            types,
+            &location,
+        );
         data_type.set_name(new_type_name);
         types.push(UserTypeDeclaration {
             data_type,
             initializer: None,
             location,
+            linkage,
         });
     }
     //make sure it gets generated
@@ -159,6 +185,7 @@ fn pre_process_variable_data_type(
 fn add_nested_datatypes(
     container_name: &str,
     datatype: &mut DataType,
+    linkage: LinkageType,
     types: &mut Vec<UserTypeDeclaration>,
     location: &SourceRange,
 ) {
@@ -172,6 +199,7 @@ fn add_nested_datatypes(
         add_nested_datatypes(
             new_type_name.as_str(),
             &mut data_type,
+            linkage,
             types,
             &inner_location,
         );
@@ -179,6 +207,7 @@ fn add_nested_datatypes(
             data_type,
             initializer: None,
             location: location.clone(),
+            linkage,
         });
     }
 }
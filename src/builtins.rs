@@ -0,0 +1,394 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+//! the builtin functions of the IEC 61131-3 standard library
+//!
+//! builtin functions are declared in structured text and registered in the index like
+//! any other function, so calls to them are resolved and validated the usual way.
+//! Their parameters use generic types (e.g. `ANY_NUM`), the actual types of a call
+//! are derived from its arguments. Calls to builtin functions are generated inline
+//! (see `ExpressionCodeGenerator::generate_builtin_call`).
+use crate::{
+    ast::{flatten_expression_list, AstStatement, CompilationUnit, LinkageType},
+    index::Index,
+    lexer::{self, IdProvider},
+    parser,
+    typesystem::{self, DataTypeInformation},
+};
+
+pub const ABS: &str = "ABS";
+pub const SQRT: &str = "SQRT";
+pub const LN: &str = "LN";
+pub const LOG: &str = "LOG";
+pub const EXP: &str = "EXP";
+pub const SIN: &str = "SIN";
+pub const COS: &str = "COS";
+pub const TAN: &str = "TAN";
+pub const SHL: &str = "SHL";
+pub const SHR: &str = "SHR";
+pub const ROL: &str = "ROL";
+pub const ROR: &str = "ROR";
+pub const SEL: &str = "SEL";
+pub const MUX: &str = "MUX";
+pub const MAX: &str = "MAX";
+pub const MIN: &str = "MIN";
+pub const LIMIT: &str = "LIMIT";
+pub const MOVE: &str = "MOVE";
+pub const LEN: &str = "LEN";
+pub const LEFT: &str = "LEFT";
+pub const RIGHT: &str = "RIGHT";
+pub const MID: &str = "MID";
+pub const CONCAT: &str = "CONCAT";
+pub const INSERT: &str = "INSERT";
+pub const DELETE: &str = "DELETE";
+pub const REPLACE: &str = "REPLACE";
+pub const FIND: &str = "FIND";
+pub const TRUNC: &str = "TRUNC";
+pub const ROUND: &str = "ROUND";
+pub const LOWER_BOUND: &str = "LOWER_BOUND";
+pub const UPPER_BOUND: &str = "UPPER_BOUND";
+pub const REF: &str = "REF";
+pub const IS_VALID_REF: &str = "__ISVALIDREF";
+pub const ADR: &str = "ADR";
+pub const SIZEOF: &str = "SIZEOF";
+/// aborts the program, used by the default implementations of the runtime checks
+pub const TRAP: &str = "__TRAP";
+
+const BUILTIN_DECLARATIONS: &str = "
+    FUNCTION ABS : ANY_NUM VAR_INPUT IN : ANY_NUM; END_VAR END_FUNCTION
+    FUNCTION SQRT : ANY_REAL VAR_INPUT IN : ANY_REAL; END_VAR END_FUNCTION
+    FUNCTION LN : ANY_REAL VAR_INPUT IN : ANY_REAL; END_VAR END_FUNCTION
+    FUNCTION LOG : ANY_REAL VAR_INPUT IN : ANY_REAL; END_VAR END_FUNCTION
+    FUNCTION EXP : ANY_REAL VAR_INPUT IN : ANY_REAL; END_VAR END_FUNCTION
+    FUNCTION SIN : ANY_REAL VAR_INPUT IN : ANY_REAL; END_VAR END_FUNCTION
+    FUNCTION COS : ANY_REAL VAR_INPUT IN : ANY_REAL; END_VAR END_FUNCTION
+    FUNCTION TAN : ANY_REAL VAR_INPUT IN : ANY_REAL; END_VAR END_FUNCTION
+
+    FUNCTION SHL : ANY_BIT VAR_INPUT IN : ANY_BIT; N : ANY_INT; END_VAR END_FUNCTION
+    FUNCTION SHR : ANY_BIT VAR_INPUT IN : ANY_BIT; N : ANY_INT; END_VAR END_FUNCTION
+    FUNCTION ROL : ANY_BIT VAR_INPUT IN : ANY_BIT; N : ANY_INT; END_VAR END_FUNCTION
+    FUNCTION ROR : ANY_BIT VAR_INPUT IN : ANY_BIT; N : ANY_INT; END_VAR END_FUNCTION
+
+    FUNCTION SEL : ANY VAR_INPUT G : BOOL; IN0 : ANY; IN1 : ANY; END_VAR END_FUNCTION
+    FUNCTION MUX : ANY VAR_INPUT K : ANY_INT; IN : ANY...; END_VAR END_FUNCTION
+    FUNCTION MAX : ANY_NUM VAR_INPUT IN : ANY_NUM...; END_VAR END_FUNCTION
+    FUNCTION MIN : ANY_NUM VAR_INPUT IN : ANY_NUM...; END_VAR END_FUNCTION
+    FUNCTION LIMIT : ANY_NUM VAR_INPUT MN : ANY_NUM; IN : ANY_NUM; MX : ANY_NUM; END_VAR END_FUNCTION
+    FUNCTION MOVE : ANY VAR_INPUT IN : ANY; END_VAR END_FUNCTION
+
+    FUNCTION LEN : INT VAR_INPUT IN : ANY_STRING; END_VAR END_FUNCTION
+    FUNCTION LEFT : ANY_STRING VAR_INPUT IN : ANY_STRING; L : ANY_INT; END_VAR END_FUNCTION
+    FUNCTION RIGHT : ANY_STRING VAR_INPUT IN : ANY_STRING; L : ANY_INT; END_VAR END_FUNCTION
+    FUNCTION MID : ANY_STRING VAR_INPUT IN : ANY_STRING; L : ANY_INT; P : ANY_INT; END_VAR END_FUNCTION
+    FUNCTION CONCAT : ANY_STRING VAR_INPUT IN : ANY_STRING...; END_VAR END_FUNCTION
+    FUNCTION INSERT : ANY_STRING VAR_INPUT IN1 : ANY_STRING; IN2 : ANY_STRING; P : ANY_INT; END_VAR END_FUNCTION
+    FUNCTION DELETE : ANY_STRING VAR_INPUT IN : ANY_STRING; L : ANY_INT; P : ANY_INT; END_VAR END_FUNCTION
+    FUNCTION REPLACE : ANY_STRING
+        VAR_INPUT IN1 : ANY_STRING; IN2 : ANY_STRING; L : ANY_INT; P : ANY_INT; END_VAR
+    END_FUNCTION
+    FUNCTION FIND : INT VAR_INPUT IN1 : ANY_STRING; IN2 : ANY_STRING; END_VAR END_FUNCTION
+
+    FUNCTION TRUNC : DINT VAR_INPUT IN : ANY_REAL; END_VAR END_FUNCTION
+    FUNCTION ROUND : ANY_REAL VAR_INPUT IN : ANY_REAL; END_VAR END_FUNCTION
+
+    FUNCTION LOWER_BOUND : DINT VAR_INPUT ARR : ANY; DIM : ANY_INT; END_VAR END_FUNCTION
+    FUNCTION UPPER_BOUND : DINT VAR_INPUT ARR : ANY; DIM : ANY_INT; END_VAR END_FUNCTION
+
+    FUNCTION REF : ANY VAR_INPUT IN : ANY; END_VAR END_FUNCTION
+    FUNCTION __ISVALIDREF : BOOL VAR_INPUT IN : ANY; END_VAR END_FUNCTION
+    FUNCTION ADR : LWORD VAR_INPUT IN : ANY; END_VAR END_FUNCTION
+    FUNCTION SIZEOF : ULINT VAR_INPUT IN : ANY; END_VAR END_FUNCTION
+
+    FUNCTION __TRAP END_FUNCTION
+";
+
+/// the kinds of type conversion functions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Conversion {
+    /// `<FROM>_TO_<TO>` and `TO_<TO>`, floats are rounded when converted to an integer
+    Convert,
+    /// `TRUNC` and `<FROM>_TRUNC_<TO>`, floats are truncated towards zero
+    Truncate,
+    /// `ROUND`, rounds a float to the nearest integral value
+    Round,
+}
+
+/// returns the kind of conversion the builtin function with the given name performs
+/// or None if it is no conversion function
+pub fn get_conversion(builtin: &str) -> Option<Conversion> {
+    let builtin = builtin.to_uppercase();
+    if builtin == TRUNC || builtin.contains("_TRUNC_") {
+        Some(Conversion::Truncate)
+    } else if builtin == ROUND {
+        Some(Conversion::Round)
+    } else if builtin.starts_with("TO_") || builtin.contains("_TO_") {
+        Some(Conversion::Convert)
+    } else {
+        None
+    }
+}
+
+/// returns the declarations of the type conversion functions for every pair of
+/// elementary types (e.g. `INT_TO_REAL`), the generic `TO_<TYPE>` functions and the
+/// truncating `<REAL>_TRUNC_<INT>` functions
+fn get_conversion_declarations() -> String {
+    let types: Vec<DataTypeInformation> = typesystem::get_builtin_types()
+        .into_iter()
+        .map(|it| it.get_type_information().clone())
+        .filter(is_convertible)
+        .collect();
+
+    let mut declarations = String::new();
+    for target in &types {
+        let target_name = target.get_name();
+        declarations.push_str(&format!(
+            "FUNCTION TO_{to} : {to} VAR_INPUT IN : ANY; END_VAR END_FUNCTION\n",
+            to = target_name
+        ));
+        for source in types.iter().filter(|it| it.get_name() != target_name) {
+            declarations.push_str(&format!(
+                "FUNCTION {from}_TO_{to} : {to} VAR_INPUT IN : {from}; END_VAR END_FUNCTION\n",
+                from = source.get_name(),
+                to = target_name
+            ));
+            if source.is_float()
+                && target.is_int()
+                && !target.is_bool()
+                && !target.is_date_or_time()
+            {
+                declarations.push_str(&format!(
+                    "FUNCTION {from}_TRUNC_{to} : {to} VAR_INPUT IN : {from}; END_VAR END_FUNCTION\n",
+                    from = source.get_name(),
+                    to = target_name
+                ));
+            }
+        }
+    }
+    declarations
+}
+
+/// returns true if conversion functions are generated for the given type
+fn is_convertible(data_type: &DataTypeInformation) -> bool {
+    matches!(
+        data_type,
+        DataTypeInformation::Integer { .. }
+            | DataTypeInformation::Float { .. }
+            | DataTypeInformation::String { .. }
+    )
+}
+
+/// returns true if converting a value of the source type to the target type saturates
+/// the value to the target's range. Conversions between integers (e.g. `DINT_TO_SINT`)
+/// saturate, conversions from or to bit-strings, dates and times keep the lower bits
+pub fn is_saturating_conversion(
+    source: &DataTypeInformation,
+    target: &DataTypeInformation,
+) -> bool {
+    let is_integer = |data_type: &DataTypeInformation| {
+        matches!(data_type, DataTypeInformation::Integer { .. })
+            && !data_type.is_bool()
+            && !data_type.is_bit_string()
+            && !data_type.is_date_or_time()
+    };
+    is_integer(source) && is_integer(target)
+}
+
+/// returns the declarations of all builtin functions
+fn get_declarations() -> String {
+    format!("{}{}", BUILTIN_DECLARATIONS, get_conversion_declarations())
+}
+
+/// parses the declarations of all builtin functions.
+///
+/// the resulting unit should be indexed before any other unit, so POUs declared
+/// by the user replace builtin functions with the same name
+pub fn parse_built_ins(id_provider: IdProvider) -> CompilationUnit {
+    let (mut unit, _) = parser::parse(lexer::lex_with_ids(&get_declarations(), id_provider));
+    for implementation in unit.implementations.iter_mut() {
+        implementation.linkage = LinkageType::BuiltIn;
+    }
+    unit
+}
+
+/// returns the arguments of a call to the given builtin function in the order of the
+/// function's parameters, together with the name of the corresponding parameter's type.
+///
+/// named arguments (`IN := x`) are placed at their parameter's position, additional
+/// arguments of a variadic function are appended in the order of the call
+pub fn get_arguments<'a, 'i>(
+    index: &'i Index,
+    builtin: &str,
+    parameters: &'a Option<AstStatement>,
+) -> Vec<(&'i str, &'a AstStatement)> {
+    let variadic_type = index
+        .find_type(builtin)
+        .and_then(|it| it.get_type_information().get_variadic_type());
+
+    let mut arguments: Vec<(u32, &'i str, &'a AstStatement)> = parameters
+        .as_ref()
+        .map(flatten_expression_list)
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(position, argument)| match argument {
+            AstStatement::Assignment { left, right, .. } => {
+                if let AstStatement::Reference { name, .. } = left.as_ref() {
+                    index.find_member(builtin, name).map(|parameter| {
+                        (
+                            parameter.get_location_in_parent(),
+                            parameter.get_type_name(),
+                            right.as_ref(),
+                        )
+                    })
+                } else {
+                    None
+                }
+            }
+            _ => index
+                .find_declared_parameter(builtin, position as u32)
+                .map(|parameter| {
+                    (
+                        parameter.get_location_in_parent(),
+                        parameter.get_type_name(),
+                        argument,
+                    )
+                })
+                .or_else(|| variadic_type.map(|it| (position as u32, it, argument))),
+        })
+        .collect();
+    arguments.sort_by_key(|(position, ..)| *position);
+    arguments
+        .into_iter()
+        .map(|(_, type_name, argument)| (type_name, argument))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_declarations_parse_without_diagnostics() {
+        let (unit, diagnostics) =
+            parser::parse(lexer::lex_with_ids(&get_declarations(), IdProvider::new()));
+
+        assert_eq!(diagnostics, vec![]);
+        //a declaration lost to a syntax error would silently remove the builtin
+        let builtins = [
+            ABS,
+            SQRT,
+            LN,
+            LOG,
+            EXP,
+            SIN,
+            COS,
+            TAN,
+            SHL,
+            SHR,
+            ROL,
+            ROR,
+            SEL,
+            MUX,
+            MAX,
+            MIN,
+            LIMIT,
+            MOVE,
+            LEN,
+            LEFT,
+            RIGHT,
+            MID,
+            CONCAT,
+            INSERT,
+            DELETE,
+            REPLACE,
+            FIND,
+            TRUNC,
+            ROUND,
+            LOWER_BOUND,
+            UPPER_BOUND,
+            REF,
+            IS_VALID_REF,
+            ADR,
+            SIZEOF,
+            TRAP,
+            "TO_DINT",
+            "INT_TO_REAL",
+            "LREAL_TRUNC_SINT",
+            "STRING_TO_DINT",
+        ];
+        for builtin in builtins.iter() {
+            assert!(
+                unit.units
+                    .iter()
+                    .any(|it| it.name.eq_ignore_ascii_case(builtin)),
+                "missing builtin {}",
+                builtin
+            );
+        }
+    }
+
+    #[test]
+    fn conversions_of_wide_strings_and_between_strings_and_dates_are_declared() {
+        let (unit, _) = parser::parse(lexer::lex_with_ids(&get_declarations(), IdProvider::new()));
+
+        let conversions = [
+            "TO_WSTRING",
+            "WSTRING_TO_INT",
+            "INT_TO_WSTRING",
+            "STRING_TO_WSTRING",
+            "WSTRING_TO_STRING",
+            "STRING_TO_TIME",
+            "TIME_TO_STRING",
+            "DATE_AND_TIME_TO_STRING",
+            "STRING_TO_DATE",
+            "TIME_OF_DAY_TO_WSTRING",
+        ];
+        for conversion in conversions.iter() {
+            assert!(
+                unit.units
+                    .iter()
+                    .any(|it| it.name.eq_ignore_ascii_case(conversion)),
+                "missing conversion {}",
+                conversion
+            );
+        }
+    }
+
+    #[test]
+    fn only_conversions_between_integers_saturate() {
+        let get_type = |name: &str| {
+            typesystem::get_builtin_types()
+                .into_iter()
+                .find(|it| it.get_name() == name)
+                .map(|it| it.get_type_information().clone())
+                .unwrap()
+        };
+
+        assert!(is_saturating_conversion(
+            &get_type("DINT"),
+            &get_type("SINT")
+        ));
+        assert!(is_saturating_conversion(
+            &get_type("LINT"),
+            &get_type("UDINT")
+        ));
+        assert!(!is_saturating_conversion(
+            &get_type("DWORD"),
+            &get_type("BYTE")
+        ));
+        assert!(!is_saturating_conversion(
+            &get_type("DINT"),
+            &get_type("WORD")
+        ));
+        assert!(!is_saturating_conversion(
+            &get_type("TIME"),
+            &get_type("DINT")
+        ));
+        assert!(!is_saturating_conversion(
+            &get_type("DINT"),
+            &get_type("BOOL")
+        ));
+        assert!(!is_saturating_conversion(
+            &get_type("REAL"),
+            &get_type("DINT")
+        ));
+    }
+}
@@ -1,4 +1,8 @@
 // Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use crate::{
+    diagnostician::{ErrorFormat, LintOptions},
+    DebugLevel, OptimizationLevel, RuntimeChecks, TargetOptions,
+};
 use encoding_rs::Encoding;
 use std::path::Path;
 use structopt::{clap::ArgGroup, StructOpt};
@@ -65,6 +69,9 @@ pub struct CompileParameters {
     #[structopt(short = "c", help = "Do not link after compiling object code")]
     pub skip_linking: bool,
 
+    #[structopt(short = "g", help = "Generate source-level debug information (DWARF)")]
+    pub generate_debug: bool,
+
     #[structopt(
         long,
         name = "target-triple",
@@ -72,6 +79,67 @@ pub struct CompileParameters {
     )]
     pub target: Option<String>,
 
+    #[structopt(
+        long = "target-cpu",
+        name = "target-cpu",
+        help = "The cpu to generate code for (e.g. cortex-a53), a generic cpu if not provided"
+    )]
+    pub target_cpu: Option<String>,
+
+    #[structopt(
+        long = "target-features",
+        name = "target-features",
+        help = "The cpu features to enable (+) or disable (-), e.g. +neon,-crypto"
+    )]
+    pub target_features: Option<String>,
+
+    #[structopt(
+        short = "O",
+        name = "optimization",
+        possible_values = &["0", "1", "2", "3", "s"],
+        help = "The optimization level: 0 (none, default for --ir and --bc), 1, 2 (default), 3 (aggressive) or s (size)",
+        parse(try_from_str = parse_optimization_level),
+    )]
+    pub optimization: Option<OptimizationLevel>,
+
+    #[structopt(
+        long = "error-format",
+        name = "error-format",
+        default_value = "human",
+        possible_values = &["human", "json", "sarif"],
+        help = "The format diagnostics are reported in: human (default), json or sarif",
+        parse(try_from_str = parse_error_format),
+    )]
+    pub error_format: ErrorFormat,
+
+    #[structopt(
+        short = "W",
+        name = "warn",
+        number_of_values = 1,
+        help = "Report the lint with the given code (e.g. lint__unused_variable) as a warning, even if it is allowed"
+    )]
+    pub warn: Vec<String>,
+
+    #[structopt(
+        short = "A",
+        name = "allow",
+        number_of_values = 1,
+        help = "Do not report the lint with the given code, or any lint with 'all'"
+    )]
+    pub allow: Vec<String>,
+
+    #[structopt(long = "deny-warnings", help = "Report warnings as errors")]
+    pub deny_warnings: bool,
+
+    #[structopt(
+        long = "runtime-checks",
+        name = "runtime-checks",
+        require_delimiter = true,
+        possible_values = &["bounds", "div", "overflow", "null"],
+        help = "Generate the given runtime checks (e.g. bounds,div,overflow,null), a check calls its hook (e.g. CheckBounds) or a default implementation that traps"
+    )]
+    pub runtime_checks: Vec<String>,
+
     #[structopt(
         long,
         name = "encoding",
@@ -99,12 +167,46 @@ pub struct CompileParameters {
 
     #[structopt(name = "library", long, short = "l", help = "Library name to link")]
     pub libraries: Vec<String>,
+
+    #[structopt(
+        long = "interface",
+        help = "Write the declarations of all POUs, types and global variables to an interface file (<output-file>.sti)"
+    )]
+    pub generate_interface: bool,
+
+    #[structopt(
+        name = "include",
+        long,
+        short = "i",
+        help = "Include the declarations of an interface file, may be a glob expression like 'lib/*.sti'"
+    )]
+    pub includes: Vec<String>,
 }
 
 fn parse_encoding(encoding: &str) -> Result<&'static Encoding, String> {
     Encoding::for_label(encoding.as_bytes()).ok_or(format!("Unknown encoding {}", encoding))
 }
 
+fn parse_optimization_level(level: &str) -> Result<OptimizationLevel, String> {
+    match level {
+        "0" => Ok(OptimizationLevel::None),
+        "1" => Ok(OptimizationLevel::Less),
+        "2" => Ok(OptimizationLevel::Default),
+        "3" => Ok(OptimizationLevel::Aggressive),
+        "s" => Ok(OptimizationLevel::Size),
+        _ => Err(format!("Unknown optimization level {}", level)),
+    }
+}
+
+fn parse_error_format(format: &str) -> Result<ErrorFormat, String> {
+    match format {
+        "human" => Ok(ErrorFormat::Human),
+        "json" => Ok(ErrorFormat::Json),
+        "sarif" => Ok(ErrorFormat::Sarif),
+        _ => Err(format!("Unknown error format {}", format)),
+    }
+}
+
 impl CompileParameters {
     pub fn parse(args: Vec<String>) -> Result<CompileParameters, ParameterError> {
         CompileParameters::from_iter_safe(args)
@@ -134,6 +236,54 @@ impl CompileParameters {
         self.output_format().unwrap_or(DEFAULT_FORMAT)
     }
 
+    /// return the selected optimization level, or the default of the output format if none.
+    /// IR and bitcode are not optimized by default, so they show the generated code
+    pub fn optimization_level(&self) -> OptimizationLevel {
+        self.optimization
+            .unwrap_or_else(|| match self.output_format_or_default() {
+                FormatOption::IR | FormatOption::Bitcode => OptimizationLevel::None,
+                _ => OptimizationLevel::Default,
+            })
+    }
+
+    /// return the amount of debug information to generate
+    pub fn debug_level(&self) -> DebugLevel {
+        if self.generate_debug {
+            DebugLevel::Full
+        } else {
+            DebugLevel::None
+        }
+    }
+
+    /// return the target machine selected by the --target, --target-cpu and --target-features options
+    pub fn target_options(&self) -> TargetOptions {
+        TargetOptions {
+            triple: self.target.clone(),
+            cpu: self.target_cpu.clone(),
+            features: self.target_features.clone(),
+        }
+    }
+
+    /// return the lints selected by the -W, -A and --deny-warnings options
+    pub fn lint_options(&self) -> LintOptions {
+        LintOptions {
+            warn: self.warn.clone(),
+            allow: self.allow.clone(),
+            deny_warnings: self.deny_warnings,
+        }
+    }
+
+    /// return the runtime checks selected by the --runtime-checks option
+    pub fn runtime_checks(&self) -> RuntimeChecks {
+        let is_enabled = |check: &str| self.runtime_checks.iter().any(|it| it == check);
+        RuntimeChecks {
+            bounds: is_enabled("bounds"),
+            div: is_enabled("div"),
+            overflow: is_enabled("overflow"),
+            null: is_enabled("null"),
+        }
+    }
+
     /// return the output filename with the correct ending
     pub fn output_name(&self) -> Option<String> {
         let out_format = self.output_format_or_default();
@@ -153,11 +303,64 @@ impl CompileParameters {
             Some(format!("{}{}", basename, ending))
         }
     }
+
+    /// return the name of the interface file written next to the output file
+    pub fn interface_name(&self) -> Option<String> {
+        let output_name = self.output_name()?;
+        let interface_name = Path::new(&output_name).with_extension("sti");
+        interface_name.to_str().map(|it| it.to_string())
+    }
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "rustyc fmt", about = "Formats Structured Text files in place")]
+pub struct FormatParameters {
+    #[structopt(
+        long,
+        help = "Do not write the files, fail if any of them is not formatted"
+    )]
+    pub check: bool,
+
+    #[structopt(
+        long = "error-format",
+        name = "error-format",
+        default_value = "human",
+        possible_values = &["human", "json", "sarif"],
+        help = "The format diagnostics are reported in: human (default), json or sarif",
+        parse(try_from_str = parse_error_format),
+    )]
+    pub error_format: ErrorFormat,
+
+    #[structopt(
+        long,
+        name = "encoding",
+        help = "The file encoding used to read and write the input-files, as defined by the Encoding Standard",
+        parse(try_from_str = parse_encoding),
+    )]
+    pub encoding: Option<&'static Encoding>,
+
+    #[structopt(
+        name = "input-files",
+        help = "The files to format, may be a glob expression like 'src/**/*' or a sequence of files",
+        required = true,
+        min_values = 1
+    )]
+    pub input: Vec<String>,
+}
+
+impl FormatParameters {
+    pub fn parse(args: Vec<String>) -> Result<FormatParameters, ParameterError> {
+        FormatParameters::from_iter_safe(args)
+    }
 }
 
 #[cfg(test)]
 mod cli_tests {
-    use super::{CompileParameters, FormatOption, ParameterError};
+    use super::{CompileParameters, FormatOption, FormatParameters, ParameterError};
+    use crate::{
+        diagnostician::{ErrorFormat, LintOptions},
+        DebugLevel, OptimizationLevel, RuntimeChecks, TargetOptions,
+    };
     use pretty_assertions::assert_eq;
     use structopt::clap::ErrorKind;
 
@@ -271,6 +474,160 @@ mod cli_tests {
         assert_eq!(parameters.target, Some("x86_64-linux-gnu".to_string()));
     }
 
+    #[test]
+    fn optimization_levels_are_parsed() {
+        let levels = vec![
+            ("-O0", OptimizationLevel::None),
+            ("-O1", OptimizationLevel::Less),
+            ("-O2", OptimizationLevel::Default),
+            ("-O3", OptimizationLevel::Aggressive),
+            ("-Os", OptimizationLevel::Size),
+        ];
+        for (argument, level) in levels {
+            let parameters =
+                CompileParameters::parse(vec_of_strings!("alpha.st", argument)).unwrap();
+            assert_eq!(parameters.optimization_level(), level);
+        }
+
+        let parameters = CompileParameters::parse(vec_of_strings!("alpha.st")).unwrap();
+        assert_eq!(parameters.optimization_level(), OptimizationLevel::Default);
+
+        //IR and bitcode are not optimized unless requested
+        let parameters = CompileParameters::parse(vec_of_strings!("alpha.st", "--ir")).unwrap();
+        assert_eq!(parameters.optimization_level(), OptimizationLevel::None);
+        let parameters = CompileParameters::parse(vec_of_strings!("alpha.st", "--bc")).unwrap();
+        assert_eq!(parameters.optimization_level(), OptimizationLevel::None);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("alpha.st", "--ir", "-O2")).unwrap();
+        assert_eq!(parameters.optimization_level(), OptimizationLevel::Default);
+
+        expect_argument_error(vec_of_strings!("alpha.st", "-O4"), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn error_formats_are_parsed() {
+        let formats = vec![
+            ("human", ErrorFormat::Human),
+            ("json", ErrorFormat::Json),
+            ("sarif", ErrorFormat::Sarif),
+        ];
+        for (argument, format) in formats {
+            let parameters = CompileParameters::parse(vec_of_strings!(
+                "alpha.st",
+                format!("--error-format={}", argument)
+            ))
+            .unwrap();
+            assert_eq!(parameters.error_format, format);
+        }
+
+        let parameters = CompileParameters::parse(vec_of_strings!("alpha.st")).unwrap();
+        assert_eq!(parameters.error_format, ErrorFormat::Human);
+
+        expect_argument_error(
+            vec_of_strings!("alpha.st", "--error-format=xml"),
+            ErrorKind::InvalidValue,
+        );
+    }
+
+    #[test]
+    fn lint_options_are_parsed() {
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "alpha.st",
+            "-A",
+            "all",
+            "-W",
+            "lint__unused_variable",
+            "-Wlint__shadowed_global",
+            "--deny-warnings"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            parameters.lint_options(),
+            LintOptions {
+                warn: vec![
+                    "lint__unused_variable".to_string(),
+                    "lint__shadowed_global".to_string()
+                ],
+                allow: vec!["all".to_string()],
+                deny_warnings: true,
+            }
+        );
+
+        let parameters = CompileParameters::parse(vec_of_strings!("alpha.st")).unwrap();
+        assert_eq!(parameters.lint_options(), LintOptions::default());
+    }
+
+    #[test]
+    fn target_cpu_and_features_are_parsed() {
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "alpha.st",
+            "--target",
+            "armv7a-none-eabi",
+            "--target-cpu",
+            "cortex-a53",
+            "--target-features",
+            "+neon,-crypto"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            parameters.target_options(),
+            TargetOptions {
+                triple: Some("armv7a-none-eabi".to_string()),
+                cpu: Some("cortex-a53".to_string()),
+                features: Some("+neon,-crypto".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn debug_information_is_requested_with_g() {
+        let parameters = CompileParameters::parse(vec_of_strings!("alpha.st", "-g")).unwrap();
+        assert_eq!(parameters.debug_level(), DebugLevel::Full);
+
+        let parameters = CompileParameters::parse(vec_of_strings!("alpha.st")).unwrap();
+        assert_eq!(parameters.debug_level(), DebugLevel::None);
+    }
+
+    #[test]
+    fn runtime_checks_are_parsed() {
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("--runtime-checks", "bounds", "alpha.st"))
+                .unwrap();
+        assert_eq!(
+            parameters.runtime_checks(),
+            RuntimeChecks {
+                bounds: true,
+                ..RuntimeChecks::default()
+            }
+        );
+        assert_eq!(parameters.input, vec!["alpha.st"]);
+
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "alpha.st",
+            "--runtime-checks=div,overflow,null"
+        ))
+        .unwrap();
+        assert_eq!(
+            parameters.runtime_checks(),
+            RuntimeChecks {
+                bounds: false,
+                div: true,
+                overflow: true,
+                null: true,
+            }
+        );
+
+        let parameters = CompileParameters::parse(vec_of_strings!("alpha.st")).unwrap();
+        assert_eq!(parameters.runtime_checks(), RuntimeChecks::default());
+
+        expect_argument_error(
+            vec_of_strings!("alpha.st", "--runtime-checks=everything"),
+            ErrorKind::InvalidValue,
+        );
+    }
+
     #[test]
     fn test_default_format() {
         let parameters = CompileParameters::parse(vec_of_strings!("alpha.st", "--ir")).unwrap();
@@ -321,6 +678,28 @@ mod cli_tests {
         );
     }
 
+    #[test]
+    fn format_parameters_are_parsed() {
+        let parameters =
+            FormatParameters::parse(vec_of_strings!("--check", "a.st", "b.st")).unwrap();
+        assert_eq!(parameters.check, true);
+        assert_eq!(
+            parameters.input,
+            vec!["a.st".to_string(), "b.st".to_string()]
+        );
+        assert_eq!(parameters.error_format, ErrorFormat::Human);
+
+        let parameters = FormatParameters::parse(vec_of_strings!("a.st")).unwrap();
+        assert_eq!(parameters.check, false);
+
+        // no input file
+        let parameters = FormatParameters::parse(vec_of_strings!("--check"));
+        assert_eq!(
+            parameters.unwrap_err().kind,
+            ErrorKind::MissingRequiredArgument
+        );
+    }
+
     #[test]
     fn valid_output_formats() {
         let parameters = CompileParameters::parse(vec_of_strings!("input.st", "--ir")).unwrap();
@@ -395,6 +774,39 @@ mod cli_tests {
         assert_eq!(parameters.libraries, vec!["test", "c", "xx"]);
     }
 
+    #[test]
+    fn interface_files_are_included_and_generated() {
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "input.st",
+            "-i",
+            "lib.sti",
+            "--include",
+            "other/*.sti",
+            "-c"
+        ))
+        .unwrap();
+        assert_eq!(parameters.includes, vec!["lib.sti", "other/*.sti"]);
+        assert!(!parameters.generate_interface);
+
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "--interface", "-c")).unwrap();
+        assert!(parameters.generate_interface);
+        assert_eq!(parameters.interface_name(), Some("input.sti".to_string()));
+
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "input.st",
+            "--interface",
+            "--shared",
+            "-o",
+            "out/libfoo.so"
+        ))
+        .unwrap();
+        assert_eq!(
+            parameters.interface_name(),
+            Some("out/libfoo.sti".to_string())
+        );
+    }
+
     #[test]
     fn cli_supports_version() {
         match CompileParameters::parse(vec_of_strings!("input.st", "--version")) {
@@ -2,8 +2,9 @@
 
 /// module to generate llvm intermediate representation for a CompilationUnit
 use self::{
+    debug::DebugBuilder,
     generators::{
-        data_type_generator,
+        configuration_generator, data_type_generator,
         llvm::Llvm,
         pou_generator::{self, PouGenerator},
         variable_generator,
@@ -19,6 +20,7 @@ use inkwell::context::Context;
 use inkwell::module::Module;
 use inkwell::values::{BasicValueEnum, PointerValue};
 
+mod debug;
 mod generators;
 mod llvm_index;
 mod llvm_typesystem;
@@ -78,9 +80,14 @@ impl<'ink> CodeGen<'ink> {
         let mut index = LlvmTypedIndex::new();
         //Generate types index, and any global variables associated with them.
         let llvm_type_index =
-            data_type_generator::generate_data_types(&llvm, global_index, annotations)?;
+            data_type_generator::generate_data_types(module, &llvm, global_index, annotations)?;
         index.merge(llvm_type_index);
 
+        //Generate the process images holding the located variables
+        let llvm_image_index =
+            variable_generator::generate_process_images(module, &llvm, global_index)?;
+        index.merge(llvm_image_index);
+
         //Generate global variables
         let llvm_gv_index = variable_generator::generate_global_variables(
             module,
@@ -101,32 +108,151 @@ impl<'ink> CodeGen<'ink> {
             &index,
         )?;
         index.merge(llvm_impl_index);
+
+        //Generate the vtables of all classes implementing interfaces and the method tables of all polymorphic classes
+        let llvm = Llvm::new(self.context, self.context.create_builder());
+        let llvm_vtable_index =
+            pou_generator::generate_vtables(module, &llvm, global_index, &index)?;
+        index.merge(llvm_vtable_index);
         Ok(index)
     }
 
-    /// generates all TYPEs, GLOBAL-sections and POUs of the given CompilationUnit
+    /// generates all TYPEs, GLOBAL-sections, POUs and CONFIGURATIONs of the given CompilationUnit
+    ///
+    /// a POU that cannot be generated does not stop the generation of the other POUs,
+    /// the errors of all POUs are returned
     pub fn generate(
         &self,
         unit: &CompilationUnit,
         annotations: &AnnotationMap,
         global_index: &Index,
-    ) -> Result<String, CompileError> {
+    ) -> Result<String, Vec<CompileError>> {
+        self.generate_unit(unit, annotations, global_index, None)
+    }
+
+    /// generates the given CompilationUnit like `generate` and describes its POUs,
+    /// variables and datatypes with DWARF debug information
+    ///
+    /// - `file_name` the path of the source file the unit was parsed from
+    /// - `source` the source code the unit was parsed from
+    pub fn generate_with_debug_info(
+        &self,
+        unit: &CompilationUnit,
+        annotations: &AnnotationMap,
+        global_index: &Index,
+        file_name: &str,
+        source: &str,
+    ) -> Result<String, Vec<CompileError>> {
+        let debug = DebugBuilder::new(self.context, &self.module, file_name, source);
+        let result = self.generate_unit(unit, annotations, global_index, Some(&debug));
+        debug.finalize();
+        result.map(|_| self.module.print_to_string().to_string())
+    }
+
+    fn generate_unit(
+        &self,
+        unit: &CompilationUnit,
+        annotations: &AnnotationMap,
+        global_index: &Index,
+        debug: Option<&DebugBuilder<'ink>>,
+    ) -> Result<String, Vec<CompileError>> {
         //Associate the index type with LLVM types
-        let llvm_index = self.generate_llvm_index(&self.module, annotations, global_index)?;
+        let llvm_index = self
+            .generate_llvm_index(&self.module, annotations, global_index)
+            .map_err(|err| vec![err])?;
+
+        //describe the global variables and program instances declared in this unit
+        if let Some(debug) = debug {
+            self.generate_global_debug_info(unit, global_index, debug);
+        }
 
         //generate all pous
         let llvm = Llvm::new(self.context, self.context.create_builder());
-        let pou_generator = PouGenerator::new(llvm, global_index, annotations, &llvm_index);
+        let pou_generator = PouGenerator::new(llvm, global_index, annotations, &llvm_index, debug);
 
+        let mut errors = Vec::new();
         //Generate the POU stubs in the first go to make sure they can be referenced.
         for implementation in &unit.implementations {
             //Don't generate external functions
             if implementation.linkage != LinkageType::External {
-                pou_generator.generate_implementation(implementation)?;
+                if let Err(err) =
+                    pou_generator.generate_implementation(implementation, &self.module)
+                {
+                    errors.push(err);
+                }
             }
         }
 
-        Ok(self.module.print_to_string().to_string())
+        //generate the cycle functions and the task table of all configurations
+        let llvm = Llvm::new(self.context, self.context.create_builder());
+        if let Err(err) = configuration_generator::generate_configurations(
+            &self.module,
+            &llvm,
+            global_index,
+            annotations,
+            &llvm_index,
+            &unit.configurations,
+        ) {
+            errors.push(err);
+        }
+
+        //generate the retain table describing all retained variables into the configuration's object
+        if !unit.configurations.is_empty() {
+            let llvm = Llvm::new(self.context, self.context.create_builder());
+            if let Err(err) = variable_generator::generate_retain_table(
+                &self.module,
+                &llvm,
+                global_index,
+                &llvm_index,
+            ) {
+                errors.push(err);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(self.module.print_to_string().to_string())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// describes the global variables and program instances declared in the given unit
+    fn generate_global_debug_info(
+        &self,
+        unit: &CompilationUnit,
+        global_index: &Index,
+        debug: &DebugBuilder<'ink>,
+    ) {
+        let variables = unit
+            .global_vars
+            .iter()
+            .flat_map(|it| it.variables.iter())
+            .map(|it| (it.name.as_str(), &it.location));
+        let programs = unit
+            .units
+            .iter()
+            .filter(|it| it.pou_type == PouType::Program)
+            .map(|it| (it.name.as_str(), &it.location));
+        for (name, location) in variables.chain(programs) {
+            //located variables have no global of their own, external ones are described by their object
+            let global = global_index
+                .find_global_variable(name)
+                .filter(|it| !it.is_external())
+                .and_then(|it| {
+                    self.module
+                        .get_global(it.get_name())
+                        .map(|global| (it, global))
+                });
+            if let Some((variable, global)) = global {
+                debug.register_global_variable(
+                    global_index,
+                    global,
+                    name,
+                    variable.get_type_name(),
+                    location,
+                );
+            }
+        }
     }
 }
 
@@ -0,0 +1,557 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+
+/// module to generate the DWARF debug information of a compilation unit
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    ops::Range,
+    path::Path,
+};
+
+use inkwell::{
+    basic_block::BasicBlock,
+    builder::Builder,
+    context::Context,
+    debug_info::{
+        AsDIScope, DICompileUnit, DIFile, DIFlags, DIFlagsConstants, DIType, DWARFEmissionKind,
+        DWARFSourceLanguage, DebugInfoBuilder,
+    },
+    module::{FlagBehavior, Module},
+    values::{FunctionValue, GlobalValue, PointerValue},
+    AddressSpace,
+};
+
+use crate::{
+    ast::SourceRange,
+    index::{Index, VariableIndexEntry},
+    typesystem::{DataTypeInformation, StringEncoding, BOOL_SIZE, DINT_TYPE},
+};
+
+/// the version of the debug-metadata format generated into the module
+const DEBUG_INFO_VERSION: u64 = 3;
+/// the DWARF version of the generated debug information
+const DWARF_VERSION: u64 = 4;
+/// the size of a pointer in bits, the debug information assumes a 64bit target
+const POINTER_SIZE: u64 = 64;
+
+/// DWARF base type encodings (see DWARF 4, chapter 7.8)
+const DW_ATE_BOOLEAN: u32 = 0x02;
+const DW_ATE_FLOAT: u32 = 0x04;
+const DW_ATE_SIGNED: u32 = 0x05;
+const DW_ATE_UNSIGNED: u32 = 0x07;
+const DW_ATE_UNSIGNED_CHAR: u32 = 0x08;
+const DW_ATE_UTF: u32 = 0x10;
+
+/// a generated debug type with its size and alignment in bits
+#[derive(Clone, Copy)]
+struct DebugType<'ink> {
+    di_type: DIType<'ink>,
+    size: u64,
+    alignment: u32,
+}
+
+/// generates the DWARF debug information of a single source file
+///
+/// POUs are described as subprograms, their variables as local variables and
+/// datatypes (incl. the instance-structs of POUs) as debug types.
+pub struct DebugBuilder<'ink> {
+    context: &'ink Context,
+    debug_info: DebugInfoBuilder<'ink>,
+    compile_unit: DICompileUnit<'ink>,
+    file: DIFile<'ink>,
+    /// the byte-offsets of the first character of every line of the source file
+    line_starts: Vec<usize>,
+    /// the generated debug types by their lowercase name
+    types: RefCell<HashMap<String, DebugType<'ink>>>,
+    /// the structs that are currently generated, used to break recursive pointers
+    pending_structs: RefCell<HashSet<String>>,
+}
+
+impl<'ink> DebugBuilder<'ink> {
+    /// creates a new DebugBuilder describing the given source file in the given module
+    ///
+    /// - `file_name` the path of the source file
+    /// - `source` the source file's content, used to map source-offsets to lines & columns
+    pub fn new(
+        context: &'ink Context,
+        module: &Module<'ink>,
+        file_name: &str,
+        source: &str,
+    ) -> DebugBuilder<'ink> {
+        //the module flags are shared by all compile units of the module
+        if module.get_flag("Debug Info Version").is_none() {
+            let flag_type = context.i32_type();
+            module.add_basic_value_flag(
+                "Debug Info Version",
+                FlagBehavior::Warning,
+                flag_type.const_int(DEBUG_INFO_VERSION, false),
+            );
+            module.add_basic_value_flag(
+                "Dwarf Version",
+                FlagBehavior::Warning,
+                flag_type.const_int(DWARF_VERSION, false),
+            );
+        }
+
+        let path = Path::new(file_name);
+        let name = path
+            .file_name()
+            .and_then(|it| it.to_str())
+            .unwrap_or(file_name);
+        let directory = path
+            .parent()
+            .and_then(|it| it.to_str())
+            .filter(|it| !it.is_empty())
+            .unwrap_or(".");
+        //DWARF has no language-code for structured text, C lets debuggers evaluate expressions
+        let (debug_info, compile_unit) = module.create_debug_info_builder(
+            true,
+            DWARFSourceLanguage::C,
+            name,
+            directory,
+            "RuSTy Structured text Compiler",
+            false,
+            "",
+            0,
+            "",
+            DWARFEmissionKind::Full,
+            0,
+            false,
+            false,
+            "",
+            "",
+        );
+        let file = compile_unit.get_file();
+
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+
+        DebugBuilder {
+            context,
+            debug_info,
+            compile_unit,
+            file,
+            line_starts,
+            types: RefCell::new(HashMap::new()),
+            pending_structs: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// returns the line and column (both starting at 1) of the given source-offset
+    pub fn get_line_and_column(&self, offset: usize) -> (u32, u32) {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let column = offset - self.line_starts[line];
+        (line as u32 + 1, column as u32 + 1)
+    }
+
+    /// describes the given function as a subprogram
+    ///
+    /// - `name` the name of the POU implemented by the function
+    /// - `return_type` the name of the POU's return type, if any
+    /// - `location` the location of the POU's implementation
+    pub fn register_function(
+        &self,
+        index: &Index,
+        function: FunctionValue<'ink>,
+        name: &str,
+        return_type: Option<&str>,
+        location: &SourceRange,
+    ) {
+        let return_type = return_type
+            .and_then(|it| self.get_or_create_type(index, it))
+            .map(|it| it.di_type);
+        let subroutine_type =
+            self.debug_info
+                .create_subroutine_type(self.file, return_type, &[], DIFlags::PUBLIC);
+        let (line, _) = self.get_line_and_column(location.get_start());
+        let subprogram = self.debug_info.create_function(
+            self.compile_unit.as_debug_info_scope(),
+            name,
+            None,
+            self.file,
+            line,
+            subroutine_type,
+            false,
+            true,
+            line,
+            DIFlags::PUBLIC,
+            false,
+        );
+        function.set_subprogram(subprogram);
+    }
+
+    /// describes the variable stored at the given pointer as a local variable of the given function
+    pub fn register_local_variable(
+        &self,
+        index: &Index,
+        function: FunctionValue<'ink>,
+        name: &str,
+        variable: &VariableIndexEntry,
+        pointer: PointerValue<'ink>,
+        block: BasicBlock<'ink>,
+    ) {
+        let subprogram = function.get_subprogram();
+        let debug_type = self.get_or_create_type(index, variable.get_type_name());
+        if let (Some(subprogram), Some(debug_type)) = (subprogram, debug_type) {
+            let scope = subprogram.as_debug_info_scope();
+            let (line, column) = self.get_line_and_column(variable.source_location.get_start());
+            let local_variable = self.debug_info.create_auto_variable(
+                scope,
+                name,
+                self.file,
+                line,
+                debug_type.di_type,
+                true,
+                DIFlags::ZERO,
+                debug_type.alignment,
+            );
+            let location =
+                self.debug_info
+                    .create_debug_location(self.context, line, column, scope, None);
+            self.debug_info.insert_declare_at_end(
+                pointer,
+                Some(local_variable),
+                None,
+                location,
+                block,
+            );
+        }
+    }
+
+    /// describes the given global as a global variable
+    ///
+    /// - `name` the variable's name as seen by the user (e.g. the program's name for its instance)
+    pub fn register_global_variable(
+        &self,
+        index: &Index,
+        global: GlobalValue<'ink>,
+        name: &str,
+        type_name: &str,
+        location: &SourceRange,
+    ) {
+        if let Some(debug_type) = self.get_or_create_type(index, type_name) {
+            let (line, _) = self.get_line_and_column(location.get_start());
+            let linkage_name = global.get_name().to_str().unwrap_or(name).to_string();
+            let expression = self.debug_info.create_global_variable_expression(
+                self.compile_unit.as_debug_info_scope(),
+                name,
+                &linkage_name,
+                self.file,
+                line,
+                debug_type.di_type,
+                false,
+                None,
+                None,
+                debug_type.alignment,
+            );
+            global.set_metadata(
+                expression.as_metadata_value(self.context),
+                self.context.get_kind_id("dbg"),
+            );
+        }
+    }
+
+    /// sets the builder's debug location to the given location inside the given function
+    ///
+    /// all instructions generated afterwards are attributed to this location
+    pub fn set_debug_location(
+        &self,
+        builder: &Builder<'ink>,
+        function: FunctionValue<'ink>,
+        location: &SourceRange,
+    ) {
+        if let Some(subprogram) = function.get_subprogram() {
+            let (line, column) = self.get_line_and_column(location.get_start());
+            let location = self.debug_info.create_debug_location(
+                self.context,
+                line,
+                column,
+                subprogram.as_debug_info_scope(),
+                None,
+            );
+            builder.set_current_debug_location(self.context, location);
+        }
+    }
+
+    /// resolves all temporary debug information, must be called before the module is emitted
+    pub fn finalize(&self) {
+        self.debug_info.finalize();
+    }
+
+    /// returns the debug type of the given datatype, generates it if necessary
+    ///
+    /// returns None if the datatype cannot be described (e.g. VOID or generic types)
+    fn get_or_create_type(&self, index: &Index, type_name: &str) -> Option<DebugType<'ink>> {
+        let key = type_name.to_lowercase();
+        if let Some(debug_type) = self.types.borrow().get(&key) {
+            return Some(*debug_type);
+        }
+        let debug_type = self.create_type(index, type_name)?;
+        self.types.borrow_mut().insert(key, debug_type);
+        Some(debug_type)
+    }
+
+    fn create_type(&self, index: &Index, type_name: &str) -> Option<DebugType<'ink>> {
+        match index.find_type(type_name)?.get_type_information() {
+            DataTypeInformation::Integer {
+                size: BOOL_SIZE,
+                name,
+                ..
+            } => self.create_basic_type(name, 8, DW_ATE_BOOLEAN),
+            DataTypeInformation::Integer {
+                name, signed, size, ..
+            } => {
+                let encoding = if *signed {
+                    DW_ATE_SIGNED
+                } else {
+                    DW_ATE_UNSIGNED
+                };
+                self.create_basic_type(name, *size as u64, encoding)
+            }
+            DataTypeInformation::Float { name, size } => {
+                self.create_basic_type(name, *size as u64, DW_ATE_FLOAT)
+            }
+            DataTypeInformation::String { size, encoding } => {
+                let character_type = match encoding {
+                    StringEncoding::Utf8 => {
+                        self.create_basic_type("CHAR", 8, DW_ATE_UNSIGNED_CHAR)?
+                    }
+                    StringEncoding::Utf16 => self.create_basic_type("WCHAR", 16, DW_ATE_UTF)?,
+                };
+                let length = size.as_int_value(index).ok()?;
+                Some(self.create_array_type(character_type, &[0..length]))
+            }
+            DataTypeInformation::Array {
+                inner_type_name,
+                dimensions,
+                ..
+            } => {
+                let inner_type = self.get_or_create_type(index, inner_type_name)?;
+                let ranges = dimensions
+                    .iter()
+                    .map(|it| {
+                        let start = it.start_offset.as_int_value(index).ok()?;
+                        let end = it.end_offset.as_int_value(index).ok()?;
+                        Some(start..end + 1)
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(self.create_array_type(inner_type, &ranges))
+            }
+            DataTypeInformation::Pointer {
+                name,
+                inner_type_name,
+                ..
+            } => {
+                //a pointer to a struct that is currently generated refers to itself
+                let is_recursive = self
+                    .pending_structs
+                    .borrow()
+                    .contains(&inner_type_name.to_lowercase());
+                let inner_type = if is_recursive {
+                    self.create_basic_type("BYTE", 8, DW_ATE_UNSIGNED)?
+                } else {
+                    self.get_or_create_type(index, inner_type_name)?
+                };
+                let pointer_type = self.debug_info.create_pointer_type(
+                    name,
+                    inner_type.di_type,
+                    POINTER_SIZE,
+                    POINTER_SIZE as u32,
+                    AddressSpace::Generic,
+                );
+                Some(DebugType {
+                    di_type: pointer_type.as_type(),
+                    size: POINTER_SIZE,
+                    alignment: POINTER_SIZE as u32,
+                })
+            }
+            DataTypeInformation::Struct {
+                name, member_names, ..
+            } => self.create_struct_type(index, name, member_names),
+            //enums are represented as DINTs
+            DataTypeInformation::Enum { name, .. } => self.create_typedef(index, name, DINT_TYPE),
+            DataTypeInformation::SubRange {
+                name,
+                referenced_type,
+                ..
+            }
+            | DataTypeInformation::Alias {
+                name,
+                referenced_type,
+            } => self.create_typedef(index, name, referenced_type),
+            DataTypeInformation::VariableLengthArray {
+                name,
+                inner_type_name,
+                dimension_count,
+            } => self.create_descriptor_type(index, name, inner_type_name, *dimension_count),
+            DataTypeInformation::Generic { .. } | DataTypeInformation::Void => None,
+        }
+    }
+
+    fn create_basic_type(&self, name: &str, size: u64, encoding: u32) -> Option<DebugType<'ink>> {
+        let basic_type = self
+            .debug_info
+            .create_basic_type(name, size, encoding, DIFlags::PUBLIC)
+            .ok()?;
+        Some(DebugType {
+            di_type: basic_type.as_type(),
+            size,
+            alignment: size as u32,
+        })
+    }
+
+    fn create_array_type(
+        &self,
+        inner_type: DebugType<'ink>,
+        dimensions: &[Range<i64>],
+    ) -> DebugType<'ink> {
+        let length: i64 = dimensions.iter().map(|it| it.end - it.start).product();
+        let size = inner_type.size * length.max(0) as u64;
+        let array_type = self.debug_info.create_array_type(
+            inner_type.di_type,
+            size,
+            inner_type.alignment,
+            dimensions,
+        );
+        DebugType {
+            di_type: array_type.as_type(),
+            size,
+            alignment: inner_type.alignment,
+        }
+    }
+
+    fn create_typedef(
+        &self,
+        index: &Index,
+        name: &str,
+        referenced_type: &str,
+    ) -> Option<DebugType<'ink>> {
+        let referenced_type = self.get_or_create_type(index, referenced_type)?;
+        let typedef = self.debug_info.create_typedef(
+            referenced_type.di_type,
+            name,
+            self.file,
+            0,
+            self.compile_unit.as_debug_info_scope(),
+            referenced_type.alignment,
+        );
+        Some(DebugType {
+            di_type: typedef.as_type(),
+            ..referenced_type
+        })
+    }
+
+    /// creates the debug type of a STRUCT or of a POU's instance-struct
+    ///
+    /// the members are laid out in declaration order using their natural alignment
+    fn create_struct_type(
+        &self,
+        index: &Index,
+        name: &str,
+        member_names: &[String],
+    ) -> Option<DebugType<'ink>> {
+        self.pending_structs
+            .borrow_mut()
+            .insert(name.to_lowercase());
+        let members = member_names
+            .iter()
+            .filter_map(|it| index.find_member(name, it))
+            .filter(|it| !it.is_return())
+            .map(|it| {
+                self.get_or_create_type(index, it.get_type_name())
+                    .map(|debug_type| (it.get_name(), debug_type))
+            })
+            .collect::<Option<Vec<_>>>();
+        self.pending_structs
+            .borrow_mut()
+            .remove(&name.to_lowercase());
+        Some(self.create_struct_layout(name, members?))
+    }
+
+    /// describes the descriptor a variable-length array is passed as: a pointer to its
+    /// first element and the lower and upper bound of every dimension
+    fn create_descriptor_type(
+        &self,
+        index: &Index,
+        name: &str,
+        inner_type_name: &str,
+        dimension_count: usize,
+    ) -> Option<DebugType<'ink>> {
+        let inner_type = self.get_or_create_type(index, inner_type_name)?;
+        let data_type = self.debug_info.create_pointer_type(
+            &format!("pointer_to_{}", inner_type_name),
+            inner_type.di_type,
+            POINTER_SIZE,
+            POINTER_SIZE as u32,
+            AddressSpace::Generic,
+        );
+        let data = DebugType {
+            di_type: data_type.as_type(),
+            size: POINTER_SIZE,
+            alignment: POINTER_SIZE as u32,
+        };
+        let bound_type = self.get_or_create_type(index, DINT_TYPE)?;
+        let bounds = self.create_array_type(bound_type, &[0..2 * dimension_count as i64]);
+        Some(self.create_struct_layout(name, vec![("data", data), ("bounds", bounds)]))
+    }
+
+    /// creates a struct type with the given members, every member is aligned to its type
+    fn create_struct_layout(
+        &self,
+        name: &str,
+        members: Vec<(&str, DebugType<'ink>)>,
+    ) -> DebugType<'ink> {
+        let scope = self.compile_unit.as_debug_info_scope();
+        let mut offset = 0;
+        let mut alignment = 8;
+        let mut elements = vec![];
+        for (member_name, member_type) in members {
+            offset = align_to(offset, member_type.alignment);
+            let element = self.debug_info.create_member_type(
+                scope,
+                member_name,
+                self.file,
+                0,
+                member_type.size,
+                member_type.alignment,
+                offset,
+                DIFlags::PUBLIC,
+                member_type.di_type,
+            );
+            elements.push(element.as_type());
+            offset += member_type.size;
+            alignment = alignment.max(member_type.alignment);
+        }
+        let size = align_to(offset, alignment);
+
+        let struct_type = self.debug_info.create_struct_type(
+            scope,
+            name,
+            self.file,
+            0,
+            size,
+            alignment,
+            DIFlags::PUBLIC,
+            None,
+            &elements,
+            0,
+            None,
+            name,
+        );
+        DebugType {
+            di_type: struct_type.as_type(),
+            size,
+            alignment,
+        }
+    }
+}
+
+/// rounds the given offset up to the next multiple of the given alignment (both in bits)
+fn align_to(offset: u64, alignment: u32) -> u64 {
+    let alignment = alignment.max(8) as u64;
+    (offset + alignment - 1) / alignment * alignment
+}
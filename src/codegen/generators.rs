@@ -1,7 +1,10 @@
+pub mod configuration_generator;
 pub mod data_type_generator;
+pub mod date_time_generator;
 pub mod expression_generator;
 pub mod llvm;
 pub mod pou_generator;
 pub mod statement_generator;
+pub mod string_generator;
 pub mod struct_generator;
 pub mod variable_generator;
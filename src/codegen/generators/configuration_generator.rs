@@ -0,0 +1,251 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+use inkwell::{
+    module::Module,
+    types::StructType,
+    values::{BasicValueEnum, FunctionValue, StructValue},
+    AddressSpace,
+};
+
+use crate::{
+    ast::{AstStatement, Configuration, Resource, SourceRange, Task},
+    codegen::llvm_index::LlvmTypedIndex,
+    compile_error::CompileError,
+    index::Index,
+    resolver::{const_evaluator, AnnotationMap},
+    typesystem::{DINT_TYPE, TIME_TYPE},
+};
+
+use super::{expression_generator::ExpressionCodeGenerator, llvm::Llvm};
+
+/// the name of the global constant array describing all tasks
+pub const TASK_TABLE: &str = "__task_table";
+/// the name of the global constant holding the number of entries in the task table
+pub const TASK_COUNT: &str = "__task_count";
+
+/// returns the name of the cycle function of the given resource's task
+pub fn get_cycle_function_name(resource: &str, task: &str) -> String {
+    format!("__cycle_{}_{}", resource, task)
+}
+
+/// generates the scheduler entry points of the given configurations
+///
+/// every task gets an exported cycle function (see `get_cycle_function_name`) that calls the
+/// program instances running with the task in the order of their declaration.
+/// The task table is a constant global array with one entry per task:
+/// `{ i8* resource, i8* task, i64 interval, i32 priority, void()* cycle }`.
+/// The interval is given in nanoseconds, a missing interval or priority is `0`.
+///
+/// A program has a single task table, so all configurations have to be declared in the same
+/// compilation unit. The table is only generated into the object declaring them.
+pub fn generate_configurations<'ink>(
+    module: &Module<'ink>,
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    annotations: &AnnotationMap,
+    llvm_index: &LlvmTypedIndex<'ink>,
+    configurations: &[Configuration],
+) -> Result<(), CompileError> {
+    if configurations.is_empty() {
+        return Ok(());
+    }
+    if module.get_global(TASK_TABLE).is_some() {
+        return Err(CompileError::codegen_error(
+            "All configurations of a program have to be declared in the same file".into(),
+            configurations[0].location.clone(),
+        ));
+    }
+
+    let entry_type = get_task_entry_type(llvm);
+    let mut entries = Vec::new();
+    for resource in configurations.iter().flat_map(|it| it.resources.iter()) {
+        for task in &resource.tasks {
+            let cycle_function = generate_cycle_function(module, llvm, llvm_index, resource, task)?;
+            entries.push(generate_task_entry(
+                module,
+                llvm,
+                index,
+                annotations,
+                llvm_index,
+                entry_type,
+                resource,
+                task,
+                cycle_function,
+            )?);
+        }
+    }
+
+    let task_table = entry_type.const_array(&entries);
+    let global = llvm.create_global_variable(
+        module,
+        TASK_TABLE,
+        task_table.get_type().into(),
+        Some(task_table.into()),
+    );
+    global.set_constant(true);
+
+    let task_count = llvm
+        .context
+        .i32_type()
+        .const_int(entries.len() as u64, false);
+    let global = llvm.create_global_variable(
+        module,
+        TASK_COUNT,
+        task_count.get_type().into(),
+        Some(task_count.into()),
+    );
+    global.set_constant(true);
+    Ok(())
+}
+
+/// the type of an entry in the task table
+fn get_task_entry_type<'ink>(llvm: &Llvm<'ink>) -> StructType<'ink> {
+    let context = llvm.context;
+    let string_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let cycle_type = context
+        .void_type()
+        .fn_type(&[], false)
+        .ptr_type(AddressSpace::Generic);
+    context.struct_type(
+        &[
+            string_type.into(),
+            string_type.into(),
+            context.i64_type().into(),
+            context.i32_type().into(),
+            cycle_type.into(),
+        ],
+        false,
+    )
+}
+
+/// generates the cycle function of the given task that calls all program instances
+/// of the resource running with this task
+fn generate_cycle_function<'ink>(
+    module: &Module<'ink>,
+    llvm: &Llvm<'ink>,
+    llvm_index: &LlvmTypedIndex<'ink>,
+    resource: &Resource,
+    task: &Task,
+) -> Result<FunctionValue<'ink>, CompileError> {
+    let function_type = llvm.context.void_type().fn_type(&[], false);
+    let function = module.add_function(
+        &get_cycle_function_name(&resource.name, &task.name),
+        function_type,
+        None,
+    );
+    let block = llvm.context.append_basic_block(function, "entry");
+    llvm.builder.position_at_end(block);
+
+    let programs = resource.programs.iter().filter(|it| {
+        it.task_name
+            .as_ref()
+            .map_or(false, |it| it.eq_ignore_ascii_case(&task.name))
+    });
+    for program in programs {
+        let implementation = llvm_index
+            .find_associated_implementation(&program.type_name)
+            .ok_or_else(|| {
+                CompileError::invalid_reference(&program.type_name, program.location.clone())
+            })?;
+        let instance = llvm_index
+            .find_associated_variable_value(&program.instance_name)
+            .filter(BasicValueEnum::is_pointer_value)
+            .ok_or_else(|| {
+                CompileError::invalid_reference(&program.instance_name, program.location.clone())
+            })?;
+        llvm.builder.build_call(implementation, &[instance], "call");
+    }
+    llvm.builder.build_return(None);
+    Ok(function)
+}
+
+/// generates the task table's entry of the given task
+#[allow(clippy::too_many_arguments)]
+fn generate_task_entry<'ink>(
+    module: &Module<'ink>,
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    annotations: &AnnotationMap,
+    llvm_index: &LlvmTypedIndex<'ink>,
+    entry_type: StructType<'ink>,
+    resource: &Resource,
+    task: &Task,
+    cycle_function: FunctionValue<'ink>,
+) -> Result<StructValue<'ink>, CompileError> {
+    let resource_name = llvm.create_const_global_string(
+        module,
+        &format!("__task_table.{}", resource.name),
+        &resource.name,
+    )?;
+    let task_name = llvm.create_const_global_string(
+        module,
+        &format!("__task_table.{}.{}", resource.name, task.name),
+        &task.name,
+    )?;
+    let interval = generate_task_property(
+        llvm,
+        index,
+        annotations,
+        llvm_index,
+        task.interval.as_ref(),
+        TIME_TYPE,
+        &task.location,
+    )?;
+    let priority = generate_task_property(
+        llvm,
+        index,
+        annotations,
+        llvm_index,
+        task.priority.as_ref(),
+        DINT_TYPE,
+        &task.location,
+    )?;
+
+    Ok(entry_type.const_named_struct(&[
+        resource_name.into(),
+        task_name.into(),
+        interval,
+        priority,
+        cycle_function.as_global_value().as_pointer_value().into(),
+    ]))
+}
+
+/// generates the constant value of the given INTERVAL or PRIORITY of a task, `0` if missing
+fn generate_task_property<'ink>(
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    annotations: &AnnotationMap,
+    llvm_index: &LlvmTypedIndex<'ink>,
+    property: Option<&AstStatement>,
+    type_name: &str,
+    location: &SourceRange,
+) -> Result<BasicValueEnum<'ink>, CompileError> {
+    let data_type = index.get_type_information(type_name)?;
+    let value = property
+        .map(|it| const_evaluator::evaluate(it, None, index))
+        .transpose()
+        .map_err(|err| CompileError::codegen_error(err, location.clone()))?
+        .flatten();
+    if let Some(value) = value {
+        let generator = ExpressionCodeGenerator::new_context_free(
+            llvm,
+            index,
+            annotations,
+            llvm_index,
+            Some(data_type),
+        );
+        let (_, value) = generator.generate_expression(&value)?;
+        let llvm_type = llvm_index.get_associated_type(type_name)?.into_int_type();
+        if value.is_int_value() {
+            return Ok(value.into_int_value().const_cast(llvm_type, true).into());
+        }
+        Err(CompileError::literal_or_constant_int_expected(
+            location.clone(),
+        ))
+    } else {
+        Ok(llvm_index
+            .get_associated_type(type_name)?
+            .into_int_type()
+            .const_zero()
+            .into())
+    }
+}
@@ -18,6 +18,7 @@ use crate::{
     typesystem::DataType,
 };
 use inkwell::{
+    module::Module,
     types::{ArrayType, BasicType, BasicTypeEnum},
     values::BasicValueEnum,
     AddressSpace,
@@ -29,6 +30,7 @@ use super::{
 
 pub struct DataTypeGenerator<'ink, 'b> {
     llvm: &'b Llvm<'ink>,
+    module: &'b Module<'ink>,
     index: &'b Index,
     annotations: &'b AnnotationMap,
     types_index: LlvmTypedIndex<'ink>,
@@ -40,21 +42,30 @@ pub struct DataTypeGenerator<'ink, 'b> {
 /// - global variables for enum-elements
 /// - an alias index entry for sub-range types
 /// - Array type for arrays
+/// - descriptor struct type for variable-length arrays
 /// - array type for sized Strings
 pub fn generate_data_types<'ink>(
+    module: &Module<'ink>,
     llvm: &Llvm<'ink>,
     index: &Index,
     annotations: &AnnotationMap,
 ) -> Result<LlvmTypedIndex<'ink>, CompileError> {
     let mut generator = DataTypeGenerator {
         llvm,
+        module,
         index,
         annotations,
         types_index: LlvmTypedIndex::new(),
     };
 
-    let types = generator.index.get_types();
-    for (name, user_type) in types {
+    //generic types and the interfaces of builtin functions have no llvm representation
+    let types: Vec<(&String, &DataType)> = generator
+        .index
+        .get_types()
+        .iter()
+        .filter(|(name, it)| !it.get_type_information().is_generic() && !index.is_builtin(name))
+        .collect();
+    for &(name, user_type) in &types {
         if let DataTypeInformation::Struct {
             name: struct_name, ..
         } = user_type.get_type_information()
@@ -64,11 +75,11 @@ pub fn generate_data_types<'ink>(
                 .associate_type(name, llvm.create_struct_stub(struct_name).into())?;
         }
     }
-    for (name, user_type) in types {
+    for &(name, user_type) in &types {
         let gen_type = generator.create_type(name, user_type)?;
         generator.types_index.associate_type(name, gen_type)?
     }
-    for (name, user_type) in types {
+    for &(name, user_type) in &types {
         generator.expand_opaque_types(user_type)?;
         if let Some(initial_value) = generator.generate_initial_value(user_type) {
             generator
@@ -84,8 +95,13 @@ impl<'ink, 'b> DataTypeGenerator<'ink, 'b> {
     fn expand_opaque_types(&mut self, data_type: &DataType) -> Result<(), CompileError> {
         let information = data_type.get_type_information();
         if let DataTypeInformation::Struct { member_names, .. } = information {
-            let mut struct_generator =
-                StructGenerator::new(self.llvm, self.index, self.annotations, &self.types_index);
+            let mut struct_generator = StructGenerator::new(
+                self.llvm,
+                self.module,
+                self.index,
+                self.annotations,
+                &self.types_index,
+            );
             let members: Vec<&VariableIndexEntry> = member_names
                 .iter()
                 .map(|variable_name| {
@@ -138,6 +154,27 @@ impl<'ink, 'b> DataTypeGenerator<'ink, 'b> {
                     .map_err(|err| CompileError::codegen_error(err, SourceRange::undefined()))
                 //TODO error location
             }
+            DataTypeInformation::VariableLengthArray {
+                inner_type_name,
+                dimension_count,
+                ..
+            } => {
+                let inner_type =
+                    self.create_type(inner_type_name, self.index.get_type(inner_type_name)?)?;
+                //the descriptor: { pointer to the first element, [lower, upper] of every dimension }
+                let bounds_type = self.llvm.i32_type().array_type(2 * *dimension_count as u32);
+                Ok(self
+                    .llvm
+                    .context
+                    .struct_type(
+                        &[
+                            inner_type.ptr_type(AddressSpace::Generic).into(),
+                            bounds_type.into(),
+                        ],
+                        false,
+                    )
+                    .into())
+            }
             DataTypeInformation::Integer { size, .. } => {
                 get_llvm_int_type(self.llvm.context, *size, name).map(|it| it.into())
             }
@@ -185,6 +222,10 @@ impl<'ink, 'b> DataTypeGenerator<'ink, 'b> {
                     self.create_type(inner_type_name, self.index.get_type(inner_type_name)?)?;
                 Ok(inner_type.ptr_type(AddressSpace::Generic).into())
             }
+            DataTypeInformation::Generic { .. } => Err(CompileError::codegen_error(
+                format!("Cannot generate generic type {}", name),
+                SourceRange::undefined(),
+            )),
         }
     }
 
@@ -200,6 +241,7 @@ impl<'ink, 'b> DataTypeGenerator<'ink, 'b> {
                     "LiteralArray",
                 )
                 .unwrap(),
+            DataTypeInformation::VariableLengthArray { .. } => None,
             DataTypeInformation::Integer { .. } => None,
             DataTypeInformation::Enum { .. } => None,
             DataTypeInformation::Float { .. } => None,
@@ -220,6 +262,7 @@ impl<'ink, 'b> DataTypeGenerator<'ink, 'b> {
             // Void types are not basic type enums, so we return an int here
             DataTypeInformation::Void => None, //get_llvm_int_type(llvm.context, 32, "Void").map(Into::into),
             DataTypeInformation::Pointer { .. } => None,
+            DataTypeInformation::Generic { .. } => None,
         }
     }
 
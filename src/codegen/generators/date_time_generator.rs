@@ -0,0 +1,571 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+use super::{llvm::Llvm, statement_generator::FunctionContext};
+use crate::typesystem::{
+    DataTypeInformation, DATE_AND_TIME_TYPE, DATE_TYPE, SHORT_DATE_AND_TIME_TYPE, SHORT_DATE_TYPE,
+    SHORT_TIME_OF_DAY_TYPE, SHORT_TIME_TYPE, TIME_OF_DAY_TYPE, TIME_TYPE,
+};
+use inkwell::{
+    types::FunctionType,
+    values::{BasicValueEnum, FloatValue, IntValue, PointerValue},
+    AddressSpace, IntPredicate,
+};
+
+const MILLIS_PER_SECOND: i64 = 1000;
+const MILLIS_PER_MINUTE: i64 = 60 * MILLIS_PER_SECOND;
+const MILLIS_PER_HOUR: i64 = 60 * MILLIS_PER_MINUTE;
+const MILLIS_PER_DAY: i64 = 24 * MILLIS_PER_HOUR;
+/// the units of a TIME with their length in nanoseconds, from the biggest to the smallest
+const TIME_UNITS: [(&str, i64); 7] = [
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+/// the days from 0000-03-01 to 1970-01-01 in the proleptic gregorian calendar
+const DAYS_TO_EPOCH: i64 = 719_468;
+const DAYS_PER_ERA: i64 = 146_097;
+
+/// the kinds of date and time types
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateTimeKind {
+    /// DATE, stored as milliseconds since 1970-01-01
+    Date,
+    /// DATE_AND_TIME, stored as milliseconds since 1970-01-01-00:00:00
+    DateAndTime,
+    /// TIME_OF_DAY, stored as milliseconds since midnight
+    TimeOfDay,
+    /// TIME, stored as nanoseconds
+    Time,
+}
+
+impl DateTimeKind {
+    /// returns the kind of the given type or None if it is no date or time type
+    pub fn of(data_type: &DataTypeInformation) -> Option<DateTimeKind> {
+        let is_one_of = |names: &[&str]| {
+            names
+                .iter()
+                .any(|it| data_type.get_name().eq_ignore_ascii_case(it))
+        };
+        if is_one_of(&[DATE_TYPE, SHORT_DATE_TYPE]) {
+            Some(DateTimeKind::Date)
+        } else if is_one_of(&[DATE_AND_TIME_TYPE, SHORT_DATE_AND_TIME_TYPE]) {
+            Some(DateTimeKind::DateAndTime)
+        } else if is_one_of(&[TIME_OF_DAY_TYPE, SHORT_TIME_OF_DAY_TYPE]) {
+            Some(DateTimeKind::TimeOfDay)
+        } else if is_one_of(&[TIME_TYPE, SHORT_TIME_TYPE]) {
+            Some(DateTimeKind::Time)
+        } else {
+            None
+        }
+    }
+}
+
+/// generates the conversions of dates and times from and to STRING.
+///
+/// dates and times are formatted like their literals (e.g. `D#2021-08-31`,
+/// `TOD#12:30:00.000` or `T#1h30m`). Strings are parsed with or without the literal's
+/// prefix, fields that cannot be parsed are left at their default (`D#1970-01-01`, `0`).
+pub struct DateTimeGenerator<'a, 'b> {
+    llvm: &'b Llvm<'a>,
+    function_context: &'b FunctionContext<'a, 'b>,
+}
+
+impl<'a, 'b> DateTimeGenerator<'a, 'b> {
+    /// creates a new DateTimeGenerator generating into the given function
+    pub fn new(
+        llvm: &'b Llvm<'a>,
+        function_context: &'b FunctionContext<'a, 'b>,
+    ) -> DateTimeGenerator<'a, 'b> {
+        DateTimeGenerator {
+            llvm,
+            function_context,
+        }
+    }
+
+    /// returns the `printf` format and its arguments to print the given date or time
+    pub fn get_format(
+        &self,
+        value: IntValue<'a>,
+        kind: DateTimeKind,
+    ) -> (String, Vec<BasicValueEnum<'a>>) {
+        let builder = &self.llvm.builder;
+        let value = builder.build_int_s_extend_or_bit_cast(value, self.llvm.context.i64_type(), "");
+        match kind {
+            DateTimeKind::Date => {
+                let (year, month, day) = self.days_to_date(self.floor_div(value, MILLIS_PER_DAY));
+                (
+                    "D#%04lld-%02lld-%02lld".to_string(),
+                    vec![year.into(), month.into(), day.into()],
+                )
+            }
+            DateTimeKind::DateAndTime => {
+                let days = self.floor_div(value, MILLIS_PER_DAY);
+                let (year, month, day) = self.days_to_date(days);
+                let millis = builder.build_int_sub(
+                    value,
+                    builder.build_int_mul(days, self.const_int(MILLIS_PER_DAY), ""),
+                    "",
+                );
+                let mut arguments = vec![year.into(), month.into(), day.into()];
+                arguments.append(&mut self.get_time_of_day(millis));
+                (
+                    "DT#%04lld-%02lld-%02lld-%02lld:%02lld:%02lld.%03lld".to_string(),
+                    arguments,
+                )
+            }
+            DateTimeKind::TimeOfDay => {
+                let days = self.floor_div(value, MILLIS_PER_DAY);
+                let millis = builder.build_int_sub(
+                    value,
+                    builder.build_int_mul(days, self.const_int(MILLIS_PER_DAY), ""),
+                    "",
+                );
+                (
+                    "TOD#%02lld:%02lld:%02lld.%03lld".to_string(),
+                    self.get_time_of_day(millis),
+                )
+            }
+            DateTimeKind::Time => self.get_time_format(value),
+        }
+    }
+
+    /// parses the given zero-terminated string as a date or time of the given kind
+    pub fn parse(
+        &self,
+        string: PointerValue<'a>,
+        kind: DateTimeKind,
+    ) -> Result<IntValue<'a>, String> {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let i64_type = context.i64_type();
+        let string_pointer_type = context.i8_type().ptr_type(AddressSpace::Generic);
+
+        //skip the literal's prefix (e.g. `DT#`)
+        let hash = self.call(
+            "strchr",
+            string_pointer_type.fn_type(
+                &[string_pointer_type.into(), context.i32_type().into()],
+                false,
+            ),
+            &[
+                string.into(),
+                context.i32_type().const_int('#' as u64, false).into(),
+            ],
+        )?;
+        let hash = hash.into_pointer_value();
+        let after_hash = unsafe { builder.build_in_bounds_gep(hash, &[self.const_int(1)], "") };
+        let start = builder
+            .build_select(builder.build_is_null(hash, ""), string, after_hash, "")
+            .into_pointer_value();
+
+        if kind == DateTimeKind::Time {
+            return self.parse_time(start);
+        }
+
+        let year = self.allocate_int(1970);
+        let month = self.allocate_int(1);
+        let day = self.allocate_int(1);
+        let hour = self.allocate_int(0);
+        let minute = self.allocate_int(0);
+        let second = builder.build_alloca(context.f64_type(), "");
+        builder.build_store(second, context.f64_type().const_zero());
+        let (format, fields) = match kind {
+            DateTimeKind::Date => ("%lld-%lld-%lld", vec![year, month, day]),
+            DateTimeKind::DateAndTime => (
+                "%lld-%lld-%lld-%lld:%lld:%lf",
+                vec![year, month, day, hour, minute, second],
+            ),
+            _ => ("%lld:%lld:%lf", vec![hour, minute, second]),
+        };
+        let format = builder
+            .build_global_string_ptr(format, "")
+            .as_pointer_value();
+        let mut arguments: Vec<BasicValueEnum<'a>> = vec![start.into(), format.into()];
+        arguments.extend(fields.into_iter().map(BasicValueEnum::from));
+        self.call(
+            "sscanf",
+            context.i32_type().fn_type(
+                &[string_pointer_type.into(), string_pointer_type.into()],
+                true,
+            ),
+            &arguments,
+        )?;
+
+        let load = |pointer: PointerValue<'a>| builder.build_load(pointer, "").into_int_value();
+        let millis = builder.build_float_to_signed_int(
+            self.round(builder.build_float_mul(
+                builder.build_load(second, "").into_float_value(),
+                context.f64_type().const_float(MILLIS_PER_SECOND as f64),
+                "",
+            ))?,
+            i64_type,
+            "",
+        );
+        let millis = builder.build_int_add(
+            builder.build_int_add(
+                builder.build_int_mul(load(hour), self.const_int(MILLIS_PER_HOUR), ""),
+                builder.build_int_mul(load(minute), self.const_int(MILLIS_PER_MINUTE), ""),
+                "",
+            ),
+            millis,
+            "",
+        );
+        Ok(match kind {
+            DateTimeKind::TimeOfDay => millis,
+            _ => {
+                let days = self.date_to_days(load(year), load(month), load(day));
+                let date = builder.build_int_mul(days, self.const_int(MILLIS_PER_DAY), "");
+                builder.build_int_add(date, millis, "")
+            }
+        })
+    }
+
+    /// returns the format of a TIME and its arguments. Units with a value of 0 are left out
+    /// (e.g. `T#1h30m`), `T#0s` is printed for 0
+    fn get_time_format(&self, value: IntValue<'a>) -> (String, Vec<BasicValueEnum<'a>>) {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let is_negative =
+            builder.build_int_compare(IntPredicate::SLT, value, self.const_int(0), "");
+        let is_zero = builder.build_int_compare(IntPredicate::EQ, value, self.const_int(0), "");
+        let magnitude = builder
+            .build_select(is_negative, builder.build_int_neg(value, ""), value, "")
+            .into_int_value();
+        let empty = builder.build_global_string_ptr("", "").as_pointer_value();
+        let minus = builder.build_global_string_ptr("-", "").as_pointer_value();
+
+        let mut format = "T#%s".to_string();
+        let mut arguments = vec![builder.build_select(is_negative, minus, empty, "")];
+        let mut bigger_unit = None;
+        for (unit, nanos) in TIME_UNITS.iter() {
+            //the number of units, without the bigger units
+            let count = builder.build_int_unsigned_div(magnitude, self.const_int(*nanos), "");
+            let count = match bigger_unit {
+                Some(bigger_nanos) => {
+                    builder.build_int_unsigned_rem(count, self.const_int(bigger_nanos / nanos), "")
+                }
+                None => count,
+            };
+            bigger_unit = Some(*nanos);
+            let is_shown =
+                builder.build_int_compare(IntPredicate::NE, count, self.const_int(0), "");
+            let is_shown = if *unit == "s" {
+                builder.build_or(is_shown, is_zero, "")
+            } else {
+                is_shown
+            };
+            //a precision of 0 prints no digits for a count of 0
+            format.push_str("%.*lld%s");
+            arguments.push(
+                builder
+                    .build_int_z_extend(is_shown, context.i32_type(), "")
+                    .into(),
+            );
+            arguments.push(count.into());
+            let unit = builder.build_global_string_ptr(unit, "").as_pointer_value();
+            arguments.push(builder.build_select(is_shown, unit, empty, ""));
+        }
+        (format, arguments)
+    }
+
+    /// parses the units of a TIME (e.g. `-1h30.5m`) starting at the given position
+    fn parse_time(&self, start: PointerValue<'a>) -> Result<IntValue<'a>, String> {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let function = self.function_context.function;
+        let i8_type = context.i8_type();
+        let string_pointer_type = i8_type.ptr_type(AddressSpace::Generic);
+        let condition_block = context.append_basic_block(function, "time_condition");
+        let add_block = context.append_basic_block(function, "time_add");
+        let end_block = context.append_basic_block(function, "time_end");
+
+        let character = |pointer: PointerValue<'a>, offset: i64| {
+            let pointer =
+                unsafe { builder.build_in_bounds_gep(pointer, &[self.const_int(offset)], "") };
+            builder.build_load(pointer, "").into_int_value()
+        };
+        let is_negative = builder.build_int_compare(
+            IntPredicate::EQ,
+            character(start, 0),
+            i8_type.const_int('-' as u64, false),
+            "",
+        );
+        let after_sign = unsafe { builder.build_in_bounds_gep(start, &[self.const_int(1)], "") };
+        let position = builder.build_alloca(string_pointer_type, "");
+        builder.build_store(
+            position,
+            builder.build_select(is_negative, after_sign, start, ""),
+        );
+        let end = builder.build_alloca(string_pointer_type, "");
+        let total = self.allocate_int(0);
+        builder.build_unconditional_branch(condition_block);
+
+        //while a number followed by a unit is found
+        builder.position_at_end(condition_block);
+        let current = builder.build_load(position, "").into_pointer_value();
+        let number = self
+            .call(
+                "strtod",
+                context.f64_type().fn_type(
+                    &[
+                        string_pointer_type.into(),
+                        string_pointer_type.ptr_type(AddressSpace::Generic).into(),
+                    ],
+                    false,
+                ),
+                &[current.into(), end.into()],
+            )?
+            .into_float_value();
+        let unit = builder.build_load(end, "").into_pointer_value();
+        let is_parsed = builder.build_int_compare(
+            IntPredicate::NE,
+            builder.build_ptr_to_int(unit, context.i64_type(), ""),
+            builder.build_ptr_to_int(current, context.i64_type(), ""),
+            "",
+        );
+        //units are case-insensitive, the second character is not read after the terminator
+        let lower_case = |character: IntValue<'a>| {
+            builder.build_or(character, i8_type.const_int(0x20, false), "")
+        };
+        let first = character(unit, 0);
+        let is_terminated =
+            builder.build_int_compare(IntPredicate::EQ, first, i8_type.const_zero(), "");
+        let second_pointer = builder
+            .build_select(
+                is_terminated,
+                unit,
+                unsafe { builder.build_in_bounds_gep(unit, &[self.const_int(1)], "") },
+                "",
+            )
+            .into_pointer_value();
+        let (first, second) = (lower_case(first), lower_case(character(second_pointer, 0)));
+        let is = |value: IntValue<'a>, expected: char| {
+            builder.build_int_compare(
+                IntPredicate::EQ,
+                value,
+                i8_type.const_int(expected as u64, false),
+                "",
+            )
+        };
+        let mut nanos = context.f64_type().const_zero();
+        let mut unit_length = self.const_int(1);
+        let mut is_unit = context.bool_type().const_zero();
+        //the two-letter units (ns, us, ms) are checked first, so `ms` is not read as minutes
+        for (name, unit_nanos) in TIME_UNITS.iter().rev() {
+            let mut characters = name.chars();
+            let is_this_unit = match (characters.next(), characters.next()) {
+                (Some(first_character), Some(second_character)) => {
+                    builder.build_and(is(first, first_character), is(second, second_character), "")
+                }
+                (Some(first_character), _) => is(first, first_character),
+                _ => continue,
+            };
+            let is_this_unit = builder.build_and(is_this_unit, builder.build_not(is_unit, ""), "");
+            nanos = builder
+                .build_select(
+                    is_this_unit,
+                    context.f64_type().const_float(*unit_nanos as f64),
+                    nanos,
+                    "",
+                )
+                .into_float_value();
+            unit_length = builder
+                .build_select(
+                    is_this_unit,
+                    self.const_int(name.len() as i64),
+                    unit_length,
+                    "",
+                )
+                .into_int_value();
+            is_unit = builder.build_or(is_unit, is_this_unit, "");
+        }
+        builder.build_conditional_branch(
+            builder.build_and(is_parsed, is_unit, ""),
+            add_block,
+            end_block,
+        );
+
+        builder.position_at_end(add_block);
+        let value = builder.build_float_to_signed_int(
+            self.round(builder.build_float_mul(number, nanos, ""))?,
+            context.i64_type(),
+            "",
+        );
+        let sum = builder.build_int_add(builder.build_load(total, "").into_int_value(), value, "");
+        builder.build_store(total, sum);
+        builder.build_store(position, unsafe {
+            builder.build_in_bounds_gep(unit, &[unit_length], "")
+        });
+        builder.build_unconditional_branch(condition_block);
+
+        builder.position_at_end(end_block);
+        let total = builder.build_load(total, "").into_int_value();
+        Ok(builder
+            .build_select(is_negative, builder.build_int_neg(total, ""), total, "")
+            .into_int_value())
+    }
+
+    /// returns the hours, minutes, seconds and milliseconds of the given milliseconds since midnight
+    fn get_time_of_day(&self, millis: IntValue<'a>) -> Vec<BasicValueEnum<'a>> {
+        let builder = &self.llvm.builder;
+        let part = |unit: i64, count: i64| -> BasicValueEnum<'a> {
+            let value = builder.build_int_signed_div(millis, self.const_int(unit), "");
+            builder
+                .build_int_signed_rem(value, self.const_int(count), "")
+                .into()
+        };
+        vec![
+            part(MILLIS_PER_HOUR, 24),
+            part(MILLIS_PER_MINUTE, 60),
+            part(MILLIS_PER_SECOND, 60),
+            part(1, 1000),
+        ]
+    }
+
+    /// returns the year, month and day of the given days since 1970-01-01
+    /// (see http://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+    fn days_to_date(&self, days: IntValue<'a>) -> (IntValue<'a>, IntValue<'a>, IntValue<'a>) {
+        let builder = &self.llvm.builder;
+        let c = |value: i64| self.const_int(value);
+        let add = |left: IntValue<'a>, right| builder.build_int_add(left, right, "");
+        let sub = |left: IntValue<'a>, right| builder.build_int_sub(left, right, "");
+        let mul = |left: IntValue<'a>, right| builder.build_int_mul(left, right, "");
+        let div = |left: IntValue<'a>, right| builder.build_int_signed_div(left, right, "");
+
+        let shifted = add(days, c(DAYS_TO_EPOCH));
+        let era = self.floor_div(shifted, DAYS_PER_ERA);
+        let day_of_era = sub(shifted, mul(era, c(DAYS_PER_ERA)));
+        let year_of_era = div(
+            sub(
+                add(
+                    sub(day_of_era, div(day_of_era, c(1460))),
+                    div(day_of_era, c(36524)),
+                ),
+                div(day_of_era, c(146_096)),
+            ),
+            c(365),
+        );
+        let day_of_year = sub(
+            day_of_era,
+            sub(
+                add(mul(year_of_era, c(365)), div(year_of_era, c(4))),
+                div(year_of_era, c(100)),
+            ),
+        );
+        //the month starting with march
+        let month = div(add(mul(day_of_year, c(5)), c(2)), c(153));
+        let day = add(
+            sub(day_of_year, div(add(mul(month, c(153)), c(2)), c(5))),
+            c(1),
+        );
+        let is_after_february = builder.build_int_compare(IntPredicate::SLT, month, c(10), "");
+        let month = builder
+            .build_select(is_after_february, add(month, c(3)), sub(month, c(9)), "")
+            .into_int_value();
+        let year = add(year_of_era, mul(era, c(400)));
+        let year = builder
+            .build_select(is_after_february, year, add(year, c(1)), "")
+            .into_int_value();
+        (year, month, day)
+    }
+
+    /// returns the days since 1970-01-01 of the given date
+    /// (see http://howardhinnant.github.io/date_algorithms.html#days_from_civil)
+    fn date_to_days(
+        &self,
+        year: IntValue<'a>,
+        month: IntValue<'a>,
+        day: IntValue<'a>,
+    ) -> IntValue<'a> {
+        let builder = &self.llvm.builder;
+        let c = |value: i64| self.const_int(value);
+        let add = |left: IntValue<'a>, right| builder.build_int_add(left, right, "");
+        let sub = |left: IntValue<'a>, right| builder.build_int_sub(left, right, "");
+        let mul = |left: IntValue<'a>, right| builder.build_int_mul(left, right, "");
+        let div = |left: IntValue<'a>, right| builder.build_int_signed_div(left, right, "");
+
+        let is_after_february = builder.build_int_compare(IntPredicate::SGT, month, c(2), "");
+        //the year starting with march
+        let year = builder
+            .build_select(is_after_february, year, sub(year, c(1)), "")
+            .into_int_value();
+        let era = self.floor_div(year, 400);
+        let year_of_era = sub(year, mul(era, c(400)));
+        let month = builder
+            .build_select(is_after_february, sub(month, c(3)), add(month, c(9)), "")
+            .into_int_value();
+        let day_of_year = sub(add(div(add(mul(month, c(153)), c(2)), c(5)), day), c(1));
+        let day_of_era = add(
+            sub(
+                add(mul(year_of_era, c(365)), div(year_of_era, c(4))),
+                div(year_of_era, c(100)),
+            ),
+            day_of_year,
+        );
+        sub(add(mul(era, c(DAYS_PER_ERA)), day_of_era), c(DAYS_TO_EPOCH))
+    }
+
+    /// divides the given value by the given divisor, rounding towards negative infinity
+    fn floor_div(&self, value: IntValue<'a>, divisor: i64) -> IntValue<'a> {
+        let builder = &self.llvm.builder;
+        let quotient = builder.build_int_signed_div(value, self.const_int(divisor), "");
+        let remainder = builder.build_int_signed_rem(value, self.const_int(divisor), "");
+        let is_negative =
+            builder.build_int_compare(IntPredicate::SLT, remainder, self.const_int(0), "");
+        builder
+            .build_select(
+                is_negative,
+                builder.build_int_sub(quotient, self.const_int(1), ""),
+                quotient,
+                "",
+            )
+            .into_int_value()
+    }
+
+    /// rounds the given LREAL to the nearest integral value (`llvm.round`)
+    fn round(&self, value: FloatValue<'a>) -> Result<FloatValue<'a>, String> {
+        let f64_type = self.llvm.context.f64_type();
+        Ok(self
+            .call(
+                "llvm.round.f64",
+                f64_type.fn_type(&[f64_type.into()], false),
+                &[value.into()],
+            )?
+            .into_float_value())
+    }
+
+    /// allocates a LINT with the given initial value on the stack
+    fn allocate_int(&self, value: i64) -> PointerValue<'a> {
+        let builder = &self.llvm.builder;
+        let pointer = builder.build_alloca(self.llvm.context.i64_type(), "");
+        builder.build_store(pointer, self.const_int(value));
+        pointer
+    }
+
+    /// calls the given external function (e.g. `sscanf` of the C standard library),
+    /// the function is declared in the current module if necessary
+    fn call(
+        &self,
+        name: &str,
+        function_type: FunctionType<'a>,
+        arguments: &[BasicValueEnum<'a>],
+    ) -> Result<BasicValueEnum<'a>, String> {
+        let module = self.function_context.module;
+        let function = module
+            .get_function(name)
+            .unwrap_or_else(|| module.add_function(name, function_type, None));
+        self.llvm
+            .builder
+            .build_call(function, arguments, "")
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| format!("{} does not return a value", name))
+    }
+
+    fn const_int(&self, value: i64) -> IntValue<'a> {
+        self.llvm.context.i64_type().const_int(value as u64, true)
+    }
+}
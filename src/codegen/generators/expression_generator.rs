@@ -1,26 +1,43 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use crate::{
-    ast::{Pou, SourceRange},
-    index::{ImplementationType, Index},
+    ast::{DirectAccessType, HardwareAddress, Pou, SourceRange},
+    builtins::{self, Conversion},
+    index::{
+        ImplementationIndexEntry, ImplementationType, Index, PouIndexEntry,
+        INTERFACE_INSTANCE_MEMBER, INTERFACE_VTABLE_MEMBER, METHOD_TABLE_MEMBER,
+    },
     resolver::{AnnotationMap, StatementAnnotation},
-    typesystem::{Dimension, StringEncoding, DINT_TYPE, LINT_TYPE, LREAL_TYPE},
+    typesystem::{
+        Dimension, StringEncoding, TypeSize, BOOL_TYPE, BOUNDS_CHECK_FN, DATE_AND_TIME_TYPE,
+        DATE_TYPE, DINT_TYPE, DIV_CHECK_FN, DIV_CHECK_L_FN, LINT_TYPE, LREAL_TYPE,
+        OVERFLOW_CHECK_FN, POINTER_CHECK_FN, RUNTIME_CHECK_FNS, SHORT_DATE_AND_TIME_TYPE,
+        SHORT_DATE_TYPE, SHORT_TIME_OF_DAY_TYPE, SHORT_TIME_TYPE, TIME_OF_DAY_TYPE, TIME_TYPE,
+        ULINT_TYPE,
+    },
 };
 use inkwell::{
     basic_block::BasicBlock,
-    types::BasicTypeEnum,
+    targets::TargetData,
+    types::{AnyTypeEnum, BasicType, BasicTypeEnum, FunctionType, IntType},
     values::{
-        ArrayValue, BasicValue, BasicValueEnum, FloatValue, IntValue, PointerValue, StructValue,
-        VectorValue,
+        ArrayValue, BasicValue, BasicValueEnum, CallableValue, FloatValue, IntValue, PointerValue,
+        StructValue, VectorValue,
     },
     AddressSpace, FloatPredicate, IntPredicate,
 };
-use std::{collections::HashSet, convert::TryInto};
+use std::{
+    collections::HashSet,
+    convert::{TryFrom, TryInto},
+};
 
 use crate::{
     ast::{flatten_expression_list, AstStatement, Operator},
     codegen::{
         llvm_index::LlvmTypedIndex,
-        llvm_typesystem::{cast_if_needed, get_llvm_int_type, promote_if_needed},
+        llvm_typesystem::{
+            cast_if_needed, get_llvm_float_type, get_llvm_int_type, get_type_size,
+            promote_if_needed,
+        },
         TypeAndPointer, TypeAndValue,
     },
     compile_error::CompileError,
@@ -28,10 +45,23 @@ use crate::{
     typesystem::{DataType, DataTypeInformation},
 };
 
-use super::{llvm::Llvm, statement_generator::FunctionContext, struct_generator};
+use super::{
+    date_time_generator::{DateTimeGenerator, DateTimeKind},
+    llvm::Llvm,
+    pou_generator::get_vtable_name,
+    statement_generator::{create_call_to_check_function_ast, FunctionContext},
+    string_generator::StringGenerator,
+    struct_generator,
+    variable_generator::get_located_variable_pointer,
+};
 
 use chrono::{LocalResult, TimeZone, Utc};
 
+/// DATE, DATE_AND_TIME and TIME_OF_DAY are stored in milliseconds
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+/// TIME is stored in nanoseconds
+const NANOS_PER_MILLI: u64 = 1_000_000;
+
 /// the generator for expressions
 pub struct ExpressionCodeGenerator<'a, 'b> {
     llvm: &'b Llvm<'a>,
@@ -41,7 +71,7 @@ pub struct ExpressionCodeGenerator<'a, 'b> {
     /// an optional type hint for generating literals
     type_hint: Option<DataTypeInformation>,
     /// the current function to create blocks in
-    function_context: Option<&'b FunctionContext<'a>>,
+    function_context: Option<&'b FunctionContext<'a, 'b>>,
 
     /// the string-prefix to use for temporary variables
     pub temp_variable_prefix: String,
@@ -71,7 +101,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         annotations: &'b AnnotationMap,
         llvm_index: &'b LlvmTypedIndex<'a>,
         type_hint: Option<DataTypeInformation>,
-        function_context: &'b FunctionContext<'a>,
+        function_context: &'b FunctionContext<'a, 'b>,
     ) -> ExpressionCodeGenerator<'a, 'b> {
         ExpressionCodeGenerator {
             llvm,
@@ -127,7 +157,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
     fn get_function_context(
         &self,
         statement: &AstStatement,
-    ) -> Result<&'b FunctionContext<'a>, CompileError> {
+    ) -> Result<&'b FunctionContext<'a, 'b>, CompileError> {
         self.function_context
             .ok_or_else(|| CompileError::missing_function(statement.get_location()))
     }
@@ -163,6 +193,11 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             }
         }
 
+        //bit-located variables cannot be loaded through a pointer
+        if let Some(address) = self.find_located_bit_address(expression) {
+            return self.generate_located_bit_load(address);
+        }
+
         match expression {
             AstStatement::Reference { name, .. } => {
                 let load_name = format!(
@@ -208,6 +243,41 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 let left_type_and_value = self.generate_expression(left)?;
                 let right_type_and_value = self.generate_expression(right)?;
 
+                //typed pointer arithmetic: pointer +/- integer, integer + pointer
+                let is_pointer = |(data_type, _): &TypeAndValue<'a>| {
+                    matches!(
+                        self.index
+                            .find_effective_type_information(data_type)
+                            .unwrap_or(data_type),
+                        DataTypeInformation::Pointer {
+                            auto_deref: false,
+                            ..
+                        }
+                    )
+                };
+                let is_offset = |(data_type, _): &TypeAndValue<'a>| data_type.is_int();
+                if matches!(operator, Operator::Plus | Operator::Minus)
+                    && is_pointer(&left_type_and_value)
+                    && is_offset(&right_type_and_value)
+                {
+                    return self.generate_pointer_arithmetic(
+                        operator,
+                        left_type_and_value,
+                        right_type_and_value,
+                        expression,
+                    );
+                } else if operator == &Operator::Plus
+                    && is_offset(&left_type_and_value)
+                    && is_pointer(&right_type_and_value)
+                {
+                    return self.generate_pointer_arithmetic(
+                        operator,
+                        right_type_and_value,
+                        left_type_and_value,
+                        expression,
+                    );
+                }
+
                 let (common_type, left_value, right_value) = promote_if_needed(
                     self.llvm.context,
                     builder,
@@ -218,12 +288,13 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 );
 
                 if common_type.is_int() {
-                    Ok(self.create_llvm_int_binary_expression(
+                    self.generate_int_binary_expression(
                         operator,
                         left_value,
                         right_value,
                         &common_type,
-                    ))
+                        expression,
+                    )
                 } else if common_type.is_float() {
                     Ok(self.create_llvm_float_binary_expression(
                         operator,
@@ -243,7 +314,13 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 operator,
                 parameters,
                 ..
-            } => self.generate_call_statement(operator, parameters),
+            } => {
+                if let Some(builtin) = self.find_builtin(operator) {
+                    self.generate_builtin_call(builtin, expression, parameters)
+                } else {
+                    self.generate_call_statement(operator, parameters)
+                }
+            }
             AstStatement::UnaryExpression {
                 operator, value, ..
             } => self.generate_unary_expression(operator, value),
@@ -252,6 +329,93 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         }
     }
 
+    /// returns the address of the variable referenced by the given statement if it is a
+    /// bit-located variable (e.g. `x AT %IX1.2 : BOOL`)
+    pub fn find_located_bit_address(
+        &self,
+        statement: &AstStatement,
+    ) -> Option<&'b HardwareAddress> {
+        if let Some(StatementAnnotation::Variable { qualified_name, .. }) =
+            self.annotations.get_annotation(statement)
+        {
+            let variable = match qualified_name.rfind('.') {
+                Some(pos) => self
+                    .index
+                    .find_member(&qualified_name[..pos], &qualified_name[pos + 1..]),
+                None => self.index.find_global_variable(qualified_name),
+            };
+            variable
+                .and_then(VariableIndexEntry::get_hardware_address)
+                .filter(|it| it.access == DirectAccessType::Bit)
+        } else {
+            None
+        }
+    }
+
+    /// loads the bit addressed by the given bit-address as a BOOL
+    fn generate_located_bit_load(
+        &self,
+        address: &HardwareAddress,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let byte_type = self.llvm.context.i8_type();
+        let byte_ptr =
+            get_located_variable_pointer(self.llvm, self.llvm_index, address, byte_type.into())?;
+        let builder = &self.llvm.builder;
+        let byte = builder.build_load(byte_ptr, "load_byte").into_int_value();
+        let shift = builder.build_right_shift(
+            byte,
+            byte_type.const_int(address.get_bit_offset() as u64, false),
+            false,
+            "shift",
+        );
+        let bool_type = self.index.get_type_information(BOOL_TYPE)?;
+        let value = builder.build_int_truncate(
+            shift,
+            self.llvm_index
+                .get_associated_type(BOOL_TYPE)?
+                .into_int_type(),
+            "",
+        );
+        Ok((bool_type, value.into()))
+    }
+
+    /// assigns the given value to the bit addressed by the given bit-address
+    pub fn generate_located_bit_assignment(
+        &self,
+        address: &HardwareAddress,
+        right_statement: &AstStatement,
+    ) -> Result<(), CompileError> {
+        let (right_type, right) = self.generate_expression(right_statement)?;
+        let bool_type = self.index.get_type_information(BOOL_TYPE)?;
+        let value = cast_if_needed(
+            self.llvm,
+            self.index,
+            &bool_type,
+            right,
+            &right_type,
+            right_statement,
+        )?
+        .into_int_value();
+
+        let byte_type = self.llvm.context.i8_type();
+        let byte_ptr =
+            get_located_variable_pointer(self.llvm, self.llvm_index, address, byte_type.into())?;
+        let builder = &self.llvm.builder;
+        let byte = builder.build_load(byte_ptr, "load_byte").into_int_value();
+        //clear the bit and set it to the assigned value
+        let bit_offset = byte_type.const_int(address.get_bit_offset() as u64, false);
+        let mask = byte_type
+            .const_int(1, false)
+            .const_shl(bit_offset)
+            .const_not();
+        let cleared = builder.build_and(byte, mask, "");
+        let bit = builder.build_int_z_extend(value, byte_type, "");
+        let bit = builder.build_left_shift(bit, bit_offset, "");
+        let result = builder.build_or(cleared, bit, "");
+        builder.build_store(byte_ptr, result);
+        Ok(())
+    }
+
     fn generate_directaccess(
         &self,
         elements: &[AstStatement],
@@ -391,6 +555,1291 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         Ok((data_type, BasicValueEnum::IntValue(value)))
     }
 
+    /// returns the name of the builtin function the given call-operator refers to
+    /// or None if it is no builtin function
+    fn find_builtin(&self, operator: &AstStatement) -> Option<&'b str> {
+        match self.annotations.get_annotation(operator) {
+            Some(StatementAnnotation::Function { qualified_name, .. })
+                if self.index.is_builtin(qualified_name) =>
+            {
+                Some(qualified_name.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// generates an inline call to the given builtin function.
+    /// The arguments are converted to the call's resulting type (see `TypeAnnotator`),
+    /// numeric functions are lowered to llvm intrinsics
+    ///
+    /// - `builtin` the name of the builtin function
+    /// - `call` the call-statement
+    /// - `parameters` an optional StatementList of parameters
+    fn generate_builtin_call(
+        &self,
+        builtin: &str,
+        call: &AstStatement,
+        parameters: &Option<AstStatement>,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let result_type = self
+            .annotations
+            .get_type_or_void(call, self.index)
+            .clone_type_information();
+        let arguments: Vec<&AstStatement> =
+            builtins::get_arguments(self.index, builtin, parameters)
+                .into_iter()
+                .map(|(_, argument)| argument)
+                .collect();
+        let get_argument = |position: usize| {
+            arguments.get(position).copied().ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("Missing argument {} in call to {}", position, builtin),
+                    call.get_location(),
+                )
+            })
+        };
+        if let Some(conversion) = builtins::get_conversion(builtin) {
+            let (value_type, value) = self.generate_expression(get_argument(0)?)?;
+            let value =
+                self.generate_conversion(conversion, value, &value_type, &result_type, call)?;
+            return Ok((result_type, value));
+        }
+
+        //generates the argument at the given position, converted to the call's resulting type
+        let generate_argument =
+            |position: usize| self.generate_builtin_argument(get_argument(position)?, &result_type);
+        let generate_arguments_from = |position: usize| {
+            (position..arguments.len().max(position + 1))
+                .map(&generate_argument)
+                .collect::<Result<Vec<BasicValueEnum<'a>>, CompileError>>()
+        };
+
+        let value = match builtin.to_uppercase().as_str() {
+            builtins::ABS => {
+                let value = generate_argument(0)?;
+                if result_type.is_float() {
+                    self.generate_intrinsic_call("llvm.fabs", &[value], &result_type, call)?
+                } else if result_type.is_signed_int() {
+                    let value = value.into_int_value();
+                    let is_negative = self.llvm.builder.build_int_compare(
+                        IntPredicate::SLT,
+                        value,
+                        value.get_type().const_zero(),
+                        "",
+                    );
+                    let negated = self.llvm.builder.build_int_neg(value, "");
+                    self.llvm
+                        .builder
+                        .build_select(is_negative, negated, value, "")
+                } else {
+                    value
+                }
+            }
+            builtins::SQRT => self.generate_intrinsic_call(
+                "llvm.sqrt",
+                &[generate_argument(0)?],
+                &result_type,
+                call,
+            )?,
+            builtins::LN => self.generate_intrinsic_call(
+                "llvm.log",
+                &[generate_argument(0)?],
+                &result_type,
+                call,
+            )?,
+            builtins::LOG => self.generate_intrinsic_call(
+                "llvm.log10",
+                &[generate_argument(0)?],
+                &result_type,
+                call,
+            )?,
+            builtins::EXP => self.generate_intrinsic_call(
+                "llvm.exp",
+                &[generate_argument(0)?],
+                &result_type,
+                call,
+            )?,
+            builtins::SIN => self.generate_intrinsic_call(
+                "llvm.sin",
+                &[generate_argument(0)?],
+                &result_type,
+                call,
+            )?,
+            builtins::COS => self.generate_intrinsic_call(
+                "llvm.cos",
+                &[generate_argument(0)?],
+                &result_type,
+                call,
+            )?,
+            builtins::TAN => {
+                //there is no intrinsic for tan, so we calculate sin / cos
+                let value = generate_argument(0)?;
+                let sin = self.generate_intrinsic_call("llvm.sin", &[value], &result_type, call)?;
+                let cos = self.generate_intrinsic_call("llvm.cos", &[value], &result_type, call)?;
+                self.llvm
+                    .builder
+                    .build_float_div(sin.into_float_value(), cos.into_float_value(), "")
+                    .as_basic_value_enum()
+            }
+            builtins::SHL | builtins::SHR => {
+                //shifting by the bit-width or more is undefined in llvm, the result is 0 instead
+                let value = generate_argument(0)?.into_int_value();
+                let (shift, is_in_range) =
+                    self.generate_shift_amount(get_argument(1)?, value, false)?;
+                let shifted = if builtin.eq_ignore_ascii_case(builtins::SHL) {
+                    self.llvm.builder.build_left_shift(value, shift, "")
+                } else {
+                    self.llvm.builder.build_right_shift(value, shift, false, "")
+                };
+                self.llvm.builder.build_select(
+                    is_in_range,
+                    shifted,
+                    value.get_type().const_zero(),
+                    "",
+                )
+            }
+            builtins::ROL | builtins::ROR => {
+                //a rotation is a funnel shift of the value with itself, N is taken modulo the bit-width
+                let value = generate_argument(0)?.into_int_value();
+                let (shift, _) = self.generate_shift_amount(get_argument(1)?, value, true)?;
+                let intrinsic = if builtin.eq_ignore_ascii_case(builtins::ROL) {
+                    "llvm.fshl"
+                } else {
+                    "llvm.fshr"
+                };
+                self.generate_intrinsic_call(
+                    intrinsic,
+                    &[value.into(), value.into(), shift.into()],
+                    &result_type,
+                    call,
+                )?
+            }
+            builtins::SEL => {
+                let selector = self.generate_bool_argument(get_argument(0)?)?;
+                let (in0, in1) = (generate_argument(1)?, generate_argument(2)?);
+                self.llvm.builder.build_select(selector, in1, in0, "")
+            }
+            builtins::MUX => {
+                let (selector_type, selector) = self.generate_expression(get_argument(0)?)?;
+                let selector = selector.into_int_value();
+                let inputs = generate_arguments_from(1)?;
+                let mut result = inputs[0];
+                for (position, input) in inputs.iter().enumerate().skip(1) {
+                    let is_selected = self.llvm.builder.build_int_compare(
+                        IntPredicate::EQ,
+                        selector,
+                        selector
+                            .get_type()
+                            .const_int(position as u64, selector_type.is_signed_int()),
+                        "",
+                    );
+                    result = self
+                        .llvm
+                        .builder
+                        .build_select(is_selected, *input, result, "");
+                }
+                result
+            }
+            builtins::MAX | builtins::MIN => {
+                let is_max = builtin.eq_ignore_ascii_case(builtins::MAX);
+                let inputs = generate_arguments_from(0)?;
+                let mut result = inputs[0];
+                for input in inputs.iter().skip(1) {
+                    result = self.generate_min_max(is_max, result, *input, &result_type);
+                }
+                result
+            }
+            builtins::LIMIT => {
+                let (min, value, max) = (
+                    generate_argument(0)?,
+                    generate_argument(1)?,
+                    generate_argument(2)?,
+                );
+                let lower_limited = self.generate_min_max(true, value, min, &result_type);
+                self.generate_min_max(false, lower_limited, max, &result_type)
+            }
+            builtins::MOVE => generate_argument(0)?,
+            builtins::LOWER_BOUND | builtins::UPPER_BOUND => {
+                let is_upper = builtin.eq_ignore_ascii_case(builtins::UPPER_BOUND);
+                self.generate_array_bound(is_upper, get_argument(0)?, get_argument(1)?)?
+            }
+            builtins::REF => self
+                .generate_element_pointer(get_argument(0)?)?
+                .ptr_value
+                .as_basic_value_enum(),
+            builtins::IS_VALID_REF => {
+                //checks the reference itself, not the variable it refers to
+                let reference = self.generate_reference_pointer(get_argument(0)?)?;
+                match self.llvm.load_pointer(&reference, "") {
+                    (_, BasicValueEnum::PointerValue(address)) => self
+                        .llvm
+                        .builder
+                        .build_is_not_null(address, "")
+                        .as_basic_value_enum(),
+                    _ => {
+                        return Err(CompileError::codegen_error(
+                            format!("{} expects a reference", builtins::IS_VALID_REF),
+                            call.get_location(),
+                        ))
+                    }
+                }
+            }
+            builtins::ADR => {
+                let address = self.generate_element_pointer(get_argument(0)?)?.ptr_value;
+                let address_type = self
+                    .llvm_index
+                    .get_associated_type(result_type.get_name())?
+                    .into_int_type();
+                self.llvm
+                    .builder
+                    .build_ptr_to_int(address, address_type, "")
+                    .as_basic_value_enum()
+            }
+            builtins::SIZEOF => {
+                //only the argument's type is needed, the argument itself is not evaluated
+                let argument_type = self
+                    .annotations
+                    .get_type_or_void(get_argument(0)?, self.index);
+                let argument_type = match argument_type.get_type_information() {
+                    DataTypeInformation::Pointer {
+                        inner_type_name,
+                        auto_deref: true,
+                        ..
+                    } => self.index.get_type(inner_type_name)?,
+                    _ => argument_type,
+                };
+                get_type_size(self.llvm, self.index, self.llvm_index, argument_type)?
+                    .as_basic_value_enum()
+            }
+            builtins::TRAP => {
+                let module = self.get_function_context(call)?.module;
+                let trap = module.get_function("llvm.trap").unwrap_or_else(|| {
+                    let function_type = self.llvm.context.void_type().fn_type(&[], false);
+                    module.add_function("llvm.trap", function_type, None)
+                });
+                self.llvm.builder.build_call(trap, &[], "");
+                //like other void-calls, the trap results in a null-pointer (see generate_call_statement)
+                self.llvm
+                    .context
+                    .i16_type()
+                    .ptr_type(AddressSpace::Const)
+                    .const_null()
+                    .as_basic_value_enum()
+            }
+            builtins::LEN
+            | builtins::LEFT
+            | builtins::RIGHT
+            | builtins::MID
+            | builtins::CONCAT
+            | builtins::INSERT
+            | builtins::DELETE
+            | builtins::REPLACE
+            | builtins::FIND => {
+                self.generate_string_function(builtin, call, &arguments, &result_type)?
+            }
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!("Unknown builtin function {}", builtin),
+                    call.get_location(),
+                ))
+            }
+        };
+        Ok((result_type, value))
+    }
+
+    /// generates `LOWER_BOUND(array, dimension)` or `UPPER_BOUND(array, dimension)`
+    ///
+    /// the bounds of a variable-length array are loaded from its descriptor, the bounds
+    /// of a fixed-size array are selected from its declared dimensions
+    ///
+    /// - `is_upper` whether the upper bound is requested
+    /// - `array` the array whose bound is requested
+    /// - `dimension` the dimension of the bound, starting with 1
+    fn generate_array_bound(
+        &self,
+        is_upper: bool,
+        array: &AstStatement,
+        dimension: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let dint = self.index.get_type_information_or_void(DINT_TYPE);
+        let (dimension_type, dimension_value) = self.generate_expression(dimension)?;
+        let dimension_value = cast_if_needed(
+            self.llvm,
+            self.index,
+            dint,
+            dimension_value,
+            &dimension_type,
+            dimension,
+        )?
+        .into_int_value();
+        let array_pointer = self.generate_element_pointer(array)?;
+        match array_pointer.get_type_information() {
+            DataTypeInformation::VariableLengthArray { .. } => {
+                //the bounds are stored as [lower1, upper1, lower2, upper2, ...]
+                let position = builder.build_int_mul(
+                    builder.build_int_sub(
+                        dimension_value,
+                        self.llvm.i32_type().const_int(1, false),
+                        "",
+                    ),
+                    self.llvm.i32_type().const_int(2, false),
+                    "",
+                );
+                let position = builder.build_int_add(
+                    position,
+                    self.llvm.i32_type().const_int(is_upper as u64, false),
+                    "",
+                );
+                let bounds = self.llvm.get_member_pointer_from_struct(
+                    array_pointer.ptr_value,
+                    1,
+                    "bounds",
+                    &array.get_location(),
+                )?;
+                let bound = self.llvm.load_array_element(
+                    bounds,
+                    &[self.llvm.i32_type().const_zero(), position],
+                    "",
+                )?;
+                Ok(builder.build_load(bound, "bound"))
+            }
+            DataTypeInformation::Array { dimensions, .. } => {
+                let mut result = None;
+                for (position, declared_dimension) in dimensions.iter().enumerate() {
+                    let bound = if is_upper {
+                        &declared_dimension.end_offset
+                    } else {
+                        &declared_dimension.start_offset
+                    };
+                    let bound = bound
+                        .as_int_value(self.index)
+                        .map_err(|it| CompileError::codegen_error(it, array.get_location()))?;
+                    let bound = self
+                        .llvm
+                        .i32_type()
+                        .const_int(bound as u64, true)
+                        .as_basic_value_enum();
+                    result = Some(match result {
+                        Some(result) => {
+                            let is_selected = builder.build_int_compare(
+                                IntPredicate::EQ,
+                                dimension_value,
+                                self.llvm.i32_type().const_int(position as u64 + 1, false),
+                                "",
+                            );
+                            builder.build_select(is_selected, bound, result, "")
+                        }
+                        None => bound,
+                    });
+                }
+                result.ok_or_else(|| {
+                    CompileError::codegen_error(
+                        "Cannot get the bounds of an array without dimensions".to_string(),
+                        array.get_location(),
+                    )
+                })
+            }
+            _ => Err(CompileError::codegen_error(
+                format!(
+                    "Cannot get the bounds of {}, it is not an array",
+                    array_pointer.type_entry.get_name()
+                ),
+                array.get_location(),
+            )),
+        }
+    }
+
+    /// generates the given argument of a builtin function and converts it to the given type
+    fn generate_builtin_argument(
+        &self,
+        argument: &AstStatement,
+        target_type: &DataTypeInformation,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let (value_type, value) = self.generate_expression(argument)?;
+        cast_if_needed(
+            self.llvm,
+            self.index,
+            target_type,
+            value,
+            &value_type,
+            argument,
+        )
+    }
+
+    /// generates the shift-amount `N` of a bit-shift or rotation of the given value.
+    /// `N` is evaluated in its own type (so it is not truncated) and then converted to the
+    /// value's type. Returns the converted amount and whether `N` is smaller than the value's
+    /// bit-width. Rotations take `N` modulo the bit-width.
+    ///
+    /// - `argument` the `N` argument of the call
+    /// - `value` the value to shift or rotate
+    /// - `is_rotation` whether `N` is taken modulo the bit-width
+    fn generate_shift_amount(
+        &self,
+        argument: &AstStatement,
+        value: IntValue<'a>,
+        is_rotation: bool,
+    ) -> Result<(IntValue<'a>, IntValue<'a>), CompileError> {
+        let (_, shift) = self.generate_expression(argument)?;
+        if !shift.is_int_value() {
+            return Err(CompileError::codegen_error(
+                "Expected an integer shift amount".to_string(),
+                argument.get_location(),
+            ));
+        }
+        let shift = shift.into_int_value();
+        let value_type = value.get_type();
+        let bit_width = shift
+            .get_type()
+            .const_int(value_type.get_bit_width() as u64, false);
+        let is_in_range =
+            self.llvm
+                .builder
+                .build_int_compare(IntPredicate::ULT, shift, bit_width, "");
+        let shift = if is_rotation {
+            self.llvm
+                .builder
+                .build_int_unsigned_rem(shift, bit_width, "")
+        } else {
+            shift
+        };
+        let shift_width = shift.get_type().get_bit_width();
+        let shift = if shift_width > value_type.get_bit_width() {
+            self.llvm.builder.build_int_truncate(shift, value_type, "")
+        } else if shift_width < value_type.get_bit_width() {
+            self.llvm.builder.build_int_z_extend(shift, value_type, "")
+        } else {
+            shift
+        };
+        Ok((shift, is_in_range))
+    }
+
+    /// generates a call to one of the builtin string functions (see `StringGenerator`)
+    ///
+    /// - `builtin` the name of the string function
+    /// - `call` the call-statement
+    /// - `arguments` the call's arguments in the order of the function's parameters
+    /// - `result_type` the resulting type of the call
+    fn generate_string_function(
+        &self,
+        builtin: &str,
+        call: &AstStatement,
+        arguments: &[&AstStatement],
+        result_type: &DataTypeInformation,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let to_compile_error =
+            |message: String| CompileError::codegen_error(message, call.get_location());
+        let strings = StringGenerator::new(self.llvm, self.get_function_context(call)?);
+        let get_argument = |position: usize| {
+            arguments.get(position).copied().ok_or_else(|| {
+                to_compile_error(format!(
+                    "Missing argument {} in call to {}",
+                    position, builtin
+                ))
+            })
+        };
+        //generates the string argument at the given position and stores it on the stack
+        let generate_string = |position: usize| {
+            let (argument_type, value) = self.generate_expression(get_argument(position)?)?;
+            strings
+                .store(value, self.get_character_type(&argument_type))
+                .map_err(to_compile_error)
+        };
+        //generates the integer argument at the given position as a DINT
+        let dint = self.index.get_type_information_or_void(DINT_TYPE);
+        let generate_int = |position: usize| {
+            let argument = get_argument(position)?;
+            let (argument_type, value) = self.generate_expression(argument)?;
+            cast_if_needed(self.llvm, self.index, dint, value, &argument_type, argument)
+                .map(BasicValueEnum::into_int_value)
+        };
+
+        match builtin.to_uppercase().as_str() {
+            builtins::LEN => {
+                let length = strings.length(generate_string(0)?);
+                return cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    result_type,
+                    length.into(),
+                    dint,
+                    call,
+                );
+            }
+            builtins::FIND => {
+                let position = strings
+                    .find(generate_string(0)?, generate_string(1)?)
+                    .map_err(to_compile_error)?;
+                return cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    result_type,
+                    position.into(),
+                    dint,
+                    call,
+                );
+            }
+            _ => {}
+        }
+
+        let string_type = match self.index.find_effective_type_information(result_type) {
+            Some(DataTypeInformation::String { size, .. }) => {
+                let size = size.as_int_value(self.index).map_err(to_compile_error)?;
+                self.get_character_type(result_type).array_type(size as u32)
+            }
+            _ => {
+                return Err(to_compile_error(format!(
+                    "{} does not result in a string",
+                    builtin
+                )))
+            }
+        };
+        let result = strings.allocate(string_type);
+        match builtin.to_uppercase().as_str() {
+            builtins::LEFT => strings.left(result, generate_string(0)?, generate_int(1)?),
+            builtins::RIGHT => strings.right(result, generate_string(0)?, generate_int(1)?),
+            builtins::MID => strings.mid(
+                result,
+                generate_string(0)?,
+                generate_int(1)?,
+                generate_int(2)?,
+            ),
+            builtins::CONCAT => {
+                let inputs = (0..arguments.len())
+                    .map(&generate_string)
+                    .collect::<Result<Vec<_>, CompileError>>()?;
+                strings.concat(result, &inputs)
+            }
+            builtins::INSERT => strings.insert(
+                result,
+                generate_string(0)?,
+                generate_string(1)?,
+                generate_int(2)?,
+            ),
+            builtins::DELETE => strings.delete(
+                result,
+                generate_string(0)?,
+                generate_int(1)?,
+                generate_int(2)?,
+            ),
+            builtins::REPLACE => strings.replace(
+                result,
+                generate_string(0)?,
+                generate_string(1)?,
+                generate_int(2)?,
+                generate_int(3)?,
+            ),
+            _ => Err(format!("Unknown string function {}", builtin)),
+        }
+        .map_err(to_compile_error)?;
+        Ok(strings.load(result))
+    }
+
+    /// returns the llvm type of a single character of the given string type
+    fn get_character_type(&self, string_type: &DataTypeInformation) -> IntType<'a> {
+        match self.index.find_effective_type_information(string_type) {
+            Some(DataTypeInformation::String {
+                encoding: StringEncoding::Utf16,
+                ..
+            }) => self.llvm.context.i16_type(),
+            _ => self.llvm.context.i8_type(),
+        }
+    }
+
+    /// generates the given BOOL argument of a builtin function as an i1 value
+    fn generate_bool_argument(
+        &self,
+        argument: &AstStatement,
+    ) -> Result<IntValue<'a>, CompileError> {
+        let value = self.generate_expression(argument)?.1.into_int_value();
+        if value.get_type().get_bit_width() == 1 {
+            Ok(value)
+        } else {
+            Ok(self.llvm.builder.build_int_compare(
+                IntPredicate::NE,
+                value,
+                value.get_type().const_zero(),
+                "",
+            ))
+        }
+    }
+
+    /// returns the bigger (`is_max`) or the smaller value of the two given numeric values
+    fn generate_min_max(
+        &self,
+        is_max: bool,
+        left: BasicValueEnum<'a>,
+        right: BasicValueEnum<'a>,
+        data_type: &DataTypeInformation,
+    ) -> BasicValueEnum<'a> {
+        let builder = &self.llvm.builder;
+        let left_is_bigger = if data_type.is_float() {
+            builder.build_float_compare(
+                FloatPredicate::OGT,
+                left.into_float_value(),
+                right.into_float_value(),
+                "",
+            )
+        } else {
+            let predicate = if data_type.is_signed_int() {
+                IntPredicate::SGT
+            } else {
+                IntPredicate::UGT
+            };
+            builder.build_int_compare(predicate, left.into_int_value(), right.into_int_value(), "")
+        };
+        if is_max {
+            builder.build_select(left_is_bigger, left, right, "")
+        } else {
+            builder.build_select(left_is_bigger, right, left, "")
+        }
+    }
+
+    /// generates a call to the given llvm intrinsic (e.g. `llvm.sqrt`). The intrinsic is
+    /// declared for the given type (e.g. `llvm.sqrt.f32` for a REAL) if necessary
+    fn generate_intrinsic_call(
+        &self,
+        intrinsic: &str,
+        arguments: &[BasicValueEnum<'a>],
+        data_type: &DataTypeInformation,
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let name = match data_type {
+            DataTypeInformation::Float { size, .. } => format!("{}.f{}", intrinsic, size),
+            _ => format!("{}.i{}", intrinsic, data_type.get_size()),
+        };
+        let parameter_types: Vec<BasicTypeEnum<'a>> =
+            arguments.iter().map(|it| it.get_type()).collect();
+        let function_type = match parameter_types.first() {
+            Some(BasicTypeEnum::FloatType(it)) => it.fn_type(&parameter_types, false),
+            Some(BasicTypeEnum::IntType(it)) => it.fn_type(&parameter_types, false),
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!("Cannot call {} for type {}", name, data_type.get_name()),
+                    call.get_location(),
+                ))
+            }
+        };
+        self.generate_external_call(&name, function_type, arguments, call)
+    }
+
+    /// generates a call to the given external function (e.g. an llvm intrinsic or a function
+    /// of the C standard library). The function is declared in the current module if necessary
+    fn generate_external_call(
+        &self,
+        name: &str,
+        function_type: FunctionType<'a>,
+        arguments: &[BasicValueEnum<'a>],
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let module = self.get_function_context(call)?.module;
+        let function = module
+            .get_function(name)
+            .unwrap_or_else(|| module.add_function(name, function_type, None));
+        self.llvm
+            .builder
+            .build_call(function, arguments, "")
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("{} does not return a value", name),
+                    call.get_location(),
+                )
+            })
+    }
+
+    /// generates the conversion of the given value to the given target type
+    /// (see `builtins::Conversion`)
+    fn generate_conversion(
+        &self,
+        conversion: Conversion,
+        value: BasicValueEnum<'a>,
+        value_type: &DataTypeInformation,
+        target_type: &DataTypeInformation,
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let value_type = self
+            .index
+            .find_effective_type_information(value_type)
+            .unwrap_or(value_type);
+        let target_type = self
+            .index
+            .find_effective_type_information(target_type)
+            .unwrap_or(target_type);
+        match conversion {
+            Conversion::Round => {
+                let value =
+                    cast_if_needed(self.llvm, self.index, target_type, value, value_type, call)?;
+                if target_type.is_float() {
+                    self.generate_intrinsic_call("llvm.round", &[value], target_type, call)
+                } else {
+                    Ok(value)
+                }
+            }
+            Conversion::Truncate
+                if value_type.is_float() && target_type.is_int() && !target_type.is_bool() =>
+            {
+                self.generate_float_to_int(
+                    value.into_float_value(),
+                    value_type,
+                    target_type,
+                    false,
+                    call,
+                )
+            }
+            _ => self.generate_type_conversion(value, value_type, target_type, call),
+        }
+    }
+
+    /// converts the given value to the given target type following IEC 61131-3:
+    /// - any number converted to BOOL is TRUE if it is not zero
+    /// - floats converted to integers are rounded and saturated to the integer's range
+    /// - integers converted to smaller integers are saturated to the integer's range
+    /// - DATE_AND_TIME converted to DATE or TIME_OF_DAY extracts the date or the time
+    /// - numbers, dates and times are converted to and from STRING using the C standard library
+    /// - WSTRINGs are converted to and from STRING first
+    fn generate_type_conversion(
+        &self,
+        value: BasicValueEnum<'a>,
+        value_type: &DataTypeInformation,
+        target_type: &DataTypeInformation,
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let is_one_of = |data_type: &DataTypeInformation, names: &[&str]| {
+            names
+                .iter()
+                .any(|it| data_type.get_name().eq_ignore_ascii_case(it))
+        };
+        let date_and_time = &[DATE_AND_TIME_TYPE, SHORT_DATE_AND_TIME_TYPE];
+        let time_of_day = &[TIME_OF_DAY_TYPE, SHORT_TIME_OF_DAY_TYPE];
+        let time = &[TIME_TYPE, SHORT_TIME_TYPE];
+        let date = &[DATE_TYPE, SHORT_DATE_TYPE];
+
+        match (value_type, target_type) {
+            (
+                DataTypeInformation::String {
+                    encoding: value_encoding,
+                    ..
+                },
+                DataTypeInformation::String { encoding, .. },
+            ) if value_encoding != encoding => {
+                self.generate_string_transcoding(value, value_type, target_type, call)
+            }
+            (DataTypeInformation::String { .. }, DataTypeInformation::String { .. }) => {
+                cast_if_needed(self.llvm, self.index, target_type, value, value_type, call)
+            }
+            //a WSTRING is converted to a STRING big enough to hold every character in UTF-8
+            (
+                DataTypeInformation::String {
+                    size,
+                    encoding: StringEncoding::Utf16,
+                },
+                _,
+            ) => {
+                let size = self.get_string_size(size, call)?;
+                let string_type = DataTypeInformation::String {
+                    size: TypeSize::from_literal((size - 1) * 3 + 1),
+                    encoding: StringEncoding::Utf8,
+                };
+                let string =
+                    self.generate_string_transcoding(value, value_type, &string_type, call)?;
+                self.generate_type_conversion(string, &string_type, target_type, call)
+            }
+            (
+                _,
+                DataTypeInformation::String {
+                    size,
+                    encoding: StringEncoding::Utf16,
+                },
+            ) => {
+                let string_type = DataTypeInformation::String {
+                    size: size.clone(),
+                    encoding: StringEncoding::Utf8,
+                };
+                let string =
+                    self.generate_type_conversion(value, value_type, &string_type, call)?;
+                self.generate_string_transcoding(string, &string_type, target_type, call)
+            }
+            (DataTypeInformation::String { .. }, _) => match DateTimeKind::of(target_type) {
+                Some(kind) => self.generate_string_to_date_time(value, kind, target_type, call),
+                None => self.generate_string_to_number(value, target_type, call),
+            },
+            (_, DataTypeInformation::String { .. }) => match DateTimeKind::of(value_type) {
+                Some(kind) => {
+                    let dates = DateTimeGenerator::new(self.llvm, self.get_function_context(call)?);
+                    let (format, arguments) = dates.get_format(value.into_int_value(), kind);
+                    self.generate_snprintf(target_type, &format, &arguments, call)
+                }
+                None => self.generate_number_to_string(value, value_type, target_type, call),
+            },
+            _ if target_type.is_bool() => Ok(if value_type.is_float() {
+                let value = value.into_float_value();
+                builder
+                    .build_float_compare(
+                        FloatPredicate::UNE,
+                        value,
+                        value.get_type().const_zero(),
+                        "",
+                    )
+                    .as_basic_value_enum()
+            } else {
+                let value = value.into_int_value();
+                builder
+                    .build_int_compare(IntPredicate::NE, value, value.get_type().const_zero(), "")
+                    .as_basic_value_enum()
+            }),
+            (_, DataTypeInformation::Float { size, .. }) if value_type.is_bool() => Ok(builder
+                .build_unsigned_int_to_float(
+                    value.into_int_value(),
+                    get_llvm_float_type(self.llvm.context, *size, "Float")?,
+                    "",
+                )
+                .as_basic_value_enum()),
+            (_, DataTypeInformation::Integer { size, .. }) if value_type.is_bool() => Ok(builder
+                .build_int_z_extend_or_bit_cast(
+                    value.into_int_value(),
+                    get_llvm_int_type(self.llvm.context, *size, "Integer")?,
+                    "",
+                )
+                .as_basic_value_enum()),
+            (DataTypeInformation::Float { .. }, DataTypeInformation::Integer { .. }) => self
+                .generate_float_to_int(
+                    value.into_float_value(),
+                    value_type,
+                    target_type,
+                    true,
+                    call,
+                ),
+            _ if builtins::is_saturating_conversion(value_type, target_type) => self
+                .generate_saturating_int_conversion(
+                    value.into_int_value(),
+                    value_type,
+                    target_type,
+                    call,
+                ),
+            _ if is_one_of(value_type, date_and_time) && is_one_of(target_type, date) => {
+                let value = value.into_int_value();
+                let time_of_day = builder.build_int_signed_rem(
+                    value,
+                    value.get_type().const_int(MILLIS_PER_DAY, false),
+                    "",
+                );
+                Ok(builder
+                    .build_int_sub(value, time_of_day, "")
+                    .as_basic_value_enum())
+            }
+            _ if is_one_of(value_type, date_and_time) && is_one_of(target_type, time_of_day) => {
+                let value = value.into_int_value();
+                Ok(builder
+                    .build_int_signed_rem(
+                        value,
+                        value.get_type().const_int(MILLIS_PER_DAY, false),
+                        "",
+                    )
+                    .as_basic_value_enum())
+            }
+            //TIME is stored in nanoseconds, TIME_OF_DAY in milliseconds
+            _ if is_one_of(value_type, time_of_day) && is_one_of(target_type, time) => {
+                let value = value.into_int_value();
+                Ok(builder
+                    .build_int_mul(
+                        value,
+                        value.get_type().const_int(NANOS_PER_MILLI, false),
+                        "",
+                    )
+                    .as_basic_value_enum())
+            }
+            _ if is_one_of(value_type, time) && is_one_of(target_type, time_of_day) => {
+                let value = value.into_int_value();
+                Ok(builder
+                    .build_int_signed_div(
+                        value,
+                        value.get_type().const_int(NANOS_PER_MILLI, false),
+                        "",
+                    )
+                    .as_basic_value_enum())
+            }
+            _ => cast_if_needed(self.llvm, self.index, target_type, value, value_type, call),
+        }
+    }
+
+    /// converts the given float to an integer of the given type. The value is rounded (`round`)
+    /// or truncated. Values out of the integer's range are saturated, NaN is converted to 0
+    fn generate_float_to_int(
+        &self,
+        value: FloatValue<'a>,
+        value_type: &DataTypeInformation,
+        target_type: &DataTypeInformation,
+        round: bool,
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let (signed, size) = match target_type {
+            DataTypeInformation::Integer { signed, size, .. } => (*signed, *size),
+            _ => {
+                return Err(CompileError::casting_error(
+                    value_type.get_name(),
+                    target_type.get_name(),
+                    call.get_location(),
+                ))
+            }
+        };
+        let intrinsic = if round { "llvm.round" } else { "llvm.trunc" };
+        let value = self
+            .generate_intrinsic_call(intrinsic, &[value.into()], value_type, call)?
+            .into_float_value();
+
+        let builder = &self.llvm.builder;
+        let int_type = get_llvm_int_type(self.llvm.context, size, "Integer")?;
+        let float_type = value.get_type();
+        let (min, max) = get_integer_range(signed, size);
+        let is_too_small = builder.build_float_compare(
+            FloatPredicate::OLT,
+            value,
+            float_type.const_float(min as f64),
+            "",
+        );
+        let is_too_big = builder.build_float_compare(
+            FloatPredicate::OGE,
+            value,
+            float_type.const_float((max + 1) as f64),
+            "",
+        );
+        let is_nan = builder.build_float_compare(FloatPredicate::UNO, value, value, "");
+        let converted = if signed {
+            builder.build_float_to_signed_int(value, int_type, "")
+        } else {
+            builder.build_float_to_unsigned_int(value, int_type, "")
+        };
+        let result = builder.build_select(is_nan, int_type.const_zero(), converted, "");
+        let result = builder.build_select(
+            is_too_small,
+            int_type.const_int(min as u64, false),
+            result.into_int_value(),
+            "",
+        );
+        Ok(builder.build_select(
+            is_too_big,
+            int_type.const_int(max as u64, false),
+            result.into_int_value(),
+            "",
+        ))
+    }
+
+    /// converts the given integer to the given integer type. Values out of the target's
+    /// range are saturated to its smallest or biggest value
+    fn generate_saturating_int_conversion(
+        &self,
+        value: IntValue<'a>,
+        value_type: &DataTypeInformation,
+        target_type: &DataTypeInformation,
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let converted = cast_if_needed(
+            self.llvm,
+            self.index,
+            target_type,
+            value.into(),
+            value_type,
+            call,
+        )?
+        .into_int_value();
+        let (value_min, value_max) =
+            get_integer_range(value_type.is_signed_int(), value_type.get_size());
+        let (min, max) = get_integer_range(target_type.is_signed_int(), target_type.get_size());
+        let (less, greater) = if value_type.is_signed_int() {
+            (IntPredicate::SLT, IntPredicate::SGT)
+        } else {
+            (IntPredicate::ULT, IntPredicate::UGT)
+        };
+        //the limits are only compared if they are within the value's range
+        let mut result = converted;
+        if min > value_min {
+            let is_too_small = builder.build_int_compare(
+                less,
+                value,
+                value.get_type().const_int(min as u64, true),
+                "",
+            );
+            result = builder
+                .build_select(
+                    is_too_small,
+                    converted.get_type().const_int(min as u64, true),
+                    result,
+                    "",
+                )
+                .into_int_value();
+        }
+        if max < value_max {
+            let is_too_big = builder.build_int_compare(
+                greater,
+                value,
+                value.get_type().const_int(max as u64, false),
+                "",
+            );
+            result = builder
+                .build_select(
+                    is_too_big,
+                    converted.get_type().const_int(max as u64, false),
+                    result,
+                    "",
+                )
+                .into_int_value();
+        }
+        Ok(result.as_basic_value_enum())
+    }
+
+    /// converts the given number to a STRING of the given type using `snprintf`
+    fn generate_number_to_string(
+        &self,
+        value: BasicValueEnum<'a>,
+        value_type: &DataTypeInformation,
+        target_type: &DataTypeInformation,
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let (format, argument) = if value_type.is_bool() {
+            let true_value = builder
+                .build_global_string_ptr("TRUE", "")
+                .as_pointer_value();
+            let false_value = builder
+                .build_global_string_ptr("FALSE", "")
+                .as_pointer_value();
+            let value = builder.build_select(value.into_int_value(), true_value, false_value, "");
+            ("%s", value)
+        } else if value_type.is_float() {
+            let lreal = self.index.get_type_information_or_void(LREAL_TYPE);
+            let value = cast_if_needed(self.llvm, self.index, lreal, value, value_type, call)?;
+            //enough digits to read back the same value
+            if value_type.get_size() == 32 {
+                ("%.9g", value)
+            } else {
+                ("%.17g", value)
+            }
+        } else if value_type.is_signed_int() {
+            let lint = self.index.get_type_information_or_void(LINT_TYPE);
+            let value = cast_if_needed(self.llvm, self.index, lint, value, value_type, call)?;
+            ("%lld", value)
+        } else {
+            let ulint = self.index.get_type_information_or_void(ULINT_TYPE);
+            let value = cast_if_needed(self.llvm, self.index, ulint, value, value_type, call)?;
+            ("%llu", value)
+        };
+        self.generate_snprintf(target_type, format, &[argument], call)
+    }
+
+    /// prints the given arguments into a STRING of the given type using `snprintf`
+    fn generate_snprintf(
+        &self,
+        target_type: &DataTypeInformation,
+        format: &str,
+        arguments: &[BasicValueEnum<'a>],
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let size = match target_type {
+            DataTypeInformation::String { size, .. } => self.get_string_size(size, call)?,
+            _ => unreachable!("target must be a string"),
+        };
+        let string_pointer_type = context.i8_type().ptr_type(AddressSpace::Generic);
+        //the buffer's size is a size_t, which is as wide as a pointer of the target
+        let module = self.get_function_context(call)?.module;
+        let size_type = TargetData::create(&module.get_data_layout().as_str().to_string_lossy())
+            .ptr_sized_int_type_in_context(context, None);
+        let buffer = builder.build_alloca(context.i8_type().array_type(size), "");
+        let format = builder
+            .build_global_string_ptr(format, "")
+            .as_pointer_value();
+        let mut snprintf_arguments = vec![
+            builder
+                .build_pointer_cast(buffer, string_pointer_type, "")
+                .into(),
+            size_type.const_int(size as u64, false).into(),
+            format.into(),
+        ];
+        snprintf_arguments.extend_from_slice(arguments);
+        self.generate_external_call(
+            "snprintf",
+            context.i32_type().fn_type(
+                &[
+                    string_pointer_type.into(),
+                    size_type.into(),
+                    string_pointer_type.into(),
+                ],
+                true,
+            ),
+            &snprintf_arguments,
+            call,
+        )?;
+        Ok(builder.build_load(buffer, ""))
+    }
+
+    /// converts the given STRING to a date or time of the given kind (see `DateTimeGenerator`)
+    fn generate_string_to_date_time(
+        &self,
+        value: BasicValueEnum<'a>,
+        kind: DateTimeKind,
+        target_type: &DataTypeInformation,
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let dates = DateTimeGenerator::new(self.llvm, self.get_function_context(call)?);
+        let value = dates
+            .parse(self.generate_string_pointer(value), kind)
+            .map_err(|msg| CompileError::codegen_error(msg, call.get_location()))?;
+        let lint = self.index.get_type_information_or_void(LINT_TYPE);
+        cast_if_needed(self.llvm, self.index, target_type, value.into(), lint, call)
+    }
+
+    /// converts the given STRING to a WSTRING or the given WSTRING to a STRING
+    /// (see `StringGenerator::transcode`)
+    fn generate_string_transcoding(
+        &self,
+        value: BasicValueEnum<'a>,
+        value_type: &DataTypeInformation,
+        target_type: &DataTypeInformation,
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let to_compile_error =
+            |message: String| CompileError::codegen_error(message, call.get_location());
+        let strings = StringGenerator::new(self.llvm, self.get_function_context(call)?);
+        let size = match target_type {
+            DataTypeInformation::String { size, .. } => self.get_string_size(size, call)?,
+            _ => unreachable!("target must be a string"),
+        };
+        let input = strings
+            .store(value, self.get_character_type(value_type))
+            .map_err(to_compile_error)?;
+        let result = strings.allocate(self.get_character_type(target_type).array_type(size));
+        strings.transcode(result, input).map_err(to_compile_error)?;
+        Ok(strings.load(result))
+    }
+
+    /// returns the number of characters (including the terminator) of a string of the given size
+    fn get_string_size(&self, size: &TypeSize, call: &AstStatement) -> Result<u32, CompileError> {
+        size.as_int_value(self.index)
+            .map(|it| it as u32)
+            .map_err(|msg| CompileError::codegen_error(msg, call.get_location()))
+    }
+
+    /// stores the given string value on the stack and returns a pointer to its first character
+    fn generate_string_pointer(&self, value: BasicValueEnum<'a>) -> PointerValue<'a> {
+        let builder = &self.llvm.builder;
+        let buffer = builder.build_alloca(value.get_type(), "");
+        builder.build_store(buffer, value);
+        builder.build_pointer_cast(
+            buffer,
+            self.llvm.context.i8_type().ptr_type(AddressSpace::Generic),
+            "",
+        )
+    }
+
+    /// converts the given STRING to a number of the given type using `strtoll`, `strtoull`
+    /// or `strtod`. A STRING is converted to TRUE if it is 'TRUE' or a number other than 0
+    fn generate_string_to_number(
+        &self,
+        value: BasicValueEnum<'a>,
+        target_type: &DataTypeInformation,
+        call: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let string_pointer_type = context.i8_type().ptr_type(AddressSpace::Generic);
+        let end_pointer_type = string_pointer_type.ptr_type(AddressSpace::Generic);
+
+        let string = self.generate_string_pointer(value);
+
+        if target_type.is_float() {
+            let number = self.generate_external_call(
+                "strtod",
+                context.f64_type().fn_type(
+                    &[string_pointer_type.into(), end_pointer_type.into()],
+                    false,
+                ),
+                &[string.into(), end_pointer_type.const_null().into()],
+                call,
+            )?;
+            let lreal = self.index.get_type_information_or_void(LREAL_TYPE);
+            return cast_if_needed(self.llvm, self.index, target_type, number, lreal, call);
+        }
+
+        let (function, number_type) = if target_type.is_unsigned_int() {
+            ("strtoull", ULINT_TYPE)
+        } else {
+            ("strtoll", LINT_TYPE)
+        };
+        let number = self.generate_external_call(
+            function,
+            context.i64_type().fn_type(
+                &[
+                    string_pointer_type.into(),
+                    end_pointer_type.into(),
+                    context.i32_type().into(),
+                ],
+                false,
+            ),
+            &[
+                string.into(),
+                end_pointer_type.const_null().into(),
+                context.i32_type().const_int(10, false).into(),
+            ],
+            call,
+        )?;
+        if target_type.is_bool() {
+            let number = number.into_int_value();
+            let is_not_zero = builder.build_int_compare(
+                IntPredicate::NE,
+                number,
+                number.get_type().const_zero(),
+                "",
+            );
+            let true_literal = builder
+                .build_global_string_ptr("TRUE", "")
+                .as_pointer_value();
+            let comparison = self.generate_external_call(
+                "strcasecmp",
+                context.i32_type().fn_type(
+                    &[string_pointer_type.into(), string_pointer_type.into()],
+                    false,
+                ),
+                &[string.into(), true_literal.into()],
+                call,
+            )?;
+            let comparison = comparison.into_int_value();
+            let is_true = builder.build_int_compare(
+                IntPredicate::EQ,
+                comparison,
+                comparison.get_type().const_zero(),
+                "",
+            );
+            Ok(builder
+                .build_or(is_true, is_not_zero, "")
+                .as_basic_value_enum())
+        } else {
+            let number_type = self.index.get_type_information_or_void(number_type);
+            if builtins::is_saturating_conversion(number_type, target_type) {
+                self.generate_saturating_int_conversion(
+                    number.into_int_value(),
+                    number_type,
+                    target_type,
+                    call,
+                )
+            } else {
+                cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    target_type,
+                    number,
+                    number_type,
+                    call,
+                )
+            }
+        }
+    }
+
     /// generates the given call-statement <operator>(<parameters>)
     /// returns the result of the call as a TypeAndValue (may be an invalid pointer and void-type for PROGRAMs)
     ///
@@ -410,52 +1859,85 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                     &[name],
                 );
 
-                let (implementation, callable_reference) = if let Some(variable_instance) = variable
-                {
-                    let implementation = self
-                        .index
-                        .find_implementation(variable_instance.get_type_name())
-                        .unwrap();
-                    (
-                        implementation,
-                        self.llvm_index
-                            .find_loaded_associated_variable_value(
-                                variable_instance.get_qualified_name(),
-                            )
-                            .ok_or_else(|| CompileError::CodeGenError {
-                                message: format!("cannot find callable type for {:?}", operator),
-                                location: operator.get_location(),
-                            })?,
-                    )
-                } else {
-                    let implementation = self.index.find_implementation(name);
-                    if let Some(implementation) = implementation {
+                //a method of the current class (or one of its super classes) called from within a method
+                let method = function_context
+                    .linking_context
+                    .get_associated_class_name()
+                    .and_then(|class_name| self.index.find_method(class_name, name));
+
+                let (class_struct, implementation, callable_reference) =
+                    if let Some(variable_instance) = variable {
+                        let implementation = self
+                            .index
+                            .find_implementation(variable_instance.get_type_name())
+                            .unwrap();
                         (
+                            None,
                             implementation,
+                            self.llvm_index
+                                .find_loaded_associated_variable_value(
+                                    variable_instance.get_qualified_name(),
+                                )
+                                .ok_or_else(|| CompileError::CodeGenError {
+                                    message: format!(
+                                        "cannot find callable type for {:?}",
+                                        operator
+                                    ),
+                                    location: operator.get_location(),
+                                })?,
+                        )
+                    } else if let Some(method) = method {
+                        //the method is called on the current instance (the first parameter)
+                        let this = function_context
+                            .function
+                            .get_first_param()
+                            .map(BasicValueEnum::into_pointer_value)
+                            .ok_or_else(|| {
+                                CompileError::invalid_reference(name, operator.get_location())
+                            })?;
+                        let class_name = method
+                            .get_associated_class_name()
+                            .map(String::as_str)
+                            .unwrap_or_default();
+                        (
+                            Some(self.cast_to_class_pointer(this, class_name, operator)?),
+                            method,
                             self.allocate_function_struct_instance(
-                                implementation.get_call_name(),
+                                method.get_call_name(),
                                 operator,
                             )?,
                         )
                     } else {
-                        //Look for a possible action
-                        let qualified_name = format!(
-                            "{}.{}",
-                            function_context.linking_context.get_type_name(),
-                            name
-                        );
-                        let function = function_context.function;
-                        let ptr = function.get_first_param().unwrap();
-                        (
-                            self.index.find_implementation(&qualified_name).unwrap(),
-                            ptr.into_pointer_value(),
-                        )
-                    }
-                };
+                        let implementation = self.index.find_implementation(name);
+                        if let Some(implementation) = implementation {
+                            (
+                                None,
+                                implementation,
+                                self.allocate_function_struct_instance(
+                                    implementation.get_call_name(),
+                                    operator,
+                                )?,
+                            )
+                        } else {
+                            //Look for a possible action
+                            let qualified_name = format!(
+                                "{}.{}",
+                                function_context.linking_context.get_type_name(),
+                                name
+                            );
+                            let function = function_context.function;
+                            let ptr = function.get_first_param().unwrap();
+                            (
+                                None,
+                                self.index.find_implementation(&qualified_name).unwrap(),
+                                ptr.into_pointer_value(),
+                            )
+                        }
+                    };
 
-                Ok((None, callable_reference, implementation))
+                Ok((class_struct, callable_reference, implementation))
             }
-            AstStatement::QualifiedReference { .. } => {
+            AstStatement::QualifiedReference { .. } | AstStatement::PointerAccess { .. } => {
                 let loaded_value = self.generate_element_pointer_for_rec(None, operator);
                 loaded_value.map(
                     |TypeAndPointer {
@@ -500,6 +1982,29 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
 
         let (class_struct, instance, index_entry) = instance_and_index_entry?;
         let function_name = index_entry.get_call_name();
+        //methods of an interface are dispatched via the referenced instance's vtable
+        let (class_struct, dispatched_function) = match (
+            class_struct,
+            self.index.find_interface_of_method(index_entry),
+        ) {
+            (Some(interface_reference), Some(interface)) => {
+                let (this, function) = self.generate_interface_dispatch(
+                    interface.get_name(),
+                    interface_reference,
+                    index_entry,
+                    operator,
+                )?;
+                (Some(this), Some(function))
+            }
+            (Some(instance), None)
+                if !is_super_call(operator) && !self.is_called_on_instance_variable(operator) =>
+            {
+                let method_pointer =
+                    self.generate_virtual_dispatch(instance, index_entry, operator)?;
+                (Some(instance), method_pointer)
+            }
+            (class_struct, _) => (class_struct, None),
+        };
         //Create parameters for input and output blocks
         let current_f = function_context.function;
         let input_block = self.llvm.context.append_basic_block(current_f, "input");
@@ -530,16 +2035,25 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             .map(VariableIndexEntry::get_type_name)
             .or(Some("__VOID"))
             .and_then(|it| self.index.find_type_information(it));
-        let function = self
-            .llvm_index
-            .find_associated_implementation(function_name) //using the non error option to control the output error
-            .ok_or_else(|| CompileError::CodeGenError {
-                message: format!(
-                    "No callable implementation associated to {:?}",
-                    function_name
-                ),
-                location: operator.get_location(),
-            })?;
+        let function: CallableValue = if let Some(function_pointer) = dispatched_function {
+            CallableValue::try_from(function_pointer).map_err(|_| {
+                CompileError::codegen_error(
+                    format!("Cannot call {:?} through a function pointer", function_name),
+                    operator.get_location(),
+                )
+            })?
+        } else {
+            self.llvm_index
+                .find_associated_implementation(function_name) //using the non error option to control the output error
+                .ok_or_else(|| CompileError::CodeGenError {
+                    message: format!(
+                        "No callable implementation associated to {:?}",
+                        function_name
+                    ),
+                    location: operator.get_location(),
+                })?
+                .into()
+        };
         //If the target is a function, declare the struct locally
         //Assign all parameters into the struct values
         let call_result = builder
@@ -562,6 +2076,243 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         Ok((return_type.unwrap(), value))
     }
 
+    /// loads the referenced instance and the called method's function pointer from an interface-reference
+    ///
+    /// - `interface_name` the name of the interface declaring the method
+    /// - `interface_reference` a pointer to the interface-reference (the instance and its vtable)
+    /// - `method` the interface's method to call
+    /// - `context` the statement used to report a possible CompileError on
+    fn generate_interface_dispatch(
+        &self,
+        interface_name: &str,
+        interface_reference: PointerValue<'a>,
+        method: &ImplementationIndexEntry,
+        context: &AstStatement,
+    ) -> Result<(PointerValue<'a>, PointerValue<'a>), CompileError> {
+        let location = context.get_location();
+        let slot = self
+            .index
+            .find_declared_methods(interface_name)
+            .iter()
+            .position(|it| it.get_name().eq_ignore_ascii_case(method.get_call_name()))
+            .ok_or_else(|| {
+                CompileError::invalid_reference(method.get_call_name(), location.clone())
+            })?;
+
+        let builder = &self.llvm.builder;
+        let load_member = |member_name: &str| {
+            let member_location = self
+                .index
+                .find_member(interface_name, member_name)
+                .map(VariableIndexEntry::get_location_in_parent)
+                .ok_or_else(|| CompileError::invalid_reference(member_name, location.clone()))?;
+            self.llvm
+                .get_member_pointer_from_struct(
+                    interface_reference,
+                    member_location,
+                    member_name,
+                    &location,
+                )
+                .map(|it| builder.build_load(it, member_name).into_pointer_value())
+        };
+        let this = load_member(INTERFACE_INSTANCE_MEMBER)?;
+        let vtable = load_member(INTERFACE_VTABLE_MEMBER)?;
+        let slot_pointer = unsafe {
+            builder.build_in_bounds_gep(
+                vtable,
+                &[self.llvm.i32_type().const_int(slot as u64, false)],
+                "",
+            )
+        };
+        let function = builder.build_load(slot_pointer, "").into_pointer_value();
+
+        //the dispatched function takes the untyped instance-pointer and the method's parameter-struct
+        let method_struct_type = self
+            .llvm_index
+            .get_associated_type(method.get_type_name())?
+            .ptr_type(AddressSpace::Generic);
+        let parameters = [this.get_type().into(), method_struct_type.into()];
+        let function_type = match self.index.find_return_type(method.get_type_name()) {
+            Some(return_type) => self
+                .llvm_index
+                .get_associated_type(return_type.get_name())?
+                .fn_type(&parameters, false),
+            None => self.llvm.context.void_type().fn_type(&parameters, false),
+        };
+        let function = builder.build_pointer_cast(
+            function,
+            function_type.ptr_type(AddressSpace::Generic),
+            method.get_call_name(),
+        );
+        Ok((this, function))
+    }
+
+    /// returns true if the given call-operator calls a method on a variable holding an instance
+    /// (e.g. `cl.foo()`), the instance's class is known and the method can be called directly.
+    /// Methods called on VAR_IN_OUTs, references or pointers are dispatched via the method table
+    fn is_called_on_instance_variable(&self, operator: &AstStatement) -> bool {
+        match operator {
+            AstStatement::QualifiedReference { elements, .. } if elements.len() > 1 => {
+                let instance = &elements[elements.len() - 2];
+                matches!(instance, AstStatement::Reference { .. })
+                    && matches!(
+                        self.annotations
+                            .get_type(instance, self.index)
+                            .map(DataType::get_type_information),
+                        Some(DataTypeInformation::Struct { .. })
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    /// loads the most derived implementation of the given method from the instance's method table.
+    /// returns None if the method cannot be overridden, so it can be called directly
+    ///
+    /// - `instance` a pointer to the instance the method is called on
+    /// - `method` the called method
+    /// - `context` the statement used to report a possible CompileError on
+    fn generate_virtual_dispatch(
+        &self,
+        instance: PointerValue<'a>,
+        method: &ImplementationIndexEntry,
+        context: &AstStatement,
+    ) -> Result<Option<PointerValue<'a>>, CompileError> {
+        let class_name = match method.get_associated_class_name() {
+            Some(class_name) if self.index.has_method_table(class_name) => class_name,
+            _ => return Ok(None),
+        };
+        if self
+            .index
+            .find_pou(method.get_call_name())
+            .map(PouIndexEntry::is_final)
+            .unwrap_or(false)
+        {
+            return Ok(None);
+        }
+        let location = context.get_location();
+        let slot = self
+            .index
+            .find_method_table(class_name)
+            .iter()
+            .position(|it| {
+                it.get_call_name()
+                    .eq_ignore_ascii_case(method.get_call_name())
+            })
+            .ok_or_else(|| {
+                CompileError::invalid_reference(method.get_call_name(), location.clone())
+            })?;
+        let method_table_location = self
+            .index
+            .find_member(class_name, METHOD_TABLE_MEMBER)
+            .map(VariableIndexEntry::get_location_in_parent)
+            .ok_or_else(|| {
+                CompileError::invalid_reference(METHOD_TABLE_MEMBER, location.clone())
+            })?;
+
+        let builder = &self.llvm.builder;
+        let method_table = self
+            .llvm
+            .get_member_pointer_from_struct(
+                instance,
+                method_table_location,
+                METHOD_TABLE_MEMBER,
+                &location,
+            )
+            .map(|it| {
+                builder
+                    .build_load(it, METHOD_TABLE_MEMBER)
+                    .into_pointer_value()
+            })?;
+        let slot_pointer = unsafe {
+            builder.build_in_bounds_gep(
+                method_table,
+                &[self.llvm.i32_type().const_int(slot as u64, false)],
+                "",
+            )
+        };
+        let function = builder.build_load(slot_pointer, "").into_pointer_value();
+
+        //the overriding methods take a pointer to their own class, which starts with the called class' members
+        let function_type = self
+            .llvm_index
+            .find_associated_implementation(method.get_call_name())
+            .ok_or_else(|| CompileError::invalid_reference(method.get_call_name(), location))?
+            .get_type();
+        Ok(Some(builder.build_pointer_cast(
+            function,
+            function_type.ptr_type(AddressSpace::Generic),
+            method.get_call_name(),
+        )))
+    }
+
+    /// generates an interface-reference (the instance and its vtable) if an instance of a
+    /// class or function block is assigned to a variable of an interface-type.
+    /// returns None if the `target_type` is no interface or the expression is an interface-reference itself
+    ///
+    /// - `target_type` the datatype of the assignment's target
+    /// - `expression` the assigned expression
+    pub fn generate_interface_reference(
+        &self,
+        target_type: &DataType,
+        expression: &AstStatement,
+    ) -> Result<Option<TypeAndValue<'a>>, CompileError> {
+        let interface = match self
+            .index
+            .find_pou(target_type.get_name())
+            .filter(|it| it.is_interface())
+        {
+            Some(interface) => interface.get_name(),
+            None => return Ok(None),
+        };
+        let class_name = match self.annotations.get_type(expression, self.index) {
+            Some(it) if !it.get_name().eq_ignore_ascii_case(interface) => it.get_name(),
+            _ => return Ok(None),
+        };
+
+        let vtable = self
+            .llvm_index
+            .find_associated_variable_value(&get_vtable_name(class_name, interface))
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!(
+                        "'{:}' does not implement the interface '{:}'",
+                        class_name, interface
+                    ),
+                    expression.get_location(),
+                )
+            })?;
+        let instance = self.generate_element_pointer(expression)?.ptr_value;
+
+        let builder = &self.llvm.builder;
+        let interface_type = self
+            .llvm_index
+            .get_associated_type(interface)?
+            .into_struct_type();
+        let field_types = interface_type.get_field_types();
+        let instance = builder.build_pointer_cast(instance, field_types[0].into_pointer_type(), "");
+        let vtable = builder.build_pointer_cast(
+            vtable.into_pointer_value(),
+            field_types[1].into_pointer_type(),
+            "",
+        );
+        let reference = builder
+            .build_insert_value(interface_type.get_undef(), instance, 0, "")
+            .map(|it| it.into_struct_value())
+            .and_then(|it| builder.build_insert_value(it, vtable, 1, ""))
+            .map(|it| it.into_struct_value().as_basic_value_enum())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("Cannot generate a reference to interface '{:}'", interface),
+                    expression.get_location(),
+                )
+            })?;
+        Ok(Some((
+            target_type.get_type_information().clone(),
+            reference,
+        )))
+    }
+
     /// generates a new instance of a function called `function_name` and returns a PointerValue to it
     ///
     /// - `function_name` the name of the function as registered in the index
@@ -610,13 +2361,13 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         };
         match &parameters {
             Some(AstStatement::ExpressionList { expressions, .. }) => {
-                for (index, exp) in expressions.iter().enumerate() {
+                for (position, exp) in expressions.iter().enumerate() {
                     let parameter = self.generate_single_parameter(
                         &ParameterContext {
                             assignment_statement: exp,
                             function_name,
                             parameter_type: None,
-                            index: index as u32,
+                            index: self.get_parameter_location(function_name, position as u32),
                             parameter_struct,
                         },
                         input_block,
@@ -633,7 +2384,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                         assignment_statement: statement,
                         function_name,
                         parameter_type: None,
-                        index: 0,
+                        index: self.get_parameter_location(function_name, 0),
                         parameter_struct,
                     },
                     input_block,
@@ -648,6 +2399,15 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         Ok(result)
     }
 
+    /// returns the location of the parameter receiving the `position`-th nameless argument
+    /// or the position itself if there is no such parameter (e.g. variadic arguments)
+    fn get_parameter_location(&self, function_name: &str, position: u32) -> u32 {
+        self.index
+            .find_declared_parameter(function_name, position)
+            .map(VariableIndexEntry::get_location_in_parent)
+            .unwrap_or(position)
+    }
+
     /// generates an assignemnt of a single call's parameter
     ///
     /// - `assignment_statement' the parameter-assignment, either an AssignmentStatement, an OutputAssignmentStatement or an expression
@@ -703,36 +2463,57 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             let pointer_to_param = builder
                 .build_struct_gep(parameter_struct, index as u32, "")
                 .unwrap();
+            let parameter_type = parameter_type.or_else(|| {
+                self.index
+                    .find_input_parameter(function_name, index as u32)
+                    .and_then(|var| self.index.find_type(var.get_type_name()))
+            });
+            if let Some((_, interface_reference)) = parameter_type
+                .map(|it| self.generate_interface_reference(it, assignment_statement))
+                .transpose()?
+                .flatten()
+            {
+                //an instance is passed to an interface-parameter
+                builder.build_store(pointer_to_param, interface_reference);
+                return Ok(None);
+            }
             let parameter = parameter_type
-                .or_else(|| {
-                    self.index
-                        .find_input_parameter(function_name, index as u32)
-                        .and_then(|var| self.index.find_type(var.get_type_name()))
-                })
                 .map(|var| var.get_type_information())
                 .unwrap();
             let (value_type, generated_exp) = if let DataTypeInformation::Pointer {
                 auto_deref: true,
+                inner_type_name,
                 ..
             } = parameter
             {
                 //this is VAR_IN_OUT assignemt, so don't load the value, assign the pointer
-                self.generate_element_pointer_for_rec(None, assignment_statement)
+                let argument = self
+                    .generate_element_pointer_for_rec(None, assignment_statement)
                     //get a pointer for that variable
+                    // auto-deref, if it is a var_in_out itself
                     .and_then(|tp| {
                         self.auto_deref_if_necessary(
                             tp.type_entry,
                             tp.ptr_value,
                             assignment_statement,
                         )
-                    })
-                    // auto-deref, if it is a var_in_out itself
-                    .map(|v| {
-                        (
-                            v.type_entry.get_type_information().clone(),
-                            v.ptr_value.as_basic_value_enum(),
-                        )
-                    })?
+                    })?;
+                let argument_type = argument.get_type_information();
+                let parameter_type = self.index.get_effective_type_by_name(inner_type_name);
+                //an array is passed to an ARRAY[*] as a descriptor of its bounds
+                let pointer = match (parameter_type.get_type_information(), argument_type) {
+                    (
+                        DataTypeInformation::VariableLengthArray { .. },
+                        DataTypeInformation::Array { dimensions, .. },
+                    ) => self.generate_array_descriptor(
+                        parameter_type.get_name(),
+                        dimensions,
+                        argument.ptr_value,
+                        assignment_statement,
+                    )?,
+                    _ => argument.ptr_value,
+                };
+                (argument_type.clone(), pointer.as_basic_value_enum())
             } else {
                 self.generate_expression(assignment_statement)?
             };
@@ -744,6 +2525,8 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 &value_type,
                 assignment_statement,
             )?;
+            //a derived instance may be passed to a VAR_IN_OUT of its super class' type
+            let value = self.cast_to_pointer_if_needed(value, pointer_to_param);
             builder.build_store(pointer_to_param, value);
             Ok(None)
         } else {
@@ -836,6 +2619,12 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         &self,
         reference_statement: &AstStatement,
     ) -> Result<TypeAndPointer<'a, '_>, CompileError> {
+        if let Some(address) = self.find_located_bit_address(reference_statement) {
+            return Err(CompileError::codegen_error(
+                format!("Cannot reference the bit-located variable at {}", address),
+                reference_statement.get_location(),
+            ));
+        }
         let result = match reference_statement {
             AstStatement::Reference { name, .. } => {
                 self.create_llvm_pointer_value_for_reference(None, name, reference_statement)
@@ -861,6 +2650,15 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         })
     }
 
+    /// generates the pointer to the given reference's own storage, other than
+    /// `generate_element_pointer` an auto-deref pointer (e.g. a `REFERENCE TO`) is not dereferenced
+    pub fn generate_reference_pointer(
+        &self,
+        reference_statement: &AstStatement,
+    ) -> Result<TypeAndPointer<'a, '_>, CompileError> {
+        self.generate_element_pointer_for_rec(None, reference_statement)
+    }
+
     /// geneartes a gep for the given reference with an optional qualifier
     ///
     /// - `qualifier` an optional qualifier for a reference (e.g. myStruct.x where myStruct is the qualifier for x)
@@ -889,12 +2687,18 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             //.unwrap();
             let member_data_type = member.map(|it| it.get_type_name()).unwrap();
             let member_type = self.index.get_type(member_data_type)?;
-            let gep = self.llvm.get_member_pointer_from_struct(
-                l_value.ptr_value,
-                member_location,
-                name,
-                offset,
-            )?;
+            let gep = if let Some(address) = member.and_then(|it| it.get_hardware_address()) {
+                //located members live in their process image, not in the instance
+                let llvm_type = self.llvm_index.get_associated_type(member_data_type)?;
+                get_located_variable_pointer(self.llvm, self.llvm_index, address, llvm_type)?
+            } else {
+                self.llvm.get_member_pointer_from_struct(
+                    l_value.ptr_value,
+                    member_location,
+                    name,
+                    offset,
+                )?
+            };
 
             TypeAndPointer::new(member_type, gep)
         } else {
@@ -961,8 +2765,12 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             let (_, value) = self
                 .llvm
                 .load_pointer(&TypeAndPointer::new(variable_type, accessor_ptr), "deref");
+            let mut pointer = value.into_pointer_value();
+            if let Some(hook) = self.find_check_hook(POINTER_CHECK_FN) {
+                pointer = self.generate_null_check(hook, pointer, variable_type, statement)?;
+            }
             let inner_type = self.index.get_type(inner_type_name)?;
-            Ok(TypeAndPointer::new(inner_type, value.into_pointer_value()))
+            Ok(TypeAndPointer::new(inner_type, pointer))
         } else {
             Err(CompileError::codegen_error(
                 format!("Cannot derefence non pointer type : {:?}", variable_type),
@@ -995,7 +2803,9 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
     }
 
     /// generates the access-expression for an array-reference
-    /// myArray[array_expression] where array_expression is the access-expression
+    /// myArray[array_expression] where array_expression is the access-expression.
+    /// If a `CheckBounds` function is declared, the access-expression is wrapped into
+    /// a call to it: myArray[CheckBounds(array_expression, lower, upper)]
     ///
     /// - `dimension` the array's dimension
     /// - `access_expression` the expression inside the array-statement
@@ -1004,12 +2814,34 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         dimension: &Dimension,
         access_expression: &AstStatement,
     ) -> Result<IntValue<'a>, CompileError> {
+        let location = access_expression.get_location();
         let start_offset = dimension
             .start_offset
             .as_int_value(self.index)
-            .map_err(|it| CompileError::codegen_error(it, access_expression.get_location()))?;
+            .map_err(|it| CompileError::codegen_error(it, location.clone()))?;
 
-        let (_, access_value) = self.generate_expression(access_expression)?;
+        let (_, access_value) = if let Some(hook) = self.find_check_hook(BOUNDS_CHECK_FN) {
+            let end_offset = dimension
+                .end_offset
+                .as_int_value(self.index)
+                .map_err(|it| CompileError::codegen_error(it, location.clone()))?;
+            let bound = |value: i64| AstStatement::LiteralInteger {
+                value: value as i128,
+                location: location.clone(),
+                id: 0,
+            };
+            let check_call = create_call_to_check_function_ast(
+                hook.get_call_name().to_string(),
+                access_expression.clone(),
+                bound(start_offset)..bound(end_offset),
+                &location,
+            );
+            //the index and the bounds are passed as DINTs
+            let dint = self.index.get_type_information_or_void(DINT_TYPE);
+            self.morph_to_typed(dint).generate_expression(&check_call)?
+        } else {
+            self.generate_expression(access_expression)?
+        };
         //If start offset is not 0, adjust the current statement with an add operation
         if start_offset != 0 {
             Ok(self.llvm.builder.build_int_sub(
@@ -1035,7 +2867,25 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
     ) -> Result<TypeAndPointer<'a, '_>, CompileError> {
         //Load the reference
         self.generate_element_pointer_for_rec(qualifier, reference)
+            //a VAR_IN_OUT array is accessed through its pointer
             .and_then(|lvalue| {
+                self.auto_deref_if_necessary(lvalue.type_entry, lvalue.ptr_value, reference)
+            })
+            .and_then(|lvalue| {
+                if let DataTypeInformation::VariableLengthArray {
+                    inner_type_name,
+                    dimension_count,
+                    ..
+                } = lvalue.get_type_information()
+                {
+                    let pointer = self.generate_element_pointer_for_descriptor(
+                        lvalue.ptr_value,
+                        *dimension_count,
+                        access,
+                    )?;
+                    let internal_type = self.index.get_type(inner_type_name)?;
+                    return Ok(TypeAndPointer::new(internal_type, pointer));
+                }
                 if let DataTypeInformation::Array {
                     inner_type_name,
                     dimensions,
@@ -1077,6 +2927,156 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             })
     }
 
+    /// generates a gep statement for an element of a variable-length array (`ARRAY[*]`)
+    ///
+    /// the element is addressed by its row-major position behind the descriptor's data-pointer:
+    /// `((i1 - lower1) * length2 + (i2 - lower2)) * length3 + ...`. If a `CheckBounds` function
+    /// is declared, every index is passed to it together with the dimension's runtime-bounds.
+    ///
+    /// - `descriptor` a pointer to the array's descriptor
+    /// - `dimension_count` the number of the array's dimensions
+    /// - `access` the accessor expression (the expression between the brackets: reference[access])
+    fn generate_element_pointer_for_descriptor(
+        &self,
+        descriptor: PointerValue<'a>,
+        dimension_count: usize,
+        access: &AstStatement,
+    ) -> Result<PointerValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let statements = access.get_as_list();
+        if statements.len() != dimension_count {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Mismatched array access : {} -> {} ",
+                    statements.len(),
+                    dimension_count
+                ),
+                access.get_location(),
+            ));
+        }
+        let data = self
+            .llvm
+            .get_member_pointer_from_struct(descriptor, 0, "", &access.get_location())
+            .map(|it| builder.build_load(it, "data").into_pointer_value())?;
+        let bounds = self.llvm.get_member_pointer_from_struct(
+            descriptor,
+            1,
+            "bounds",
+            &access.get_location(),
+        )?;
+        let load_bound = |position: usize| {
+            let zero = self.llvm.i32_type().const_zero();
+            let position = self.llvm.i32_type().const_int(position as u64, false);
+            self.llvm
+                .load_array_element(bounds, &[zero, position], "")
+                .map(|it| builder.build_load(it, "").into_int_value())
+        };
+
+        //the index and the bounds are passed as DINTs
+        let dint = self.index.get_type_information_or_void(DINT_TYPE);
+        let mut element_index: Option<IntValue<'a>> = None;
+        for (dimension, statement) in statements.into_iter().enumerate() {
+            let lower = load_bound(2 * dimension)?;
+            let upper = load_bound(2 * dimension + 1)?;
+            let (access_type, access_value) = self.generate_expression(statement)?;
+            let access_value = cast_if_needed(
+                self.llvm,
+                self.index,
+                dint,
+                access_value,
+                &access_type,
+                statement,
+            )?;
+            let access_value = match self.find_check_hook(BOUNDS_CHECK_FN) {
+                Some(hook) => self
+                    .generate_hook_call(
+                        hook,
+                        &[
+                            (dint.clone(), access_value),
+                            (dint.clone(), lower.into()),
+                            (dint.clone(), upper.into()),
+                        ],
+                        statement,
+                    )?
+                    .map_or(access_value, |(_, value)| value),
+                None => access_value,
+            };
+            let offset = builder.build_int_sub(access_value.into_int_value(), lower, "");
+            element_index = Some(match element_index {
+                Some(element_index) => {
+                    let length = builder.build_int_add(
+                        builder.build_int_sub(upper, lower, ""),
+                        self.llvm.i32_type().const_int(1, false),
+                        "",
+                    );
+                    let element_index = builder.build_int_mul(element_index, length, "");
+                    builder.build_int_add(element_index, offset, "")
+                }
+                None => offset,
+            });
+        }
+        let element_index = element_index.unwrap_or_else(|| self.llvm.i32_type().const_zero());
+        self.llvm
+            .load_array_element(data, &[element_index], "tmpVar")
+    }
+
+    /// generates a descriptor for an array passed to a variable-length array (`ARRAY[*]`)
+    /// and returns a pointer to it. The descriptor holds a pointer to the array's first
+    /// element and the lower and upper bound of every dimension.
+    ///
+    /// - `descriptor_type_name` the name of the variable-length array's type
+    /// - `dimensions` the dimensions of the passed array
+    /// - `array` a pointer to the passed array
+    /// - `context` the statement used to report a possible CompileError on
+    fn generate_array_descriptor(
+        &self,
+        descriptor_type_name: &str,
+        dimensions: &[Dimension],
+        array: PointerValue<'a>,
+        context: &AstStatement,
+    ) -> Result<PointerValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let location = context.get_location();
+        let descriptor_type = self.llvm_index.get_associated_type(descriptor_type_name)?;
+        let descriptor = builder.build_alloca(descriptor_type, "descriptor");
+
+        let data = self
+            .llvm
+            .get_member_pointer_from_struct(descriptor, 0, "data", &location)?;
+        let element_pointer_type = data.get_type().get_element_type().into_pointer_type();
+        builder.build_store(
+            data,
+            builder.build_pointer_cast(array, element_pointer_type, ""),
+        );
+
+        let bounds = self
+            .llvm
+            .get_member_pointer_from_struct(descriptor, 1, "bounds", &location)?;
+        let zero = self.llvm.i32_type().const_zero();
+        for (position, dimension) in dimensions.iter().enumerate() {
+            for (offset, bound) in [&dimension.start_offset, &dimension.end_offset]
+                .iter()
+                .enumerate()
+            {
+                let bound = bound
+                    .as_int_value(self.index)
+                    .map_err(|it| CompileError::codegen_error(it, location.clone()))?;
+                let pointer = self.llvm.load_array_element(
+                    bounds,
+                    &[
+                        zero,
+                        self.llvm
+                            .i32_type()
+                            .const_int((2 * position + offset) as u64, false),
+                    ],
+                    "",
+                )?;
+                builder.build_store(pointer, self.llvm.i32_type().const_int(bound as u64, true));
+            }
+        }
+        Ok(descriptor)
+    }
+
     /// the entry function for recursive reference-generation (for qualified references)
     ///
     /// - `qualifier` the qualifier (TypeAndPointer) for the given reference-statement
@@ -1094,7 +3094,9 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                     self.generate_element_pointer_for_rec(qualifier, current_element.unwrap());
 
                 for it in element_iter {
+                    //members are accessed through references and VAR_IN_OUTs (auto-deref pointers)
                     let ctx = current_lvalue?;
+                    let ctx = self.auto_deref_if_necessary(ctx.type_entry, ctx.ptr_value, it)?;
                     let context_ptr = ctx.ptr_value;
                     let type_information = ctx.type_entry;
 
@@ -1108,7 +3110,8 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             AstStatement::Reference { name, .. } => {
                 if let Some(qualifier) = qualifier {
                     //Find if there is an action with the current name
-                    let qualified_name = format!("{}.{}", qualifier.type_entry.get_name(), name);
+                    let qualifier_name = qualifier.type_entry.get_name();
+                    let qualified_name = format!("{}.{}", qualifier_name, name);
                     let implementation = self.index.find_implementation(&qualified_name);
                     if implementation.is_some() {
                         let result = TypeAndPointer {
@@ -1117,6 +3120,23 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                         };
                         return Ok(result);
                     }
+                    //Find if there is a method inherited from a super class
+                    if let Some(method) = self.index.find_method(qualifier_name, name) {
+                        let class_name = method
+                            .get_associated_class_name()
+                            .map(String::as_str)
+                            .unwrap_or(qualifier_name);
+                        let result = TypeAndPointer {
+                            type_entry: self.index.get_type(method.get_call_name())?,
+                            //the super class' struct is a prefix of the derived struct
+                            ptr_value: self.cast_to_class_pointer(
+                                qualifier.ptr_value,
+                                class_name,
+                                reference,
+                            )?,
+                        };
+                        return Ok(result);
+                    }
                 };
                 //Otherwise, load a variable reference
                 self.create_llvm_pointer_value_for_reference(qualifier, name, reference)
@@ -1125,6 +3145,9 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 reference, access, ..
             } => self.generate_element_pointer_for_array(qualifier, reference, access),
             AstStatement::PointerAccess { reference, .. } => {
+                if qualifier.is_none() && is_super_reference(reference) {
+                    return self.generate_super_pointer(reference);
+                }
                 let pointer = self.generate_element_pointer_for_rec(qualifier, reference)?;
                 self.deref(pointer.type_entry, pointer.ptr_value, reference)
             }
@@ -1135,10 +3158,413 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         }
     }
 
+    /// generates the pointer to the super class' instance for a `SUPER^` access
+    ///
+    /// since the derived struct starts with all members of its super class, the current
+    /// instance (the pou's first parameter) is casted to a pointer to the super class
+    fn generate_super_pointer(
+        &self,
+        reference: &AstStatement,
+    ) -> Result<TypeAndPointer<'a, '_>, CompileError> {
+        let function_context = self.get_function_context(reference)?;
+        let super_class = self
+            .annotations
+            .get_type(reference, self.index)
+            .and_then(|it| match it.get_type_information() {
+                DataTypeInformation::Pointer {
+                    inner_type_name, ..
+                } => Some(inner_type_name.as_str()),
+                _ => None,
+            })
+            .ok_or_else(|| CompileError::invalid_reference("SUPER", reference.get_location()))?;
+
+        let this = function_context
+            .function
+            .get_first_param()
+            .ok_or_else(|| CompileError::invalid_reference("SUPER", reference.get_location()))?
+            .into_pointer_value();
+
+        Ok(TypeAndPointer::new(
+            self.index.get_type(super_class)?,
+            self.cast_to_class_pointer(this, super_class, reference)?,
+        ))
+    }
+
+    /// casts the given instance pointer to a pointer of the given class' struct
+    fn cast_to_class_pointer(
+        &self,
+        instance: PointerValue<'a>,
+        class_name: &str,
+        context: &AstStatement,
+    ) -> Result<PointerValue<'a>, CompileError> {
+        let class_type = self
+            .llvm_index
+            .find_associated_type(class_name)
+            .ok_or_else(|| CompileError::no_type_associated(class_name, context.get_location()))?;
+        let target_type = class_type.ptr_type(AddressSpace::Generic);
+        if instance.get_type() == target_type {
+            Ok(instance)
+        } else {
+            Ok(self
+                .llvm
+                .builder
+                .build_pointer_cast(instance, target_type, "super"))
+        }
+    }
+
+    /// returns the implementation of the given runtime-check hook (e.g. `CheckBounds`) if it is
+    /// declared. No checks are generated into the hooks themselves.
+    fn find_check_hook(&self, hook: &str) -> Option<&'b ImplementationIndexEntry> {
+        let is_inside_hook = self.function_context.map_or(false, |context| {
+            let pou_name = context.linking_context.get_call_name();
+            RUNTIME_CHECK_FNS
+                .iter()
+                .any(|it| it.eq_ignore_ascii_case(pou_name))
+        });
+        if is_inside_hook {
+            None
+        } else {
+            self.index.find_implementation(hook)
+        }
+    }
+
+    /// generates a call to the given runtime-check hook with already generated arguments and
+    /// returns the hook's result, if it returns one. The arguments are cast to the types of the
+    /// hook's parameters.
+    ///
+    /// - `hook` the hook's implementation
+    /// - `arguments` the arguments in the order of the hook's parameters
+    /// - `context` the statement used to report a possible CompileError on
+    fn generate_hook_call(
+        &self,
+        hook: &ImplementationIndexEntry,
+        arguments: &[TypeAndValue<'a>],
+        context: &AstStatement,
+    ) -> Result<Option<TypeAndValue<'a>>, CompileError> {
+        let function_name = hook.get_call_name();
+        let instance = self.allocate_function_struct_instance(function_name, context)?;
+        for (position, (argument_type, argument)) in arguments.iter().enumerate() {
+            let parameter = self
+                .index
+                .find_declared_parameter(function_name, position as u32)
+                .ok_or_else(|| {
+                    CompileError::codegen_error(
+                        format!(
+                            "{} expects less than {} parameters",
+                            function_name,
+                            position + 1
+                        ),
+                        context.get_location(),
+                    )
+                })?;
+            let pointer = self
+                .llvm
+                .builder
+                .build_struct_gep(instance, parameter.get_location_in_parent(), "")
+                .map_err(|_| {
+                    CompileError::codegen_error(
+                        format!("Cannot access the parameters of {}", function_name),
+                        context.get_location(),
+                    )
+                })?;
+            let value = if argument.is_pointer_value() {
+                self.llvm
+                    .builder
+                    .build_pointer_cast(
+                        argument.into_pointer_value(),
+                        pointer.get_type().get_element_type().into_pointer_type(),
+                        "",
+                    )
+                    .as_basic_value_enum()
+            } else {
+                let parameter_type = self
+                    .index
+                    .get_type_information_or_void(parameter.get_type_name());
+                cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    parameter_type,
+                    *argument,
+                    argument_type,
+                    context,
+                )?
+            };
+            self.llvm.builder.build_store(pointer, value);
+        }
+
+        let function = self
+            .llvm_index
+            .find_associated_implementation(function_name)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!(
+                        "No callable implementation associated to {:?}",
+                        function_name
+                    ),
+                    context.get_location(),
+                )
+            })?;
+        let result = self
+            .llvm
+            .builder
+            .build_call(function, &[instance.as_basic_value_enum()], "")
+            .try_as_basic_value()
+            .left();
+        let return_type = self
+            .index
+            .find_return_type(function_name)
+            .map(DataType::get_type_information);
+        Ok(result
+            .zip(return_type)
+            .map(|(value, data_type)| (data_type.clone(), value)))
+    }
+
+    /// generates an int/bool binary-expression. If the corresponding hooks are declared, the
+    /// divisor of a division is passed to `CheckDivDInt` (`CheckDivLInt` for 64 bit integers)
+    /// and an overflowing addition, subtraction or multiplication calls `CheckOverflow`
+    ///
+    /// - `operator` the binary operator
+    /// - `left_value` the left side of the binary expression, needs to be an int-value
+    /// - `right_value` the right side of the binary expression, needs to be an int-value
+    /// - `target_type` the resulting type
+    /// - `expression` the binary-expression
+    fn generate_int_binary_expression(
+        &self,
+        operator: &Operator,
+        left_value: BasicValueEnum<'a>,
+        right_value: BasicValueEnum<'a>,
+        target_type: &DataTypeInformation,
+        expression: &AstStatement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        match operator {
+            Operator::Division | Operator::Modulo => {
+                let hook = if target_type.get_size() > 32 {
+                    DIV_CHECK_L_FN
+                } else {
+                    DIV_CHECK_FN
+                };
+                if let Some(hook) = self.find_check_hook(hook) {
+                    let (divisor_type, divisor) = self
+                        .generate_hook_call(
+                            hook,
+                            &[(target_type.clone(), right_value)],
+                            expression,
+                        )?
+                        .ok_or_else(|| {
+                            CompileError::codegen_error(
+                                format!("{} does not return a value", hook.get_call_name()),
+                                expression.get_location(),
+                            )
+                        })?;
+                    let divisor = cast_if_needed(
+                        self.llvm,
+                        self.index,
+                        target_type,
+                        divisor,
+                        &divisor_type,
+                        expression,
+                    )?;
+                    return Ok(self.create_llvm_int_binary_expression(
+                        operator,
+                        left_value,
+                        divisor,
+                        target_type,
+                    ));
+                }
+            }
+            Operator::Plus | Operator::Minus | Operator::Multiplication
+                if !target_type.is_bool() =>
+            {
+                if let Some(hook) = self.find_check_hook(OVERFLOW_CHECK_FN) {
+                    let value = self.generate_overflow_check(
+                        hook,
+                        operator,
+                        left_value.into_int_value(),
+                        right_value.into_int_value(),
+                        target_type,
+                        expression,
+                    )?;
+                    return Ok((target_type.clone(), value));
+                }
+            }
+            _ => {}
+        }
+        Ok(self.create_llvm_int_binary_expression(operator, left_value, right_value, target_type))
+    }
+
+    /// generates the given addition, subtraction or multiplication using the llvm intrinsic
+    /// that reports an overflow (e.g. `llvm.sadd.with.overflow.i32`) and calls the given hook
+    /// if the operation overflowed
+    fn generate_overflow_check(
+        &self,
+        hook: &ImplementationIndexEntry,
+        operator: &Operator,
+        left_value: IntValue<'a>,
+        right_value: IntValue<'a>,
+        target_type: &DataTypeInformation,
+        expression: &AstStatement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let operation = match operator {
+            Operator::Plus => "add",
+            Operator::Minus => "sub",
+            _ => "mul",
+        };
+        let sign = if target_type.is_signed_int() {
+            "s"
+        } else {
+            "u"
+        };
+        let int_type = left_value.get_type();
+        let name = format!(
+            "llvm.{}{}.with.overflow.i{}",
+            sign,
+            operation,
+            int_type.get_bit_width()
+        );
+        let function_context = self.get_function_context(expression)?;
+        let module = function_context.module;
+        let intrinsic = module.get_function(&name).unwrap_or_else(|| {
+            let result_type = self.llvm.context.struct_type(
+                &[int_type.into(), self.llvm.context.bool_type().into()],
+                false,
+            );
+            let function_type = result_type.fn_type(&[int_type.into(), int_type.into()], false);
+            module.add_function(&name, function_type, None)
+        });
+
+        let builder = &self.llvm.builder;
+        let result = builder
+            .build_call(intrinsic, &[left_value.into(), right_value.into()], "")
+            .try_as_basic_value()
+            .left()
+            .map(BasicValueEnum::into_struct_value)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("{} does not return a value", name),
+                    expression.get_location(),
+                )
+            })?;
+        let value = builder.build_extract_value(result, 0, "tmpVar");
+        let overflow = builder.build_extract_value(result, 1, "overflow");
+        let (value, overflow) = value.zip(overflow).ok_or_else(|| {
+            CompileError::codegen_error(
+                format!("Cannot read the result of {}", name),
+                expression.get_location(),
+            )
+        })?;
+
+        let overflow_block = self
+            .llvm
+            .context
+            .append_basic_block(function_context.function, "overflow");
+        let continue_block = self
+            .llvm
+            .context
+            .append_basic_block(function_context.function, "continue");
+        builder.build_conditional_branch(overflow.into_int_value(), overflow_block, continue_block);
+        builder.position_at_end(overflow_block);
+        self.generate_hook_call(hook, &[], expression)?;
+        builder.build_unconditional_branch(continue_block);
+        builder.position_at_end(continue_block);
+        Ok(value)
+    }
+
+    /// calls the given hook (`CheckPointer`) if the given pointer is null. The pointer returned by
+    /// the hook is dereferenced instead of the null-pointer
+    ///
+    /// - `pointer` the pointer to dereference
+    /// - `pointer_type` the pointer's data type
+    fn generate_null_check(
+        &self,
+        hook: &ImplementationIndexEntry,
+        pointer: PointerValue<'a>,
+        pointer_type: &DataType,
+        statement: &AstStatement,
+    ) -> Result<PointerValue<'a>, CompileError> {
+        let function = self.get_function_context(statement)?.function;
+        let builder = &self.llvm.builder;
+        let is_null = builder.build_is_null(pointer, "is_null");
+        let pointer_block = builder.get_insert_block().ok_or_else(|| {
+            CompileError::codegen_error(
+                "Cannot check a pointer outside of a function".into(),
+                statement.get_location(),
+            )
+        })?;
+        let null_block = self.llvm.context.append_basic_block(function, "null");
+        let continue_block = self.llvm.context.append_basic_block(function, "continue");
+        builder.build_conditional_branch(is_null, null_block, continue_block);
+
+        builder.position_at_end(null_block);
+        let arguments = [(
+            pointer_type.get_type_information().clone(),
+            pointer.as_basic_value_enum(),
+        )];
+        let checked_pointer = match self.generate_hook_call(hook, &arguments, statement)? {
+            Some((_, value)) if value.is_pointer_value() => {
+                builder.build_pointer_cast(value.into_pointer_value(), pointer.get_type(), "")
+            }
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!("{} does not return a pointer", hook.get_call_name()),
+                    statement.get_location(),
+                ))
+            }
+        };
+        builder.build_unconditional_branch(continue_block);
+
+        builder.position_at_end(continue_block);
+        let checked = builder.build_phi(pointer.get_type(), "checked");
+        checked.add_incoming(&[(&pointer, pointer_block), (&checked_pointer, null_block)]);
+        Ok(checked.as_basic_value().into_pointer_value())
+    }
+
+    /// moves the given pointer by the given number of elements of the pointer's type
+    /// (`operator` is either `+` or `-`)
+    fn generate_pointer_arithmetic(
+        &self,
+        operator: &Operator,
+        (pointer_type, pointer): TypeAndValue<'a>,
+        (offset_type, offset): TypeAndValue<'a>,
+        expression: &AstStatement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        //the offset is extended to 64 bit according to its signedness
+        let lint = self.index.get_type_information_or_void(LINT_TYPE);
+        let offset = cast_if_needed(
+            self.llvm,
+            self.index,
+            lint,
+            offset,
+            &offset_type,
+            expression,
+        )?;
+        Ok(self.create_llvm_int_binary_expression(operator, pointer, offset, &pointer_type))
+    }
+
+    /// converts the given value so it can be stored at the given pointer if the pointer points
+    /// to a pointer: other pointers are cast to its type, addresses (e.g. from `ADR`) are
+    /// converted to pointers. All other values are returned unchanged
+    pub fn cast_to_pointer_if_needed(
+        &self,
+        value: BasicValueEnum<'a>,
+        target: PointerValue<'a>,
+    ) -> BasicValueEnum<'a> {
+        let builder = &self.llvm.builder;
+        match (value, target.get_type().get_element_type()) {
+            (BasicValueEnum::PointerValue(pointer), AnyTypeEnum::PointerType(target_type)) => {
+                builder.build_pointer_cast(pointer, target_type, "").into()
+            }
+            (BasicValueEnum::IntValue(address), AnyTypeEnum::PointerType(target_type)) => {
+                builder.build_int_to_ptr(address, target_type, "").into()
+            }
+            (value, _) => value,
+        }
+    }
+
     /// generates the result of an int/bool binary-expression (+, -, *, /, %, ==)
     ///
     /// - `operator` the binary operator
     /// - `left_value` the left side of the binary expression, needs to be an int-value
+    ///   or a pointer which is moved by `right_value` elements (+, -)
     /// - `right_value` the right side of the binary expression, needs to be an int-value
     /// - `target_type` the resulting type
     pub fn create_llvm_int_binary_expression(
@@ -1148,6 +3574,17 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         right_value: BasicValueEnum<'a>,
         target_type: &DataTypeInformation,
     ) -> TypeAndValue<'a> {
+        if let BasicValueEnum::PointerValue(pointer) = left_value {
+            //typed pointer arithmetic, the offset counts elements of the pointer's type
+            let offset = right_value.into_int_value();
+            let offset = if operator == &Operator::Minus {
+                self.llvm.builder.build_int_neg(offset, "")
+            } else {
+                offset
+            };
+            let value = unsafe { self.llvm.builder.build_gep(pointer, &[offset], "tmpVar") };
+            return (target_type.clone(), value.into());
+        }
         let int_lvalue = left_value.into_int_value();
         let int_rvalue = right_value.into_int_value();
 
@@ -1814,6 +4251,15 @@ fn calculate_time_nano(negative: bool, sec: f64, milli: f64, micro: f64, nano: u
     }
 }
 
+/// returns the smallest and the biggest value of the integer type with the given size
+fn get_integer_range(signed: bool, size: u32) -> (i128, i128) {
+    if signed {
+        (-(1_i128 << (size - 1)), (1_i128 << (size - 1)) - 1)
+    } else {
+        (0, (1_i128 << size) - 1)
+    }
+}
+
 /// calculates the milliseconds since 1970-01-01-00:00:00 for the given
 /// point in time
 fn calculate_date_time(
@@ -1837,6 +4283,18 @@ fn calculate_date_time(
     ))
 }
 
+/// returns true if the given statement is a reference to `SUPER`
+fn is_super_reference(statement: &AstStatement) -> bool {
+    matches!(statement, AstStatement::Reference { name, .. } if name.eq_ignore_ascii_case("SUPER"))
+}
+
+/// returns true if the given call-operator calls a method of the super class (`SUPER^.method`)
+/// such calls are never dispatched via the method table
+fn is_super_call(operator: &AstStatement) -> bool {
+    matches!(operator, AstStatement::QualifiedReference { elements, .. }
+        if matches!(elements.first(), Some(AstStatement::PointerAccess { reference, .. }) if is_super_reference(reference)))
+}
+
 /// Returns true if the current statement has a return access.
 fn has_direct_access(statement: &AstStatement) -> bool {
     if let AstStatement::QualifiedReference { elements, .. } = statement {
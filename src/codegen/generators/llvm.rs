@@ -54,6 +54,44 @@ impl<'a> Llvm<'a> {
         global
     }
 
+    /// declares a global variable defined by another object, it has no initializer
+    ///
+    /// - `module` the compilation module to add the declaration
+    /// - `name` the name of the global variable
+    /// - `data_type` the variable's datatype
+    pub fn declare_global_variable(
+        &self,
+        module: &Module<'a>,
+        name: &str,
+        data_type: BasicTypeEnum<'a>,
+    ) -> GlobalValue<'a> {
+        let global = module.add_global(data_type, Some(AddressSpace::Generic), name);
+        global.set_linkage(Linkage::External);
+        global
+    }
+
+    /// generates a constant global null-terminated string with the given name and value
+    /// and returns an `i8*` pointing to it
+    ///
+    /// - `module` the compilation module to add the string
+    /// - `name` the name of the global string
+    /// - `value` the string's value
+    pub fn create_const_global_string(
+        &self,
+        module: &Module<'a>,
+        name: &str,
+        value: &str,
+    ) -> Result<PointerValue<'a>, CompileError> {
+        let (_, initializer) = self.create_llvm_const_vec_string(value.as_bytes())?;
+        let string_type = self.context.i8_type().array_type(value.len() as u32 + 1);
+        let global =
+            self.create_global_variable(module, name, string_type.into(), Some(initializer));
+        global.set_constant(true);
+        Ok(global
+            .as_pointer_value()
+            .const_cast(self.context.i8_type().ptr_type(AddressSpace::Generic)))
+    }
+
     /// creates a local variable at the builder's location
     ///
     /// - `name` the name of the local variable
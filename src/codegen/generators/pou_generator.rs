@@ -3,9 +3,13 @@ use super::{
     expression_generator::ExpressionCodeGenerator,
     llvm::Llvm,
     statement_generator::{FunctionContext, StatementCodeGenerator},
+    struct_generator::get_default_for,
+    variable_generator::get_located_variable_pointer,
 };
 use crate::{
-    ast::Pou, codegen::llvm_index::LlvmTypedIndex, index::ImplementationType,
+    ast::Pou,
+    codegen::{debug::DebugBuilder, llvm_index::LlvmTypedIndex},
+    index::ImplementationType,
     resolver::AnnotationMap,
 };
 
@@ -14,7 +18,7 @@ use crate::{
 /// - generates a struct-datatype for the POU's members
 /// - generates a function for the pou
 /// - declares a global instance if the POU is a PROGRAM
-use crate::index::{ImplementationIndexEntry, VariableIndexEntry};
+use crate::index::{ImplementationIndexEntry, PouIndexEntry, VariableIndexEntry};
 use crate::typesystem::*;
 use crate::{
     ast::{AstStatement, Implementation, PouType, SourceRange},
@@ -25,7 +29,7 @@ use inkwell::types::StructType;
 use inkwell::{
     module::Module,
     types::{BasicTypeEnum, FunctionType},
-    values::{BasicValueEnum, FunctionValue},
+    values::{BasicValueEnum, FunctionValue, GlobalValue, PointerValue},
     AddressSpace,
 };
 
@@ -34,6 +38,8 @@ pub struct PouGenerator<'ink, 'cg> {
     index: &'cg Index,
     annotations: &'cg AnnotationMap,
     llvm_index: &'cg LlvmTypedIndex<'ink>,
+    /// generates the debug information of the POUs, if requested
+    debug: Option<&'cg DebugBuilder<'ink>>,
 }
 
 /// Creates opaque implementations for all callable items in the index
@@ -46,8 +52,13 @@ pub fn generate_implementation_stubs<'ink>(
     types_index: &LlvmTypedIndex<'ink>,
 ) -> Result<LlvmTypedIndex<'ink>, CompileError> {
     let mut llvm_index = LlvmTypedIndex::new();
-    let pou_generator = PouGenerator::new(llvm, index, annotations, types_index);
+    let pou_generator = PouGenerator::new(llvm, index, annotations, types_index, None);
     for (name, implementation) in index.get_implementations() {
+        //an interface's methods are dispatched via vtables and have no implementation,
+        //calls to builtin functions are generated inline
+        if index.find_interface_of_method(implementation).is_some() || implementation.is_builtin() {
+            continue;
+        }
         let curr_f = pou_generator.generate_implementation_stub(implementation, module)?;
         llvm_index.associate_implementation(name, curr_f)?;
     }
@@ -55,6 +66,132 @@ pub fn generate_implementation_stubs<'ink>(
     Ok(llvm_index)
 }
 
+/// returns the name of the global vtable of the given class for the given interface
+pub fn get_vtable_name(class_name: &str, interface_name: &str) -> String {
+    format!("__vtable_{}_{}", class_name, interface_name)
+}
+
+/// returns the name of the global method table of the given class
+pub fn get_method_table_name(class_name: &str) -> String {
+    format!("__{}_vtable", class_name)
+}
+
+/// returns the global method table of the given class, declares it if it does not exist yet.
+///
+/// the table's entries are only known after the implementation stubs were generated, so
+/// the table is declared with a zero-initializer first (see `generate_vtables`).
+/// The method table of an `@EXTERNAL` class is defined by the class' object.
+pub fn declare_method_table<'ink>(
+    module: &Module<'ink>,
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    class_name: &str,
+) -> GlobalValue<'ink> {
+    let method_table_name = get_method_table_name(class_name);
+    module.get_global(&method_table_name).unwrap_or_else(|| {
+        let table_type = llvm
+            .context
+            .i8_type()
+            .ptr_type(AddressSpace::Generic)
+            .array_type(index.find_method_table(class_name).len() as u32);
+        if index
+            .find_pou(class_name)
+            .map_or(false, PouIndexEntry::is_external)
+        {
+            llvm.declare_global_variable(module, &method_table_name, table_type.into())
+        } else {
+            llvm.create_global_variable(module, &method_table_name, table_type.into(), None)
+        }
+    })
+}
+
+/// Creates a vtable for every interface implemented by a class or function block
+/// and a method table for every class or function block that is part of an inheritance hierarchy.
+///
+/// A vtable is a constant global array of (untyped) function pointers. It holds the
+/// class' implementation of every method of the interface in the order of the
+/// interface's method declarations. Methods without an implementation are `null`.
+/// A method table holds the most derived implementation of every method of the class
+/// (see `Index::find_method_table`).
+/// The tables of `@EXTERNAL` classes are only declared, they are defined by the class' object.
+/// Returns a Typed index containing the associated vtables and method tables.
+pub fn generate_vtables<'ink>(
+    module: &Module<'ink>,
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    llvm_index: &LlvmTypedIndex<'ink>,
+) -> Result<LlvmTypedIndex<'ink>, CompileError> {
+    let mut vtables = LlvmTypedIndex::new();
+    let function_pointer_type = llvm.context.i8_type().ptr_type(AddressSpace::Generic);
+    for class in index.get_pous().values() {
+        for interface in index.find_implemented_interfaces(class.get_name()) {
+            let vtable_name = get_vtable_name(class.get_name(), interface);
+            let entries: Vec<PointerValue> = index
+                .find_declared_methods(interface)
+                .iter()
+                .map(|method| {
+                    let method_name = method.get_name().rsplit('.').next().unwrap_or_default();
+                    index
+                        .find_method(class.get_name(), method_name)
+                        .and_then(|it| {
+                            llvm_index.find_associated_implementation(it.get_call_name())
+                        })
+                        .map(|it| {
+                            it.as_global_value()
+                                .as_pointer_value()
+                                .const_cast(function_pointer_type)
+                        })
+                        .unwrap_or_else(|| function_pointer_type.const_null())
+                })
+                .collect();
+
+            let vtable = module.get_global(&vtable_name).unwrap_or_else(|| {
+                let initializer = function_pointer_type.const_array(&entries);
+                if class.is_external() {
+                    return llvm.declare_global_variable(
+                        module,
+                        &vtable_name,
+                        initializer.get_type().into(),
+                    );
+                }
+                let global = llvm.create_global_variable(
+                    module,
+                    &vtable_name,
+                    initializer.get_type().into(),
+                    Some(initializer.into()),
+                );
+                global.set_constant(true);
+                global
+            });
+            vtables.associate_global(&vtable_name, vtable)?;
+        }
+
+        if index.has_method_table(class.get_name()) {
+            let entries: Vec<PointerValue> = index
+                .find_method_table(class.get_name())
+                .iter()
+                .map(|it| {
+                    llvm_index
+                        .find_associated_implementation(it.get_call_name())
+                        .map(|it| {
+                            it.as_global_value()
+                                .as_pointer_value()
+                                .const_cast(function_pointer_type)
+                        })
+                        .unwrap_or_else(|| function_pointer_type.const_null())
+                })
+                .collect();
+            let method_table = declare_method_table(module, llvm, index, class.get_name());
+            if !class.is_external() {
+                method_table.set_initializer(&function_pointer_type.const_array(&entries));
+                method_table.set_constant(true);
+            }
+            vtables.associate_global(&get_method_table_name(class.get_name()), method_table)?;
+        }
+    }
+    Ok(vtables)
+}
+
 impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
     /// creates a new PouGenerator
     ///
@@ -64,12 +201,14 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
         index: &'cg Index,
         annotations: &'cg AnnotationMap,
         llvm_index: &'cg LlvmTypedIndex<'ink>,
+        debug: Option<&'cg DebugBuilder<'ink>>,
     ) -> PouGenerator<'ink, 'cg> {
         PouGenerator {
             llvm,
             index,
             annotations,
             llvm_index,
+            debug,
         }
     }
 
@@ -123,6 +262,7 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
     pub fn generate_implementation(
         &self,
         implementation: &Implementation,
+        module: &Module<'ink>,
     ) -> Result<(), CompileError> {
         let context = self.llvm.context;
         let mut local_index = LlvmTypedIndex::create_child(self.llvm_index);
@@ -143,6 +283,25 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
         let block = context.append_basic_block(current_function, "entry");
         self.llvm.builder.position_at_end(block);
 
+        if let Some(debug) = self.debug {
+            let return_type = self
+                .index
+                .find_return_type(&implementation.type_name)
+                .map(DataType::get_name);
+            debug.register_function(
+                self.index,
+                current_function,
+                pou_name,
+                return_type,
+                &implementation.location,
+            );
+            debug.set_debug_location(
+                &self.llvm.builder,
+                current_function,
+                &implementation.location,
+            );
+        }
+
         let mut param_index = 0;
 
         if let PouType::Method { .. } = implementation.pou_type {
@@ -171,6 +330,8 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
         let function_context = FunctionContext {
             linking_context: implementation.into(),
             function: current_function,
+            module,
+            debug: self.debug,
         };
         {
             let statement_gen = StatementCodeGenerator::new(
@@ -186,13 +347,28 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
                 implementation.pou_type,
                 PouType::Function | PouType::Method { .. }
             ) {
-                self.generate_initialization_of_local_vars(pou_members, &statement_gen)?;
+                self.generate_initialization_of_local_vars(
+                    pou_members.clone(),
+                    &local_index,
+                    &statement_gen,
+                )?;
             }
+            //VAR_TEMP-variables are initialized on every call
+            self.generate_initialization_of_temp_vars(pou_members, &local_index, &statement_gen)?;
             statement_gen.generate_body(&implementation.statements)?
         }
 
         // generate return statement
+        if let Some(debug) = self.debug {
+            let end = implementation.location.get_end();
+            debug.set_debug_location(
+                &self.llvm.builder,
+                current_function,
+                &SourceRange::new(end..end),
+            );
+        }
         self.generate_return_statement(&function_context, &local_index, None)?; //TODO location
+        self.llvm.builder.unset_current_debug_location();
 
         Ok(())
     }
@@ -244,6 +420,20 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
                     Pou::calc_return_name(type_name),
                     self.llvm.create_local_variable(type_name, &return_type),
                 )
+            } else if m.is_temp() {
+                //temporary variables live on the stack, they are not part of the instance
+                let temp_type = index.get_associated_type(m.get_type_name())?;
+                (
+                    parameter_name,
+                    self.llvm.create_local_variable(parameter_name, &temp_type),
+                )
+            } else if let Some(address) = m.get_hardware_address() {
+                //located variables live in their process image
+                let variable_type = index.get_associated_type(m.get_type_name())?;
+                (
+                    parameter_name,
+                    get_located_variable_pointer(&self.llvm, index, address, variable_type)?,
+                )
             } else {
                 let ptr_value = current_function
                     .get_nth_param(arg_index)
@@ -260,6 +450,18 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
                         .unwrap(),
                 )
             };
+            //located variables are described by their process image
+            if let (Some(debug), None) = (self.debug, m.get_hardware_address()) {
+                let block = self.llvm.builder.get_insert_block().unwrap();
+                debug.register_local_variable(
+                    self.index,
+                    current_function,
+                    name,
+                    m,
+                    variable,
+                    block,
+                );
+            }
             index.associate_loaded_local_variable(type_name, name, variable)?;
         }
 
@@ -268,12 +470,42 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
 
     /// generates assignment statements for initialized variables in the VAR-block
     ///
+    /// instances of polymorphic classes are initialized with their type's initial value,
+    /// so they point to their class' method table
+    ///
     /// - `blocks` - all declaration blocks of the current pou
     fn generate_initialization_of_local_vars(
         &self,
         variables: Vec<&VariableIndexEntry>,
+        local_index: &LlvmTypedIndex<'ink>,
         statement_generator: &StatementCodeGenerator<'ink, '_>,
     ) -> Result<(), CompileError> {
+        let polymorphic_instances = variables
+            .iter()
+            .filter(|it| it.is_local())
+            .filter(|it| it.initial_value.is_none())
+            .filter(|it| self.index.has_method_table(it.get_type_name()));
+        for variable in polymorphic_instances {
+            let instance = local_index
+                .find_loaded_associated_variable_value(variable.get_qualified_name())
+                .ok_or_else(|| {
+                    CompileError::invalid_reference(
+                        variable.get_qualified_name(),
+                        variable.source_location.clone(),
+                    )
+                })?;
+            let initial_value = self
+                .llvm_index
+                .find_associated_initial_value(variable.get_type_name())
+                .ok_or_else(|| {
+                    CompileError::cannot_generate_initializer(
+                        variable.get_qualified_name(),
+                        variable.source_location.clone(),
+                    )
+                })?;
+            self.llvm.builder.build_store(instance, initial_value);
+        }
+
         let variables_with_initializers = variables
             .iter()
             .filter(|it| it.is_local())
@@ -295,12 +527,53 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
         Ok(())
     }
 
+    /// generates assignment statements for the variables of the VAR_TEMP-blocks
+    ///
+    /// temporary variables without an initializer are initialized with their type's initial value
+    fn generate_initialization_of_temp_vars(
+        &self,
+        variables: Vec<&VariableIndexEntry>,
+        local_index: &LlvmTypedIndex<'ink>,
+        statement_generator: &StatementCodeGenerator<'ink, '_>,
+    ) -> Result<(), CompileError> {
+        for variable in variables.iter().filter(|it| it.is_temp()) {
+            if let Some(initializer) = self
+                .index
+                .get_const_expressions()
+                .maybe_get_constant_statement(&variable.initial_value)
+            {
+                let left = AstStatement::Reference {
+                    name: variable.get_name().into(),
+                    location: variable.source_location.clone(),
+                    id: 0, //TODO
+                };
+                statement_generator.generate_assignment_statement(&left, initializer)?;
+            } else {
+                let temp = local_index
+                    .find_loaded_associated_variable_value(variable.get_qualified_name())
+                    .ok_or_else(|| {
+                        CompileError::invalid_reference(
+                            variable.get_qualified_name(),
+                            variable.source_location.clone(),
+                        )
+                    })?;
+                let type_name = variable.get_type_name();
+                let initial_value = match self.llvm_index.find_associated_initial_value(type_name) {
+                    Some(initial_value) => initial_value,
+                    None => get_default_for(local_index.get_associated_type(type_name)?),
+                };
+                self.llvm.builder.build_store(temp, initial_value);
+            }
+        }
+        Ok(())
+    }
+
     /// generates the function's return statement only if the given pou_type is a `PouType::Function`
     ///
     /// a function returns the value of the local variable that has the function's name
     pub fn generate_return_statement(
         &self,
-        function_context: &FunctionContext<'ink>,
+        function_context: &FunctionContext<'ink, '_>,
         local_index: &LlvmTypedIndex<'ink>,
         location: Option<SourceRange>,
     ) -> Result<(), CompileError> {
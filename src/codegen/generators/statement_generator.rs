@@ -4,7 +4,7 @@ use super::{
 };
 use crate::{
     ast::{flatten_expression_list, AstStatement, ConditionalBlock, Operator, SourceRange},
-    codegen::{llvm_typesystem::cast_if_needed, LlvmTypedIndex},
+    codegen::{debug::DebugBuilder, llvm_typesystem::cast_if_needed, LlvmTypedIndex},
     compile_error::CompileError,
     index::{ImplementationIndexEntry, Index},
     resolver::AnnotationMap,
@@ -15,17 +15,22 @@ use crate::{
 };
 use inkwell::{
     basic_block::BasicBlock,
+    module::Module,
     values::{BasicValueEnum, FunctionValue},
     IntPredicate,
 };
 use std::ops::Range;
 
 /// the full context when generating statements inside a POU
-pub struct FunctionContext<'a> {
+pub struct FunctionContext<'a, 'b> {
     /// the current pou's name. This means that a variable x may refer to "`linking_context`.x"
     pub linking_context: ImplementationIndexEntry,
     /// the llvm function to generate statements into
     pub function: FunctionValue<'a>,
+    /// the module the function is generated into (e.g. to declare intrinsics)
+    pub module: &'b Module<'a>,
+    /// generates the debug information of the statements, if requested
+    pub debug: Option<&'b DebugBuilder<'a>>,
 }
 
 /// the StatementCodeGenerator is used to generate statements (For, If, etc.) or expressions (references, literals, etc.)
@@ -35,7 +40,7 @@ pub struct StatementCodeGenerator<'a, 'b> {
     annotations: &'b AnnotationMap,
     pou_generator: &'b PouGenerator<'a, 'b>,
     llvm_index: &'b LlvmTypedIndex<'a>,
-    function_context: &'b FunctionContext<'a>,
+    function_context: &'b FunctionContext<'a, 'b>,
 
     pub load_prefix: String,
     pub load_suffix: String,
@@ -54,7 +59,7 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
         annotations: &'b AnnotationMap,
         pou_generator: &'b PouGenerator<'a, 'b>,
         llvm_index: &'b LlvmTypedIndex<'a>,
-        linking_context: &'b FunctionContext<'a>,
+        linking_context: &'b FunctionContext<'a, 'b>,
     ) -> StatementCodeGenerator<'a, 'b> {
         StatementCodeGenerator {
             llvm,
@@ -107,6 +112,13 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
     ///
     /// - `statement` the statement to be generated
     pub fn generate_statement(&self, statement: &AstStatement) -> Result<(), CompileError> {
+        if let Some(debug) = self.function_context.debug {
+            debug.set_debug_location(
+                &self.llvm.builder,
+                self.function_context.function,
+                &statement.get_location(),
+            );
+        }
         match statement {
             AstStatement::EmptyStatement { .. } => {
                 //nothing to generate
@@ -114,6 +126,9 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
             AstStatement::Assignment { left, right, .. } => {
                 self.generate_assignment_statement(left, right)?;
             }
+            AstStatement::RefAssignment { left, right, .. } => {
+                self.generate_ref_assignment_statement(left, right)?;
+            }
             AstStatement::ForLoopStatement {
                 start,
                 end,
@@ -195,7 +210,19 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
         right_statement: &AstStatement,
     ) -> Result<(), CompileError> {
         let exp_gen = self.create_expr_generator();
+        if let Some(address) = exp_gen.find_located_bit_address(left_statement) {
+            return exp_gen.generate_located_bit_assignment(address, right_statement);
+        }
         let left = exp_gen.generate_element_pointer(left_statement)?;
+        if let Some((_, interface_reference)) =
+            exp_gen.generate_interface_reference(left.type_entry, right_statement)?
+        {
+            //an instance is assigned to an interface-variable
+            self.llvm
+                .builder
+                .build_store(left.ptr_value, interface_reference);
+            return Ok(());
+        }
         // if the lhs-type is a subrange type we may need to generate a check-call
         // e.g. x := y,  ==> x := CheckSignedInt(y);
         let range_checked_right_side =
@@ -228,10 +255,52 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
             &right_type,
             right_statement,
         )?;
+        let cast_value = exp_gen.cast_to_pointer_if_needed(cast_value, left.ptr_value);
         self.llvm.builder.build_store(left.ptr_value, cast_value);
         Ok(())
     }
 
+    /// generates a reference assignment _left_ REF= _right_
+    ///
+    /// the reference on the left is bound to the variable on the right, or to the address
+    /// the right side evaluates to (e.g. `REF(x)` or `NULL`)
+    pub fn generate_ref_assignment_statement(
+        &self,
+        left_statement: &AstStatement,
+        right_statement: &AstStatement,
+    ) -> Result<(), CompileError> {
+        let exp_gen = self.create_expr_generator();
+        let reference = exp_gen.generate_reference_pointer(left_statement)?;
+        let address = match right_statement {
+            AstStatement::Reference { .. }
+            | AstStatement::QualifiedReference { .. }
+            | AstStatement::ArrayAccess { .. }
+            | AstStatement::PointerAccess { .. } => {
+                exp_gen.generate_element_pointer(right_statement)?.ptr_value
+            }
+            _ => match exp_gen.generate_expression(right_statement)? {
+                (_, BasicValueEnum::PointerValue(address)) => address,
+                _ => {
+                    return Err(CompileError::codegen_error(
+                        format!("Cannot bind a reference to {:?}", right_statement),
+                        right_statement.get_location(),
+                    ))
+                }
+            },
+        };
+        let address = self.llvm.builder.build_pointer_cast(
+            address,
+            reference
+                .ptr_value
+                .get_type()
+                .get_element_type()
+                .into_pointer_type(),
+            "",
+        );
+        self.llvm.builder.build_store(reference.ptr_value, address);
+        Ok(())
+    }
+
     /// returns the implementation of the sub-range-check-function for a variable of the given dataType
     fn find_range_check_impolementation_for(
         &self,
@@ -680,7 +749,9 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
     }
 }
 
-fn create_call_to_check_function_ast(
+/// creates a call to the given check function (e.g. `CheckRangeSigned(x, 0, 100)`), passing
+/// the checked value and the bounds of the given range
+pub(crate) fn create_call_to_check_function_ast(
     check_function_name: String,
     parameter: AstStatement,
     sub_range: Range<AstStatement>,
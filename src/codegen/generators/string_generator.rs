@@ -0,0 +1,744 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+use super::{llvm::Llvm, statement_generator::FunctionContext};
+use inkwell::{
+    types::{ArrayType, BasicTypeEnum, IntType},
+    values::{BasicValueEnum, IntValue, PointerValue},
+    AddressSpace, IntPredicate,
+};
+
+/// a STRING or WSTRING stored in memory
+#[derive(Clone, Copy)]
+pub struct StringPointer<'a> {
+    /// pointer to the string's array of characters
+    buffer: PointerValue<'a>,
+    /// pointer to the string's first character
+    characters: PointerValue<'a>,
+    /// the number of characters the string can hold (without the terminating zero)
+    capacity: u32,
+}
+
+/// generates the builtin string functions (LEN, LEFT, CONCAT, ...) for STRING and WSTRING.
+///
+/// strings are zero-terminated arrays of characters (`i8` for STRING, `i16` for WSTRING).
+/// Results longer than the resulting string's capacity are truncated.
+pub struct StringGenerator<'a, 'b> {
+    llvm: &'b Llvm<'a>,
+    function_context: &'b FunctionContext<'a, 'b>,
+}
+
+impl<'a, 'b> StringGenerator<'a, 'b> {
+    /// creates a new StringGenerator generating into the given function
+    pub fn new(
+        llvm: &'b Llvm<'a>,
+        function_context: &'b FunctionContext<'a, 'b>,
+    ) -> StringGenerator<'a, 'b> {
+        StringGenerator {
+            llvm,
+            function_context,
+        }
+    }
+
+    /// stores the given string value (an array or a literal vector) on the stack
+    pub fn store(
+        &self,
+        value: BasicValueEnum<'a>,
+        character_type: IntType<'a>,
+    ) -> Result<StringPointer<'a>, String> {
+        let builder = &self.llvm.builder;
+        let bits = match value.get_type() {
+            BasicTypeEnum::ArrayType(it) => it.len() * get_bit_width(it.get_element_type()),
+            BasicTypeEnum::VectorType(it) => it.get_size() * get_bit_width(it.get_element_type()),
+            _ => {
+                return Err(format!(
+                    "Expected a string but found {:?}",
+                    value.get_type()
+                ))
+            }
+        };
+        let buffer = builder.build_alloca(value.get_type(), "");
+        builder.build_store(buffer, value);
+        Ok(StringPointer {
+            buffer,
+            characters: builder.build_pointer_cast(
+                buffer,
+                character_type.ptr_type(AddressSpace::Generic),
+                "",
+            ),
+            capacity: (bits / character_type.get_bit_width()).saturating_sub(1),
+        })
+    }
+
+    /// allocates an empty string of the given type on the stack
+    pub fn allocate(&self, string_type: ArrayType<'a>) -> StringPointer<'a> {
+        let builder = &self.llvm.builder;
+        let buffer = builder.build_alloca(string_type, "");
+        builder.build_store(buffer, string_type.const_zero());
+        let character_type = string_type.get_element_type().into_int_type();
+        StringPointer {
+            buffer,
+            characters: builder.build_pointer_cast(
+                buffer,
+                character_type.ptr_type(AddressSpace::Generic),
+                "",
+            ),
+            capacity: string_type.len().saturating_sub(1),
+        }
+    }
+
+    /// loads the value of the given string
+    pub fn load(&self, string: StringPointer<'a>) -> BasicValueEnum<'a> {
+        self.llvm.builder.build_load(string.buffer, "")
+    }
+
+    /// returns the number of characters in the given string (LEN)
+    pub fn length(&self, string: StringPointer<'a>) -> IntValue<'a> {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let function = self.function_context.function;
+        let condition_block = context.append_basic_block(function, "length_condition");
+        let check_block = context.append_basic_block(function, "length_check");
+        let increment_block = context.append_basic_block(function, "length_increment");
+        let end_block = context.append_basic_block(function, "length_end");
+
+        let counter = builder.build_alloca(context.i32_type(), "");
+        builder.build_store(counter, self.const_int(0));
+        builder.build_unconditional_branch(condition_block);
+
+        //while counter < capacity
+        builder.position_at_end(condition_block);
+        let position = builder.build_load(counter, "").into_int_value();
+        let is_in_range = builder.build_int_compare(
+            IntPredicate::SLT,
+            position,
+            self.const_int(string.capacity),
+            "",
+        );
+        builder.build_conditional_branch(is_in_range, check_block, end_block);
+
+        //and string[counter] <> 0
+        builder.position_at_end(check_block);
+        let character = builder
+            .build_load(self.get_character_pointer(string, position), "")
+            .into_int_value();
+        let is_terminator = builder.build_int_compare(
+            IntPredicate::EQ,
+            character,
+            character.get_type().const_zero(),
+            "",
+        );
+        builder.build_conditional_branch(is_terminator, end_block, increment_block);
+
+        builder.position_at_end(increment_block);
+        let next = builder.build_int_add(position, self.const_int(1), "");
+        builder.build_store(counter, next);
+        builder.build_unconditional_branch(condition_block);
+
+        builder.position_at_end(end_block);
+        builder.build_load(counter, "").into_int_value()
+    }
+
+    /// writes the first `length` characters of `input` into `result` (LEFT)
+    pub fn left(
+        &self,
+        result: StringPointer<'a>,
+        input: StringPointer<'a>,
+        length: IntValue<'a>,
+    ) -> Result<(), String> {
+        let input_length = self.length(input);
+        let length = self.clamp(length, self.const_int(0), input_length);
+        self.write(result, &[(input, self.const_int(0), length)])
+    }
+
+    /// writes the last `length` characters of `input` into `result` (RIGHT)
+    pub fn right(
+        &self,
+        result: StringPointer<'a>,
+        input: StringPointer<'a>,
+        length: IntValue<'a>,
+    ) -> Result<(), String> {
+        let input_length = self.length(input);
+        let length = self.clamp(length, self.const_int(0), input_length);
+        let start = self.llvm.builder.build_int_sub(input_length, length, "");
+        self.write(result, &[(input, start, length)])
+    }
+
+    /// writes `length` characters of `input` starting at the 1-based `position`
+    /// into `result` (MID)
+    pub fn mid(
+        &self,
+        result: StringPointer<'a>,
+        input: StringPointer<'a>,
+        length: IntValue<'a>,
+        position: IntValue<'a>,
+    ) -> Result<(), String> {
+        let builder = &self.llvm.builder;
+        let input_length = self.length(input);
+        let start = self.clamp(
+            builder.build_int_sub(position, self.const_int(1), ""),
+            self.const_int(0),
+            input_length,
+        );
+        let length = self.clamp(
+            length,
+            self.const_int(0),
+            builder.build_int_sub(input_length, start, ""),
+        );
+        self.write(result, &[(input, start, length)])
+    }
+
+    /// writes all inputs one after another into `result` (CONCAT)
+    pub fn concat(
+        &self,
+        result: StringPointer<'a>,
+        inputs: &[StringPointer<'a>],
+    ) -> Result<(), String> {
+        let segments: Vec<_> = inputs
+            .iter()
+            .map(|input| (*input, self.const_int(0), self.length(*input)))
+            .collect();
+        self.write(result, &segments)
+    }
+
+    /// writes `first` with `second` inserted after the `position`th character into `result` (INSERT)
+    pub fn insert(
+        &self,
+        result: StringPointer<'a>,
+        first: StringPointer<'a>,
+        second: StringPointer<'a>,
+        position: IntValue<'a>,
+    ) -> Result<(), String> {
+        let first_length = self.length(first);
+        let second_length = self.length(second);
+        let position = self.clamp(position, self.const_int(0), first_length);
+        let rest = self.llvm.builder.build_int_sub(first_length, position, "");
+        self.write(
+            result,
+            &[
+                (first, self.const_int(0), position),
+                (second, self.const_int(0), second_length),
+                (first, position, rest),
+            ],
+        )
+    }
+
+    /// writes `input` without the `length` characters starting at the 1-based `position`
+    /// into `result` (DELETE)
+    pub fn delete(
+        &self,
+        result: StringPointer<'a>,
+        input: StringPointer<'a>,
+        length: IntValue<'a>,
+        position: IntValue<'a>,
+    ) -> Result<(), String> {
+        let (start, end, rest) = self.get_range(input, length, position);
+        self.write(
+            result,
+            &[(input, self.const_int(0), start), (input, end, rest)],
+        )
+    }
+
+    /// writes `first` with the `length` characters starting at the 1-based `position` replaced
+    /// by `second` into `result` (REPLACE)
+    pub fn replace(
+        &self,
+        result: StringPointer<'a>,
+        first: StringPointer<'a>,
+        second: StringPointer<'a>,
+        length: IntValue<'a>,
+        position: IntValue<'a>,
+    ) -> Result<(), String> {
+        let (start, end, rest) = self.get_range(first, length, position);
+        let second_length = self.length(second);
+        self.write(
+            result,
+            &[
+                (first, self.const_int(0), start),
+                (second, self.const_int(0), second_length),
+                (first, end, rest),
+            ],
+        )
+    }
+
+    /// returns the 1-based position of the first occurrence of `second` in `first`
+    /// or 0 if it is not found (FIND)
+    pub fn find(
+        &self,
+        first: StringPointer<'a>,
+        second: StringPointer<'a>,
+    ) -> Result<IntValue<'a>, String> {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let function = self.function_context.function;
+        let first_length = self.length(first);
+        let second_length = self.length(second);
+        let last_position = builder.build_int_sub(first_length, second_length, "");
+        let byte_count = self.get_byte_count(second, second_length);
+
+        let condition_block = context.append_basic_block(function, "find_condition");
+        let compare_block = context.append_basic_block(function, "find_compare");
+        let found_block = context.append_basic_block(function, "find_found");
+        let increment_block = context.append_basic_block(function, "find_increment");
+        let end_block = context.append_basic_block(function, "find_end");
+
+        let result = builder.build_alloca(context.i32_type(), "");
+        builder.build_store(result, self.const_int(0));
+        let counter = builder.build_alloca(context.i32_type(), "");
+        builder.build_store(counter, self.const_int(0));
+        builder.build_unconditional_branch(condition_block);
+
+        //while counter <= len(first) - len(second) and second is not empty
+        builder.position_at_end(condition_block);
+        let position = builder.build_load(counter, "").into_int_value();
+        let is_in_range = builder.build_and(
+            builder.build_int_compare(IntPredicate::SLE, position, last_position, ""),
+            builder.build_int_compare(IntPredicate::SGT, second_length, self.const_int(0), ""),
+            "",
+        );
+        builder.build_conditional_branch(is_in_range, compare_block, end_block);
+
+        builder.position_at_end(compare_block);
+        let comparison = self
+            .call_memcmp(
+                self.get_character_pointer(first, position),
+                second.characters,
+                byte_count,
+            )?
+            .into_int_value();
+        let is_equal = builder.build_int_compare(
+            IntPredicate::EQ,
+            comparison,
+            comparison.get_type().const_zero(),
+            "",
+        );
+        builder.build_conditional_branch(is_equal, found_block, increment_block);
+
+        builder.position_at_end(found_block);
+        builder.build_store(
+            result,
+            builder.build_int_add(position, self.const_int(1), ""),
+        );
+        builder.build_unconditional_branch(end_block);
+
+        builder.position_at_end(increment_block);
+        builder.build_store(
+            counter,
+            builder.build_int_add(position, self.const_int(1), ""),
+        );
+        builder.build_unconditional_branch(condition_block);
+
+        builder.position_at_end(end_block);
+        Ok(builder.build_load(result, "").into_int_value())
+    }
+
+    /// writes `input` converted to the encoding of `result` into `result`
+    /// (STRING_TO_WSTRING and WSTRING_TO_STRING). STRINGs are encoded in UTF-8, WSTRINGs in
+    /// UTF-16. The conversion stops at the first character that does not fit into `result`
+    pub fn transcode(
+        &self,
+        result: StringPointer<'a>,
+        input: StringPointer<'a>,
+    ) -> Result<(), String> {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let function = self.function_context.function;
+        let i32_type = context.i32_type();
+        let result_type = result
+            .characters
+            .get_type()
+            .get_element_type()
+            .into_int_type();
+        let is_widening = result_type.get_bit_width() == 16;
+        let condition_block = context.append_basic_block(function, "transcode_condition");
+        let write_block = context.append_basic_block(function, "transcode_write");
+        let end_block = context.append_basic_block(function, "transcode_end");
+
+        let input_counter = builder.build_alloca(i32_type, "");
+        builder.build_store(input_counter, self.const_int(0));
+        let result_counter = builder.build_alloca(i32_type, "");
+        builder.build_store(result_counter, self.const_int(0));
+        builder.build_unconditional_branch(condition_block);
+
+        builder.position_at_end(condition_block);
+        let input_position = builder.build_load(input_counter, "").into_int_value();
+        let result_position = builder.build_load(result_counter, "").into_int_value();
+        //reads the code unit at the given offset, positions behind the input's capacity read its terminator
+        let read = |offset: u32| {
+            let position = builder.build_int_add(input_position, self.const_int(offset), "");
+            let position = self.clamp(position, self.const_int(0), self.const_int(input.capacity));
+            let unit = builder
+                .build_load(self.get_character_pointer(input, position), "")
+                .into_int_value();
+            builder.build_int_z_extend(unit, i32_type, "")
+        };
+        let first = read(0);
+        let (code_point, input_length) = if is_widening {
+            self.decode_utf8(first, read(1), read(2), read(3))
+        } else {
+            self.decode_utf16(first, read(1))
+        };
+        let units = if is_widening {
+            self.encode_utf16(code_point)
+        } else {
+            self.encode_utf8(code_point)
+        };
+        //the number of code units actually written, unused units are 0
+        let written = units
+            .iter()
+            .map(|(_, is_used)| builder.build_int_z_extend(*is_used, i32_type, ""))
+            .fold(self.const_int(0), |sum, it| {
+                builder.build_int_add(sum, it, "")
+            });
+        let next_position = builder.build_int_add(result_position, written, "");
+        let is_in_range = builder.build_and(
+            builder.build_int_compare(
+                IntPredicate::SLT,
+                input_position,
+                self.const_int(input.capacity),
+                "",
+            ),
+            builder.build_int_compare(IntPredicate::NE, first, self.const_int(0), ""),
+            "",
+        );
+        let fits = builder.build_int_compare(
+            IntPredicate::SLE,
+            next_position,
+            self.const_int(result.capacity),
+            "",
+        );
+        builder.build_conditional_branch(
+            builder.build_and(is_in_range, fits, ""),
+            write_block,
+            end_block,
+        );
+
+        builder.position_at_end(write_block);
+        for (offset, (unit, is_used)) in units.into_iter().enumerate() {
+            //unused units write the 0 that is already there, at most up to the terminator
+            let position =
+                builder.build_int_add(result_position, self.const_int(offset as u32), "");
+            let position = self.clamp(position, self.const_int(0), self.const_int(result.capacity));
+            let unit = builder
+                .build_select(is_used, unit, i32_type.const_zero(), "")
+                .into_int_value();
+            builder.build_store(
+                self.get_character_pointer(result, position),
+                builder.build_int_truncate(unit, result_type, ""),
+            );
+        }
+        builder.build_store(
+            input_counter,
+            builder.build_int_add(input_position, input_length, ""),
+        );
+        builder.build_store(result_counter, next_position);
+        builder.build_unconditional_branch(condition_block);
+
+        builder.position_at_end(end_block);
+        Ok(())
+    }
+
+    /// returns the code point starting with the given UTF-8 bytes and the number of its bytes
+    fn decode_utf8(
+        &self,
+        first: IntValue<'a>,
+        second: IntValue<'a>,
+        third: IntValue<'a>,
+        fourth: IntValue<'a>,
+    ) -> (IntValue<'a>, IntValue<'a>) {
+        let builder = &self.llvm.builder;
+        let c = |value: u32| self.const_int(value);
+        let bits = |value: IntValue<'a>, mask: u32, shift: u32| {
+            builder.build_left_shift(builder.build_and(value, c(mask), ""), c(shift), "")
+        };
+        let or = |left: IntValue<'a>, right: IntValue<'a>| builder.build_or(left, right, "");
+        let one_byte = first;
+        let two_bytes = or(bits(first, 0x1F, 6), bits(second, 0x3F, 0));
+        let three_bytes = or(
+            or(bits(first, 0x0F, 12), bits(second, 0x3F, 6)),
+            bits(third, 0x3F, 0),
+        );
+        let four_bytes = or(
+            or(bits(first, 0x07, 18), bits(second, 0x3F, 12)),
+            or(bits(third, 0x3F, 6), bits(fourth, 0x3F, 0)),
+        );
+        let is_below =
+            |limit: u32| builder.build_int_compare(IntPredicate::ULT, first, c(limit), "");
+        let select = |condition, left: IntValue<'a>, right: IntValue<'a>| {
+            builder
+                .build_select(condition, left, right, "")
+                .into_int_value()
+        };
+        let code_point = select(
+            is_below(0x80),
+            one_byte,
+            select(
+                is_below(0xE0),
+                two_bytes,
+                select(is_below(0xF0), three_bytes, four_bytes),
+            ),
+        );
+        let length = select(
+            is_below(0x80),
+            c(1),
+            select(is_below(0xE0), c(2), select(is_below(0xF0), c(3), c(4))),
+        );
+        (code_point, length)
+    }
+
+    /// returns the code point starting with the given UTF-16 units and the number of its units
+    fn decode_utf16(
+        &self,
+        first: IntValue<'a>,
+        second: IntValue<'a>,
+    ) -> (IntValue<'a>, IntValue<'a>) {
+        let builder = &self.llvm.builder;
+        let c = |value: u32| self.const_int(value);
+        let is_in = |value: IntValue<'a>, from: u32, to: u32| {
+            builder.build_and(
+                builder.build_int_compare(IntPredicate::UGE, value, c(from), ""),
+                builder.build_int_compare(IntPredicate::ULT, value, c(to), ""),
+                "",
+            )
+        };
+        let is_pair = builder.build_and(
+            is_in(first, 0xD800, 0xDC00),
+            is_in(second, 0xDC00, 0xE000),
+            "",
+        );
+        let pair = builder.build_int_add(
+            builder.build_or(
+                builder.build_left_shift(builder.build_int_sub(first, c(0xD800), ""), c(10), ""),
+                builder.build_int_sub(second, c(0xDC00), ""),
+                "",
+            ),
+            c(0x10000),
+            "",
+        );
+        (
+            builder
+                .build_select(is_pair, pair, first, "")
+                .into_int_value(),
+            builder
+                .build_select(is_pair, c(2), c(1), "")
+                .into_int_value(),
+        )
+    }
+
+    /// returns the UTF-16 units of the given code point, each with a flag whether it is used
+    fn encode_utf16(&self, code_point: IntValue<'a>) -> Vec<(IntValue<'a>, IntValue<'a>)> {
+        let builder = &self.llvm.builder;
+        let c = |value: u32| self.const_int(value);
+        let is_pair = builder.build_int_compare(IntPredicate::UGE, code_point, c(0x10000), "");
+        let offset = builder.build_int_sub(code_point, c(0x10000), "");
+        let high = builder.build_int_add(
+            builder.build_right_shift(offset, c(10), false, ""),
+            c(0xD800),
+            "",
+        );
+        let low = builder.build_int_add(builder.build_and(offset, c(0x3FF), ""), c(0xDC00), "");
+        vec![
+            (
+                builder
+                    .build_select(is_pair, high, code_point, "")
+                    .into_int_value(),
+                self.llvm.context.bool_type().const_int(1, false),
+            ),
+            (low, is_pair),
+        ]
+    }
+
+    /// returns the UTF-8 bytes of the given code point, each with a flag whether it is used
+    fn encode_utf8(&self, code_point: IntValue<'a>) -> Vec<(IntValue<'a>, IntValue<'a>)> {
+        let builder = &self.llvm.builder;
+        let c = |value: u32| self.const_int(value);
+        let is_at_least =
+            |limit: u32| builder.build_int_compare(IntPredicate::UGE, code_point, c(limit), "");
+        let (has_two, has_three, has_four) =
+            (is_at_least(0x80), is_at_least(0x800), is_at_least(0x10000));
+        //the bits of the code point below the given byte, counted from the last byte
+        let continuation = |shift: u32| {
+            builder.build_or(
+                builder.build_and(
+                    builder.build_right_shift(code_point, c(shift), false, ""),
+                    c(0x3F),
+                    "",
+                ),
+                c(0x80),
+                "",
+            )
+        };
+        let lead = |prefix: u32, shift: u32| {
+            builder.build_or(
+                builder.build_right_shift(code_point, c(shift), false, ""),
+                c(prefix),
+                "",
+            )
+        };
+        let select = |condition, left: IntValue<'a>, right: IntValue<'a>| {
+            builder
+                .build_select(condition, left, right, "")
+                .into_int_value()
+        };
+        let first = select(
+            has_four,
+            lead(0xF0, 18),
+            select(
+                has_three,
+                lead(0xE0, 12),
+                select(has_two, lead(0xC0, 6), code_point),
+            ),
+        );
+        let second = select(
+            has_four,
+            continuation(12),
+            select(has_three, continuation(6), continuation(0)),
+        );
+        let third = select(has_four, continuation(6), continuation(0));
+        vec![
+            (first, self.llvm.context.bool_type().const_int(1, false)),
+            (second, has_two),
+            (third, has_three),
+            (continuation(0), has_four),
+        ]
+    }
+
+    /// returns the start, the end and the number of remaining characters after the
+    /// range of `length` characters starting at the 1-based `position` in `input`
+    fn get_range(
+        &self,
+        input: StringPointer<'a>,
+        length: IntValue<'a>,
+        position: IntValue<'a>,
+    ) -> (IntValue<'a>, IntValue<'a>, IntValue<'a>) {
+        let builder = &self.llvm.builder;
+        let input_length = self.length(input);
+        let start = self.clamp(
+            builder.build_int_sub(position, self.const_int(1), ""),
+            self.const_int(0),
+            input_length,
+        );
+        let length = self.clamp(
+            length,
+            self.const_int(0),
+            builder.build_int_sub(input_length, start, ""),
+        );
+        let end = builder.build_int_add(start, length, "");
+        let rest = builder.build_int_sub(input_length, end, "");
+        (start, end, rest)
+    }
+
+    /// copies the given segments (string, start, number of characters) one after another
+    /// into `result`. Characters exceeding the result's capacity are dropped
+    fn write(
+        &self,
+        result: StringPointer<'a>,
+        segments: &[(StringPointer<'a>, IntValue<'a>, IntValue<'a>)],
+    ) -> Result<(), String> {
+        let builder = &self.llvm.builder;
+        let mut position = self.const_int(0);
+        for (source, start, count) in segments {
+            let remaining = builder.build_int_sub(self.const_int(result.capacity), position, "");
+            let count = self.clamp(*count, self.const_int(0), remaining);
+            builder.build_memcpy(
+                self.get_character_pointer(result, position),
+                1,
+                self.get_character_pointer(*source, *start),
+                1,
+                self.get_byte_count(result, count),
+            )?;
+            position = builder.build_int_add(position, count, "");
+        }
+        Ok(())
+    }
+
+    /// returns `value` limited to the range `min..max`
+    fn clamp(&self, value: IntValue<'a>, min: IntValue<'a>, max: IntValue<'a>) -> IntValue<'a> {
+        let builder = &self.llvm.builder;
+        let is_too_small = builder.build_int_compare(IntPredicate::SLT, value, min, "");
+        let value = builder
+            .build_select(is_too_small, min, value, "")
+            .into_int_value();
+        let is_too_big = builder.build_int_compare(IntPredicate::SGT, value, max, "");
+        builder
+            .build_select(is_too_big, max, value, "")
+            .into_int_value()
+    }
+
+    /// returns a pointer to the character at the given 0-based position
+    fn get_character_pointer(
+        &self,
+        string: StringPointer<'a>,
+        position: IntValue<'a>,
+    ) -> PointerValue<'a> {
+        unsafe {
+            self.llvm
+                .builder
+                .build_in_bounds_gep(string.characters, &[position], "")
+        }
+    }
+
+    /// returns the number of bytes of the given number of characters of the given string
+    fn get_byte_count(&self, string: StringPointer<'a>, characters: IntValue<'a>) -> IntValue<'a> {
+        let builder = &self.llvm.builder;
+        let character_size = string
+            .characters
+            .get_type()
+            .get_element_type()
+            .into_int_type()
+            .size_of();
+        let characters =
+            builder.build_int_z_extend_or_bit_cast(characters, character_size.get_type(), "");
+        builder.build_int_mul(characters, character_size, "")
+    }
+
+    /// calls `memcmp` of the C standard library
+    fn call_memcmp(
+        &self,
+        left: PointerValue<'a>,
+        right: PointerValue<'a>,
+        byte_count: IntValue<'a>,
+    ) -> Result<BasicValueEnum<'a>, String> {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let module = self.function_context.module;
+        let byte_pointer_type = context.i8_type().ptr_type(AddressSpace::Generic);
+        let memcmp = module.get_function("memcmp").unwrap_or_else(|| {
+            let function_type = context.i32_type().fn_type(
+                &[
+                    byte_pointer_type.into(),
+                    byte_pointer_type.into(),
+                    byte_count.get_type().into(),
+                ],
+                false,
+            );
+            module.add_function("memcmp", function_type, None)
+        });
+        builder
+            .build_call(
+                memcmp,
+                &[
+                    builder
+                        .build_pointer_cast(left, byte_pointer_type, "")
+                        .into(),
+                    builder
+                        .build_pointer_cast(right, byte_pointer_type, "")
+                        .into(),
+                    byte_count.into(),
+                ],
+                "",
+            )
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| "memcmp does not return a value".to_string())
+    }
+
+    fn const_int(&self, value: u32) -> IntValue<'a> {
+        self.llvm.context.i32_type().const_int(value as u64, false)
+    }
+}
+
+fn get_bit_width(data_type: BasicTypeEnum) -> u32 {
+    match data_type {
+        BasicTypeEnum::IntType(it) => it.get_bit_width(),
+        _ => 8,
+    }
+}
@@ -1,11 +1,12 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
-use super::{expression_generator::ExpressionCodeGenerator, llvm::Llvm};
-use crate::index::Index;
+use super::{expression_generator::ExpressionCodeGenerator, llvm::Llvm, pou_generator};
+use crate::index::{Index, METHOD_TABLE_MEMBER};
 use crate::resolver::AnnotationMap;
 use crate::{
     codegen::llvm_index::LlvmTypedIndex, compile_error::CompileError, index::VariableIndexEntry,
 };
 use inkwell::{
+    module::Module,
     types::{BasicTypeEnum, StructType},
     values::BasicValueEnum,
 };
@@ -13,6 +14,7 @@ use inkwell::{
 /// object that offers convinient operations to create struct types and instances
 pub struct StructGenerator<'a, 'b> {
     llvm: &'b Llvm<'a>,
+    module: &'b Module<'a>,
     index: &'b Index,
     annotations: &'b AnnotationMap,
     llvm_index: &'b LlvmTypedIndex<'a>,
@@ -28,12 +30,14 @@ impl<'a, 'b> StructGenerator<'a, 'b> {
     /// creates a new StructGenerator
     pub fn new(
         llvm: &'b Llvm<'a>,
+        module: &'b Module<'a>,
         index: &'b Index,
         annotations: &'b AnnotationMap,
         llvm_index: &'b LlvmTypedIndex<'a>,
     ) -> StructGenerator<'a, 'b> {
         StructGenerator {
             llvm,
+            module,
             index,
             annotations,
             llvm_index,
@@ -56,7 +60,7 @@ impl<'a, 'b> StructGenerator<'a, 'b> {
 
         let mut members = Vec::new();
         for member in member_variables {
-            members.push(self.create_llvm_variable_declaration_elements(member)?);
+            members.push(self.create_llvm_variable_declaration_elements(member, name)?);
         }
 
         let member_types: Vec<BasicTypeEnum> = members.iter().map(|(_, t, _)| *t).collect();
@@ -94,10 +98,32 @@ impl<'a, 'b> StructGenerator<'a, 'b> {
     fn create_llvm_variable_declaration_elements(
         &self,
         variable: &VariableIndexEntry,
+        container_name: &str,
     ) -> Result<VariableDeclarationInformation<'a>, CompileError> {
         let type_name = variable.get_type_name();
 
         let variable_type = self.index.get_type_information(type_name)?;
+        if variable.get_name() == METHOD_TABLE_MEMBER && self.index.has_method_table(container_name)
+        {
+            //every instance points to its class' method table
+            let member_type = self.llvm_index.get_associated_type(type_name)?;
+            let method_table = pou_generator::declare_method_table(
+                self.module,
+                self.llvm,
+                self.index,
+                container_name,
+            );
+            return Ok((
+                variable.get_name().to_string(),
+                member_type,
+                Some(
+                    method_table
+                        .as_pointer_value()
+                        .const_cast(member_type.into_pointer_type())
+                        .into(),
+                ),
+            ));
+        }
         let initializer = match self
             .index
             .get_const_expressions()
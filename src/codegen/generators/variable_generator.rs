@@ -1,8 +1,18 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 
 /// offers operations to generate global variables
-use crate::{ast::SourceRange, index::Index, resolver::AnnotationMap};
-use inkwell::{module::Module, values::GlobalValue};
+use crate::{
+    ast::{DirectAccessType, HardwareAccessType, HardwareAddress, SourceRange},
+    index::{Index, VariableType},
+    resolver::AnnotationMap,
+    typesystem::{DataType, DataTypeInformation},
+};
+use inkwell::{
+    module::Module,
+    types::{BasicType, BasicTypeEnum},
+    values::{BasicValueEnum, GlobalValue, PointerValue},
+    AddressSpace,
+};
 
 use crate::{
     codegen::llvm_index::LlvmTypedIndex, compile_error::CompileError, index::VariableIndexEntry,
@@ -21,6 +31,13 @@ pub fn generate_global_variables<'ctx, 'b>(
     let globals = global_index.get_globals();
     let enums = global_index.get_global_qualified_enums();
     for (name, variable) in globals.into_iter().chain(enums.into_iter()) {
+        //located variables live in their process image
+        if let Some(address) = variable.get_hardware_address() {
+            let variable_type = types_index.get_associated_type(variable.get_type_name())?;
+            let pointer = get_located_variable_pointer(llvm, types_index, address, variable_type)?;
+            index.associate_located_global(name, pointer)?;
+            continue;
+        }
         let global_variable = generate_global_variable(
             module,
             llvm,
@@ -58,6 +75,11 @@ pub fn generate_global_variable<'ctx, 'b>(
     let type_name = global_variable.get_type_name();
     let variable_type = index.get_associated_type(type_name)?;
 
+    //an external variable is defined (and initialized) by another object
+    if global_variable.is_external() {
+        return Ok(llvm.declare_global_variable(module, global_variable.get_name(), variable_type));
+    }
+
     let initial_value = if let Some(initializer) = global_index
         .get_const_expressions()
         .maybe_get_constant_statement(&global_variable.initial_value)
@@ -91,3 +113,267 @@ pub fn generate_global_variable<'ctx, 'b>(
     );
     Ok(global_ir_variable)
 }
+
+/// returns the name of the global variable holding the given process image
+pub fn get_process_image_name(area: HardwareAccessType) -> &'static str {
+    match area {
+        HardwareAccessType::Input => "__input_image",
+        HardwareAccessType::Output => "__output_image",
+        HardwareAccessType::Memory => "__memory_image",
+    }
+}
+
+/// generates the input (`%I`), output (`%Q`) and memory (`%M`) process images
+///
+/// a process image is a byte-array holding all located variables of its area. It is exported
+/// together with its size in bytes (`<image>_size`), so the runtime can copy it from and to
+/// the I/O-devices. Process images without located variables are not generated.
+pub fn generate_process_images<'ink>(
+    module: &Module<'ink>,
+    llvm: &Llvm<'ink>,
+    index: &Index,
+) -> Result<LlvmTypedIndex<'ink>, CompileError> {
+    let mut images = LlvmTypedIndex::new();
+    let areas = [
+        HardwareAccessType::Input,
+        HardwareAccessType::Output,
+        HardwareAccessType::Memory,
+    ];
+    for area in areas.iter() {
+        let size = index.get_process_image_size(*area);
+        if size == 0 {
+            continue;
+        }
+        let name = get_process_image_name(*area);
+        let image = module.get_global(name).unwrap_or_else(|| {
+            let image_type = llvm.context.i8_type().array_type(size);
+            let image = llvm.create_global_variable(
+                module,
+                name,
+                image_type.into(),
+                Some(image_type.const_zero().into()),
+            );
+            let size_value = llvm.context.i32_type().const_int(size as u64, false);
+            let size_global = llvm.create_global_variable(
+                module,
+                &format!("{}_size", name),
+                size_value.get_type().into(),
+                Some(size_value.into()),
+            );
+            size_global.set_constant(true);
+            image
+        });
+        images.associate_global(name, image)?;
+    }
+    Ok(images)
+}
+
+/// returns a constant pointer to the given address in its process image
+///
+/// the pointer of a bit-address points to the byte holding the bit
+pub fn get_located_variable_pointer<'ink>(
+    llvm: &Llvm<'ink>,
+    index: &LlvmTypedIndex<'ink>,
+    address: &HardwareAddress,
+    variable_type: BasicTypeEnum<'ink>,
+) -> Result<PointerValue<'ink>, CompileError> {
+    let image = index
+        .find_associated_variable_value(get_process_image_name(address.area))
+        .filter(BasicValueEnum::is_pointer_value)
+        .map(BasicValueEnum::into_pointer_value)
+        .ok_or_else(|| {
+            CompileError::codegen_error(
+                format!("Cannot find the process image of {}", address),
+                address.location.clone(),
+            )
+        })?;
+    let offset_type = llvm.context.i32_type();
+    let byte = unsafe {
+        image.const_in_bounds_gep(&[
+            offset_type.const_zero(),
+            offset_type.const_int(address.get_byte_offset() as u64, false),
+        ])
+    };
+    if address.access == DirectAccessType::Bit {
+        Ok(byte)
+    } else {
+        Ok(byte.const_cast(variable_type.ptr_type(AddressSpace::Generic)))
+    }
+}
+
+/// the name of the global constant array describing all retained variables
+pub const RETAIN_TABLE: &str = "__retain_table";
+/// the name of the global constant holding the number of entries in the retain table
+pub const RETAIN_COUNT: &str = "__retain_count";
+
+/// generates the retain table describing all retained variables
+///
+/// every RETAIN variable of a global instance (a global variable, a program instance or a
+/// function block instance within them) gets an entry `{ i8* name, i8* address, i64 size }`,
+/// so the runtime can save its value on shutdown and restore it on a warm start.
+/// Arrays of instances with retained members are retained as a whole.
+///
+/// A program has a single retain table, it is only generated into the object that declares the
+/// CONFIGURATION. It describes the external globals of the included interfaces too, so it
+/// covers the retained variables of all linked objects.
+pub fn generate_retain_table<'ink>(
+    module: &Module<'ink>,
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    llvm_index: &LlvmTypedIndex<'ink>,
+) -> Result<(), CompileError> {
+    //the table describes the variables of all units, it is only generated once
+    if module.get_global(RETAIN_TABLE).is_some() {
+        return Ok(());
+    }
+
+    let mut retained = vec![];
+    for variable in index.get_globals().values() {
+        if let Some(pointer) = llvm_index
+            .find_associated_variable_value(variable.get_qualified_name())
+            .filter(BasicValueEnum::is_pointer_value)
+            .map(BasicValueEnum::into_pointer_value)
+        {
+            collect_retained_variables(
+                llvm,
+                index,
+                llvm_index,
+                variable,
+                variable.get_qualified_name().to_string(),
+                pointer,
+                &mut retained,
+            )?;
+        }
+    }
+    if retained.is_empty() {
+        return Ok(());
+    }
+
+    let context = llvm.context;
+    let address_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let entry_type = context.struct_type(
+        &[
+            address_type.into(),
+            address_type.into(),
+            context.i64_type().into(),
+        ],
+        false,
+    );
+    let mut entries = Vec::with_capacity(retained.len());
+    for (name, pointer) in retained {
+        let name_value =
+            llvm.create_const_global_string(module, &format!("{}.{}", RETAIN_TABLE, name), &name)?;
+        //the size of the pointee: the address of the second element behind a null-pointer
+        let size = unsafe {
+            pointer
+                .get_type()
+                .const_null()
+                .const_gep(&[context.i32_type().const_int(1, false)])
+        }
+        .const_to_int(context.i64_type());
+        entries.push(entry_type.const_named_struct(&[
+            name_value.into(),
+            pointer.const_cast(address_type).into(),
+            size.into(),
+        ]));
+    }
+
+    let retain_table = entry_type.const_array(&entries);
+    let global = llvm.create_global_variable(
+        module,
+        RETAIN_TABLE,
+        retain_table.get_type().into(),
+        Some(retain_table.into()),
+    );
+    global.set_constant(true);
+
+    let retain_count = context.i32_type().const_int(entries.len() as u64, false);
+    let global = llvm.create_global_variable(
+        module,
+        RETAIN_COUNT,
+        retain_count.get_type().into(),
+        Some(retain_count.into()),
+    );
+    global.set_constant(true);
+    Ok(())
+}
+
+/// collects the given variable if it is retained, otherwise its retained members
+fn collect_retained_variables<'ink>(
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    llvm_index: &LlvmTypedIndex<'ink>,
+    variable: &VariableIndexEntry,
+    name: String,
+    pointer: PointerValue<'ink>,
+    retained: &mut Vec<(String, PointerValue<'ink>)>,
+) -> Result<(), CompileError> {
+    if variable.is_retain() {
+        retained.push((name, pointer));
+        return Ok(());
+    }
+
+    let data_type = index.find_effective_type_by_name(variable.get_type_name());
+    match data_type.map(DataType::get_type_information) {
+        Some(DataTypeInformation::Struct { .. }) => {
+            let offset_type = llvm.context.i32_type();
+            for member in get_instance_members(index, data_type.unwrap().get_name()) {
+                let member_pointer = if let Some(address) = member.get_hardware_address() {
+                    let member_type = llvm_index.get_associated_type(member.get_type_name())?;
+                    get_located_variable_pointer(llvm, llvm_index, address, member_type)?
+                } else {
+                    unsafe {
+                        pointer.const_in_bounds_gep(&[
+                            offset_type.const_zero(),
+                            offset_type.const_int(member.get_location_in_parent() as u64, false),
+                        ])
+                    }
+                };
+                collect_retained_variables(
+                    llvm,
+                    index,
+                    llvm_index,
+                    member,
+                    format!("{}.{}", name, member.get_name()),
+                    member_pointer,
+                    retained,
+                )?;
+            }
+        }
+        Some(DataTypeInformation::Array {
+            inner_type_name, ..
+        }) if has_retained_members(index, inner_type_name) => retained.push((name, pointer)),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// returns true if an instance of the given type contains a retained variable
+fn has_retained_members(index: &Index, type_name: &str) -> bool {
+    let data_type = index.find_effective_type_by_name(type_name);
+    match data_type.map(DataType::get_type_information) {
+        Some(DataTypeInformation::Struct { .. }) => {
+            get_instance_members(index, data_type.unwrap().get_name())
+                .iter()
+                .any(|it| it.is_retain() || has_retained_members(index, it.get_type_name()))
+        }
+        Some(DataTypeInformation::Array {
+            inner_type_name, ..
+        }) => has_retained_members(index, inner_type_name),
+        _ => false,
+    }
+}
+
+/// returns the members of the given type stored in its instances,
+/// references (VAR_IN_OUT) are not followed
+fn get_instance_members<'idx>(
+    index: &'idx Index,
+    type_name: &str,
+) -> Vec<&'idx VariableIndexEntry> {
+    index
+        .find_local_members(type_name)
+        .into_iter()
+        .filter(|it| !it.is_temp() && !it.is_return())
+        .filter(|it| it.get_variable_type() != &VariableType::InOut)
+        .collect()
+}
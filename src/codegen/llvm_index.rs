@@ -132,6 +132,17 @@ impl<'ink> LlvmTypedIndex<'ink> {
         Ok(())
     }
 
+    /// associates a located global variable with its pointer into the process image
+    pub fn associate_located_global(
+        &mut self,
+        variable_name: &str,
+        pointer: PointerValue<'ink>,
+    ) -> Result<(), CompileError> {
+        self.initial_value_associations
+            .insert(variable_name.to_lowercase(), pointer.into());
+        Ok(())
+    }
+
     pub fn associate_implementation(
         &mut self,
         callable_name: &str,
@@ -2,8 +2,8 @@
 use inkwell::{
     builder::Builder,
     context::Context,
-    types::{FloatType, IntType},
-    values::{BasicValueEnum, IntValue},
+    types::{BasicTypeEnum, FloatType, IntType},
+    values::{ArrayValue, BasicValueEnum, IntValue},
 };
 
 use crate::{
@@ -11,7 +11,7 @@ use crate::{
     ast::SourceRange,
     compile_error::CompileError,
     index::Index,
-    typesystem::{get_bigger_type, DataTypeInformation},
+    typesystem::{get_bigger_type, DataType, DataTypeInformation, DINT_SIZE},
 };
 
 use super::{generators::llvm::Llvm, llvm_index::LlvmTypedIndex, TypeAndValue};
@@ -224,6 +224,14 @@ pub fn cast_if_needed<'ctx>(
                             .into())
                     }
                 }
+                //the address of a pointer
+                DataTypeInformation::Pointer { .. } if value.is_pointer_value() => Ok(builder
+                    .build_ptr_to_int(
+                        value.into_pointer_value(),
+                        get_llvm_int_type(llvm.context, *lsize, "Integer").unwrap(),
+                        "",
+                    )
+                    .into()),
                 _ => Err(CompileError::casting_error(
                     value_type.get_name(),
                     target_type.get_name(),
@@ -292,7 +300,16 @@ pub fn cast_if_needed<'ctx>(
                     .as_int_value(index)
                     .map_err(|msg| CompileError::codegen_error(msg, SourceRange::undefined()))?
                     as u32;
-                if size < value_size {
+                if value.is_array_value()
+                    && size != value_size
+                    && is_builtin_call(location_context, index)
+                {
+                    //copy the result of a string function into a string of the target's size,
+                    //truncate it if necessary
+                    resize_string(llvm, value.into_array_value(), size, value_size).map_err(|msg| {
+                        CompileError::codegen_error(msg, location_context.get_location())
+                    })
+                } else if size < value_size {
                     //if we are on a vector replace it
                     if value.is_vector_value() {
                         let vec_value = value.into_vector_value();
@@ -321,6 +338,102 @@ pub fn cast_if_needed<'ctx>(
     }
 }
 
+/// returns the size of the given type in bytes as a constant. The sizes of elementary types,
+/// strings and arrays of them are calculated directly, the sizes of all other types
+/// (e.g. structs and their padding) are left to llvm
+pub fn get_type_size<'ctx>(
+    llvm: &Llvm<'ctx>,
+    index: &Index,
+    llvm_index: &LlvmTypedIndex<'ctx>,
+    data_type: &DataType,
+) -> Result<IntValue<'ctx>, CompileError> {
+    let size_type = llvm.context.i64_type();
+    if let Some(size) = get_known_size(index, data_type.get_type_information()) {
+        return Ok(size_type.const_int(size, false));
+    }
+    let size = match llvm_index.get_associated_type(data_type.get_name())? {
+        BasicTypeEnum::ArrayType(it) => it.size_of(),
+        BasicTypeEnum::FloatType(it) => Some(it.size_of()),
+        BasicTypeEnum::IntType(it) => Some(it.size_of()),
+        BasicTypeEnum::PointerType(it) => Some(it.size_of()),
+        BasicTypeEnum::StructType(it) => it.size_of(),
+        BasicTypeEnum::VectorType(it) => it.size_of(),
+    };
+    size.map(|it| it.const_cast(size_type, false))
+        .ok_or_else(|| {
+            CompileError::codegen_error(
+                format!("Cannot determine the size of {}", data_type.get_name()),
+                SourceRange::undefined(),
+            )
+        })
+}
+
+/// returns the size of the given type in bytes if it does not depend on the target
+fn get_known_size(index: &Index, data_type: &DataTypeInformation) -> Option<u64> {
+    match index.find_effective_type_information(data_type)? {
+        //a BOOL still occupies a whole byte
+        DataTypeInformation::Integer { size, .. } => Some(((size + 7) / 8) as u64),
+        DataTypeInformation::Float { size, .. } => Some((size / 8) as u64),
+        DataTypeInformation::Enum { .. } => Some((DINT_SIZE / 8) as u64),
+        DataTypeInformation::String { size, encoding } => size
+            .as_int_value(index)
+            .ok()
+            .map(|it| it as u64 * encoding.get_bytes_per_char() as u64),
+        DataTypeInformation::Array {
+            inner_type_name,
+            dimensions,
+            ..
+        } => {
+            let length = dimensions
+                .iter()
+                .map(|it| it.get_length(index).ok())
+                .product::<Option<u32>>()?;
+            let inner_type = index.find_effective_type_by_name(inner_type_name)?;
+            get_known_size(index, inner_type.get_type_information()).map(|it| it * length as u64)
+        }
+        _ => None,
+    }
+}
+
+/// returns true if the given statement calls a builtin function (e.g. `LEFT(s, 3)`)
+fn is_builtin_call(statement: &AstStatement, index: &Index) -> bool {
+    if let AstStatement::CallStatement { operator, .. } = statement {
+        if let AstStatement::Reference { name, .. } = operator.as_ref() {
+            return index.is_builtin(name);
+        }
+    }
+    false
+}
+
+/// copies the given string value into a string with the given size (number of characters
+/// including the terminating zero). Longer strings are truncated, shorter ones padded with zeros
+fn resize_string<'ctx>(
+    llvm: &Llvm<'ctx>,
+    value: ArrayValue<'ctx>,
+    size: u32,
+    value_size: u32,
+) -> Result<BasicValueEnum<'ctx>, String> {
+    let builder = &llvm.builder;
+    let character_type = value.get_type().get_element_type().into_int_type();
+    let target_type = character_type.array_type(size);
+
+    let target = builder.build_alloca(target_type, "");
+    builder.build_store(target, target_type.const_zero());
+    let source = builder.build_alloca(value.get_type(), "");
+    builder.build_store(source, value);
+
+    //the last character of the target remains the terminating zero
+    let length = (size - 1).min(value_size) as u64;
+    let character_size = character_type.size_of();
+    let byte_count = builder.build_int_mul(
+        character_size,
+        character_size.get_type().const_int(length, false),
+        "",
+    );
+    builder.build_memcpy(target, 1, source, 1, byte_count)?;
+    Ok(builder.build_load(target, ""))
+}
+
 pub fn get_llvm_int_type<'a>(
     context: &'a Context,
     size: u32,
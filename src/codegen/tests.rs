@@ -1,18 +1,23 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 mod code_gen_tests;
 mod codegen_error_messages_tests;
+mod debug_tests;
 mod statement_codegen_test;
 mod typesystem_test;
 
+/// generates the given code and returns the errors of all POUs that could not be generated
 #[macro_export]
-macro_rules! codegen_wihout_unwrap {
+macro_rules! codegen_with_all_errors {
     ($code:tt) => {{
         let lexer = crate::lexer::lex($code);
         let (mut ast, ..) = crate::parser::parse(lexer);
 
         let context = inkwell::context::Context::create();
         crate::ast::pre_process(&mut ast);
-        let index = crate::index::visitor::visit(&ast);
+        let mut index = crate::index::visitor::visit(&crate::builtins::parse_built_ins(
+            crate::lexer::IdProvider::new(),
+        ));
+        index.import(crate::index::visitor::visit(&ast));
         let annotations = crate::resolver::TypeAnnotator::visit_unit(&index, &ast);
         let (index, _unresolvable) = crate::resolver::const_evaluator::evaluate_constants(index);
 
@@ -21,6 +26,13 @@ macro_rules! codegen_wihout_unwrap {
     }};
 }
 
+#[macro_export]
+macro_rules! codegen_wihout_unwrap {
+    ($code:tt) => {{
+        crate::codegen_with_all_errors!($code).map_err(|mut errors| errors.remove(0))
+    }};
+}
+
 #[macro_export]
 macro_rules! codegen {
     ($code:tt) => {{
@@ -847,6 +847,39 @@ entry:
     assert_eq!(result, expected);
 }
 
+#[test]
+fn program_with_string_variable_assignment() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+y : STRING[80];
+z : STRING[10];
+END_VAR
+y := z;
+END_PROGRAM
+"#
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { [81 x i8], [11 x i8] }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %y = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %z = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  %load_z = load [11 x i8], [11 x i8]* %z, align 1
+  store [11 x i8] %load_z, [81 x i8]* %y, align 1
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
 #[test]
 fn different_case_references() {
     let result = codegen!(
@@ -1839,15 +1872,17 @@ fn class_method_in_pou() {
 source_filename = "main"
 
 %prg_interface = type { %MyClass_interface, i16 }
-%MyClass_interface = type { i16, i16 }
+%MyClass_interface = type { i8**, i16, i16 }
 %MyClass.testMethod_interface = type { i16, i16 }
 
-@prg_instance = global %prg_interface zeroinitializer
+@__MyClass_vtable = constant [1 x i8*] [i8* bitcast (void (%MyClass_interface*, %MyClass.testMethod_interface*)* @MyClass.testMethod to i8*)]
+@prg_instance = global %prg_interface { %MyClass_interface { i8** bitcast ([1 x i8*]* @__MyClass_vtable to i8**), i16 0, i16 0 }, i16 0 }
 
 define void @MyClass.testMethod(%MyClass_interface* %0, %MyClass.testMethod_interface* %1) {
 entry:
-  %x = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
-  %y = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 1
+  %__vtable = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
+  %x = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 1
+  %y = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 2
   %myMethodArg = getelementptr inbounds %MyClass.testMethod_interface, %MyClass.testMethod_interface* %1, i32 0, i32 0
   %myMethodLocalVar = getelementptr inbounds %MyClass.testMethod_interface, %MyClass.testMethod_interface* %1, i32 0, i32 1
   %load_myMethodArg = load i16, i16* %myMethodArg, align 2
@@ -1864,7 +1899,7 @@ define void @prg(%prg_interface* %0) {
 entry:
   %cl = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
   %x = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
-  %x1 = getelementptr inbounds %MyClass_interface, %MyClass_interface* %cl, i32 0, i32 0
+  %x1 = getelementptr inbounds %MyClass_interface, %MyClass_interface* %cl, i32 0, i32 1
   %load_ = load i16, i16* %x1, align 2
   store i16 %load_, i16* %x, align 2
   %MyClass.testMethod_instance = alloca %MyClass.testMethod_interface, align 8
@@ -1943,15 +1978,17 @@ fn fb_method_in_pou() {
 source_filename = "main"
 
 %prg_interface = type { %MyClass_interface, i16 }
-%MyClass_interface = type { i16, i16 }
+%MyClass_interface = type { i8**, i16, i16 }
 %MyClass.testMethod_interface = type { i16, i16 }
 
-@prg_instance = global %prg_interface zeroinitializer
+@__MyClass_vtable = constant [1 x i8*] [i8* bitcast (void (%MyClass_interface*, %MyClass.testMethod_interface*)* @MyClass.testMethod to i8*)]
+@prg_instance = global %prg_interface { %MyClass_interface { i8** bitcast ([1 x i8*]* @__MyClass_vtable to i8**), i16 0, i16 0 }, i16 0 }
 
 define void @MyClass.testMethod(%MyClass_interface* %0, %MyClass.testMethod_interface* %1) {
 entry:
-  %x = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
-  %y = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 1
+  %__vtable = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
+  %x = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 1
+  %y = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 2
   %myMethodArg = getelementptr inbounds %MyClass.testMethod_interface, %MyClass.testMethod_interface* %1, i32 0, i32 0
   %myMethodLocalVar = getelementptr inbounds %MyClass.testMethod_interface, %MyClass.testMethod_interface* %1, i32 0, i32 1
   %load_myMethodArg = load i16, i16* %myMethodArg, align 2
@@ -1966,8 +2003,9 @@ entry:
 
 define void @MyClass(%MyClass_interface* %0) {
 entry:
-  %x = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
-  %y = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 1
+  %__vtable = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
+  %x = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 1
+  %y = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 2
   ret void
 }
 
@@ -1975,7 +2013,7 @@ define void @prg(%prg_interface* %0) {
 entry:
   %cl = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
   %x = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
-  %x1 = getelementptr inbounds %MyClass_interface, %MyClass_interface* %cl, i32 0, i32 0
+  %x1 = getelementptr inbounds %MyClass_interface, %MyClass_interface* %cl, i32 0, i32 1
   %load_ = load i16, i16* %x1, align 2
   store i16 %load_, i16* %x, align 2
   %MyClass.testMethod_instance = alloca %MyClass.testMethod_interface, align 8
@@ -2035,11 +2073,14 @@ fn method_codegen_return() {
     let expected = r#"; ModuleID = 'main'
 source_filename = "main"
 
-%MyClass_interface = type {}
+%MyClass_interface = type { i8** }
 %MyClass.testMethod_interface = type { i16 }
 
+@__MyClass_vtable = constant [1 x i8*] [i8* bitcast (i16 (%MyClass_interface*, %MyClass.testMethod_interface*)* @MyClass.testMethod to i8*)]
+
 define i16 @MyClass.testMethod(%MyClass_interface* %0, %MyClass.testMethod_interface* %1) {
 entry:
+  %__vtable = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
   %myMethodArg = getelementptr inbounds %MyClass.testMethod_interface, %MyClass.testMethod_interface* %1, i32 0, i32 0
   %MyClass.testMethod = alloca i16, align 2
   store i16 1, i16* %MyClass.testMethod, align 2
@@ -2069,11 +2110,14 @@ fn method_codegen_void() {
     let expected = r#"; ModuleID = 'main'
 source_filename = "main"
 
-%MyClass_interface = type {}
+%MyClass_interface = type { i8** }
 %MyClass.testMethod_interface = type { i16, i16 }
 
+@__MyClass_vtable = constant [1 x i8*] [i8* bitcast (void (%MyClass_interface*, %MyClass.testMethod_interface*)* @MyClass.testMethod to i8*)]
+
 define void @MyClass.testMethod(%MyClass_interface* %0, %MyClass.testMethod_interface* %1) {
 entry:
+  %__vtable = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
   %myMethodArg = getelementptr inbounds %MyClass.testMethod_interface, %MyClass.testMethod_interface* %1, i32 0, i32 0
   %myMethodLocalVar = getelementptr inbounds %MyClass.testMethod_interface, %MyClass.testMethod_interface* %1, i32 0, i32 1
   store i16 1, i16* %myMethodLocalVar, align 2
@@ -2108,13 +2152,16 @@ fn class_member_access_from_method() {
     let expected = r#"; ModuleID = 'main'
 source_filename = "main"
 
-%MyClass_interface = type { i16, i16 }
+%MyClass_interface = type { i8**, i16, i16 }
 %MyClass.testMethod_interface = type { i16, i16 }
 
+@__MyClass_vtable = constant [1 x i8*] [i8* bitcast (void (%MyClass_interface*, %MyClass.testMethod_interface*)* @MyClass.testMethod to i8*)]
+
 define void @MyClass.testMethod(%MyClass_interface* %0, %MyClass.testMethod_interface* %1) {
 entry:
-  %x = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
-  %y = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 1
+  %__vtable = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 0
+  %x = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 1
+  %y = getelementptr inbounds %MyClass_interface, %MyClass_interface* %0, i32 0, i32 2
   %myMethodArg = getelementptr inbounds %MyClass.testMethod_interface, %MyClass.testMethod_interface* %1, i32 0, i32 0
   %myMethodLocalVar = getelementptr inbounds %MyClass.testMethod_interface, %MyClass.testMethod_interface* %1, i32 0, i32 1
   %load_myMethodArg = load i16, i16* %myMethodArg, align 2
@@ -3922,6 +3969,111 @@ entry:
     assert_eq!(result, expected);
 }
 
+#[test]
+fn references_are_bound_with_ref_assignments_and_dereferenced_automatically() {
+    let result = codegen!(
+        "
+        PROGRAM prg
+        VAR
+            x : INT;
+            y : INT;
+            r : REFERENCE TO INT;
+            valid : BOOL;
+        END_VAR
+        r REF= x;
+        r := 5;
+        y := r;
+        r REF= REF(y);
+        valid := __ISVALIDREF(r);
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { i16, i16, i16*, i1 }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %x = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %y = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  %r = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 2
+  %valid = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 3
+  store i16* %x, i16** %r, align 8
+  %deref = load i16*, i16** %r, align 8
+  store i16 5, i16* %deref, align 2
+  %deref1 = load i16*, i16** %r, align 8
+  %load_r = load i16, i16* %deref1, align 2
+  store i16 %load_r, i16* %y, align 2
+  store i16* %y, i16** %r, align 8
+  %1 = load i16*, i16** %r, align 8
+  %2 = icmp ne i16* %1, null
+  store i1 %2, i1* %valid, align 1
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn addresses_sizes_and_pointer_arithmetic() {
+    let result = codegen!(
+        "
+        PROGRAM prg
+        VAR
+            buffer : ARRAY[0..9] OF INT;
+            p : REF_TO INT;
+            q : REF_TO INT;
+            address : LWORD;
+            size : ULINT;
+        END_VAR
+        address := ADR(buffer);
+        p := ADR(buffer[2]);
+        q := p + 3;
+        q := q - 1;
+        size := SIZEOF(buffer);
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { [10 x i16], i16*, i16*, i64, i64 }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %buffer = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %p = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  %q = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 2
+  %address = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 3
+  %size = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 4
+  %1 = ptrtoint [10 x i16]* %buffer to i64
+  store i64 %1, i64* %address, align 4
+  %tmpVar = getelementptr inbounds [10 x i16], [10 x i16]* %buffer, i32 0, i32 2
+  %2 = ptrtoint i16* %tmpVar to i64
+  %3 = inttoptr i64 %2 to i16*
+  store i16* %3, i16** %p, align 8
+  %load_p = load i16*, i16** %p, align 8
+  %tmpVar1 = getelementptr i16, i16* %load_p, i64 3
+  store i16* %tmpVar1, i16** %q, align 8
+  %load_q = load i16*, i16** %q, align 8
+  %tmpVar2 = getelementptr i16, i16* %load_q, i64 -1
+  store i16* %tmpVar2, i16** %q, align 8
+  store i64 20, i64* %size, align 4
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
 #[test]
 fn program_with_var_out_called_mixed_in_program() {
     let result = codegen!(
@@ -4410,6 +4562,50 @@ source_filename = "main"
     assert_eq!(result, expected);
 }
 
+#[test]
+fn external_global_variables_and_enums_are_declared() {
+    let result = codegen!(
+        "
+      @EXTERNAL VAR_GLOBAL
+        x : INT;
+      END_VAR
+
+      @EXTERNAL TYPE MyEnum: (red, green);
+      END_TYPE
+
+      PROGRAM main
+      VAR
+        color : MyEnum;
+      END_VAR
+      x := 1;
+      color := green;
+      END_PROGRAM
+      "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%main_interface = type { i32 }
+
+@x = external global i16
+@main_instance = global %main_interface zeroinitializer
+@red = external global i32
+@green = external global i32
+
+define void @main(%main_interface* %0) {
+entry:
+  %color = getelementptr inbounds %main_interface, %main_interface* %0, i32 0, i32 0
+  store i16 1, i16* @x, align 2
+  %load_green = load i32, i32* @green, align 4
+  store i32 %load_green, i32* %color, align 4
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
 #[test]
 fn enum_members_can_be_used_in_asignments() {
     let result = codegen!(
@@ -5702,6 +5898,150 @@ continue:                                         ; preds = %output
     assert_eq!(expected, result);
 }
 
+#[test]
+fn array_access_calls_check_bounds_for_every_dimension() {
+    let result = codegen!(
+        "
+        FUNCTION CheckBounds : DINT
+        VAR_INPUT
+          index : DINT;
+          lower : DINT;
+          upper : DINT;
+        END_VAR
+        CheckBounds := index;
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+          matrix : ARRAY[1..3, -2..2] OF INT;
+          x : INT;
+        END_VAR
+        x := matrix[x, 0];
+        END_PROGRAM
+        "
+    );
+
+    assert_eq!(
+        result
+            .matches("call i32 @CheckBounds(%CheckBounds_interface* %CheckBounds_instance")
+            .count(),
+        2
+    );
+    //the bounds of both dimensions are passed to the check
+    for bound in &[1, 3, -2, 2] {
+        assert!(result.contains(&format!("store i32 {}, i32* %", bound)));
+    }
+}
+
+#[test]
+fn array_access_is_not_checked_without_check_bounds() {
+    let result = codegen!(
+        "
+        PROGRAM main
+        VAR
+          values : ARRAY[1..3] OF INT;
+          x : INT;
+        END_VAR
+        x := values[x];
+        END_PROGRAM
+        "
+    );
+
+    assert!(!result.contains("CheckBounds"));
+}
+
+#[test]
+fn divisions_pass_the_divisor_to_check_div() {
+    let result = codegen!(
+        "
+        FUNCTION CheckDivDInt : DINT
+        VAR_INPUT divisor : DINT; END_VAR
+        CheckDivDInt := divisor;
+        END_FUNCTION
+
+        FUNCTION CheckDivLInt : LINT
+        VAR_INPUT divisor : LINT; END_VAR
+        CheckDivLInt := divisor;
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+          x, y : DINT;
+          l : LINT;
+        END_VAR
+        x := x / y;
+        y := x MOD y;
+        l := l / l;
+        END_PROGRAM
+        "
+    );
+
+    assert_eq!(
+        result
+            .matches("call i32 @CheckDivDInt(%CheckDivDInt_interface* %CheckDivDInt_instance")
+            .count(),
+        2
+    );
+    assert!(
+        result.contains("call i64 @CheckDivLInt(%CheckDivLInt_interface* %CheckDivLInt_instance")
+    );
+}
+
+#[test]
+fn arithmetic_operations_call_check_overflow_if_they_overflow() {
+    let result = codegen!(
+        "
+        FUNCTION CheckOverflow
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+          x : DINT;
+          u : UDINT;
+        END_VAR
+        x := x * 2 + 1;
+        u := u - u;
+        END_PROGRAM
+        "
+    );
+
+    assert!(result.contains("call { i32, i1 } @llvm.smul.with.overflow.i32("));
+    assert!(result.contains("call { i32, i1 } @llvm.sadd.with.overflow.i32("));
+    assert!(result.contains("call { i32, i1 } @llvm.usub.with.overflow.i32("));
+    assert_eq!(
+        result
+            .matches("call void @CheckOverflow(%CheckOverflow_interface* %CheckOverflow_instance")
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn dereferenced_null_pointers_are_passed_to_check_pointer() {
+    let result = codegen!(
+        "
+        FUNCTION CheckPointer : REF_TO BYTE
+        VAR_INPUT ptToTest : REF_TO BYTE; END_VAR
+        CheckPointer := ptToTest;
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+          x : INT;
+          p : REF_TO INT;
+        END_VAR
+        x := p^;
+        END_PROGRAM
+        "
+    );
+
+    assert!(result.contains("%is_null = icmp eq i16* %deref, null"));
+    assert!(
+        result.contains("call i8* @CheckPointer(%CheckPointer_interface* %CheckPointer_instance")
+    );
+    assert!(result.contains("%checked = phi i16* [ %deref, %entry ]"));
+}
+
 #[test]
 fn initial_values_in_global_constant_variables() {
     let result = codegen!(
@@ -5849,3 +6189,201 @@ fn using_global_consts_in_expressions() {
     // https://github.com/ghaith/rusty/issues/291
     assert_eq!(result, expected);
 }
+
+#[test]
+fn configurations_generate_cycle_functions_and_a_task_table() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+x : DINT;
+END_VAR
+x := x + 1;
+END_PROGRAM
+
+CONFIGURATION config
+RESOURCE res ON PLC
+TASK fast(INTERVAL := T#10ms, PRIORITY := 1);
+TASK slow(PRIORITY := 2);
+PROGRAM p1 WITH fast : prg;
+PROGRAM prg WITH fast : prg;
+END_RESOURCE
+END_CONFIGURATION
+"#
+    );
+
+    //every task calls its program instances in order
+    assert!(result.contains(
+        r#"define void @__cycle_res_fast() {
+entry:
+  call void @prg(%prg_interface* @p1)
+  call void @prg(%prg_interface* @prg_instance)
+  ret void
+}"#
+    ));
+    assert!(result.contains(
+        r#"define void @__cycle_res_slow() {
+entry:
+  ret void
+}"#
+    ));
+
+    //the task table describes the resource, name, interval (ns), priority and cycle function of every task
+    assert!(result.contains(r#"@__task_table.res = constant [4 x i8] c"res\00""#));
+    assert!(result.contains(r#"@__task_table.res.fast = constant [5 x i8] c"fast\00""#));
+    assert!(result.contains("i64 10000000, i32 1, void ()* @__cycle_res_fast }"));
+    assert!(result.contains("i64 0, i32 2, void ()* @__cycle_res_slow }"));
+    assert!(result.contains("@__task_count = constant i32 2"));
+}
+
+#[test]
+fn retained_variables_generate_a_retain_table() {
+    let result = codegen!(
+        r#"VAR_GLOBAL RETAIN
+counter : DINT;
+END_VAR
+VAR_GLOBAL
+volatile : DINT;
+END_VAR
+
+FUNCTION_BLOCK fb
+VAR RETAIN
+total : DINT;
+END_VAR
+VAR
+current : DINT;
+END_VAR
+END_FUNCTION_BLOCK
+
+PROGRAM prg
+VAR
+instance : fb;
+END_VAR
+VAR PERSISTENT
+setpoint : INT;
+END_VAR
+END_PROGRAM
+
+@EXTERNAL VAR_GLOBAL RETAIN
+library_counter : DINT;
+END_VAR
+
+CONFIGURATION config
+RESOURCE res ON PLC
+TASK cyclic(PRIORITY := 1);
+PROGRAM prg WITH cyclic : prg;
+END_RESOURCE
+END_CONFIGURATION
+"#
+    );
+
+    //every retained variable of a global instance is described by its name, address and size
+    assert!(result.contains(r#"@__retain_table.counter = constant [8 x i8] c"counter\00""#));
+    assert!(result.contains(
+        r#"@__retain_table.prg.instance.total = constant [19 x i8] c"prg.instance.total\00""#
+    ));
+    assert!(
+        result.contains(r#"@__retain_table.prg.setpoint = constant [13 x i8] c"prg.setpoint\00""#)
+    );
+    assert!(!result.contains("@__retain_table.volatile"));
+    assert!(!result.contains("@__retain_table.prg.instance.current"));
+    assert!(result.contains("i8* bitcast (i32* @counter to i8*)"));
+    //the retained variables of other objects are described by the configuration's object
+    assert!(result.contains("i8* bitcast (i32* @library_counter to i8*)"));
+    assert!(result.contains("@__retain_count = constant i32 4"));
+}
+
+#[test]
+fn retain_tables_are_only_generated_with_a_configuration() {
+    let result = codegen!(
+        r#"VAR_GLOBAL RETAIN
+counter : DINT;
+END_VAR
+"#
+    );
+
+    //a library's retained variables are described by the object declaring the configuration
+    assert!(!result.contains("@__retain_table"));
+    assert!(!result.contains("@__retain_count"));
+}
+
+#[test]
+fn arrays_are_passed_to_variable_length_arrays_as_descriptors() {
+    let result = codegen!(
+        "
+        FUNCTION first : INT
+        VAR_IN_OUT
+          values : ARRAY[*, *] OF INT;
+        END_VAR
+        first := values[LOWER_BOUND(values, 1), UPPER_BOUND(values, 2)];
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+          matrix : ARRAY[1..2, 5..7] OF INT;
+          x : INT;
+        END_VAR
+        x := first(matrix);
+        END_PROGRAM
+        "
+    );
+
+    //the caller describes its array by a pointer to the first element and the bounds
+    assert!(result.contains("%descriptor = alloca { i16*, [4 x i32] }"));
+    assert!(result.contains("bitcast [2 x [3 x i16]]* %matrix to i16*"));
+    for bound in &[1, 2, 5, 7] {
+        assert!(result.contains(&format!("store i32 {}, i32* %", bound)));
+    }
+    //the callee accesses the elements behind the descriptor's data-pointer
+    assert!(result.contains("%data = load i16*, i16** %"));
+    assert!(result.contains("getelementptr inbounds i16, i16* %data, i32 %"));
+}
+
+#[test]
+fn bounds_of_fixed_size_arrays_are_constant() {
+    let result = codegen!(
+        "
+        PROGRAM main
+        VAR
+          matrix : ARRAY[1..2, 5..7] OF INT;
+          x : INT;
+          y : INT;
+        END_VAR
+        x := LOWER_BOUND(matrix, 2);
+        y := UPPER_BOUND(matrix, 1);
+        END_PROGRAM
+        "
+    );
+
+    assert!(result.contains("store i16 5, i16* %x"));
+    assert!(result.contains("store i16 2, i16* %y"));
+}
+
+#[test]
+fn variable_length_array_access_calls_check_bounds_with_the_runtime_bounds() {
+    let result = codegen!(
+        "
+        FUNCTION CheckBounds : DINT
+        VAR_INPUT
+          index : DINT;
+          lower : DINT;
+          upper : DINT;
+        END_VAR
+        CheckBounds := index;
+        END_FUNCTION
+
+        FUNCTION sum : DINT
+        VAR_IN_OUT
+          values : ARRAY[*] OF DINT;
+        END_VAR
+        sum := values[1] + values[2];
+        END_FUNCTION
+        "
+    );
+
+    assert_eq!(
+        result
+            .matches("call i32 @CheckBounds(%CheckBounds_interface* %CheckBounds_instance")
+            .count(),
+        2
+    );
+}
@@ -1,5 +1,7 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
-use crate::{ast::SourceRange, codegen_wihout_unwrap, compile_error::CompileError};
+use crate::{
+    ast::SourceRange, codegen_wihout_unwrap, codegen_with_all_errors, compile_error::CompileError,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -309,3 +311,35 @@ fn recursive_initial_constant_values() {
         panic!("expected code-gen error but got none")
     }
 }
+
+#[test]
+fn errors_of_all_pous_are_reported() {
+    let result = codegen_with_all_errors!(
+        "
+        PROGRAM prg
+            EXIT;
+        END_PROGRAM
+
+        FUNCTION foo : INT
+            CONTINUE;
+        END_FUNCTION
+        "
+    );
+    if let Err(errors) = result {
+        assert_eq!(
+            vec![
+                CompileError::codegen_error(
+                    "Cannot break out of loop when not inside a loop".into(),
+                    SourceRange::new(33..37)
+                ),
+                CompileError::codegen_error(
+                    "Cannot continue loop when not inside a loop".into(),
+                    SourceRange::new(99..107)
+                ),
+            ],
+            errors
+        );
+    } else {
+        panic!("expected code-gen errors but got none")
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+use crate::codegen::debug::DebugBuilder;
+
+/// generates the given source with debug information as if it was read from `debug_test.st`
+fn codegen_with_debug_info(src: &str) -> String {
+    let lexer = crate::lexer::lex(src);
+    let (mut ast, ..) = crate::parser::parse(lexer);
+
+    let context = inkwell::context::Context::create();
+    crate::ast::pre_process(&mut ast);
+    let mut index = crate::index::visitor::visit(&crate::builtins::parse_built_ins(
+        crate::lexer::IdProvider::new(),
+    ));
+    index.import(crate::index::visitor::visit(&ast));
+    let annotations = crate::resolver::TypeAnnotator::visit_unit(&index, &ast);
+    let (index, _unresolvable) = crate::resolver::const_evaluator::evaluate_constants(index);
+
+    let code_generator = crate::codegen::CodeGen::new(&context, "main");
+    let ir = code_generator
+        .generate_with_debug_info(&ast, &annotations, &index, "src/debug_test.st", src)
+        .unwrap();
+    code_generator.module.verify().unwrap();
+    ir
+}
+
+#[test]
+fn source_offsets_are_converted_to_lines_and_columns() {
+    let context = inkwell::context::Context::create();
+    let module = context.create_module("main");
+    let debug = DebugBuilder::new(&context, &module, "debug_test.st", "ab\ncd\n\nef");
+
+    assert_eq!(debug.get_line_and_column(0), (1, 1));
+    assert_eq!(debug.get_line_and_column(1), (1, 2));
+    assert_eq!(debug.get_line_and_column(3), (2, 1));
+    assert_eq!(debug.get_line_and_column(6), (3, 1));
+    assert_eq!(debug.get_line_and_column(8), (4, 2));
+}
+
+#[test]
+fn pous_are_described_as_subprograms_with_line_locations() {
+    let result = codegen_with_debug_info(
+        "FUNCTION foo : DINT
+VAR_INPUT
+    a : DINT;
+END_VAR
+    foo := a + 1;
+END_FUNCTION
+
+PROGRAM prg
+VAR
+    x : DINT;
+END_VAR
+    x := foo(x);
+END_PROGRAM
+",
+    );
+
+    assert!(result.contains(r#"!DIFile(filename: "debug_test.st", directory: "src")"#));
+    assert!(result.contains(r#"!DISubprogram(name: "foo""#));
+    assert!(result.contains(r#"!DISubprogram(name: "prg""#));
+    //the call in prg is located at line 12
+    assert!(result.contains("!DILocation(line: 12, column: 5"));
+    assert!(result.contains("!\"Debug Info Version\", i32 3"));
+}
+
+#[test]
+fn variables_and_types_are_described() {
+    let result = codegen_with_debug_info(
+        "TYPE Point : STRUCT
+    x : INT;
+    y : REAL;
+END_STRUCT
+END_TYPE
+
+VAR_GLOBAL
+    origin : Point;
+END_VAR
+
+FUNCTION_BLOCK fb
+VAR
+    count : UDINT;
+    flag : BOOL;
+END_VAR
+END_FUNCTION_BLOCK
+
+PROGRAM prg
+VAR
+    instance : fb;
+    values : ARRAY[1..3] OF LINT;
+END_VAR
+VAR_TEMP
+    name : STRING;
+END_VAR
+END_PROGRAM
+",
+    );
+
+    //the members of POUs are local variables
+    assert!(result.contains(r#"!DILocalVariable(name: "count""#));
+    assert!(result.contains(r#"!DILocalVariable(name: "instance""#));
+    assert!(result.contains(r#"!DILocalVariable(name: "name""#));
+    //globals and program instances are global variables
+    assert!(result.contains(r#"!DIGlobalVariable(name: "origin""#));
+    assert!(result.contains(r#"!DIGlobalVariable(name: "prg", linkageName: "prg_instance""#));
+    //datatypes and instance-structs are described as debug types
+    assert!(result.contains(r#"!DICompositeType(tag: DW_TAG_structure_type, name: "Point""#));
+    assert!(result.contains(r#"!DICompositeType(tag: DW_TAG_structure_type, name: "fb""#));
+    assert!(result.contains(r#"!DIDerivedType(tag: DW_TAG_member, name: "y", scope: "#));
+    assert!(result.contains(r#"!DIBasicType(name: "UDINT", size: 32, encoding: DW_ATE_unsigned)"#));
+    assert!(result.contains(r#"!DIBasicType(name: "BOOL", size: 8, encoding: DW_ATE_boolean)"#));
+    assert!(result.contains("!DISubrange(count: 3, lowerBound: 1)"));
+}
+
+#[test]
+fn variable_length_arrays_are_described_as_descriptors() {
+    let result = codegen_with_debug_info(
+        "FUNCTION total : DINT
+VAR_IN_OUT
+    values : ARRAY[*, *] OF INT;
+END_VAR
+END_FUNCTION
+",
+    );
+
+    assert!(
+        result.contains(r#"!DICompositeType(tag: DW_TAG_structure_type, name: "__total_values""#)
+    );
+    assert!(result.contains(r#"!DIDerivedType(tag: DW_TAG_member, name: "data", scope: "#));
+    assert!(result.contains(r#"!DIDerivedType(tag: DW_TAG_member, name: "bounds", scope: "#));
+    //the lower and upper bound of both dimensions
+    assert!(result.contains("!DISubrange(count: 4"));
+}
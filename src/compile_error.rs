@@ -41,6 +41,15 @@ pub enum CompileError {
 
     #[error("Cannot link: {reason:}")]
     LinkerError { reason: String },
+
+    #[error("{reason:}")]
+    InvalidInput { reason: String },
+
+    #[error("Aborting due to {error_count:} previous error(s) and {warning_count:} warning(s)")]
+    CompilationAborted {
+        error_count: usize,
+        warning_count: usize,
+    },
 }
 
 impl CompileError {
@@ -106,4 +115,31 @@ impl CompileError {
     pub fn literal_or_constant_int_expected(location: SourceRange) -> CompileError {
         CompileError::codegen_error("Expected integer literal or constant".to_string(), location)
     }
+
+    pub fn linker_error(reason: String) -> CompileError {
+        CompileError::LinkerError { reason }
+    }
+
+    pub fn invalid_input(reason: String) -> CompileError {
+        CompileError::InvalidInput { reason }
+    }
+
+    pub fn compilation_aborted(error_count: usize, warning_count: usize) -> CompileError {
+        CompileError::CompilationAborted {
+            error_count,
+            warning_count,
+        }
+    }
+
+    /// returns the location in the source code this error was raised for, if any
+    pub fn get_location(&self) -> Option<&SourceRange> {
+        match self {
+            CompileError::InvalidReference { location, .. }
+            | CompileError::UnknownType { location, .. }
+            | CompileError::CodeGenError { location, .. }
+            | CompileError::MissingFunctionError { location }
+            | CompileError::CastError { location, .. } => Some(location),
+            _ => None,
+        }
+    }
 }
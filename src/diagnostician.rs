@@ -0,0 +1,566 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+
+//! the diagnostician reports the diagnostics of a compilation in the requested format
+
+use std::fmt::Write;
+
+use codespan_reporting::{
+    diagnostic::{self, Label},
+    files::{Files, SimpleFiles},
+    term::{
+        self,
+        termcolor::{ColorChoice, StandardStream},
+        Chars, Styles,
+    },
+};
+
+use crate::{
+    ast::{SourceRange, Suppression},
+    compile_error::CompileError,
+    Diagnostic, Severity,
+};
+
+/// the format diagnostics are reported in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorFormat {
+    /// colored text rendering the affected source-code
+    Human,
+    /// a json array with one object per diagnostic
+    Json,
+    /// a SARIF 2.1.0 log (Static Analysis Results Interchange Format)
+    Sarif,
+}
+
+/// selects which lints are reported and with which severity
+///
+/// lints are reported as warnings unless their code (or `all`) is allowed. A code listed in
+/// `warn` is reported even if it is allowed, so `-A all -W <code>` reports a single lint.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintOptions {
+    /// the codes of the lints to report as warnings
+    pub warn: Vec<String>,
+    /// the codes of the lints not to report
+    pub allow: Vec<String>,
+    /// report warnings as errors
+    pub deny_warnings: bool,
+}
+
+impl LintOptions {
+    fn is_allowed(&self, code: &str) -> bool {
+        let matches = |it: &String| it.eq_ignore_ascii_case(code);
+        let is_warned = self.warn.iter().any(matches);
+        let is_allowed = self.allow.iter().any(|it| matches(it) || it == "all");
+        is_allowed && !is_warned
+    }
+}
+
+/// a position in a source file, line and column start at 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// a diagnostic together with the file and the position it was reported for
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportedDiagnostic {
+    pub diagnostic: Diagnostic,
+    /// the severity the diagnostic was reported with (warnings may be reported as errors)
+    pub severity: Severity,
+    pub file: String,
+    pub start: Position,
+    pub end: Position,
+}
+
+/// collects the diagnostics of a compilation and reports them in the requested format
+///
+/// human readable diagnostics are printed to stderr immediately, json and sarif reports are
+/// printed to stderr as one document when the diagnostician is flushed.
+pub struct Diagnostician {
+    format: ErrorFormat,
+    lint_options: LintOptions,
+    files: SimpleFiles<String, String>,
+    /// the suppressed codes per file-id and line
+    suppressions: Vec<(usize, usize, Vec<String>)>,
+    reported: Vec<ReportedDiagnostic>,
+    /// the number of reported diagnostics that were already flushed
+    flushed: usize,
+}
+
+impl Default for Diagnostician {
+    fn default() -> Self {
+        Diagnostician::new(ErrorFormat::Human)
+    }
+}
+
+impl Diagnostician {
+    /// creates a diagnostician reporting in the given format
+    pub fn new(format: ErrorFormat) -> Diagnostician {
+        Diagnostician {
+            format,
+            lint_options: LintOptions::default(),
+            files: SimpleFiles::new(),
+            suppressions: Vec::new(),
+            reported: Vec::new(),
+            flushed: 0,
+        }
+    }
+
+    /// selects which lints are reported and with which severity
+    pub fn with_lint_options(mut self, lint_options: LintOptions) -> Diagnostician {
+        self.lint_options = lint_options;
+        self
+    }
+
+    /// registers a source file, diagnostics are reported with the returned file-id
+    pub fn register_file(&mut self, name: String, source: String) -> usize {
+        self.files.add(name, source)
+    }
+
+    /// returns the name and source of the registered file with the given id
+    pub fn get_file(&self, file_id: usize) -> Option<(&str, &str)> {
+        self.files
+            .get(file_id)
+            .ok()
+            .map(|it| (it.name().as_str(), it.source().as_str()))
+    }
+
+    /// registers the `{allow(...)}` pragmas of the file with the given id
+    pub fn suppress(&mut self, file_id: usize, suppressions: &[Suppression]) {
+        for suppression in suppressions {
+            let line = self
+                .get_position(file_id, suppression.location.get_start())
+                .line;
+            self.suppressions
+                .push((file_id, line, suppression.codes.clone()));
+        }
+    }
+
+    /// returns all diagnostics reported so far
+    pub fn get_reported_diagnostics(&self) -> &[ReportedDiagnostic] {
+        &self.reported
+    }
+
+    /// returns the number of reported diagnostics with the given (effective) severity
+    pub fn count(&self, severity: Severity) -> usize {
+        self.reported
+            .iter()
+            .filter(|it| it.severity == severity)
+            .count()
+    }
+
+    /// returns an error summarizing the reported diagnostics if any of them is an error
+    pub fn check_errors(&self) -> Result<(), CompileError> {
+        match self.count(Severity::Error) {
+            0 => Ok(()),
+            error_count => Err(CompileError::compilation_aborted(
+                error_count,
+                self.count(Severity::Warning),
+            )),
+        }
+    }
+
+    /// reports the given diagnostics of the file with the given id
+    ///
+    /// allowed and suppressed lints are dropped, with `deny_warnings` lints are reported as errors
+    pub fn handle(
+        &mut self,
+        file_id: usize,
+        diagnostics: &[Diagnostic],
+    ) -> Result<(), CompileError> {
+        for diagnostic in diagnostics {
+            let location = diagnostic.get_location();
+            let start = self.get_position(file_id, location.get_start());
+            let severity = match diagnostic.get_severity() {
+                Severity::Warning => {
+                    let code = get_code(diagnostic);
+                    if self.lint_options.is_allowed(&code)
+                        || self.is_suppressed(file_id, start, &code)
+                    {
+                        continue;
+                    }
+                    if self.lint_options.deny_warnings {
+                        Severity::Error
+                    } else {
+                        Severity::Warning
+                    }
+                }
+                severity => severity,
+            };
+
+            if self.format == ErrorFormat::Human {
+                self.emit_human(file_id, diagnostic, severity)?;
+            }
+            let file = self
+                .get_file(file_id)
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_default();
+            self.reported.push(ReportedDiagnostic {
+                diagnostic: diagnostic.clone(),
+                severity,
+                file,
+                start,
+                end: self.get_position(file_id, location.get_end()),
+            });
+        }
+        Ok(())
+    }
+
+    /// returns true if an `{allow(...)}` pragma on the same or on the previous line suppresses the code
+    fn is_suppressed(&self, file_id: usize, position: Position, code: &str) -> bool {
+        self.suppressions.iter().any(|(file, line, codes)| {
+            *file == file_id
+                && (*line == position.line || *line + 1 == position.line)
+                && codes.iter().any(|it| it.eq_ignore_ascii_case(code))
+        })
+    }
+
+    /// prints the diagnostics reported since the last flush as a json or sarif document
+    pub fn flush(&mut self) {
+        let diagnostics = &self.reported[self.flushed..];
+        self.flushed = self.reported.len();
+        match self.format {
+            ErrorFormat::Human => {}
+            ErrorFormat::Json => eprintln!("{}", to_json(diagnostics)),
+            ErrorFormat::Sarif => eprintln!("{}", to_sarif(diagnostics)),
+        }
+    }
+
+    fn get_position(&self, file_id: usize, offset: usize) -> Position {
+        self.files
+            .location(file_id, offset)
+            .map(|it| Position {
+                line: it.line_number,
+                column: it.column_number,
+            })
+            .unwrap_or(Position { line: 0, column: 0 })
+    }
+
+    fn emit_human(
+        &self,
+        file_id: usize,
+        diagnostic: &Diagnostic,
+        severity: Severity,
+    ) -> Result<(), CompileError> {
+        let severity = match severity {
+            Severity::Error => diagnostic::Severity::Error,
+            Severity::Warning => diagnostic::Severity::Warning,
+            Severity::Info => diagnostic::Severity::Note,
+        };
+        let location = diagnostic.get_location();
+        let diag = diagnostic::Diagnostic::new(severity)
+            .with_message(diagnostic.get_message())
+            .with_labels(vec![Label::primary(
+                file_id,
+                location.get_start()..location.get_end(),
+            )]);
+        let writer = StandardStream::stderr(ColorChoice::Always);
+        let config = codespan_reporting::term::Config {
+            display_style: term::DisplayStyle::Rich,
+            tab_width: 2,
+            styles: Styles::default(),
+            chars: Chars::default(),
+            start_context_lines: 5,
+            end_context_lines: 3,
+        };
+
+        term::emit(&mut writer.lock(), &config, &self.files, &diag).map_err(|err| {
+            CompileError::codegen_error(
+                format!("Cannot print errors {:#?}", err),
+                SourceRange::undefined(),
+            )
+        })
+    }
+}
+
+/// returns the code of the given diagnostic (the name of its ErrNo)
+fn get_code(diagnostic: &Diagnostic) -> String {
+    format!("{:?}", diagnostic.get_err_no())
+}
+
+fn get_severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
+
+/// renders the given diagnostics as a json array:
+///
+/// `{ "severity", "code", "message", "file", "range": { "start": { "line", "column" }, "end" } }`
+pub fn to_json(diagnostics: &[ReportedDiagnostic]) -> String {
+    let entries = diagnostics
+        .iter()
+        .map(|it| {
+            format!(
+                r#"{{"severity":"{}","code":"{}","message":"{}","file":"{}","range":{{"start":{},"end":{}}}}}"#,
+                get_severity_name(it.severity),
+                get_code(&it.diagnostic),
+                escape_json(it.diagnostic.get_message()),
+                escape_json(&it.file),
+                position_to_json(it.start),
+                position_to_json(it.end),
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", entries.join(","))
+}
+
+fn position_to_json(position: Position) -> String {
+    format!(
+        r#"{{"line":{},"column":{}}}"#,
+        position.line, position.column
+    )
+}
+
+/// renders the given diagnostics as a SARIF 2.1.0 log with a single run
+pub fn to_sarif(diagnostics: &[ReportedDiagnostic]) -> String {
+    let mut rules: Vec<String> = diagnostics
+        .iter()
+        .map(|it| get_code(&it.diagnostic))
+        .collect();
+    rules.sort();
+    rules.dedup();
+    let rules = rules
+        .iter()
+        .map(|it| format!(r#"{{"id":"{}"}}"#, it))
+        .collect::<Vec<_>>();
+
+    let results = diagnostics
+        .iter()
+        .map(|it| {
+            let level = match it.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "note",
+            };
+            let mut result = String::new();
+            let _ = write!(
+                result,
+                r#"{{"ruleId":"{}","level":"{}","message":{{"text":"{}"}},"#,
+                get_code(&it.diagnostic),
+                level,
+                escape_json(it.diagnostic.get_message()),
+            );
+            let _ = write!(
+                result,
+                r#""locations":[{{"physicalLocation":{{"artifactLocation":{{"uri":"{}"}},"region":{{"startLine":{},"startColumn":{},"endLine":{},"endColumn":{}}}}}}}]}}"#,
+                escape_json(&it.file),
+                it.start.line,
+                it.start.column,
+                it.end.line,
+                it.end.column,
+            );
+            result
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        r#"{{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{{"tool":{{"driver":{{"name":"rustyc","informationUri":"https://github.com/ghaith/rusty","rules":[{}]}}}},"results":[{}]}}]}}"#,
+        rules.join(","),
+        results.join(",")
+    )
+}
+
+/// escapes the given text to be used inside a json string
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{get_code, to_json, to_sarif, Diagnostician, ErrorFormat, LintOptions};
+    use crate::{ast::Suppression, compile_error::CompileError, Diagnostic, Severity};
+
+    fn report(diagnostics: &[Diagnostic]) -> Diagnostician {
+        let mut diagnostician = Diagnostician::new(ErrorFormat::Json);
+        let file_id = diagnostician
+            .register_file("src/main.st".into(), "PROGRAM prg\n  x := \"a\";\n".into());
+        diagnostician.handle(file_id, diagnostics).unwrap();
+        diagnostician
+    }
+
+    #[test]
+    fn diagnostics_are_reported_with_their_position() {
+        let diagnostician = report(&[Diagnostic::syntax_error(
+            "Unexpected \"a\"",
+            (19..22).into(),
+        )]);
+
+        let reported = &diagnostician.get_reported_diagnostics()[0];
+        assert_eq!(reported.file, "src/main.st");
+        assert_eq!((reported.start.line, reported.start.column), (2, 8));
+        assert_eq!((reported.end.line, reported.end.column), (2, 11));
+    }
+
+    #[test]
+    fn diagnostics_are_rendered_as_json() {
+        let diagnostician = report(&[
+            Diagnostic::syntax_error("Unexpected \"a\"", (19..22).into()),
+            Diagnostic::ImprovementSuggestion {
+                message: "use REF_TO".into(),
+                range: (0..7).into(),
+            },
+        ]);
+
+        assert_eq!(
+            to_json(diagnostician.get_reported_diagnostics()),
+            r#"[{"severity":"error","code":"syntax__generic_error","message":"Unexpected \"a\"","file":"src/main.st","range":{"start":{"line":2,"column":8},"end":{"line":2,"column":11}}},{"severity":"info","code":"undefined","message":"use REF_TO","file":"src/main.st","range":{"start":{"line":1,"column":1},"end":{"line":1,"column":8}}}]"#
+        );
+    }
+
+    #[test]
+    fn diagnostics_are_rendered_as_sarif() {
+        let diagnostician = report(&[Diagnostic::syntax_error(
+            "Unexpected \"a\"",
+            (19..22).into(),
+        )]);
+
+        assert_eq!(
+            to_sarif(diagnostician.get_reported_diagnostics()),
+            r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{"tool":{"driver":{"name":"rustyc","informationUri":"https://github.com/ghaith/rusty","rules":[{"id":"syntax__generic_error"}]}},"results":[{"ruleId":"syntax__generic_error","level":"error","message":{"text":"Unexpected \"a\""},"locations":[{"physicalLocation":{"artifactLocation":{"uri":"src/main.st"},"region":{"startLine":2,"startColumn":8,"endLine":2,"endColumn":11}}}]}]}]}"#
+        );
+    }
+
+    #[test]
+    fn reported_errors_abort_the_compilation_with_a_summary() {
+        let diagnostician = report(&[Diagnostic::ImprovementSuggestion {
+            message: "use REF_TO".into(),
+            range: (0..7).into(),
+        }]);
+        assert_eq!(diagnostician.check_errors(), Ok(()));
+
+        let diagnostician = report(&[
+            Diagnostic::syntax_error("Unexpected \"a\"", (19..22).into()),
+            Diagnostic::unused_variable("x", (14..15).into()),
+            Diagnostic::syntax_error("Unexpected ;", (22..23).into()),
+        ]);
+        assert_eq!(diagnostician.count(Severity::Error), 2);
+        assert_eq!(
+            diagnostician.check_errors(),
+            Err(CompileError::compilation_aborted(2, 1))
+        );
+    }
+
+    fn report_lints(
+        lint_options: LintOptions,
+        suppressions: &[Suppression],
+    ) -> Vec<(Severity, String)> {
+        let mut diagnostician =
+            Diagnostician::new(ErrorFormat::Json).with_lint_options(lint_options);
+        let file_id = diagnostician.register_file(
+            "src/main.st".into(),
+            "VAR\n  x : INT;\n  y : INT;\nEND_VAR\n".into(),
+        );
+        diagnostician.suppress(file_id, suppressions);
+        diagnostician
+            .handle(
+                file_id,
+                &[
+                    Diagnostic::unused_variable("x", (6..7).into()),
+                    Diagnostic::shadowed_global("y", (17..18).into()),
+                    Diagnostic::syntax_error("Unexpected VAR", (0..3).into()),
+                ],
+            )
+            .unwrap();
+        diagnostician
+            .get_reported_diagnostics()
+            .iter()
+            .map(|it| (it.severity, get_code(&it.diagnostic)))
+            .collect()
+    }
+
+    #[test]
+    fn lints_are_reported_as_warnings() {
+        assert_eq!(
+            report_lints(LintOptions::default(), &[]),
+            vec![
+                (Severity::Warning, "lint__unused_variable".to_string()),
+                (Severity::Warning, "lint__shadowed_global".to_string()),
+                (Severity::Error, "syntax__generic_error".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn allowed_lints_are_not_reported() {
+        let lint_options = LintOptions {
+            allow: vec!["lint__unused_variable".to_string()],
+            ..LintOptions::default()
+        };
+        assert_eq!(
+            report_lints(lint_options, &[]),
+            vec![
+                (Severity::Warning, "lint__shadowed_global".to_string()),
+                (Severity::Error, "syntax__generic_error".to_string()),
+            ]
+        );
+
+        //warn overrides allow
+        let lint_options = LintOptions {
+            allow: vec!["all".to_string()],
+            warn: vec!["lint__shadowed_global".to_string()],
+            ..LintOptions::default()
+        };
+        assert_eq!(
+            report_lints(lint_options, &[]),
+            vec![
+                (Severity::Warning, "lint__shadowed_global".to_string()),
+                (Severity::Error, "syntax__generic_error".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn denied_warnings_are_reported_as_errors() {
+        let lint_options = LintOptions {
+            deny_warnings: true,
+            ..LintOptions::default()
+        };
+        assert_eq!(
+            report_lints(lint_options, &[]),
+            vec![
+                (Severity::Error, "lint__unused_variable".to_string()),
+                (Severity::Error, "lint__shadowed_global".to_string()),
+                (Severity::Error, "syntax__generic_error".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn pragmas_suppress_lints_on_their_line_and_the_next_one() {
+        //the pragma on line 1 covers x (line 2) but not y (line 3), errors cannot be suppressed
+        let suppressions = vec![Suppression {
+            codes: vec![
+                "lint__unused_variable".to_string(),
+                "lint__shadowed_global".to_string(),
+                "syntax__generic_error".to_string(),
+            ],
+            location: (0..3).into(),
+        }];
+        assert_eq!(
+            report_lints(LintOptions::default(), &suppressions),
+            vec![
+                (Severity::Warning, "lint__shadowed_global".to_string()),
+                (Severity::Error, "syntax__generic_error".to_string()),
+            ]
+        );
+    }
+}
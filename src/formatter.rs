@@ -0,0 +1,1226 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+//! pretty-prints a parsed `CompilationUnit` back to canonical Structured Text
+//!
+//! keywords are written in upper case, the contents of POUs, VAR-blocks and control-structures
+//! are indented with 4 spaces and the declarations of a block are aligned on their `:`.
+//! Comments and pragmas are reproduced before the element they precede, or at the end of the
+//! line they were written on. At most one empty line is kept between two elements.
+use std::ops::Range;
+
+use logos::Logos;
+
+use crate::{
+    ast::*,
+    lexer::{self, IdProvider, Token},
+    parser, Diagnostic, Severity,
+};
+
+#[cfg(test)]
+mod tests;
+
+const INDENT: &str = "    ";
+
+/// the precedence of leafs like literals, references and calls
+const LEAF_PRECEDENCE: u8 = 10;
+
+/// formats the given source-code
+///
+/// returns the syntax errors of the source-code if it cannot be parsed
+pub fn format(source: &str) -> Result<String, Vec<Diagnostic>> {
+    let (unit, diagnostics) = parser::parse(lexer::lex_with_ids(source, IdProvider::new()));
+    let errors: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|it| it.get_severity() == Severity::Error)
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut formatter = Formatter::new(source, &unit.comments);
+    formatter.format_unit(&unit);
+    Ok(formatter.finish())
+}
+
+/// prints the interface of the given unit: the declarations of its POUs, actions, types and
+/// global variables without their bodies, every element is declared `@EXTERNAL`
+///
+/// the unit must be parsed from the given source-code and must not be pre-processed.
+/// Configurations are left out, the global variables and program instances they declare are not.
+pub fn format_interface(unit: &CompilationUnit, source: &str) -> String {
+    let mut formatter = Formatter::new(source, &[]);
+    formatter.interface = true;
+    formatter.format_unit(unit);
+    formatter.finish()
+}
+
+/// a top-level element of a unit
+enum Element<'a> {
+    Globals(&'a VariableBlock),
+    Pou(&'a Pou),
+    Action(&'a Implementation),
+    /// an ACTIONS block with its container and the range from ACTIONS to END_ACTIONS
+    Actions(String, Range<usize>),
+    Type(&'a UserTypeDeclaration),
+    Configuration(&'a Configuration),
+    /// the named program instances of a configuration, printed by an interface
+    ProgramInstances(Vec<&'a ProgramConfiguration>),
+}
+
+/// an element of a configuration or a resource
+enum ConfigurationElement<'a> {
+    Globals(&'a VariableBlock),
+    Resource(&'a Resource),
+    Task(&'a Task),
+    Program(&'a ProgramConfiguration),
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    /// the tokens of the source without comments and pragmas
+    tokens: Vec<(Token, Range<usize>)>,
+    comments: &'a [Comment],
+    /// the index of the next comment to print
+    next_comment: usize,
+    output: String,
+    indent: usize,
+    /// the end of the last element printed, used to keep empty lines
+    last_end: usize,
+    /// true if nothing was printed since the last block was opened
+    block_start: bool,
+    /// true if only the declarations are printed (see `format_interface`)
+    interface: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(source: &'a str, comments: &'a [Comment]) -> Formatter<'a> {
+        let mut lexer = Token::lexer(source);
+        let mut tokens = Vec::new();
+        while let Some(token) = lexer.next() {
+            if token != Token::Comment && token != Token::Pragma {
+                tokens.push((token, lexer.span()));
+            }
+        }
+        Formatter {
+            source,
+            tokens,
+            comments,
+            next_comment: 0,
+            output: String::new(),
+            indent: 0,
+            last_end: 0,
+            block_start: true,
+            interface: false,
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.indent = 0;
+        self.flush_comments(self.source.len());
+        self.output
+    }
+
+    fn format_unit(&mut self, unit: &CompilationUnit) {
+        let mut elements: Vec<(usize, Element)> = Vec::new();
+        for block in &unit.global_vars {
+            let in_configuration = unit
+                .configurations
+                .iter()
+                .any(|it| contains(&it.location, block.location.get_start()));
+            if !in_configuration || self.interface {
+                elements.push((block.location.get_start(), Element::Globals(block)));
+            }
+        }
+        for pou in &unit.units {
+            if !matches!(pou.pou_type, PouType::Method { .. }) {
+                elements.push((pou.location.get_start(), Element::Pou(pou)));
+            }
+        }
+        let action_blocks = self.find_action_blocks();
+        for implementation in &unit.implementations {
+            let start = implementation.location.get_start();
+            if implementation.pou_type == PouType::Action
+                && !action_blocks.iter().any(|(_, it)| it.contains(&start))
+            {
+                let action = self.previous_token(start, &Token::KeywordAction);
+                elements.push((action.start, Element::Action(implementation)));
+            }
+        }
+        for (container, range) in action_blocks {
+            elements.push((range.start, Element::Actions(container, range)));
+        }
+        for user_type in &unit.types {
+            let start = self
+                .previous_token(user_type.location.get_start(), &Token::KeywordType)
+                .start;
+            elements.push((start, Element::Type(user_type)));
+        }
+        for configuration in &unit.configurations {
+            let start = configuration.location.get_start();
+            if !self.interface {
+                elements.push((start, Element::Configuration(configuration)));
+                continue;
+            }
+            //an instance named like its program is the program's default instance
+            let instances: Vec<&ProgramConfiguration> = configuration
+                .resources
+                .iter()
+                .flat_map(|it| it.programs.iter())
+                .filter(|it| !it.instance_name.eq_ignore_ascii_case(&it.type_name))
+                .collect();
+            if !instances.is_empty() {
+                elements.push((start, Element::ProgramInstances(instances)));
+            }
+        }
+        elements.sort_by_key(|(start, _)| *start);
+
+        for (start, element) in elements {
+            self.blank_line();
+            let (start, prefix) = self.external_prefix(start);
+            let prefix = if self.interface { "@EXTERNAL " } else { prefix };
+            match element {
+                Element::Globals(block) => {
+                    self.format_variable_block(block, start, prefix);
+                }
+                Element::Pou(pou) => self.format_pou(unit, pou, start, prefix),
+                Element::Action(implementation) => {
+                    self.format_action(implementation, &implementation.name, start, prefix);
+                }
+                Element::Actions(container, range) => {
+                    self.format_actions(unit, &container, range, start, prefix)
+                }
+                Element::Type(user_type) => self.format_type(user_type, start, prefix),
+                Element::Configuration(configuration) => {
+                    self.format_configuration(unit, configuration)
+                }
+                Element::ProgramInstances(instances) => {
+                    self.format_program_instances(&instances, start, prefix)
+                }
+            }
+        }
+    }
+
+    /// returns the containers and ranges of all `ACTIONS ... END_ACTIONS` blocks
+    fn find_action_blocks(&self) -> Vec<(String, Range<usize>)> {
+        self.tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, (token, _))| *token == Token::KeywordActions)
+            .map(|(index, (_, range))| {
+                let container = self
+                    .tokens
+                    .get(index + 1)
+                    .map(|(_, it)| self.source[it.clone()].to_string())
+                    .unwrap_or_default();
+                let end = self.find_token(range.end, &Token::KeywordEndActions);
+                (container, range.start..end.end)
+            })
+            .collect()
+    }
+
+    /// returns the start of the element and `@EXTERNAL ` if the element is declared external
+    fn external_prefix(&self, start: usize) -> (usize, &'static str) {
+        let index = self.tokens.partition_point(|(_, it)| it.start < start);
+        match index.checked_sub(1).map(|it| &self.tokens[it]) {
+            Some((Token::PropertyExternal, range)) => (range.start, "@EXTERNAL "),
+            _ => (start, ""),
+        }
+    }
+
+    fn format_pou(&mut self, unit: &CompilationUnit, pou: &Pou, start: usize, prefix: &str) {
+        let implementation = unit
+            .implementations
+            .iter()
+            .find(|it| it.name == pou.name && it.pou_type != PouType::Action);
+        let methods: Vec<&Pou> = unit
+            .units
+            .iter()
+            .filter(|it| {
+                matches!(&it.pou_type, PouType::Method { owner_class } if owner_class == &pou.name)
+                    && contains(&pou.location, it.location.get_start())
+            })
+            .collect();
+        let statements = implementation
+            .filter(|_| !self.interface)
+            .map_or(&[][..], |it| it.statements.as_slice());
+
+        let end_token = self.token_before(pou.location.get_end());
+        let content_start = pou
+            .variable_blocks
+            .first()
+            .map(|it| it.location.get_start())
+            .or_else(|| methods.first().map(|it| it.location.get_start()))
+            .or_else(|| statements.first().map(|it| it.get_location().get_start()))
+            .unwrap_or(end_token.start);
+        let header = format!("{}{}", prefix, self.pou_header(pou, implementation));
+        let header_end = self.token_before(content_start).end;
+        self.write_line(start, &header, header_end);
+
+        self.block_start = true;
+        for block in &pou.variable_blocks {
+            self.format_variable_block(block, block.location.get_start(), "");
+        }
+        if !methods.is_empty() {
+            self.indent += 1;
+            for method in methods {
+                self.format_pou(unit, method, method.location.get_start(), "");
+            }
+            self.indent -= 1;
+        }
+        if !matches!(pou.pou_type, PouType::Class | PouType::Interface) {
+            self.format_block(statements, end_token.start);
+        }
+        let end_keyword = match pou.pou_type {
+            PouType::Program => "END_PROGRAM",
+            PouType::Function => "END_FUNCTION",
+            PouType::FunctionBlock => "END_FUNCTION_BLOCK",
+            PouType::Class => "END_CLASS",
+            PouType::Interface => "END_INTERFACE",
+            PouType::Method { .. } => "END_METHOD",
+            PouType::Action => "END_ACTION",
+        };
+        self.write_line(end_token.start, end_keyword, end_token.end);
+    }
+
+    fn pou_header(&self, pou: &Pou, implementation: Option<&Implementation>) -> String {
+        let mut header = match &pou.pou_type {
+            PouType::Program => "PROGRAM".to_string(),
+            PouType::Function => "FUNCTION".to_string(),
+            PouType::FunctionBlock => "FUNCTION_BLOCK".to_string(),
+            PouType::Class => "CLASS".to_string(),
+            PouType::Interface => "INTERFACE".to_string(),
+            PouType::Action => "ACTION".to_string(),
+            PouType::Method { .. } => {
+                let mut header = "METHOD".to_string();
+                match implementation.and_then(|it| it.access.as_ref()) {
+                    Some(AccessModifier::Protected) => header.push_str(" PROTECTED"),
+                    Some(AccessModifier::Private) => header.push_str(" PRIVATE"),
+                    Some(AccessModifier::Internal) => header.push_str(" INTERNAL"),
+                    //public is the default
+                    Some(AccessModifier::Public) | None => {}
+                }
+                header
+            }
+        };
+        match pou.poly_mode {
+            Some(PolymorphismMode::Abstract) => header.push_str(" ABSTRACT"),
+            Some(PolymorphismMode::Final) => header.push_str(" FINAL"),
+            Some(PolymorphismMode::None) | None => {}
+        }
+        if implementation.map_or(false, |it| it.overriding) {
+            header.push_str(" OVERRIDE");
+        }
+        //methods are named after their class
+        let name = match pou.pou_type {
+            PouType::Method { .. } => pou.name.rsplit('.').next().unwrap_or_default(),
+            _ => pou.name.as_str(),
+        };
+        header.push(' ');
+        header.push_str(name);
+        if let Some(super_class) = &pou.super_class {
+            header.push_str(" EXTENDS ");
+            header.push_str(super_class);
+        }
+        if !pou.interfaces.is_empty() {
+            header.push_str(" IMPLEMENTS ");
+            header.push_str(&pou.interfaces.join(", "));
+        }
+        if let Some(return_type) = &pou.return_type {
+            header.push_str(" : ");
+            header.push_str(&self.data_type_text(return_type));
+        }
+        header
+    }
+
+    fn format_action(
+        &mut self,
+        implementation: &Implementation,
+        name: &str,
+        start: usize,
+        prefix: &str,
+    ) {
+        let statements = if self.interface {
+            &[]
+        } else {
+            implementation.statements.as_slice()
+        };
+        let end_token = self.token_before(implementation.location.get_end());
+        let header_end = self
+            .token_before(
+                statements
+                    .first()
+                    .map_or(end_token.start, |it| it.get_location().get_start()),
+            )
+            .end;
+        self.write_line(start, &format!("{}ACTION {}", prefix, name), header_end);
+        self.format_block(statements, end_token.start);
+        self.write_line(end_token.start, "END_ACTION", end_token.end);
+    }
+
+    fn format_actions(
+        &mut self,
+        unit: &CompilationUnit,
+        container: &str,
+        range: Range<usize>,
+        start: usize,
+        prefix: &str,
+    ) {
+        let header_end = self.find_token(range.start, &Token::Identifier).end;
+        self.write_line(
+            start,
+            &format!("{}ACTIONS {}", prefix, container),
+            header_end,
+        );
+        self.block_start = true;
+        for implementation in &unit.implementations {
+            let action_start = implementation.location.get_start();
+            if implementation.pou_type == PouType::Action && range.contains(&action_start) {
+                let start = self
+                    .previous_token(action_start, &Token::KeywordAction)
+                    .start;
+                let name = implementation.name.rsplit('.').next().unwrap_or_default();
+                self.format_action(implementation, name, start, "");
+            }
+        }
+        let end_token = self.token_before(range.end);
+        self.flush_comments(end_token.start);
+        self.write_line(end_token.start, "END_ACTIONS", end_token.end);
+    }
+
+    fn format_type(&mut self, user_type: &UserTypeDeclaration, start: usize, prefix: &str) {
+        let name = user_type.data_type.get_name().unwrap_or_default();
+        let end_token = self.find_token(user_type.location.get_end(), &Token::KeywordEndType);
+        if let DataType::StructType { variables, .. } = &user_type.data_type {
+            let struct_token =
+                self.find_token(user_type.location.get_start(), &Token::KeywordStruct);
+            let header = format!("{}TYPE {} : STRUCT", prefix, name);
+            self.write_line(start, &header, struct_token.end);
+            self.format_struct_members(variables, struct_token.end);
+        } else {
+            let mut line = format!(
+                "{}TYPE {} : {}",
+                prefix,
+                name,
+                self.data_type_definition_text(&user_type.data_type)
+            );
+            if let Some(initializer) = &user_type.initializer {
+                line.push_str(" := ");
+                line.push_str(&self.expression(initializer));
+            }
+            line.push(';');
+            self.write_line(start, &line, user_type.location.get_end());
+        }
+        self.write_line(end_token.start, "END_TYPE", end_token.end);
+    }
+
+    /// prints the members of a struct and its END_STRUCT, returns the end of the END_STRUCT
+    fn format_struct_members(&mut self, variables: &[Variable], header_end: usize) -> usize {
+        self.block_start = true;
+        self.indent += 1;
+        let last_end = self.format_variables(variables).unwrap_or(header_end);
+        let end_token = self.find_token(last_end, &Token::KeywordEndStruct);
+        self.flush_comments(end_token.start);
+        self.indent -= 1;
+        self.write_line(end_token.start, "END_STRUCT", end_token.end);
+        end_token.end
+    }
+
+    fn format_configuration(&mut self, unit: &CompilationUnit, configuration: &Configuration) {
+        let start = configuration.location.get_start();
+        let header_end = self.find_token(start, &Token::Identifier).end;
+        self.write_line(
+            start,
+            &format!("CONFIGURATION {}", configuration.name),
+            header_end,
+        );
+
+        let mut elements: Vec<(usize, ConfigurationElement)> = Vec::new();
+        for block in &unit.global_vars {
+            let block_start = block.location.get_start();
+            if contains(&configuration.location, block_start)
+                && !configuration
+                    .resources
+                    .iter()
+                    .any(|it| contains(&it.location, block_start))
+            {
+                elements.push((block_start, ConfigurationElement::Globals(block)));
+            }
+        }
+        for resource in &configuration.resources {
+            elements.push((
+                resource.location.get_start(),
+                ConfigurationElement::Resource(resource),
+            ));
+        }
+        let end_token = self.format_configuration_elements(
+            unit,
+            elements,
+            header_end,
+            &Token::KeywordEndConfiguration,
+        );
+        self.write_line(end_token.start, "END_CONFIGURATION", end_token.end);
+    }
+
+    /// declares the given program instances of a configuration as global variables
+    fn format_program_instances(
+        &mut self,
+        instances: &[&ProgramConfiguration],
+        start: usize,
+        prefix: &str,
+    ) {
+        self.write_line(start, &format!("{}VAR_GLOBAL", prefix), start);
+        let width = instances
+            .iter()
+            .map(|it| it.instance_name.chars().count())
+            .max()
+            .unwrap_or_default();
+        self.block_start = true;
+        self.indent += 1;
+        let mut last_end = start;
+        for instance in instances {
+            let line = format!(
+                "{:width$} : {};",
+                instance.instance_name,
+                instance.type_name,
+                width = width
+            );
+            last_end = self.write_line(
+                instance.location.get_start(),
+                &line,
+                instance.location.get_end(),
+            );
+        }
+        self.indent -= 1;
+        self.write_line(last_end, "END_VAR", last_end);
+    }
+
+    /// prints the elements of a configuration or a resource, returns the range of the
+    /// given `end_token` closing the configuration or the resource
+    fn format_configuration_elements(
+        &mut self,
+        unit: &CompilationUnit,
+        mut elements: Vec<(usize, ConfigurationElement)>,
+        header_end: usize,
+        end_token: &Token,
+    ) -> Range<usize> {
+        elements.sort_by_key(|(start, _)| *start);
+        self.block_start = true;
+        self.indent += 1;
+        let mut last_end = header_end;
+        for (start, element) in elements {
+            last_end = match element {
+                ConfigurationElement::Globals(block) => {
+                    self.format_variable_block(block, start, "")
+                }
+                ConfigurationElement::Resource(resource) => self.format_resource(unit, resource),
+                ConfigurationElement::Task(task) => {
+                    let properties: Vec<String> = vec![
+                        task.interval
+                            .as_ref()
+                            .map(|it| format!("INTERVAL := {}", self.expression(it))),
+                        task.priority
+                            .as_ref()
+                            .map(|it| format!("PRIORITY := {}", self.expression(it))),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                    let line = format!("TASK {}({});", task.name, properties.join(", "));
+                    self.write_line(start, &line, task.location.get_end())
+                }
+                ConfigurationElement::Program(program) => {
+                    let mut line = format!("PROGRAM {}", program.instance_name);
+                    if let Some(task) = &program.task_name {
+                        line.push_str(" WITH ");
+                        line.push_str(task);
+                    }
+                    line.push_str(&format!(" : {};", program.type_name));
+                    self.write_line(start, &line, program.location.get_end())
+                }
+            };
+        }
+        let end_token = self.find_token(last_end, end_token);
+        self.flush_comments(end_token.start);
+        self.indent -= 1;
+        end_token
+    }
+
+    /// prints a RESOURCE, returns the end of its END_RESOURCE
+    fn format_resource(&mut self, unit: &CompilationUnit, resource: &Resource) -> usize {
+        let start = resource.location.get_start();
+        let on = self.find_token(start, &Token::KeywordOn);
+        let header_end = self.find_token(on.end, &Token::Identifier).end;
+        let header = format!("RESOURCE {} ON {}", resource.name, resource.target);
+        self.write_line(start, &header, header_end);
+
+        let mut elements: Vec<(usize, ConfigurationElement)> = Vec::new();
+        for block in &unit.global_vars {
+            if contains(&resource.location, block.location.get_start()) {
+                elements.push((
+                    block.location.get_start(),
+                    ConfigurationElement::Globals(block),
+                ));
+            }
+        }
+        for task in &resource.tasks {
+            elements.push((task.location.get_start(), ConfigurationElement::Task(task)));
+        }
+        for program in &resource.programs {
+            elements.push((
+                program.location.get_start(),
+                ConfigurationElement::Program(program),
+            ));
+        }
+        let end_token = self.format_configuration_elements(
+            unit,
+            elements,
+            header_end,
+            &Token::KeywordEndResource,
+        );
+        self.write_line(end_token.start, "END_RESOURCE", end_token.end)
+    }
+
+    /// prints a VAR-block, returns the end of its END_VAR
+    fn format_variable_block(
+        &mut self,
+        block: &VariableBlock,
+        start: usize,
+        prefix: &str,
+    ) -> usize {
+        let mut header = prefix.to_string();
+        header.push_str(match block.variable_block_type {
+            VariableBlockType::Local => "VAR",
+            VariableBlockType::Temp => "VAR_TEMP",
+            VariableBlockType::Input => "VAR_INPUT",
+            VariableBlockType::Output => "VAR_OUTPUT",
+            VariableBlockType::Global => "VAR_GLOBAL",
+            VariableBlockType::InOut => "VAR_IN_OUT",
+        });
+        //the modifiers are reproduced as they were written, e.g. PERSISTENT or NON_RETAIN
+        let mut header_end = block.location.get_end();
+        let mut index = self.tokens.partition_point(|(_, it)| it.start < header_end);
+        while let Some((token, range)) = self.tokens.get(index) {
+            let modifier = match token {
+                Token::KeywordConstant => "CONSTANT",
+                Token::KeywordRetain => "RETAIN",
+                Token::KeywordPersistent => "PERSISTENT",
+                Token::KeywordNonRetain => "NON_RETAIN",
+                Token::KeywordAccessPublic => "PUBLIC",
+                Token::KeywordAccessPrivate => "PRIVATE",
+                Token::KeywordAccessProtected => "PROTECTED",
+                Token::KeywordAccessInternal => "INTERNAL",
+                _ => break,
+            };
+            header.push(' ');
+            header.push_str(modifier);
+            header_end = range.end;
+            index += 1;
+        }
+        self.write_line(start, &header, header_end);
+
+        self.block_start = true;
+        self.indent += 1;
+        let last_end = self
+            .format_variables(&block.variables)
+            .unwrap_or(header_end);
+        let end_token = self.find_token(last_end, &Token::KeywordEndVar);
+        self.flush_comments(end_token.start);
+        self.indent -= 1;
+        self.write_line(end_token.start, "END_VAR", end_token.end)
+    }
+
+    /// prints the given declarations aligned on their `:`, returns the end of the last declaration
+    fn format_variables(&mut self, variables: &[Variable]) -> Option<usize> {
+        let names: Vec<String> = variables
+            .iter()
+            .map(|variable| match &variable.address {
+                Some(address) => format!("{} AT {}", variable.name, self.slice(&address.location)),
+                None => variable.name.clone(),
+            })
+            .collect();
+        let width = names
+            .iter()
+            .map(|it| it.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut last_end = None;
+        for (variable, name) in variables.iter().zip(names) {
+            let start = variable.location.get_start();
+            let end = variable
+                .initializer
+                .as_ref()
+                .map_or(variable.data_type.get_location().get_end(), |it| {
+                    it.get_location().get_end()
+                });
+            if let DataTypeDeclaration::DataTypeDefinition {
+                data_type: DataType::StructType { variables, .. },
+                location,
+            } = &variable.data_type
+            {
+                let struct_end = self
+                    .find_token(location.get_start(), &Token::KeywordStruct)
+                    .end;
+                self.write_line(start, &format!("{} : STRUCT", name), struct_end);
+                last_end = Some(self.format_struct_members(variables, struct_end));
+            } else {
+                let mut line = format!(
+                    "{:width$} : {}",
+                    name,
+                    self.data_type_text(&variable.data_type),
+                    width = width
+                );
+                if let Some(initializer) = &variable.initializer {
+                    line.push_str(" := ");
+                    line.push_str(&self.expression(initializer));
+                }
+                line.push(';');
+                last_end = Some(self.write_line(start, &line, self.skip_semicolon(end)));
+            }
+        }
+        last_end
+    }
+
+    fn data_type_text(&self, declaration: &DataTypeDeclaration) -> String {
+        match declaration {
+            DataTypeDeclaration::DataTypeReference {
+                referenced_type, ..
+            } => referenced_type.clone(),
+            DataTypeDeclaration::DataTypeDefinition { data_type, .. } => {
+                self.data_type_definition_text(data_type)
+            }
+        }
+    }
+
+    fn data_type_definition_text(&self, data_type: &DataType) -> String {
+        match data_type {
+            DataType::StructType { variables, .. } => {
+                //structs nested in other types are written on a single line
+                let members: Vec<String> = variables
+                    .iter()
+                    .map(|it| format!("{} : {}; ", it.name, self.data_type_text(&it.data_type)))
+                    .collect();
+                format!("STRUCT {}END_STRUCT", members.concat())
+            }
+            DataType::EnumType { elements, .. } => format!("({})", elements.join(", ")),
+            DataType::SubRangeType {
+                referenced_type,
+                bounds,
+                ..
+            } => match bounds {
+                Some(bounds) => format!("{}({})", referenced_type, self.expression(bounds)),
+                None => referenced_type.clone(),
+            },
+            DataType::ArrayType {
+                bounds,
+                referenced_type,
+                ..
+            } => {
+                //the dimensions of a variable-length array are parsed as EmptyStatements
+                let dimensions: Vec<String> = bounds
+                    .get_as_list()
+                    .into_iter()
+                    .map(|it| match it {
+                        AstStatement::EmptyStatement { .. } => "*".to_string(),
+                        _ => self.expression(it),
+                    })
+                    .collect();
+                format!(
+                    "ARRAY[{}] OF {}",
+                    dimensions.join(", "),
+                    self.data_type_text(referenced_type)
+                )
+            }
+            DataType::PointerType {
+                referenced_type,
+                auto_deref,
+                ..
+            } => format!(
+                "{} {}",
+                if *auto_deref {
+                    "REFERENCE TO"
+                } else {
+                    "REF_TO"
+                },
+                self.data_type_text(referenced_type)
+            ),
+            DataType::StringType { is_wide, size, .. } => {
+                let keyword = if *is_wide { "WSTRING" } else { "STRING" };
+                match size {
+                    Some(size) => format!("{}[{}]", keyword, self.expression(size)),
+                    None => keyword.to_string(),
+                }
+            }
+            DataType::VarArgs { referenced_type } => match referenced_type {
+                Some(referenced_type) => format!("{}...", self.data_type_text(referenced_type)),
+                None => "...".to_string(),
+            },
+        }
+    }
+
+    /// prints the statements of a block indented, followed by the comments before `end`
+    fn format_block(&mut self, statements: &[AstStatement], end: usize) {
+        self.block_start = true;
+        self.indent += 1;
+        for statement in statements {
+            self.format_statement(statement);
+        }
+        self.flush_comments(end);
+        self.indent -= 1;
+    }
+
+    /// prints a statement, returns its end
+    fn format_statement(&mut self, statement: &AstStatement) -> usize {
+        let location = statement.get_location();
+        match statement {
+            //`;` after an END_IF, END_FOR, RETURN, ... is parsed as an empty statement
+            AstStatement::EmptyStatement { .. } => location.get_end(),
+            AstStatement::IfStatement {
+                blocks, else_block, ..
+            } => {
+                let else_start = else_block
+                    .first()
+                    .map(|it| self.token_before(it.get_location().get_start()));
+                let end_if = self.token_before(location.get_end());
+                for (index, block) in blocks.iter().enumerate() {
+                    let condition = block.condition.get_location();
+                    let (start, keyword) = if index == 0 {
+                        (location.get_start(), "IF")
+                    } else {
+                        (self.token_before(condition.get_start()).start, "ELSIF")
+                    };
+                    let line = format!("{} {} THEN", keyword, self.expression(&block.condition));
+                    let header_end = self.skip_token(condition.get_end(), &Token::KeywordThen);
+                    self.write_line(start, &line, header_end);
+                    let block_end = blocks
+                        .get(index + 1)
+                        .map(|it| {
+                            self.token_before(it.condition.get_location().get_start())
+                                .start
+                        })
+                        .or_else(|| else_start.as_ref().map(|it| it.start))
+                        .unwrap_or(end_if.start);
+                    self.format_block(&block.body, block_end);
+                }
+                if let Some(else_token) = else_start {
+                    self.write_line(else_token.start, "ELSE", else_token.end);
+                    self.format_block(else_block, end_if.start);
+                }
+                self.write_line(end_if.start, "END_IF;", self.skip_semicolon(end_if.end))
+            }
+            AstStatement::CaseStatement {
+                selector,
+                case_blocks,
+                else_block,
+                ..
+            } => {
+                let header_end =
+                    self.skip_token(selector.get_location().get_end(), &Token::KeywordOf);
+                let line = format!("CASE {} OF", self.expression(selector));
+                self.write_line(location.get_start(), &line, header_end);
+                let else_start = else_block
+                    .first()
+                    .map(|it| self.token_before(it.get_location().get_start()));
+                let end_case = self.token_before(location.get_end());
+
+                self.block_start = true;
+                self.indent += 1;
+                for (index, block) in case_blocks.iter().enumerate() {
+                    let condition = block.condition.get_location();
+                    let line = format!("{}:", self.expression(&block.condition));
+                    let label_end = self.skip_token(condition.get_end(), &Token::KeywordColon);
+                    self.write_line(condition.get_start(), &line, label_end);
+                    let block_end = case_blocks
+                        .get(index + 1)
+                        .map(|it| it.condition.get_location().get_start())
+                        .or_else(|| else_start.as_ref().map(|it| it.start))
+                        .unwrap_or(end_case.start);
+                    self.format_block(&block.body, block_end);
+                }
+                self.indent -= 1;
+                if let Some(else_token) = else_start {
+                    self.write_line(else_token.start, "ELSE", else_token.end);
+                    self.format_block(else_block, end_case.start);
+                }
+                self.write_line(
+                    end_case.start,
+                    "END_CASE;",
+                    self.skip_semicolon(end_case.end),
+                )
+            }
+            AstStatement::ForLoopStatement {
+                counter,
+                start,
+                end,
+                by_step,
+                body,
+                ..
+            } => {
+                let mut line = format!(
+                    "FOR {} := {} TO {}",
+                    self.expression(counter),
+                    self.expression(start),
+                    self.expression(end)
+                );
+                let mut header_end = end.get_location().get_end();
+                if let Some(step) = by_step {
+                    line.push_str(&format!(" BY {}", self.expression(step)));
+                    header_end = step.get_location().get_end();
+                }
+                line.push_str(" DO");
+                let header_end = self.skip_token(header_end, &Token::KeywordDo);
+                let end_for = self.token_before(location.get_end());
+                self.format_loop(
+                    location.get_start(),
+                    &line,
+                    header_end,
+                    body,
+                    end_for,
+                    "END_FOR;",
+                )
+            }
+            AstStatement::WhileLoopStatement {
+                condition, body, ..
+            } => {
+                let line = format!("WHILE {} DO", self.expression(condition));
+                let header_end =
+                    self.skip_token(condition.get_location().get_end(), &Token::KeywordDo);
+                let end_while = self.token_before(location.get_end());
+                self.format_loop(
+                    location.get_start(),
+                    &line,
+                    header_end,
+                    body,
+                    end_while,
+                    "END_WHILE;",
+                )
+            }
+            AstStatement::RepeatLoopStatement {
+                condition, body, ..
+            } => {
+                let repeat = self.find_token(location.get_start(), &Token::KeywordRepeat);
+                self.write_line(repeat.start, "REPEAT", repeat.end);
+                let until = self.token_before(condition.get_location().get_start());
+                self.format_block(body, until.start);
+                let line = format!("UNTIL {}", self.expression(condition));
+                self.write_line(until.start, &line, condition.get_location().get_end());
+                let end_repeat =
+                    self.find_token(condition.get_location().get_end(), &Token::KeywordEndRepeat);
+                self.write_line(
+                    end_repeat.start,
+                    "END_REPEAT;",
+                    self.skip_semicolon(end_repeat.end),
+                )
+            }
+            AstStatement::ReturnStatement { .. } => self.write_line(
+                location.get_start(),
+                "RETURN;",
+                self.skip_semicolon(location.get_end()),
+            ),
+            AstStatement::ExitStatement { .. } => self.write_line(
+                location.get_start(),
+                "EXIT;",
+                self.skip_semicolon(location.get_end()),
+            ),
+            AstStatement::ContinueStatement { .. } => self.write_line(
+                location.get_start(),
+                "CONTINUE;",
+                self.skip_semicolon(location.get_end()),
+            ),
+            _ => {
+                let line = format!("{};", self.expression(statement));
+                self.write_line(
+                    location.get_start(),
+                    &line,
+                    self.skip_semicolon(location.get_end()),
+                )
+            }
+        }
+    }
+
+    /// prints a FOR or WHILE loop, returns the end of its END-keyword
+    fn format_loop(
+        &mut self,
+        start: usize,
+        header: &str,
+        header_end: usize,
+        body: &[AstStatement],
+        end_token: Range<usize>,
+        end_keyword: &str,
+    ) -> usize {
+        self.write_line(start, header, header_end);
+        self.format_block(body, end_token.start);
+        self.write_line(
+            end_token.start,
+            end_keyword,
+            self.skip_semicolon(end_token.end),
+        )
+    }
+
+    fn expression(&self, statement: &AstStatement) -> String {
+        match statement {
+            AstStatement::EmptyStatement { .. } => String::new(),
+            AstStatement::LiteralString { location, .. } => self.slice(location).to_string(),
+            AstStatement::LiteralInteger { location, .. }
+            | AstStatement::LiteralReal { location, .. }
+            | AstStatement::LiteralDate { location, .. }
+            | AstStatement::LiteralDateAndTime { location, .. }
+            | AstStatement::LiteralTimeOfDay { location, .. }
+            | AstStatement::LiteralTime { location, .. }
+            | AstStatement::DirectAccess { location, .. } => {
+                //literals are written as they were, e.g. 16#FF stays a hex-literal
+                self.slice(location).split_whitespace().collect()
+            }
+            AstStatement::LiteralBool { value, .. } => {
+                if *value { "TRUE" } else { "FALSE" }.to_string()
+            }
+            AstStatement::LiteralNull { .. } => "NULL".to_string(),
+            AstStatement::LiteralArray { elements, .. } => format!(
+                "[{}]",
+                elements
+                    .as_ref()
+                    .map(|it| self.expression(it))
+                    .unwrap_or_default()
+            ),
+            AstStatement::CastStatement {
+                target, type_name, ..
+            } => format!("{}#{}", type_name, self.expression(target)),
+            AstStatement::MultipliedStatement {
+                multiplier,
+                element,
+                ..
+            } => format!("{}({})", multiplier, self.expression(element)),
+            AstStatement::QualifiedReference { elements, .. } => elements
+                .iter()
+                .map(|it| self.expression(it))
+                .collect::<Vec<String>>()
+                .join("."),
+            AstStatement::Reference { name, .. } => name.clone(),
+            AstStatement::ArrayAccess {
+                reference, access, ..
+            } => format!(
+                "{}[{}]",
+                self.sub_expression(reference, LEAF_PRECEDENCE),
+                self.expression(access)
+            ),
+            AstStatement::PointerAccess { reference, .. } => {
+                format!("{}^", self.sub_expression(reference, LEAF_PRECEDENCE))
+            }
+            AstStatement::BinaryExpression {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                let precedence = get_precedence(statement);
+                format!(
+                    "{} {} {}",
+                    self.sub_expression(left, precedence),
+                    operator,
+                    //all binary operators are left-associative
+                    self.sub_expression(right, precedence + 1)
+                )
+            }
+            AstStatement::UnaryExpression {
+                operator, value, ..
+            } => {
+                let value = self.sub_expression(value, LEAF_PRECEDENCE);
+                match operator {
+                    Operator::Not => format!("NOT {}", value),
+                    Operator::Address => format!("&{}", value),
+                    _ => format!("{}{}", operator, value),
+                }
+            }
+            AstStatement::ExpressionList { expressions, .. } => expressions
+                .iter()
+                .map(|it| self.sub_expression(it, 1))
+                .collect::<Vec<String>>()
+                .join(", "),
+            AstStatement::RangeStatement { start, end, .. } => format!(
+                "{}..{}",
+                self.sub_expression(start, 2),
+                self.sub_expression(end, 2)
+            ),
+            AstStatement::Assignment { left, right, .. } => {
+                format!("{} := {}", self.expression(left), self.expression(right))
+            }
+            AstStatement::OutputAssignment { left, right, .. } => {
+                format!("{} => {}", self.expression(left), self.expression(right))
+            }
+            AstStatement::RefAssignment { left, right, .. } => {
+                format!("{} REF= {}", self.expression(left), self.expression(right))
+            }
+            AstStatement::CallStatement {
+                operator,
+                parameters,
+                ..
+            } => format!(
+                "{}({})",
+                self.expression(operator),
+                parameters
+                    .as_ref()
+                    .as_ref()
+                    .map(|it| self.expression(it))
+                    .unwrap_or_default()
+            ),
+            AstStatement::CaseCondition { condition, .. } => self.expression(condition),
+            //control-statements are no expressions
+            _ => self.slice(&statement.get_location()).to_string(),
+        }
+    }
+
+    /// returns the expression in parentheses if its precedence is lower than the given one
+    fn sub_expression(&self, statement: &AstStatement, precedence: u8) -> String {
+        let expression = self.expression(statement);
+        if get_precedence(statement) < precedence {
+            format!("({})", expression)
+        } else {
+            expression
+        }
+    }
+
+    /// prints a line for the element starting at `start` and ending at `end`
+    ///
+    /// the comments before `start` are printed before the line, comments following `end`
+    /// on the same line are appended to it. Returns the end of the printed line
+    fn write_line(&mut self, start: usize, text: &str, end: usize) -> usize {
+        self.flush_comments(start);
+        self.separate(start);
+        self.output.push_str(&INDENT.repeat(self.indent));
+        self.output.push_str(text);
+        let end = self.trailing_comments(end);
+        self.output.push('\n');
+        self.last_end = end;
+        self.block_start = false;
+        end
+    }
+
+    /// prints the remaining comments before the given offset on their own lines
+    fn flush_comments(&mut self, offset: usize) {
+        while let Some(comment) = self
+            .comments
+            .get(self.next_comment)
+            .filter(|it| it.location.get_start() < offset)
+        {
+            self.next_comment += 1;
+            let start = comment.location.get_start();
+            self.separate(start);
+            self.output.push_str(&INDENT.repeat(self.indent));
+            self.output.push_str(comment.text.trim_end());
+            let end = self.trailing_comments(comment.location.get_end());
+            self.output.push('\n');
+            self.last_end = end;
+            self.block_start = false;
+        }
+    }
+
+    /// appends the comments within the printed element or following it on the same line,
+    /// returns the end of the last appended comment
+    fn trailing_comments(&mut self, end: usize) -> usize {
+        let next_token = self.tokens[self.tokens.partition_point(|(_, it)| it.start < end)..]
+            .first()
+            .map_or(self.source.len(), |(_, it)| it.start);
+        let mut end = end;
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            let start = comment.location.get_start();
+            let same_line =
+                start < end || (start < next_token && !self.source[end..start].contains('\n'));
+            if !same_line {
+                break;
+            }
+            self.next_comment += 1;
+            self.output.push(' ');
+            self.output.push_str(comment.text.trim_end());
+            end = end.max(comment.location.get_end());
+        }
+        end
+    }
+
+    /// keeps one empty line if there was at least one between the last element and `offset`
+    fn separate(&mut self, offset: usize) {
+        let empty_line = self
+            .source
+            .get(self.last_end..offset)
+            .map_or(false, |it| it.matches('\n').count() > 1);
+        //the empty lines of an interface may stem from the omitted bodies
+        if empty_line && !self.block_start && !self.interface {
+            self.blank_line();
+        }
+    }
+
+    fn blank_line(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with("\n\n") {
+            self.output.push('\n');
+        }
+    }
+
+    fn slice(&self, location: &SourceRange) -> &str {
+        &self.source[location.to_range()]
+    }
+
+    /// returns the range of the first `token` at or after `offset`
+    fn find_token(&self, offset: usize, token: &Token) -> Range<usize> {
+        self.tokens[self.tokens.partition_point(|(_, it)| it.start < offset)..]
+            .iter()
+            .find(|(it, _)| it == token)
+            .map_or(offset..offset, |(_, it)| it.clone())
+    }
+
+    /// returns the range of the last `token` before `offset`
+    fn previous_token(&self, offset: usize, token: &Token) -> Range<usize> {
+        self.tokens[..self.tokens.partition_point(|(_, it)| it.start < offset)]
+            .iter()
+            .rev()
+            .find(|(it, _)| it == token)
+            .map_or(offset..offset, |(_, it)| it.clone())
+    }
+
+    /// returns the range of the last token starting before `offset`
+    fn token_before(&self, offset: usize) -> Range<usize> {
+        let index = self.tokens.partition_point(|(_, it)| it.start < offset);
+        index
+            .checked_sub(1)
+            .map_or(offset..offset, |it| self.tokens[it].1.clone())
+    }
+
+    /// returns the end of the given token if it follows `end`, otherwise `end`
+    fn skip_token(&self, end: usize, token: &Token) -> usize {
+        match self.tokens[self.tokens.partition_point(|(_, it)| it.start < end)..].first() {
+            Some((next, range)) if next == token => range.end,
+            _ => end,
+        }
+    }
+
+    fn skip_semicolon(&self, end: usize) -> usize {
+        self.skip_token(end, &Token::KeywordSemicolon)
+    }
+}
+
+fn contains(location: &SourceRange, offset: usize) -> bool {
+    location.to_range().contains(&offset)
+}
+
+/// returns the binding strength of the given expression, expressions with a lower precedence
+/// than their parent expression are put in parentheses
+fn get_precedence(statement: &AstStatement) -> u8 {
+    match statement {
+        AstStatement::ExpressionList { .. } => 0,
+        AstStatement::Assignment { .. }
+        | AstStatement::OutputAssignment { .. }
+        | AstStatement::RefAssignment { .. } => 1,
+        AstStatement::RangeStatement { .. } => 1,
+        AstStatement::BinaryExpression { operator, .. } => match operator {
+            Operator::Or => 2,
+            Operator::Xor => 3,
+            Operator::And => 4,
+            Operator::Equal | Operator::NotEqual => 5,
+            Operator::Less
+            | Operator::Greater
+            | Operator::LessOrEqual
+            | Operator::GreaterOrEqual => 6,
+            Operator::Plus | Operator::Minus => 7,
+            _ => 8,
+        },
+        AstStatement::UnaryExpression { .. } => 9,
+        _ => LEAF_PRECEDENCE,
+    }
+}
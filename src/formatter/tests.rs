@@ -0,0 +1,2 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+mod formatter_tests;
@@ -0,0 +1,447 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use pretty_assertions::assert_eq;
+
+use crate::{
+    formatter::{format, format_interface},
+    lexer::{self, IdProvider},
+    parser,
+};
+
+/// formats the given source and makes sure that formatting the result does not change it
+fn format_twice(source: &str) -> String {
+    let formatted = format(source).unwrap();
+    assert_eq!(format(&formatted).unwrap(), formatted);
+    formatted
+}
+
+#[test]
+fn keywords_are_upper_case_and_blocks_are_indented() {
+    let result = format_twice(
+        r#"
+program prg
+var
+x : int;
+longName : bool := true;
+end_var
+if x > 0 then
+x := x - 1;
+elsif x < 0 then x := 0;
+else
+x := 1;
+end_if
+end_program
+"#,
+    );
+
+    assert_eq!(
+        result,
+        r#"PROGRAM prg
+VAR
+    x        : int;
+    longName : bool := TRUE;
+END_VAR
+    IF x > 0 THEN
+        x := x - 1;
+    ELSIF x < 0 THEN
+        x := 0;
+    ELSE
+        x := 1;
+    END_IF;
+END_PROGRAM
+"#
+    );
+}
+
+#[test]
+fn loops_and_case_statements_are_indented() {
+    let result = format_twice(
+        r#"
+PROGRAM prg
+VAR i, x : INT; END_VAR
+for i := 0 to 10 by 2 do x := x + i; end_for;
+while x > 0 do x := x - 1; end_while
+repeat x := x + 1; until x > 5 end_repeat;
+case x of
+1, 2: x := 0;
+3..5: x := 1; x := 2;
+else x := 3;
+end_case
+END_PROGRAM
+"#,
+    );
+
+    assert_eq!(
+        result,
+        r#"PROGRAM prg
+VAR
+    i : INT;
+    x : INT;
+END_VAR
+    FOR i := 0 TO 10 BY 2 DO
+        x := x + i;
+    END_FOR;
+    WHILE x > 0 DO
+        x := x - 1;
+    END_WHILE;
+    REPEAT
+        x := x + 1;
+    UNTIL x > 5
+    END_REPEAT;
+    CASE x OF
+        1, 2:
+            x := 0;
+        3..5:
+            x := 1;
+            x := 2;
+    ELSE
+        x := 3;
+    END_CASE;
+END_PROGRAM
+"#
+    );
+}
+
+#[test]
+fn comments_and_pragmas_are_preserved() {
+    let result = format_twice(
+        r#"(* header comment *)
+program prg // the program
+var
+x : INT; // a counter
+  (* the flag *)
+      flag : BOOL;
+end_var
+// count up
+x:=x+1; (* done *)
+
+
+{allow(lint__assignment_without_effect)}
+  flag := flag;
+      // last words
+end_program
+// end of file
+"#,
+    );
+
+    assert_eq!(
+        result,
+        r#"(* header comment *)
+PROGRAM prg // the program
+VAR
+    x    : INT; // a counter
+    (* the flag *)
+    flag : BOOL;
+END_VAR
+    // count up
+    x := x + 1; (* done *)
+
+    {allow(lint__assignment_without_effect)}
+    flag := flag;
+    // last words
+END_PROGRAM
+// end of file
+"#
+    );
+}
+
+#[test]
+fn parentheses_are_only_kept_where_needed_and_literals_are_kept() {
+    let result = format_twice(
+        r#"
+FUNCTION foo : INT
+VAR_INPUT a, b, c : INT; END_VAR
+foo := (a + b) * c;
+foo := a - (b - c);
+foo := (a - b) - c;
+foo := -(a + b);
+foo := 16#FF + INT#2#101 + -5;
+IF NOT (a > b AND b > c) OR a = c THEN
+    RETURN;
+END_IF
+END_FUNCTION
+"#,
+    );
+
+    assert_eq!(
+        result,
+        r#"FUNCTION foo : INT
+VAR_INPUT
+    a : INT;
+    b : INT;
+    c : INT;
+END_VAR
+    foo := (a + b) * c;
+    foo := a - (b - c);
+    foo := a - b - c;
+    foo := -(a + b);
+    foo := 16#FF + INT#2#101 + -5;
+    IF NOT (a > b AND b > c) OR a = c THEN
+        RETURN;
+    END_IF;
+END_FUNCTION
+"#
+    );
+}
+
+#[test]
+fn declarations_are_aligned_and_types_are_canonical() {
+    let result = format_twice(
+        r#"
+VAR_GLOBAL CONSTANT
+  max_len : INT := 10;
+END_VAR
+VAR_GLOBAL persistent
+  name : string[max_len] := 'rusty';
+  values : array[0..max_len] of dint;
+  ptr : POINTER TO INT;
+  sensor AT %IX1.2 : BOOL;
+END_VAR
+TYPE Color : (red, green, blue); END_TYPE
+TYPE Point : STRUCT x : INT; y : INT := 0; END_STRUCT END_TYPE
+TYPE Small : INT(0..10) := 5; END_TYPE
+"#,
+    );
+
+    assert_eq!(
+        result,
+        r#"VAR_GLOBAL CONSTANT
+    max_len : INT := 10;
+END_VAR
+
+VAR_GLOBAL PERSISTENT
+    name             : STRING[max_len] := 'rusty';
+    values           : ARRAY[0..max_len] OF dint;
+    ptr              : REF_TO INT;
+    sensor AT %IX1.2 : BOOL;
+END_VAR
+
+TYPE Color : (red, green, blue);
+END_TYPE
+
+TYPE Point : STRUCT
+    x : INT;
+    y : INT := 0;
+END_STRUCT
+END_TYPE
+
+TYPE Small : INT(0..10) := 5;
+END_TYPE
+"#
+    );
+}
+
+#[test]
+fn classes_interfaces_and_actions_are_formatted() {
+    let result = format_twice(
+        r#"
+interface ICounter
+method inc : INT end_method
+end_interface
+
+function_block Counter implements ICounter
+var count : INT; end_var
+method protected inc : INT
+inc := count;
+end_method
+count := count + 1;
+end_function_block
+actions Counter
+action reset
+count := 0;
+end_action
+end_actions
+"#,
+    );
+
+    assert_eq!(
+        result,
+        r#"INTERFACE ICounter
+    METHOD inc : INT
+    END_METHOD
+END_INTERFACE
+
+FUNCTION_BLOCK Counter IMPLEMENTS ICounter
+VAR
+    count : INT;
+END_VAR
+    METHOD PROTECTED inc : INT
+        inc := count;
+    END_METHOD
+    count := count + 1;
+END_FUNCTION_BLOCK
+
+ACTIONS Counter
+ACTION reset
+    count := 0;
+END_ACTION
+END_ACTIONS
+"#
+    );
+}
+
+#[test]
+fn formatting_a_formatted_unit_does_not_change_it() {
+    let formatted = format_twice(
+        r#"
+@EXTERNAL FUNCTION puts : DINT
+VAR_INPUT text : STRING; END_VAR
+END_FUNCTION
+
+CLASS Base
+VAR x : INT; END_VAR
+METHOD get : INT get := x; END_METHOD
+END_CLASS
+
+CLASS ABSTRACT Derived EXTENDS Base
+METHOD OVERRIDE get : INT get := x + 1; END_METHOD
+END_CLASS
+
+FUNCTION_BLOCK fb
+VAR_INPUT a : INT; END_VAR
+VAR_OUTPUT b : INT; END_VAR
+b := a;
+END_FUNCTION_BLOCK
+
+FUNCTION first : INT
+VAR_IN_OUT values : ARRAY[*, *] OF INT; END_VAR
+first := values[LOWER_BOUND(values, 1), LOWER_BOUND(values, 2)];
+END_FUNCTION
+
+PROGRAM main
+VAR
+    arr : ARRAY[1..3] OF INT := [1, 2, 3];
+    p : REF_TO INT;
+    r : REFERENCE TO INT;
+    instance : fb;
+    flags : WORD;
+    t : TIME := T#1s;
+    result : INT;
+END_VAR
+    p := &arr[1];
+    r ref= arr[3];
+    result := p^ + arr[2] + r;
+    instance(a := result, b => result);
+    flags.%X1 := TRUE;
+    IF result > 0 THEN
+        IF result > 10 THEN result := 10; END_IF
+    END_IF
+END_PROGRAM
+
+ACTION main.reset
+result := 0;
+END_ACTION
+
+CONFIGURATION config
+VAR_GLOBAL g : INT; END_VAR
+RESOURCE res ON PLC
+TASK fast(INTERVAL := T#10ms, PRIORITY := 1);
+PROGRAM inst WITH fast : main;
+END_RESOURCE
+END_CONFIGURATION
+"#,
+    );
+
+    assert!(formatted.starts_with("@EXTERNAL FUNCTION puts : DINT\n"));
+    assert!(formatted.contains("\n    METHOD OVERRIDE get : INT\n        get := x + 1;\n"));
+    assert!(formatted.contains("\n    values : ARRAY[*, *] OF INT;\n"));
+    assert!(formatted.contains(" : REFERENCE TO INT;\n"));
+    assert!(formatted.contains("\n    r REF= arr[3];\n"));
+    assert!(formatted.contains("\n    instance(a := result, b => result);\n"));
+    assert!(formatted.contains("\n        IF result > 10 THEN\n            result := 10;\n"));
+    assert!(formatted.contains("\n        TASK fast(INTERVAL := T#10ms, PRIORITY := 1);\n"));
+    assert!(formatted.ends_with("    END_RESOURCE\nEND_CONFIGURATION\n"));
+}
+
+#[test]
+fn interfaces_declare_everything_external_without_bodies() {
+    let source = r#"
+(* a library *)
+VAR_GLOBAL CONSTANT max : INT := 10; END_VAR
+
+TYPE Point : STRUCT x : INT; y : INT; END_STRUCT END_TYPE
+
+FUNCTION_BLOCK Counter
+VAR_INPUT step : INT; END_VAR
+VAR count : INT; END_VAR
+count := count + step;
+END_FUNCTION_BLOCK
+
+ACTIONS Counter
+ACTION reset
+count := 0;
+END_ACTION
+END_ACTIONS
+
+FUNCTION twice : INT
+VAR_INPUT x : INT; END_VAR
+twice := 2 * x;
+END_FUNCTION
+
+CONFIGURATION config
+VAR_GLOBAL g : Counter; END_VAR
+RESOURCE res ON PLC
+TASK fast(INTERVAL := T#10ms);
+PROGRAM inst WITH fast : main;
+END_RESOURCE
+END_CONFIGURATION
+"#;
+    let (unit, _) = parser::parse(lexer::lex_with_ids(source, IdProvider::new()));
+    let result = format_interface(&unit, source);
+
+    assert_eq!(
+        result,
+        r#"@EXTERNAL VAR_GLOBAL CONSTANT
+    max : INT := 10;
+END_VAR
+
+@EXTERNAL TYPE Point : STRUCT
+    x : INT;
+    y : INT;
+END_STRUCT
+END_TYPE
+
+@EXTERNAL FUNCTION_BLOCK Counter
+VAR_INPUT
+    step : INT;
+END_VAR
+VAR
+    count : INT;
+END_VAR
+END_FUNCTION_BLOCK
+
+@EXTERNAL ACTIONS Counter
+ACTION reset
+END_ACTION
+END_ACTIONS
+
+@EXTERNAL FUNCTION twice : INT
+VAR_INPUT
+    x : INT;
+END_VAR
+END_FUNCTION
+
+@EXTERNAL VAR_GLOBAL
+    inst : main;
+END_VAR
+
+@EXTERNAL VAR_GLOBAL
+    g : Counter;
+END_VAR
+"#
+    );
+    //an interface is formatted source-code
+    assert_eq!(format(&result).unwrap(), result);
+}
+
+#[test]
+fn units_with_syntax_errors_are_not_formatted() {
+    let result = format(
+        r#"
+PROGRAM prg
+    x := 1
+END_PROGRAM
+"#,
+    );
+
+    assert!(result.is_err());
+}
@@ -2,7 +2,10 @@
 use indexmap::IndexMap;
 
 use crate::{
-    ast::{Implementation, PouType, SourceRange},
+    ast::{
+        AccessModifier, HardwareAccessType, HardwareAddress, Implementation, LinkageType,
+        PolymorphismMode, PouType, SourceRange,
+    },
     compile_error::CompileError,
     typesystem::*,
 };
@@ -21,6 +24,10 @@ pub struct VariableIndexEntry {
     pub initial_value: Option<ConstId>,
     information: VariableInformation,
     pub source_location: SourceRange,
+    /// the address of a located variable
+    hardware_address: Option<HardwareAddress>,
+    /// `External` if the variable is defined by another object (an `@EXTERNAL` declaration)
+    linkage: LinkageType,
 }
 
 pub struct MemberInfo<'b> {
@@ -29,6 +36,7 @@ pub struct MemberInfo<'b> {
     variable_linkage: VariableType,
     variable_type_name: &'b str,
     is_constant: bool,
+    access: AccessModifier,
 }
 
 impl VariableIndexEntry {
@@ -56,9 +64,53 @@ impl VariableIndexEntry {
         self.information.variable_type == VariableType::Local
     }
 
+    pub fn is_temp(&self) -> bool {
+        self.information.variable_type == VariableType::Temp
+    }
+
     pub fn is_constant(&self) -> bool {
         self.information.is_constant
     }
+
+    pub fn is_retain(&self) -> bool {
+        self.information.is_retain
+    }
+
+    pub fn get_variable_type(&self) -> &VariableType {
+        &self.information.variable_type
+    }
+
+    pub fn get_access(&self) -> &AccessModifier {
+        &self.information.access
+    }
+
+    /// returns the address of a located variable
+    pub fn get_hardware_address(&self) -> Option<&HardwareAddress> {
+        self.hardware_address.as_ref()
+    }
+
+    /// places the variable at the given address of a process image
+    pub fn set_hardware_address(&mut self, address: Option<HardwareAddress>) -> &mut Self {
+        self.hardware_address = address;
+        self
+    }
+
+    /// marks the variable as retained (declared in a RETAIN block)
+    pub fn set_retain(&mut self, is_retain: bool) -> &mut Self {
+        self.information.is_retain = is_retain;
+        self
+    }
+
+    /// returns true if the variable is defined by another object and only declared here
+    pub fn is_external(&self) -> bool {
+        self.linkage == LinkageType::External
+    }
+
+    /// marks the variable as defined by another object (`@EXTERNAL`)
+    pub fn set_linkage(&mut self, linkage: LinkageType) -> &mut Self {
+        self.linkage = linkage;
+        self
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -79,12 +131,16 @@ pub struct VariableInformation {
     variable_type: VariableType,
     /// true if this variable is a compile-time-constant
     is_constant: bool,
+    /// true if this variable keeps its value on a warm start (RETAIN)
+    is_retain: bool,
     /// the variable's datatype
     data_type_name: String,
     /// the variable's qualifier, None for global variables
     qualifier: Option<String>,
     /// Location in the qualifier defautls to 0 (Single variables)
     location: u32,
+    /// the access modifier of the variable's declaration block (PUBLIC for non-members)
+    access: AccessModifier,
 }
 
 #[derive(Debug)]
@@ -103,6 +159,7 @@ pub enum ImplementationType {
     Action,
     Class,
     Method,
+    Interface,
 }
 
 #[derive(Clone, Debug)]
@@ -111,6 +168,11 @@ pub struct ImplementationIndexEntry {
     type_name: String,
     associated_class: Option<String>,
     implementation_type: ImplementationType,
+    /// true if the implementation is a method declared OVERRIDE
+    overriding: bool,
+    /// the access modifier of a method (PUBLIC for all other implementations)
+    access: AccessModifier,
+    linkage: LinkageType,
 }
 
 impl ImplementationIndexEntry {
@@ -126,6 +188,15 @@ impl ImplementationIndexEntry {
     pub fn get_implementation_type(&self) -> &ImplementationType {
         &self.implementation_type
     }
+    pub fn is_overriding(&self) -> bool {
+        self.overriding
+    }
+    pub fn get_access(&self) -> &AccessModifier {
+        &self.access
+    }
+    pub fn is_builtin(&self) -> bool {
+        self.linkage == LinkageType::BuiltIn
+    }
 }
 
 impl From<&Implementation> for ImplementationIndexEntry {
@@ -136,6 +207,9 @@ impl From<&Implementation> for ImplementationIndexEntry {
             type_name: implementation.type_name.clone(),
             associated_class: pou_type.get_optional_owner_class(),
             implementation_type: pou_type.into(),
+            overriding: implementation.overriding,
+            access: implementation.access.unwrap_or(AccessModifier::Public),
+            linkage: implementation.linkage,
         }
     }
 }
@@ -149,10 +223,76 @@ impl From<&PouType> for ImplementationType {
             PouType::Action => ImplementationType::Action,
             PouType::Class => ImplementationType::Class,
             PouType::Method { .. } => ImplementationType::Method,
+            PouType::Interface => ImplementationType::Interface,
         }
     }
 }
 
+/// information about a declared POU (PROGRAM, FUNCTION, FUNCTION_BLOCK, CLASS, METHOD, INTERFACE)
+#[derive(Clone, Debug)]
+pub struct PouIndexEntry {
+    name: String,
+    pou_type: PouType,
+    poly_mode: Option<PolymorphismMode>,
+    super_class: Option<String>,
+    interfaces: Vec<String>,
+    /// true if the inherited members were already copied into this POU's members
+    inheritance_resolved: bool,
+    /// `External` if the POU is implemented by another object
+    linkage: LinkageType,
+}
+
+impl PouIndexEntry {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn get_pou_type(&self) -> &PouType {
+        &self.pou_type
+    }
+    pub fn get_poly_mode(&self) -> Option<&PolymorphismMode> {
+        self.poly_mode.as_ref()
+    }
+    pub fn get_super_class(&self) -> Option<&str> {
+        self.super_class.as_deref()
+    }
+    pub fn get_interfaces(&self) -> &[String] {
+        &self.interfaces
+    }
+    pub fn is_interface(&self) -> bool {
+        self.pou_type == PouType::Interface
+    }
+    pub fn is_final(&self) -> bool {
+        self.poly_mode == Some(PolymorphismMode::Final)
+    }
+    pub fn is_abstract(&self) -> bool {
+        self.poly_mode == Some(PolymorphismMode::Abstract)
+    }
+    pub fn is_external(&self) -> bool {
+        self.linkage == LinkageType::External
+    }
+
+    /// returns the name of the pointer-type used for this POU's `SUPER` reference
+    pub fn get_super_pointer_type_name(&self) -> String {
+        get_super_pointer_type_name(&self.name)
+    }
+}
+
+/// returns the name of the pointer-type pointing to the super class of the given POU
+pub fn get_super_pointer_type_name(pou_name: &str) -> String {
+    format!("__{}_super", pou_name)
+}
+
+/// the name of the member holding the referenced instance of an interface-variable
+pub const INTERFACE_INSTANCE_MEMBER: &str = "__instance";
+/// the name of the member holding the vtable of an interface-variable
+pub const INTERFACE_VTABLE_MEMBER: &str = "__vtable";
+/// the type of an interface-variable's instance pointer (an untyped pointer)
+pub const INTERFACE_INSTANCE_TYPE: &str = "__INTERFACE_INSTANCE";
+/// the type of an interface-variable's vtable pointer (a pointer to untyped function pointers)
+pub const INTERFACE_VTABLE_TYPE: &str = "__INTERFACE_VTABLE";
+/// the name of the hidden member pointing to the method table of a polymorphic class' instance
+pub const METHOD_TABLE_MEMBER: &str = "__vtable";
+
 /// the TypeIndex carries all types.
 /// it is extracted into its seaprate struct so it can be
 /// internally borrowed individually from the other maps
@@ -260,6 +400,9 @@ pub struct Index {
     /// all implementations
     implementations: IndexMap<String, ImplementationIndexEntry>,
 
+    /// all declared pous
+    pous: IndexMap<String, PouIndexEntry>,
+
     /// an index with all type-information
     type_index: TypeIndex,
 
@@ -275,6 +418,7 @@ impl Index {
             member_variables: IndexMap::new(),
             type_index: TypeIndex::new(),
             implementations: IndexMap::new(),
+            pous: IndexMap::new(),
             constant_expressions: ConstExpressions::new(),
         }
     }
@@ -343,6 +487,170 @@ impl Index {
 
         //implementations
         self.implementations.extend(other.implementations);
+
+        //pous
+        self.pous.extend(other.pous);
+        //super classes may have been declared in the imported index
+        self.resolve_inheritance();
+    }
+
+    /// copies the member variables of every resolvable super class into its derived POUs.
+    ///
+    /// the inherited members are placed in front of the POU's own members, so
+    /// the struct-layout of a derived POU starts with the layout of its super class.
+    /// POUs whose super class is not (yet) known remain unresolved.
+    pub fn resolve_inheritance(&mut self) {
+        loop {
+            let resolvable: Vec<(String, Option<String>)> = self
+                .pous
+                .values()
+                .filter(|pou| !pou.inheritance_resolved)
+                .filter_map(|pou| match pou.super_class.as_deref() {
+                    None => Some((pou.name.clone(), None)),
+                    Some(super_class) => self
+                        .find_pou(super_class)
+                        .filter(|super_pou| super_pou.inheritance_resolved)
+                        .map(|super_pou| (pou.name.clone(), Some(super_pou.name.clone()))),
+                })
+                .collect();
+
+            if resolvable.is_empty() {
+                return;
+            }
+
+            for (pou_name, super_class) in resolvable {
+                self.add_method_table_member(&pou_name, super_class.as_deref());
+                if let Some(super_class) = super_class {
+                    self.inherit_members(&pou_name, &super_class);
+                }
+                if let Some(pou) = self.pous.get_mut(&pou_name.to_lowercase()) {
+                    pou.inheritance_resolved = true;
+                }
+            }
+        }
+    }
+
+    /// adds the hidden method table member in front of the own members of the given class
+    ///
+    /// the first class or function block of an inheritance hierarchy that declares methods
+    /// gets a method table, derived POUs inherit the member. So the layout only depends on the
+    /// POU's own declaration and its super classes, POUs without methods remain untouched.
+    fn add_method_table_member(&mut self, class_name: &str, super_class: Option<&str>) {
+        let is_class = self
+            .find_pou(class_name)
+            .filter(|it| matches!(it.get_pou_type(), PouType::Class | PouType::FunctionBlock))
+            .is_some();
+        if !is_class
+            || super_class.map_or(false, |it| self.has_method_table(it))
+            || self.find_declared_methods(class_name).is_empty()
+        {
+            return;
+        }
+        self.register_vtable_types();
+
+        let own_members = self
+            .member_variables
+            .remove(&class_name.to_lowercase())
+            .unwrap_or_default();
+        let mut members = IndexMap::new();
+        members.insert(
+            METHOD_TABLE_MEMBER.to_lowercase(),
+            VariableIndexEntry {
+                name: METHOD_TABLE_MEMBER.into(),
+                qualified_name: format!("{}.{}", class_name, METHOD_TABLE_MEMBER),
+                initial_value: None,
+                source_location: SourceRange::undefined(),
+                hardware_address: None,
+                linkage: LinkageType::Internal,
+                information: VariableInformation {
+                    variable_type: VariableType::Local,
+                    data_type_name: INTERFACE_VTABLE_TYPE.into(),
+                    qualifier: Some(class_name.into()),
+                    is_constant: false,
+                    is_retain: false,
+                    location: 0,
+                    access: AccessModifier::Private,
+                },
+            },
+        );
+        for (name, mut member) in own_members {
+            member.information.location += 1;
+            members.insert(name, member);
+        }
+        self.member_variables
+            .insert(class_name.to_lowercase(), members);
+
+        if let Some(DataTypeInformation::Struct { member_names, .. }) = self
+            .type_index
+            .types
+            .get_mut(&class_name.to_lowercase())
+            .map(|it| &mut it.information)
+        {
+            member_names.insert(0, METHOD_TABLE_MEMBER.into());
+        }
+    }
+
+    /// registers the pointer types used for vtables and method tables
+    fn register_vtable_types(&mut self) {
+        //an untyped pointer to the referenced instance
+        self.register_type(
+            INTERFACE_INSTANCE_TYPE,
+            None,
+            DataTypeInformation::Pointer {
+                name: INTERFACE_INSTANCE_TYPE.into(),
+                inner_type_name: BYTE_TYPE.into(),
+                auto_deref: false,
+            },
+        );
+        //a pointer to a table of function pointers
+        self.register_type(
+            INTERFACE_VTABLE_TYPE,
+            None,
+            DataTypeInformation::Pointer {
+                name: INTERFACE_VTABLE_TYPE.into(),
+                inner_type_name: INTERFACE_INSTANCE_TYPE.into(),
+                auto_deref: false,
+            },
+        );
+    }
+
+    /// copies the members of `super_class` in front of the members of `pou_name`
+    fn inherit_members(&mut self, pou_name: &str, super_class: &str) {
+        let inherited: Vec<VariableIndexEntry> = self
+            .find_local_members(super_class)
+            .into_iter()
+            .filter(|it| !it.is_return() && !it.is_temp())
+            .cloned()
+            .collect();
+        let offset = inherited.len() as u32;
+        let inherited_names: Vec<String> = inherited.iter().map(|it| it.name.clone()).collect();
+
+        let own_members = self
+            .member_variables
+            .remove(&pou_name.to_lowercase())
+            .unwrap_or_default();
+        let mut members = IndexMap::new();
+        for mut member in inherited {
+            member.qualified_name = format!("{}.{}", pou_name, member.name);
+            member.information.qualifier = Some(pou_name.into());
+            members.insert(member.name.to_lowercase(), member);
+        }
+        for (name, mut member) in own_members {
+            member.information.location += offset;
+            members.insert(name, member);
+        }
+        self.member_variables
+            .insert(pou_name.to_lowercase(), members);
+
+        if let Some(DataTypeInformation::Struct { member_names, .. }) = self
+            .type_index
+            .types
+            .get_mut(&pou_name.to_lowercase())
+            .map(|it| &mut it.information)
+        {
+            let own_names = std::mem::replace(member_names, inherited_names);
+            member_names.extend(own_names);
+        }
     }
 
     /// imports the corresponding const-expression (according to the given initializer-id) from the given ConstExpressions
@@ -434,6 +742,28 @@ impl Index {
             .is_some()
     }
 
+    /// returns the VAR_INPUT, VAR_IN_OUT or VAR_OUTPUT that receives the `position`-th nameless
+    /// argument of a call. Hidden and inherited members may precede the declared parameters,
+    /// so the position does not necessarily match the parameter's location.
+    pub fn find_declared_parameter(
+        &self,
+        pou_name: &str,
+        position: u32,
+    ) -> Option<&VariableIndexEntry> {
+        let mut parameters: Vec<&VariableIndexEntry> = self
+            .find_local_members(pou_name)
+            .into_iter()
+            .filter(|item| {
+                matches!(
+                    item.information.variable_type,
+                    VariableType::Input | VariableType::InOut | VariableType::Output
+                )
+            })
+            .collect();
+        parameters.sort_by_key(|it| it.information.location);
+        parameters.get(position as usize).copied()
+    }
+
     pub fn find_input_parameter(&self, pou_name: &str, index: u32) -> Option<&VariableIndexEntry> {
         self.member_variables
             .get(&pou_name.to_lowercase())
@@ -586,6 +916,17 @@ impl Index {
         globals.chain(members)
     }
 
+    /// returns the size in bytes of the given process image, the end of the
+    /// located variable with the highest address in this image
+    pub fn get_process_image_size(&self, area: HardwareAccessType) -> u32 {
+        self.get_all_variable_entries()
+            .filter_map(VariableIndexEntry::get_hardware_address)
+            .filter(|it| it.area == area)
+            .map(|it| it.get_byte_offset() + it.get_byte_size())
+            .max()
+            .unwrap_or_default()
+    }
+
     pub fn get_global_qualified_enums(&self) -> &IndexMap<String, VariableIndexEntry> {
         &self.enum_qualified_variables
     }
@@ -594,26 +935,215 @@ impl Index {
         &self.implementations
     }
 
-    pub fn register_implementation(
+    pub fn register_implementation(&mut self, implementation: ImplementationIndexEntry) {
+        self.implementations
+            .insert(implementation.call_name.to_lowercase(), implementation);
+    }
+
+    /// returns true if the given name refers to a builtin function
+    pub fn is_builtin(&self, call_name: &str) -> bool {
+        self.find_implementation(call_name)
+            .map_or(false, ImplementationIndexEntry::is_builtin)
+    }
+
+    pub fn find_implementation(&self, call_name: &str) -> Option<&ImplementationIndexEntry> {
+        self.implementations.get(&call_name.to_lowercase())
+    }
+
+    pub fn register_pou(
         &mut self,
-        call_name: &str,
-        type_name: &str,
-        associated_class_name: Option<&String>,
-        impl_type: ImplementationType,
+        name: &str,
+        pou_type: PouType,
+        poly_mode: Option<PolymorphismMode>,
+        super_class: Option<&str>,
+        interfaces: &[String],
+        linkage: LinkageType,
     ) {
-        self.implementations.insert(
-            call_name.to_lowercase(),
-            ImplementationIndexEntry {
-                call_name: call_name.into(),
-                type_name: type_name.into(),
-                associated_class: associated_class_name.map(|str| str.into()),
-                implementation_type: impl_type,
+        self.pous.insert(
+            name.to_lowercase(),
+            PouIndexEntry {
+                name: name.into(),
+                pou_type,
+                poly_mode,
+                super_class: super_class.map(|it| it.into()),
+                interfaces: interfaces.to_vec(),
+                inheritance_resolved: false,
+                linkage,
             },
         );
     }
 
-    pub fn find_implementation(&self, call_name: &str) -> Option<&ImplementationIndexEntry> {
-        self.implementations.get(&call_name.to_lowercase())
+    pub fn find_pou(&self, pou_name: &str) -> Option<&PouIndexEntry> {
+        self.pous.get(&pou_name.to_lowercase())
+    }
+
+    pub fn get_pous(&self) -> &IndexMap<String, PouIndexEntry> {
+        &self.pous
+    }
+
+    /// returns the name of the POU the given POU EXTENDS
+    pub fn find_super_class(&self, pou_name: &str) -> Option<&str> {
+        self.find_pou(pou_name)
+            .and_then(PouIndexEntry::get_super_class)
+    }
+
+    /// returns true if the given class is the class `base_class` or (directly or indirectly)
+    /// EXTENDS it
+    pub fn is_same_or_derived_class(&self, class_name: &str, base_class: &str) -> bool {
+        let mut current = Some(class_name);
+        let mut visited = vec![];
+        while let Some(class_name) = current {
+            if class_name.eq_ignore_ascii_case(base_class) {
+                return true;
+            }
+            if visited.contains(&class_name.to_lowercase()) {
+                //cyclic inheritance
+                return false;
+            }
+            visited.push(class_name.to_lowercase());
+            current = self.find_super_class(class_name);
+        }
+        false
+    }
+
+    /// returns the name of the class that declares the member `member_name` of the given pou.
+    /// Inherited members are declared by the topmost super class that contains them.
+    pub fn find_declaring_class<'b>(&'b self, pou_name: &'b str, member_name: &str) -> &'b str {
+        let mut declaring_class = pou_name;
+        let mut visited = vec![pou_name.to_lowercase()];
+        while let Some(super_class) = self.find_super_class(declaring_class) {
+            if visited.contains(&super_class.to_lowercase())
+                || self.find_member(super_class, member_name).is_none()
+            {
+                break;
+            }
+            visited.push(super_class.to_lowercase());
+            declaring_class = super_class;
+        }
+        declaring_class
+    }
+
+    /// returns the method-POUs declared in the given class, function block or interface
+    /// in the order of their declaration
+    pub fn find_declared_methods(&self, pou_name: &str) -> Vec<&PouIndexEntry> {
+        self.pous
+            .values()
+            .filter(|it| {
+                matches!(&it.pou_type,
+                    PouType::Method { owner_class } if owner_class.eq_ignore_ascii_case(pou_name))
+            })
+            .collect()
+    }
+
+    /// returns all interfaces implemented by the given class or function block,
+    /// including the ones implemented by its super classes
+    pub fn find_implemented_interfaces(&self, pou_name: &str) -> Vec<&str> {
+        let mut interfaces: Vec<&str> = vec![];
+        let mut visited = vec![];
+        let mut current = self.find_pou(pou_name);
+        while let Some(pou) = current {
+            if visited.contains(&pou.name.to_lowercase()) {
+                //cyclic inheritance
+                break;
+            }
+            visited.push(pou.name.to_lowercase());
+
+            for interface in pou.get_interfaces() {
+                if !interfaces
+                    .iter()
+                    .any(|it| it.eq_ignore_ascii_case(interface))
+                {
+                    interfaces.push(interface.as_str());
+                }
+            }
+            current = pou.get_super_class().and_then(|it| self.find_pou(it));
+        }
+        interfaces
+    }
+
+    /// returns the interface that declares the given method, or None if
+    /// the given implementation is not an interface's method
+    pub fn find_interface_of_method(
+        &self,
+        implementation: &ImplementationIndexEntry,
+    ) -> Option<&PouIndexEntry> {
+        implementation
+            .get_associated_class_name()
+            .and_then(|it| self.find_pou(it))
+            .filter(|it| it.is_interface())
+    }
+
+    /// returns true if instances of the given class or function block carry a method table
+    pub fn has_method_table(&self, pou_name: &str) -> bool {
+        self.find_pou(pou_name)
+            .filter(|it| matches!(it.get_pou_type(), PouType::Class | PouType::FunctionBlock))
+            .and_then(|_| self.find_member(pou_name, METHOD_TABLE_MEMBER))
+            .is_some()
+    }
+
+    /// returns the entries of the given class' method table.
+    ///
+    /// the table starts with the methods of the root class, followed by the methods introduced
+    /// by every derived class down to the given class, so the table of a super class is always
+    /// a prefix of its derived classes' tables. Every entry is the most derived implementation.
+    pub fn find_method_table(&self, class_name: &str) -> Vec<&ImplementationIndexEntry> {
+        let mut hierarchy: Vec<&PouIndexEntry> = vec![];
+        let mut current = self.find_pou(class_name);
+        while let Some(pou) = current {
+            if hierarchy
+                .iter()
+                .any(|it| it.name.eq_ignore_ascii_case(&pou.name))
+            {
+                //cyclic inheritance
+                break;
+            }
+            hierarchy.push(pou);
+            current = pou.get_super_class().and_then(|it| self.find_pou(it));
+        }
+
+        let mut method_names: Vec<&str> = vec![];
+        for pou in hierarchy.iter().rev() {
+            for method in self.find_declared_methods(pou.get_name()) {
+                let method_name = method.get_name().rsplit('.').next().unwrap_or_default();
+                if !method_names
+                    .iter()
+                    .any(|it| it.eq_ignore_ascii_case(method_name))
+                {
+                    method_names.push(method_name);
+                }
+            }
+        }
+        method_names
+            .iter()
+            .filter_map(|it| self.find_method(class_name, it))
+            .collect()
+    }
+
+    /// returns the implementation of the method `method_name` of the given class
+    /// or of the nearest super class that declares it
+    pub fn find_method(
+        &self,
+        class_name: &str,
+        method_name: &str,
+    ) -> Option<&ImplementationIndexEntry> {
+        let mut current = Some(class_name);
+        let mut visited = vec![];
+        while let Some(class_name) = current {
+            if visited.contains(&class_name.to_lowercase()) {
+                //cyclic inheritance
+                return None;
+            }
+            visited.push(class_name.to_lowercase());
+
+            let method = self
+                .find_implementation(format!("{}.{}", class_name, method_name).as_str())
+                .filter(|it| it.get_implementation_type() == &ImplementationType::Method);
+            if method.is_some() {
+                return method;
+            }
+            current = self.find_super_class(class_name);
+        }
+        None
     }
 
     /// registers a member-variable of a container to be accessed in a qualified name.
@@ -632,7 +1162,7 @@ impl Index {
         initial_value: Option<ConstId>,
         source_location: SourceRange,
         location: u32,
-    ) {
+    ) -> &mut VariableIndexEntry {
         let container_name = member_info.container_name;
         let variable_name = member_info.variable_name;
         let variable_linkage = member_info.variable_linkage;
@@ -650,15 +1180,20 @@ impl Index {
             qualified_name,
             initial_value,
             source_location,
+            hardware_address: None,
+            linkage: LinkageType::Internal,
             information: VariableInformation {
                 variable_type: variable_linkage,
                 data_type_name: variable_type_name.into(),
                 qualifier: Some(container_name.into()),
                 is_constant: member_info.is_constant,
+                is_retain: false,
                 location,
+                access: member_info.access,
             },
         };
-        members.insert(variable_name.to_lowercase(), entry);
+        let (index, _) = members.insert_full(variable_name.to_lowercase(), entry);
+        &mut members[index]
     }
 
     pub fn register_enum_element(
@@ -667,6 +1202,7 @@ impl Index {
         enum_type_name: &str,
         initial_value: Option<ConstId>,
         source_location: SourceRange,
+        linkage: LinkageType,
     ) {
         let qualified_name = format!("{}.{}", enum_type_name, element_name);
         let entry = VariableIndexEntry {
@@ -674,12 +1210,16 @@ impl Index {
             qualified_name: qualified_name.clone(),
             initial_value,
             source_location,
+            hardware_address: None,
+            linkage,
             information: VariableInformation {
                 variable_type: VariableType::Global,
                 data_type_name: enum_type_name.into(),
                 is_constant: true,
+                is_retain: false,
                 qualifier: None,
                 location: 0,
+                access: AccessModifier::Public,
             },
         };
         self.enum_global_variables
@@ -696,7 +1236,7 @@ impl Index {
         initial_value: Option<ConstId>,
         is_constant: bool,
         source_location: SourceRange,
-    ) {
+    ) -> &mut VariableIndexEntry {
         self.register_global_variable_with_name(
             name,
             name,
@@ -704,7 +1244,7 @@ impl Index {
             initial_value,
             is_constant,
             source_location,
-        );
+        )
     }
 
     pub fn register_global_variable_with_name(
@@ -715,7 +1255,7 @@ impl Index {
         initial_value: Option<ConstId>,
         is_constant: bool,
         source_location: SourceRange,
-    ) {
+    ) -> &mut VariableIndexEntry {
         //REVIEW, this seems like a misuse of the qualified name to store the association name. Any other ideas?
         // If we do enough mental gymnastic, we could say that a Qualified name is how you would find a unique id for a variable, which the association name is.
         let qualified_name = association_name.into();
@@ -724,16 +1264,22 @@ impl Index {
             qualified_name,
             initial_value,
             source_location,
+            hardware_address: None,
+            linkage: LinkageType::Internal,
             information: VariableInformation {
                 variable_type: VariableType::Global,
                 data_type_name: type_name.into(),
                 qualifier: None,
                 is_constant,
+                is_retain: false,
                 location: 0,
+                access: AccessModifier::Public,
             },
         };
-        self.global_variables
-            .insert(association_name.to_lowercase(), entry);
+        let (index, _) = self
+            .global_variables
+            .insert_full(association_name.to_lowercase(), entry);
+        &mut self.global_variables[index]
     }
 
     pub fn print_global_variables(&self) {
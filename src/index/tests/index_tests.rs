@@ -5,7 +5,11 @@ use crate::lexer;
 use crate::parser;
 use crate::parser::tests::literal_int;
 use crate::typesystem::TypeSize;
-use crate::{ast::*, index::VariableType, typesystem::DataTypeInformation};
+use crate::{
+    ast::*,
+    index::{Index, VariableType},
+    typesystem::DataTypeInformation,
+};
 
 macro_rules! index {
     ($code:tt) => {{
@@ -224,6 +228,297 @@ fn class_methods_are_indexed() {
     }
 }
 
+#[test]
+fn inherited_members_are_indexed_in_front_of_own_members() {
+    let index = index!(
+        r#"
+        CLASS base
+            VAR x, y : INT; END_VAR
+        END_CLASS
+
+        CLASS derived EXTENDS base
+            VAR z : DINT; END_VAR
+        END_CLASS
+
+        CLASS derived2 EXTENDS derived
+            VAR a : BOOL; END_VAR
+        END_CLASS
+    "#
+    );
+
+    let members: Vec<(&str, &str, u32)> = index
+        .find_local_members("derived2")
+        .iter()
+        .map(|it| {
+            (
+                it.get_name(),
+                it.get_qualified_name(),
+                it.get_location_in_parent(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("x", "derived2.x", 0),
+            ("y", "derived2.y", 1),
+            ("z", "derived2.z", 2),
+            ("a", "derived2.a", 3),
+        ],
+        members
+    );
+
+    let info = index.get_type("derived").unwrap().get_type_information();
+    if let DataTypeInformation::Struct { member_names, .. } = info {
+        assert_eq!(&vec!["x", "y", "z"], member_names);
+    } else {
+        panic!("Wrong variant : {:#?}", info);
+    }
+
+    assert_eq!(Some("derived"), index.find_super_class("derived2"));
+}
+
+#[test]
+fn temporary_variables_are_not_inherited() {
+    let index = index!(
+        r#"
+        FUNCTION_BLOCK base
+            VAR x : INT; END_VAR
+            VAR_TEMP t : INT; END_VAR
+        END_FUNCTION_BLOCK
+
+        FUNCTION_BLOCK derived EXTENDS base
+            VAR y : INT; END_VAR
+        END_FUNCTION_BLOCK
+    "#
+    );
+
+    assert!(index.find_member("derived", "t").is_none());
+    let x = index.find_member("derived", "x").unwrap();
+    let y = index.find_member("derived", "y").unwrap();
+    assert_eq!(x.get_location_in_parent() + 1, y.get_location_in_parent());
+}
+
+#[test]
+fn inherited_methods_can_be_found() {
+    let index = index!(
+        r#"
+        CLASS base
+            METHOD foo END_METHOD
+            METHOD bar END_METHOD
+        END_CLASS
+
+        CLASS derived EXTENDS base
+            METHOD bar END_METHOD
+        END_CLASS
+    "#
+    );
+
+    assert_eq!(
+        "base.foo",
+        index.find_method("derived", "foo").unwrap().call_name
+    );
+    assert_eq!(
+        "derived.bar",
+        index.find_method("derived", "bar").unwrap().call_name
+    );
+    assert!(index.find_method("derived", "baz").is_none());
+}
+
+#[test]
+fn method_tables_contain_the_most_derived_methods() {
+    let index = index!(
+        r#"
+        CLASS base
+            METHOD foo END_METHOD
+            METHOD bar END_METHOD
+        END_CLASS
+
+        CLASS derived EXTENDS base
+            METHOD baz END_METHOD
+            METHOD OVERRIDE foo END_METHOD
+        END_CLASS
+
+        CLASS standalone
+            METHOD foo END_METHOD
+        END_CLASS
+    "#
+    );
+
+    let method_table = |class_name: &str| -> Vec<String> {
+        index
+            .find_method_table(class_name)
+            .iter()
+            .map(|it| it.call_name.clone())
+            .collect()
+    };
+    assert_eq!(vec!["base.foo", "base.bar"], method_table("base"));
+    assert_eq!(
+        vec!["derived.foo", "base.bar", "derived.baz"],
+        method_table("derived")
+    );
+
+    //every class declaring methods carries a method table, whether it is extended or not
+    assert!(index.has_method_table("base"));
+    assert!(index.has_method_table("derived"));
+    assert!(index.has_method_table("standalone"));
+    assert_eq!(
+        0,
+        index
+            .find_member("base", "__vtable")
+            .unwrap()
+            .get_location_in_parent()
+    );
+}
+
+#[test]
+fn the_method_table_is_placed_in_the_first_class_declaring_methods() {
+    let index = index!(
+        r#"
+        FUNCTION_BLOCK plain
+            VAR a : INT; END_VAR
+        END_FUNCTION_BLOCK
+
+        FUNCTION_BLOCK base EXTENDS plain
+            VAR b : INT; END_VAR
+            METHOD foo END_METHOD
+        END_FUNCTION_BLOCK
+
+        FUNCTION_BLOCK derived EXTENDS base
+            VAR c : INT; END_VAR
+            METHOD bar END_METHOD
+        END_FUNCTION_BLOCK
+    "#
+    );
+
+    let members = |pou: &str| -> Vec<(&str, u32)> {
+        index
+            .find_local_members(pou)
+            .iter()
+            .map(|it| (it.get_name(), it.get_location_in_parent()))
+            .collect()
+    };
+    //the layout of a POU without methods is not changed by the POUs extending it
+    assert_eq!(vec![("a", 0)], members("plain"));
+    assert_eq!(vec![("a", 0), ("__vtable", 1), ("b", 2)], members("base"));
+    assert_eq!(
+        vec![("a", 0), ("__vtable", 1), ("b", 2), ("c", 3)],
+        members("derived")
+    );
+    assert!(!index.has_method_table("plain"));
+    assert!(index.has_method_table("derived"));
+}
+
+#[test]
+fn access_modifiers_of_members_and_methods_are_indexed() {
+    let mut index = Index::new();
+    index.import(index!(
+        r#"
+        CLASS base
+            VAR PRIVATE a : INT; END_VAR
+            VAR b : INT; END_VAR
+            METHOD PROTECTED foo END_METHOD
+            METHOD bar END_METHOD
+        END_CLASS
+
+        CLASS derived EXTENDS base
+            VAR PROTECTED c : INT; END_VAR
+        END_CLASS
+    "#
+    ));
+
+    let access = |member: &str| *index.find_member("derived", member).unwrap().get_access();
+    //members and methods without an access modifier are public
+    assert_eq!(AccessModifier::Private, access("a"));
+    assert_eq!(AccessModifier::Public, access("b"));
+    assert_eq!(AccessModifier::Protected, access("c"));
+    assert_eq!(
+        &AccessModifier::Protected,
+        index.find_method("derived", "foo").unwrap().get_access()
+    );
+    assert_eq!(
+        &AccessModifier::Public,
+        index.find_method("derived", "bar").unwrap().get_access()
+    );
+
+    //inherited members are declared by their super class
+    assert_eq!("base", index.find_declaring_class("derived", "a"));
+    assert_eq!("derived", index.find_declaring_class("derived", "c"));
+    assert!(index.is_same_or_derived_class("derived", "base"));
+    assert!(!index.is_same_or_derived_class("base", "derived"));
+}
+
+#[test]
+fn super_classes_are_resolved_after_import() {
+    let mut index = index!(
+        r#"
+        FUNCTION_BLOCK derived EXTENDS base
+            VAR_INPUT b : INT; END_VAR
+        END_FUNCTION_BLOCK
+    "#
+    );
+    //the super class is not known yet
+    assert_eq!(1, index.find_local_members("derived").len());
+
+    index.import(index!(
+        r#"
+        FUNCTION_BLOCK base
+            VAR_INPUT a : INT; END_VAR
+        END_FUNCTION_BLOCK
+    "#
+    ));
+
+    //the inherited members are placed in front of the own members
+    let a = index.find_member("derived", "a").unwrap();
+    assert_eq!(0, a.get_location_in_parent());
+    let b = index.find_member("derived", "b").unwrap();
+    assert_eq!(1, b.get_location_in_parent());
+}
+
+#[test]
+fn interfaces_are_indexed() {
+    let index = index!(
+        r#"
+        INTERFACE I1
+            METHOD foo : INT END_METHOD
+            METHOD bar END_METHOD
+        END_INTERFACE
+
+        INTERFACE I2
+        END_INTERFACE
+
+        CLASS base IMPLEMENTS I1
+            METHOD foo : INT END_METHOD
+            METHOD bar END_METHOD
+        END_CLASS
+
+        CLASS derived EXTENDS base IMPLEMENTS I2, I1
+        END_CLASS
+    "#
+    );
+
+    assert!(index.find_pou("I1").unwrap().is_interface());
+    let methods: Vec<&str> = index
+        .find_declared_methods("I1")
+        .iter()
+        .map(|it| it.get_name())
+        .collect();
+    assert_eq!(vec!["I1.foo", "I1.bar"], methods);
+
+    //an interface-variable holds the instance and its vtable
+    let info = index.get_type("I1").unwrap().get_type_information();
+    if let DataTypeInformation::Struct { member_names, .. } = info {
+        assert_eq!(&vec!["__instance", "__vtable"], member_names);
+    } else {
+        panic!("Wrong variant : {:#?}", info);
+    }
+
+    assert_eq!(vec!["I1"], index.find_implemented_interfaces("base"));
+    assert_eq!(
+        vec!["I2", "I1"],
+        index.find_implemented_interfaces("derived")
+    );
+}
+
 #[test]
 fn function_is_indexed() {
     let index = index!(
@@ -731,6 +1026,7 @@ fn pre_processing_generates_inline_structs_global() {
                 },
                 location: (54..55).into(),
                 initializer: None,
+                address: None,
             }]
         },
         new_struct_type
@@ -820,6 +1116,7 @@ fn pre_processing_generates_inline_structs() {
                 },
                 location: (67..68).into(),
                 initializer: None,
+                address: None,
             }]
         },
         new_struct_type
@@ -862,9 +1159,11 @@ fn pre_processing_generates_inline_pointers() {
                 referenced_type: "INT".to_string(),
                 location: SourceRange::undefined(),
             }),
+            auto_deref: false,
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_pointer_type));
 
@@ -901,9 +1200,11 @@ fn pre_processing_generates_pointer_to_pointer_type() {
                 referenced_type: "INT".to_string(),
                 location: SourceRange::undefined(),
             }),
+            auto_deref: false,
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_pointer_type));
 
@@ -916,9 +1217,11 @@ fn pre_processing_generates_pointer_to_pointer_type() {
                 referenced_type: "__pointer_to_pointer".to_string(),
                 location: SourceRange::undefined(),
             }),
+            auto_deref: false,
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", original));
 }
@@ -950,9 +1253,11 @@ fn pre_processing_generates_inline_pointer_to_pointer() {
                 referenced_type: "INT".to_string(),
                 location: SourceRange::undefined(),
             }),
+            auto_deref: false,
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_pointer_type));
 
@@ -965,9 +1270,11 @@ fn pre_processing_generates_inline_pointer_to_pointer() {
                 referenced_type: "__foo_inline_pointer_".to_string(),
                 location: SourceRange::undefined(),
             }),
+            auto_deref: false,
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_pointer_type));
 
@@ -1023,6 +1330,7 @@ fn pre_processing_generates_inline_arrays() {
         },
         initializer: None,
         location: (59..77).into(),
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_array_type));
 
@@ -1080,6 +1388,7 @@ fn pre_processing_generates_inline_array_of_array() {
         },
         initializer: None,
         location: (59..92).into(),
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_array_type));
 
@@ -1108,6 +1417,7 @@ fn pre_processing_generates_inline_array_of_array() {
         },
         initializer: None,
         location: (59..92).into(),
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_array_type));
 
@@ -1150,6 +1460,7 @@ fn pre_processing_generates_array_of_array_type() {
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_type));
 
@@ -1170,6 +1481,7 @@ fn pre_processing_generates_array_of_array_type() {
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", original));
 }
@@ -1211,10 +1523,12 @@ fn pre_processing_nested_array_in_struct() {
                 },
                 location: SourceRange::undefined(),
                 initializer: None,
+                address: None,
             }],
         },
         initializer: None,
         location: (14..97).into(),
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_array_type));
 
@@ -1243,6 +1557,7 @@ fn pre_processing_nested_array_in_struct() {
         },
         initializer: None,
         location: (59..77).into(),
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_array_type));
 }
@@ -1290,6 +1605,7 @@ fn pre_processing_generates_inline_array_of_array_of_array() {
         },
         initializer: None,
         location: (74..107).into(),
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_array_type));
 
@@ -1318,6 +1634,7 @@ fn pre_processing_generates_inline_array_of_array_of_array() {
         },
         initializer: None,
         location: (59..107).into(),
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_array_type));
 
@@ -1346,6 +1663,7 @@ fn pre_processing_generates_inline_array_of_array_of_array() {
         },
         initializer: None,
         location: (59..107).into(),
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:?}", expected), format!("{:?}", new_array_type));
 
@@ -1632,3 +1950,33 @@ fn string_dimensions_are_stored_in_the_const_expression_arena() {
         unreachable!()
     }
 }
+
+#[test]
+fn retained_variables_are_marked_in_the_index() {
+    // GIVEN RETAIN, PERSISTENT and NON_RETAIN blocks
+    let index = index!(
+        "
+        VAR_GLOBAL RETAIN
+            a : INT;
+        END_VAR
+        VAR_GLOBAL
+            b : INT;
+        END_VAR
+
+        PROGRAM prg
+        VAR PERSISTENT
+            c : INT;
+        END_VAR
+        VAR NON_RETAIN
+            d : INT;
+        END_VAR
+        END_PROGRAM
+        "
+    );
+
+    // THEN only the variables of RETAIN and PERSISTENT blocks are retained
+    assert!(index.find_global_variable("a").unwrap().is_retain());
+    assert!(!index.find_global_variable("b").unwrap().is_retain());
+    assert!(index.find_member("prg", "c").unwrap().is_retain());
+    assert!(!index.find_member("prg", "d").unwrap().is_retain());
+}
@@ -1,11 +1,15 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use super::VariableType;
 use crate::ast::{
-    self, AstStatement, CompilationUnit, DataType, DataTypeDeclaration, Implementation, Pou,
-    PouType, SourceRange, UserTypeDeclaration, Variable, VariableBlock, VariableBlockType,
+    self, AccessModifier, AstStatement, CompilationUnit, Configuration, DataType,
+    DataTypeDeclaration, Implementation, Pou, PouType, SourceRange, UserTypeDeclaration, Variable,
+    VariableBlock, VariableBlockType,
 };
 use crate::compile_error::CompileError;
-use crate::index::{Index, MemberInfo};
+use crate::index::{
+    get_super_pointer_type_name, Index, MemberInfo, INTERFACE_INSTANCE_MEMBER,
+    INTERFACE_INSTANCE_TYPE, INTERFACE_VTABLE_MEMBER, INTERFACE_VTABLE_TYPE,
+};
 use crate::typesystem::{self, *};
 
 pub fn visit(unit: &CompilationUnit) -> Index {
@@ -35,32 +39,69 @@ pub fn visit(unit: &CompilationUnit) -> Index {
     for implementation in &unit.implementations {
         visit_implementation(&mut index, implementation);
     }
+
+    //Create the program instances of all configurations
+    for configuration in &unit.configurations {
+        visit_configuration(&mut index, configuration);
+    }
+
+    //copy inherited members into derived POUs
+    index.resolve_inheritance();
     index
 }
 
 pub fn visit_pou(index: &mut Index, pou: &Pou) {
     let interface_name = format!("{}_interface", &pou.name);
 
+    index.register_pou(
+        &pou.name,
+        pou.pou_type.clone(),
+        pou.poly_mode.clone(),
+        pou.super_class.as_deref(),
+        &pou.interfaces,
+        pou.linkage,
+    );
+
+    if let Some(super_class) = &pou.super_class {
+        //register a pointer type for the SUPER reference
+        let type_name = get_super_pointer_type_name(&pou.name);
+        index.register_type(
+            &type_name,
+            None,
+            DataTypeInformation::Pointer {
+                name: type_name.clone(),
+                inner_type_name: super_class.clone(),
+                auto_deref: false,
+            },
+        );
+    }
+
     if pou.pou_type == PouType::Program {
         //Associate a global variable for the program
         let instance_name = format!("{}_instance", &pou.name);
-        index.register_global_variable_with_name(
-            &pou.name,
-            &instance_name,
-            &pou.name,
-            None,
-            false, //program's instance variable is no constant
-            pou.location.clone(),
-        );
+        index
+            .register_global_variable_with_name(
+                &pou.name,
+                &instance_name,
+                &pou.name,
+                None,
+                false, //program's instance variable is no constant
+                pou.location.clone(),
+            )
+            .set_linkage(pou.linkage);
     }
 
     let mut member_names = vec![];
 
     //register the pou's member variables
+    //temporary variables are not part of the pou's instance, they are registered last
+    let (temp_blocks, blocks): (Vec<&VariableBlock>, Vec<&VariableBlock>) = pou
+        .variable_blocks
+        .iter()
+        .partition(|it| it.variable_block_type == VariableBlockType::Temp);
     let mut count = 0;
     let mut varargs = None;
-    for block in &pou.variable_blocks {
-        let block_type = get_variable_type_from_block(block);
+    for block in blocks {
         for var in &block.variables {
             if let DataTypeDeclaration::DataTypeDefinition {
                 data_type: ast::DataType::VarArgs { referenced_type },
@@ -77,37 +118,16 @@ pub fn visit_pou(index: &mut Index, pou: &Pou) {
                 continue;
             }
             member_names.push(var.name.clone());
-
-            let type_name = if block_type == VariableType::InOut {
-                //register a pointer type for the var_in_out
-                register_inout_pointer_type_for(index, var)
-            } else {
-                var.data_type.get_name().unwrap().to_string()
-            };
-            let initial_value = index
-                .get_mut_const_expressions()
-                .maybe_add_constant_expression(
-                    var.initializer.clone(),
-                    type_name.as_str(),
-                    Some(pou.name.clone()),
-                );
-
-            index.register_member_variable(
-                &MemberInfo {
-                    container_name: &pou.name,
-                    variable_name: &var.name,
-                    variable_linkage: block_type,
-                    variable_type_name: &type_name,
-                    is_constant: block.constant,
-                },
-                initial_value,
-                var.location.clone(),
-                count,
-            );
+            visit_pou_member(index, pou, block, var, count);
             count += 1;
         }
     }
 
+    //an interface-variable is a reference to an instance and its vtable
+    if pou.pou_type == PouType::Interface {
+        register_interface_members(index, pou, &mut member_names);
+    }
+
     //register a function's return type as a member variable
     if let Some(return_type) = &pou.return_type {
         member_names.push(pou.get_return_name().into());
@@ -119,11 +139,20 @@ pub fn visit_pou(index: &mut Index, pou: &Pou) {
                 variable_linkage: VariableType::Return,
                 variable_type_name: return_type.get_name().unwrap_or_default(),
                 is_constant: false, //return variables are not constants
+                access: AccessModifier::Public,
             },
             None,
             source_location,
             count,
-        )
+        );
+        count += 1;
+    }
+
+    for block in temp_blocks {
+        for var in &block.variables {
+            visit_pou_member(index, pou, block, var, count);
+            count += 1;
+        }
     }
 
     index.register_type(
@@ -138,14 +167,70 @@ pub fn visit_pou(index: &mut Index, pou: &Pou) {
     );
 }
 
+/// registers a global variable for every program instance of the configuration's resources
+///
+/// an instance with the name of its program refers to the program's default instance
+fn visit_configuration(index: &mut Index, configuration: &Configuration) {
+    let instances = configuration
+        .resources
+        .iter()
+        .flat_map(|it| it.programs.iter())
+        .filter(|it| !it.instance_name.eq_ignore_ascii_case(&it.type_name));
+    for instance in instances {
+        index.register_global_variable(
+            &instance.instance_name,
+            &instance.type_name,
+            None,
+            false,
+            instance.location.clone(),
+        );
+    }
+}
+
+/// registers the given variable of the given block as a member variable of the pou
+fn visit_pou_member(
+    index: &mut Index,
+    pou: &Pou,
+    block: &VariableBlock,
+    var: &Variable,
+    count: u32,
+) {
+    let block_type = get_variable_type_from_block(block);
+    let type_name = if block_type == VariableType::InOut {
+        //register a pointer type for the var_in_out
+        register_inout_pointer_type_for(index, var)
+    } else {
+        var.data_type.get_name().unwrap().to_string()
+    };
+    let initial_value = index
+        .get_mut_const_expressions()
+        .maybe_add_constant_expression(
+            var.initializer.clone(),
+            type_name.as_str(),
+            Some(pou.name.clone()),
+        );
+
+    index
+        .register_member_variable(
+            &MemberInfo {
+                container_name: &pou.name,
+                variable_name: &var.name,
+                variable_linkage: block_type,
+                variable_type_name: &type_name,
+                is_constant: block.constant,
+                access: block.access,
+            },
+            initial_value,
+            var.location.clone(),
+            count,
+        )
+        .set_hardware_address(var.address.clone())
+        .set_retain(block.retain);
+}
+
 fn visit_implementation(index: &mut Index, implementation: &Implementation) {
     let pou_type = &implementation.pou_type;
-    index.register_implementation(
-        &implementation.name,
-        &implementation.type_name,
-        pou_type.get_optional_owner_class().as_ref(),
-        pou_type.into(),
-    );
+    index.register_implementation(implementation.into());
     //if we are registing an action, also register a datatype for it
     if pou_type == &PouType::Action {
         index.register_type(
@@ -159,6 +244,31 @@ fn visit_implementation(index: &mut Index, implementation: &Implementation) {
     }
 }
 
+fn register_interface_members(index: &mut Index, pou: &Pou, member_names: &mut Vec<String>) {
+    index.register_vtable_types();
+
+    let members = [
+        (INTERFACE_INSTANCE_MEMBER, INTERFACE_INSTANCE_TYPE),
+        (INTERFACE_VTABLE_MEMBER, INTERFACE_VTABLE_TYPE),
+    ];
+    for (location, &(member_name, member_type)) in members.iter().enumerate() {
+        member_names.push(member_name.to_string());
+        index.register_member_variable(
+            &MemberInfo {
+                container_name: &pou.name,
+                variable_name: member_name,
+                variable_linkage: VariableType::Local,
+                variable_type_name: member_type,
+                is_constant: false,
+                access: AccessModifier::Public,
+            },
+            None,
+            pou.location.clone(),
+            location as u32,
+        );
+    }
+}
+
 fn register_inout_pointer_type_for(index: &mut Index, var: &Variable) -> String {
     let inner_type_name = var.data_type.get_name().unwrap().to_string();
     //get unique name
@@ -178,19 +288,31 @@ fn register_inout_pointer_type_for(index: &mut Index, var: &Variable) -> String
     type_name
 }
 
+/// returns true if the given array-bounds declare a variable-length array (`ARRAY[*, *]`)
+fn is_variable_length(bounds: &AstStatement) -> bool {
+    bounds
+        .get_as_list()
+        .iter()
+        .all(|it| matches!(it, AstStatement::EmptyStatement { .. }))
+}
+
 fn visit_global_var_block(index: &mut Index, block: &VariableBlock) {
     for var in &block.variables {
         let target_type = var.data_type.get_name().unwrap_or_default();
         let initializer = index
             .get_mut_const_expressions()
             .maybe_add_constant_expression(var.initializer.clone(), target_type, None);
-        index.register_global_variable(
-            &var.name,
-            var.data_type.get_name().unwrap(),
-            initializer,
-            block.constant,
-            var.location.clone(),
-        );
+        index
+            .register_global_variable(
+                &var.name,
+                var.data_type.get_name().unwrap(),
+                initializer,
+                block.constant,
+                var.location.clone(),
+            )
+            .set_hardware_address(var.address.clone())
+            .set_retain(block.retain)
+            .set_linkage(block.linkage);
     }
 }
 
@@ -240,6 +362,7 @@ fn visit_data_type(index: &mut Index, type_declatation: &UserTypeDeclaration) {
                             data_type: data_type.clone(),
                             initializer: None,
                             location: SourceRange::undefined(),
+                            linkage: type_declatation.linkage,
                         },
                     )
                 }
@@ -255,6 +378,7 @@ fn visit_data_type(index: &mut Index, type_declatation: &UserTypeDeclaration) {
                         variable_linkage: VariableType::Local,
                         variable_type_name: member_type,
                         is_constant: false, //struct members are not constants //TODO thats probably not true (you can define a struct in an CONST-block?!)
+                        access: AccessModifier::Public,
                     },
                     init,
                     var.location.clone(),
@@ -294,6 +418,7 @@ fn visit_data_type(index: &mut Index, type_declatation: &UserTypeDeclaration) {
                     enum_name.as_str(),
                     Some(init),
                     SourceRange::undefined(),
+                    type_declatation.linkage,
                 )
             }); //TODO : Enum locations
         }
@@ -326,6 +451,18 @@ fn visit_data_type(index: &mut Index, type_declatation: &UserTypeDeclaration) {
                 );
             index.register_type(name.as_ref().unwrap(), init, information)
         }
+        DataType::ArrayType {
+            name,
+            referenced_type,
+            bounds,
+        } if is_variable_length(bounds) => {
+            let information = DataTypeInformation::VariableLengthArray {
+                name: name.as_ref().unwrap().clone(),
+                inner_type_name: referenced_type.get_name().unwrap().to_string(),
+                dimension_count: bounds.get_as_list().len(),
+            };
+            index.register_type(name.as_ref().unwrap(), None, information)
+        }
         DataType::ArrayType {
             name,
             referenced_type,
@@ -381,13 +518,13 @@ fn visit_data_type(index: &mut Index, type_declatation: &UserTypeDeclaration) {
         DataType::PointerType {
             name,
             referenced_type,
-            ..
+            auto_deref,
         } => {
             let inner_type_name = referenced_type.get_name().unwrap();
             let information = DataTypeInformation::Pointer {
                 name: name.as_ref().unwrap().clone(),
                 inner_type_name: inner_type_name.into(),
-                auto_deref: false,
+                auto_deref: *auto_deref,
             };
 
             let init = index
@@ -1,6 +1,5 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use core::ops::Range;
-use logos::Filter;
 use logos::Lexer;
 use logos::Logos;
 use std::sync::atomic::AtomicUsize;
@@ -9,8 +8,10 @@ use std::sync::Arc;
 pub use tokens::Token;
 
 use crate::ast::AstId;
+use crate::ast::Comment;
 use crate::ast::DirectAccessType;
 use crate::ast::SourceRange;
+use crate::ast::Suppression;
 use crate::Diagnostic;
 
 #[cfg(test)]
@@ -27,6 +28,10 @@ pub struct ParseSession<'a> {
     /// the range of the `last_token`
     pub last_range: Range<usize>,
     pub parse_progress: usize,
+    /// the `{allow(...)}` pragmas skipped while parsing
+    pub suppressions: Vec<Suppression>,
+    /// the comments and pragmas skipped while parsing
+    pub comments: Vec<Comment>,
     id_provider: IdProvider,
 }
 
@@ -54,6 +59,8 @@ impl<'a> ParseSession<'a> {
             last_token: Token::End,
             last_range: 0..0,
             parse_progress: 0,
+            suppressions: vec![],
+            comments: vec![],
             id_provider,
         };
         lexer.advance();
@@ -112,8 +119,25 @@ impl<'a> ParseSession<'a> {
 
     pub fn advance(&mut self) {
         self.last_range = self.range();
-        self.last_token =
-            std::mem::replace(&mut self.token, self.lexer.next().unwrap_or(Token::End));
+        let mut next = self.lexer.next().unwrap_or(Token::End);
+        //comments and pragmas are not part of the ast, they are remembered for the formatter
+        while next == Token::Pragma || next == Token::Comment {
+            let location = SourceRange::new(self.lexer.span());
+            if next == Token::Pragma {
+                if let Some(codes) = parse_allow_pragma(self.lexer.slice()) {
+                    self.suppressions.push(Suppression {
+                        codes,
+                        location: location.clone(),
+                    });
+                }
+            }
+            self.comments.push(Comment {
+                text: self.lexer.slice().to_string(),
+                location,
+            });
+            next = self.lexer.next().unwrap_or(Token::End);
+        }
+        self.last_token = std::mem::replace(&mut self.token, next);
         self.parse_progress += 1;
 
         match self.token {
@@ -137,7 +161,9 @@ impl<'a> ParseSession<'a> {
             | Token::KeywordEndFor
             | Token::KeywordEndRepeat
             | Token::KeywordEndMethod
-            | Token::KeywordEndClass => {
+            | Token::KeywordEndClass
+            | Token::KeywordEndConfiguration
+            | Token::KeywordEndResource => {
                 if !self.slice().to_string().contains('_') {
                     self.accept_diagnostic(Diagnostic::ImprovementSuggestion {
                         message: format!(
@@ -246,7 +272,8 @@ impl<'a> ParseSession<'a> {
     }
 }
 
-fn parse_comments(lexer: &mut Lexer<Token>) -> Filter<()> {
+/// consumes a (nested) block comment, returns false if the comment is not closed
+fn parse_comments(lexer: &mut Lexer<Token>) -> bool {
     let (open, close) = get_closing_tag(lexer.slice());
     let remainder = lexer.remainder();
     let mut unclosed = 1;
@@ -268,11 +295,29 @@ fn parse_comments(lexer: &mut Lexer<Token>) -> Filter<()> {
         traversed += c.len_utf8();
         if unclosed == 0 {
             lexer.bump(traversed);
-            //This is a well formed comment, treat it as whitespace
-            return Filter::Skip;
+            //This is a well formed comment
+            return true;
         }
     }
-    Filter::Emit(())
+    false
+}
+
+/// returns the codes of an `{allow(<code>, ...)}` pragma, or None for any other pragma
+fn parse_allow_pragma(pragma: &str) -> Option<Vec<String>> {
+    let content = pragma.trim_start_matches('{').trim_end_matches('}').trim();
+    let prefix = content.get(..5)?;
+    if !prefix.eq_ignore_ascii_case("allow") {
+        return None;
+    }
+    let codes = content[5..]
+        .trim()
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .split(',')
+        .map(|it| it.trim().to_string())
+        .filter(|it| !it.is_empty())
+        .collect();
+    Some(codes)
 }
 
 fn get_closing_tag(open_tag: &str) -> (char, char) {
@@ -2,7 +2,7 @@
 use pretty_assertions::{assert_eq, assert_ne};
 
 use crate::{
-    ast::{DirectAccessType, SourceRange},
+    ast::{Comment, DirectAccessType, SourceRange, Suppression},
     lexer::{ParseSession, Token::*},
 };
 
@@ -48,6 +48,34 @@ fn comments_are_ignored_by_the_lexer() {
     lexer.advance();
 }
 
+#[test]
+fn comments_and_pragmas_are_remembered_by_the_lexer() {
+    let mut lexer = lex("PROGRAM (* a *) {external} // b\nEND_PROGRAM");
+    assert_eq!(lexer.token, KeywordProgram, "Token : {}", lexer.slice());
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordEndProgram, "Token : {}", lexer.slice());
+    lexer.advance();
+    assert_eq!(lexer.token, End, "Token : {}", lexer.slice());
+
+    assert_eq!(
+        lexer.comments,
+        vec![
+            Comment {
+                text: "(* a *)".to_string(),
+                location: (8..15).into(),
+            },
+            Comment {
+                text: "{external}".to_string(),
+                location: (16..26).into(),
+            },
+            Comment {
+                text: "// b".to_string(),
+                location: (27..31).into(),
+            },
+        ]
+    );
+}
+
 #[test]
 fn comments_are_not_ignored_in_strings() {
     let mut lexer = lex(r#"
@@ -135,6 +163,47 @@ fn action_tokens() {
     assert_eq!(lexer.token, KeywordEndActions);
 }
 
+#[test]
+fn configuration_tokens() {
+    let mut lexer = lex("CONFIGURATION RESOURCE ON TASK WITH END_RESOURCE END_CONFIGURATION");
+    assert_eq!(lexer.token, KeywordConfiguration);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordResource);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordOn);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordTask);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordWith);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordEndResource);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordEndConfiguration);
+}
+
+#[test]
+fn hardware_address_tokens() {
+    let mut lexer = lex("AT %IX1.2 %QW4 %md10 %I3 %MX1.2.3");
+    assert_eq!(lexer.token, KeywordAt);
+    lexer.advance();
+    assert_eq!(lexer.token, HardwareAccess);
+    assert_eq!(lexer.slice(), "%IX1.2");
+    lexer.advance();
+    assert_eq!(lexer.token, HardwareAccess);
+    assert_eq!(lexer.slice(), "%QW4");
+    lexer.advance();
+    assert_eq!(lexer.token, HardwareAccess);
+    assert_eq!(lexer.slice(), "%md10");
+    lexer.advance();
+    assert_eq!(lexer.token, HardwareAccess);
+    assert_eq!(lexer.slice(), "%I3");
+    lexer.advance();
+    assert_eq!(lexer.token, HardwareAccess);
+    assert_eq!(lexer.slice(), "%MX1.2.3");
+    lexer.advance();
+    assert_eq!(lexer.token, End);
+}
+
 #[test]
 fn var_tokens() {
     let mut lexer = lex("VAR VAR_INPUT VAR_OUTPUT VAR_GLOBAL VAR_IN_OUT END_VAR");
@@ -605,6 +674,37 @@ fn pointers_and_references_keyword() {
     lexer.advance();
 }
 
+#[test]
+fn reference_keywords() {
+    let mut lexer = lex(r#"
+    REFERENCE TO x
+    r REF= x
+    r ref= REF(x)
+    "#);
+
+    assert_eq!(lexer.token, KeywordReference);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordTo);
+    lexer.advance();
+    assert_eq!(lexer.slice(), "x");
+    lexer.advance();
+    assert_eq!(lexer.slice(), "r");
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordRefAssignment);
+    lexer.advance();
+    assert_eq!(lexer.slice(), "x");
+    lexer.advance();
+    assert_eq!(lexer.slice(), "r");
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordRefAssignment);
+    lexer.advance();
+    assert_eq!(lexer.token, Identifier);
+    assert_eq!(lexer.slice(), "REF");
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordParensOpen);
+    lexer.advance();
+}
+
 #[test]
 fn direct_access_test() {
     let mut lexer = lex(r"
@@ -682,6 +782,36 @@ fn multi_named_keywords_without_underscore_test() {
     assert_eq!(d2.get_location(), SourceRange::new(191..200));
 }
 
+#[test]
+fn pragmas_are_skipped_and_allow_pragmas_remembered() {
+    let mut lexer = lex(r"
+        PROGRAM {attribute 'hide'} {allow(lint__unused_variable, lint__shadowed_global)}
+        END_PROGRAM {ALLOW ( lint__unused_input )}
+        ");
+    assert_eq!(lexer.token, KeywordProgram, "Token : {}", lexer.slice());
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordEndProgram, "Token : {}", lexer.slice());
+    lexer.advance();
+    assert_eq!(lexer.token, End, "Token : {}", lexer.slice());
+
+    assert_eq!(
+        lexer.suppressions,
+        vec![
+            Suppression {
+                codes: vec![
+                    "lint__unused_variable".to_string(),
+                    "lint__shadowed_global".to_string()
+                ],
+                location: SourceRange::new(36..89),
+            },
+            Suppression {
+                codes: vec!["lint__unused_input".to_string()],
+                location: SourceRange::new(110..140),
+            },
+        ]
+    );
+}
+
 #[test]
 fn lowercase_keywords_accepted() {
     let mut result = lex(r###"
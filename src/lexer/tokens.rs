@@ -5,15 +5,20 @@ use crate::ast::DirectAccessType;
 #[derive(Debug, PartialEq, Logos, Clone)]
 pub enum Token {
     #[error]
-    #[regex(r"\(\*", |lex| super::parse_comments(lex))]
-    #[regex(r"/\*", |lex| super::parse_comments(lex))]
-    #[regex(r"//.*", logos::skip)]
     #[regex(r"(?m)\r", logos::skip)]
     Error,
 
+    #[regex(r"\(\*", |lex| super::parse_comments(lex))]
+    #[regex(r"/\*", |lex| super::parse_comments(lex))]
+    #[regex(r"//.*")]
+    Comment,
+
     #[token("@EXTERNAL")]
     PropertyExternal,
 
+    #[regex(r"\{[^}]*\}")]
+    Pragma,
+
     #[token("PROGRAM", ignore(case))]
     KeywordProgram,
 
@@ -50,6 +55,9 @@ pub enum Token {
     #[token("RETAIN", ignore(case))]
     KeywordRetain,
 
+    #[token("PERSISTENT", ignore(case))]
+    KeywordPersistent,
+
     #[token("NON_RETAIN", ignore(case))]
     #[token("NONRETAIN", ignore(case))]
     KeywordNonRetain,
@@ -77,6 +85,19 @@ pub enum Token {
     #[token("OVERRIDE", ignore(case))]
     KeywordOverride,
 
+    #[token("EXTENDS", ignore(case))]
+    KeywordExtends,
+
+    #[token("IMPLEMENTS", ignore(case))]
+    KeywordImplements,
+
+    #[token("INTERFACE", ignore(case))]
+    KeywordInterface,
+
+    #[token("END_INTERFACE", ignore(case))]
+    #[token("ENDINTERFACE", ignore(case))]
+    KeywordEndInterface,
+
     #[token("VAR_GLOBAL", ignore(case))]
     #[token("VARGLOBAL", ignore(case))]
     KeywordVarGlobal,
@@ -122,6 +143,32 @@ pub enum Token {
     #[token("ENDSTRUCT", ignore(case))]
     KeywordEndStruct,
 
+    #[token("CONFIGURATION", ignore(case))]
+    KeywordConfiguration,
+
+    #[token("END_CONFIGURATION", ignore(case))]
+    #[token("ENDCONFIGURATION", ignore(case))]
+    KeywordEndConfiguration,
+
+    #[token("RESOURCE", ignore(case))]
+    KeywordResource,
+
+    #[token("END_RESOURCE", ignore(case))]
+    #[token("ENDRESOURCE", ignore(case))]
+    KeywordEndResource,
+
+    #[token("ON", ignore(case))]
+    KeywordOn,
+
+    #[token("TASK", ignore(case))]
+    KeywordTask,
+
+    #[token("WITH", ignore(case))]
+    KeywordWith,
+
+    #[token("AT", ignore(case))]
+    KeywordAt,
+
     #[token("ACTIONS", ignore(case))]
     KeywordActions,
 
@@ -148,6 +195,9 @@ pub enum Token {
     #[token("=>")]
     KeywordOutputAssignment,
 
+    #[token("REF=", ignore(case))]
+    KeywordRefAssignment,
+
     #[token("(")]
     KeywordParensOpen,
 
@@ -241,6 +291,9 @@ pub enum Token {
     #[token("REFTO", ignore(case))]
     KeywordRef,
 
+    #[token("REFERENCE", ignore(case))]
+    KeywordReference,
+
     #[token("ARRAY", ignore(case))]
     KeywordArray,
 
@@ -353,6 +406,9 @@ pub enum Token {
     #[regex("%(B|b|D|d|W|w|X|x)", super::parse_access_type)]
     DirectAccess(DirectAccessType),
 
+    #[regex("%(I|i|Q|q|M|m)(X|x|B|b|W|w|D|d|L|l)?\\d+(\\.\\d+)*")]
+    HardwareAccess,
+
     #[regex("'((\\$.)|[^$'])*'")]
     LiteralString,
 
@@ -21,33 +21,38 @@ use std::fs;
 use std::ops::Range;
 use std::path::Path;
 
-use ast::{PouType, SourceRange};
-use codespan_reporting::diagnostic::{self, Label};
-use codespan_reporting::files::SimpleFiles;
-use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
-use codespan_reporting::term::{self, Chars, Styles};
+use ast::{AccessModifier, PouType, SourceRange};
 use compile_error::CompileError;
+use diagnostician::Diagnostician;
 use encoding_rs::Encoding;
 use encoding_rs_io::DecodeReaderBytesBuilder;
 use index::Index;
 use inkwell::context::Context;
+use inkwell::module::Module;
+use inkwell::passes::{PassManager, PassManagerBuilder};
 use inkwell::targets::{
     CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple,
 };
 use lexer::IdProvider;
 use std::{fs::File, io::Read};
-use validation::Validator;
+use validation::{Linter, Validator};
 
 use crate::ast::CompilationUnit;
 use crate::resolver::{AnnotationMap, TypeAnnotator};
+pub mod analysis;
 mod ast;
+mod builtins;
 pub mod cli;
 mod codegen;
 pub mod compile_error;
+pub mod diagnostician;
+pub mod formatter;
 pub mod index;
 mod lexer;
 mod parser;
 mod resolver;
+mod stdlib;
+pub mod test_clock;
 mod typesystem;
 mod validation;
 
@@ -65,6 +70,20 @@ pub enum Diagnostic {
         message: String,
         range: SourceRange,
     },
+    /// a lint: valid code that most likely does not do what was intended
+    Warning {
+        message: String,
+        range: SourceRange,
+        err_no: ErrNo,
+    },
+}
+
+/// the severity of a diagnostic
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
 }
 
 #[allow(non_camel_case_types)]
@@ -82,15 +101,37 @@ pub enum ErrNo {
     pou__missing_return_type,
     pou__unexpected_return_type,
     pou__empty_variable_block,
+    pou__invalid_super_class,
+    pou__extends_final,
+    pou__cyclic_inheritance,
+    pou__invalid_interface,
+    pou__missing_interface_method,
+    pou__interface_method_mismatch,
+    pou__invalid_override,
+    pou__overrides_final,
+    pou__abstract_instance,
+    pou__unimplemented_abstract_method,
+
+    //configuration related
+    config__invalid_program,
+    config__unknown_task,
 
     //variable related
     var__unresolved_constant,
     var__invalid_constant_block,
+    var__invalid_retain_block,
     var__invalid_constant,
     var__cannot_assign_to_const,
+    var__invalid_hardware_address,
+    var__incompatible_hardware_address,
+    var__invalid_located_variable,
+    var__invalid_variable_length_array,
 
     //reference related
     reference__unresolved,
+    reference__illegal_access,
+    reference__invalid_usage,
+    reference__unbound,
 
     //type related
     type__literal_out_of_range,
@@ -99,9 +140,40 @@ pub enum ErrNo {
     type__incompatible_directaccess_variable,
     type__incompatible_directaccess_range,
     type__expected_literal,
+    type__invalid_nature,
+    type__incompatible_assignment,
+    type__interface_not_implemented,
+    type__invalid_condition,
+
+    //call related
+    call__incompatible_argument,
+    call__too_many_arguments,
+    call__invalid_output_assignment,
+
+    //codegen related
+    codegen__generic_error,
+
+    //lints
+    lint__unused_variable,
+    lint__unused_input,
+    lint__assignment_without_effect,
+    lint__shadowed_global,
+    lint__implicit_narrowing,
 }
 
 impl Diagnostic {
+    /// reports an error raised during code generation
+    pub fn codegen_error(error: &CompileError) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: error.to_string(),
+            range: error
+                .get_location()
+                .cloned()
+                .unwrap_or_else(SourceRange::undefined),
+            err_no: ErrNo::codegen__generic_error,
+        }
+    }
+
     pub fn syntax_error(message: &str, range: SourceRange) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: message.to_string(),
@@ -172,6 +244,41 @@ impl Diagnostic {
         }
     }
 
+    pub fn illegal_access(
+        member: &str,
+        access: &AccessModifier,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Illegal access to {} member '{}'",
+                format!("{:?}", access).to_uppercase(),
+                member
+            ),
+            range: location,
+            err_no: ErrNo::reference__illegal_access,
+        }
+    }
+
+    pub fn invalid_reference_usage(reason: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("Invalid use of a reference: {}", reason),
+            range: location,
+            err_no: ErrNo::reference__invalid_usage,
+        }
+    }
+
+    pub fn unbound_reference(reference: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Reference '{:}' is used before it is bound, bind it with REF= first",
+                reference
+            ),
+            range: location,
+            err_no: ErrNo::reference__unbound,
+        }
+    }
+
     pub fn incompatible_directaccess(
         access_type: &str,
         access_size: u64,
@@ -252,6 +359,98 @@ impl Diagnostic {
         }
     }
 
+    pub fn invalid_type_nature(actual: &str, expected: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Invalid type nature for generic argument. {} is no {}.",
+                actual, expected
+            ),
+            range: location,
+            err_no: ErrNo::type__invalid_nature,
+        }
+    }
+
+    pub fn incompatible_assignment(
+        source_type: &str,
+        target_type: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Invalid assignment: cannot assign '{:}' to '{:}'",
+                source_type, target_type
+            ),
+            range: location,
+            err_no: ErrNo::type__incompatible_assignment,
+        }
+    }
+
+    pub fn interface_not_implemented(
+        source_type: &str,
+        interface: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Invalid assignment: '{:}' does not implement the interface '{:}'",
+                source_type, interface
+            ),
+            range: location,
+            err_no: ErrNo::type__interface_not_implemented,
+        }
+    }
+
+    pub fn invalid_condition(actual_type: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Invalid condition: expected a BOOL but found '{:}'",
+                actual_type
+            ),
+            range: location,
+            err_no: ErrNo::type__invalid_condition,
+        }
+    }
+
+    pub fn incompatible_argument(
+        argument_type: &str,
+        parameter_name: &str,
+        parameter_type: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Invalid argument: cannot pass '{:}' to parameter '{:}' of type '{:}'",
+                argument_type, parameter_name, parameter_type
+            ),
+            range: location,
+            err_no: ErrNo::call__incompatible_argument,
+        }
+    }
+
+    pub fn too_many_arguments(
+        pou_name: &str,
+        expected: usize,
+        actual: usize,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Too many arguments: '{:}' expects {:} arguments but {:} were passed",
+                pou_name, expected, actual
+            ),
+            range: location,
+            err_no: ErrNo::call__too_many_arguments,
+        }
+    }
+
+    pub fn invalid_output_assignment(reason: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("Invalid output assignment: {:}", reason),
+            range: location,
+            err_no: ErrNo::call__invalid_output_assignment,
+        }
+    }
+
     pub fn empty_variable_block(location: SourceRange) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: "Variable block is empty".into(),
@@ -278,6 +477,155 @@ impl Diagnostic {
         }
     }
 
+    pub fn invalid_super_class(
+        pou_name: &str,
+        super_class: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "'{:}' cannot extend '{:}' - a class can only extend a class, a function block only a function block",
+                pou_name, super_class
+            ),
+            range: location,
+            err_no: ErrNo::pou__invalid_super_class,
+        }
+    }
+
+    pub fn cannot_extend_final(
+        pou_name: &str,
+        super_class: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("'{:}' cannot extend FINAL '{:}'", pou_name, super_class),
+            range: location,
+            err_no: ErrNo::pou__extends_final,
+        }
+    }
+
+    pub fn cyclic_inheritance(
+        pou_name: &str,
+        super_class: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "'{:}' cannot extend '{:}' - '{:}' is derived from '{:}'",
+                pou_name, super_class, super_class, pou_name
+            ),
+            range: location,
+            err_no: ErrNo::pou__cyclic_inheritance,
+        }
+    }
+
+    pub fn invalid_interface(pou_name: &str, interface: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "'{:}' cannot implement '{:}' - it is not an interface",
+                pou_name, interface
+            ),
+            range: location,
+            err_no: ErrNo::pou__invalid_interface,
+        }
+    }
+
+    pub fn missing_interface_method(
+        pou_name: &str,
+        interface: &str,
+        method: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "'{:}' does not implement method '{:}' of interface '{:}'",
+                pou_name, method, interface
+            ),
+            range: location,
+            err_no: ErrNo::pou__missing_interface_method,
+        }
+    }
+
+    pub fn interface_method_mismatch(
+        method: &str,
+        interface_method: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Method '{:}' does not match the declaration of '{:}'",
+                method, interface_method
+            ),
+            range: location,
+            err_no: ErrNo::pou__interface_method_mismatch,
+        }
+    }
+
+    pub fn invalid_override(method: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Method '{:}' is declared OVERRIDE but there is no such method in a super class",
+                method
+            ),
+            range: location,
+            err_no: ErrNo::pou__invalid_override,
+        }
+    }
+
+    pub fn cannot_override_final(
+        method: &str,
+        final_method: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Method '{:}' cannot override FINAL method '{:}'",
+                method, final_method
+            ),
+            range: location,
+            err_no: ErrNo::pou__overrides_final,
+        }
+    }
+
+    pub fn abstract_instance(class_name: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("Cannot instantiate ABSTRACT '{:}'", class_name),
+            range: location,
+            err_no: ErrNo::pou__abstract_instance,
+        }
+    }
+
+    pub fn unimplemented_abstract_method(
+        pou_name: &str,
+        method: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "'{:}' does not implement ABSTRACT method '{:}'",
+                pou_name, method
+            ),
+            range: location,
+            err_no: ErrNo::pou__unimplemented_abstract_method,
+        }
+    }
+
+    pub fn invalid_program_instance(type_name: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("'{:}' is not a PROGRAM", type_name),
+            range: location,
+            err_no: ErrNo::config__invalid_program,
+        }
+    }
+
+    pub fn unknown_task(task_name: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("Unknown TASK '{:}'", task_name),
+            range: location,
+            err_no: ErrNo::config__unknown_task,
+        }
+    }
+
     pub fn invalid_constant_block(location: SourceRange) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: "This variable block does not support the CONSTANT modifier".to_string(),
@@ -286,6 +634,60 @@ impl Diagnostic {
         }
     }
 
+    pub fn invalid_retain_block(location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: "This variable block does not support the RETAIN modifier".to_string(),
+            range: location,
+            err_no: ErrNo::var__invalid_retain_block,
+        }
+    }
+
+    pub fn invalid_hardware_address(address: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("Invalid address '{:}'", address),
+            range: location,
+            err_no: ErrNo::var__invalid_hardware_address,
+        }
+    }
+
+    pub fn incompatible_hardware_address(
+        address: &str,
+        type_name: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Address '{:}' is incompatible with type '{:}'",
+                address, type_name
+            ),
+            range: location,
+            err_no: ErrNo::var__incompatible_hardware_address,
+        }
+    }
+
+    pub fn invalid_located_variable(
+        variable_name: &str,
+        reason: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("Invalid located variable '{:}': {:}", variable_name, reason),
+            range: location,
+            err_no: ErrNo::var__invalid_located_variable,
+        }
+    }
+
+    pub fn invalid_variable_length_array(variable_name: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Invalid variable-length array '{:}': only VAR_IN_OUT parameters can be declared as ARRAY[*]",
+                variable_name
+            ),
+            range: location,
+            err_no: ErrNo::var__invalid_variable_length_array,
+        }
+    }
+
     pub fn invalid_constant(constant_name: &str, location: SourceRange) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: format!("Invalid constant {:} - Functionblock- and Class-instances cannot be delcared constant", constant_name),
@@ -302,10 +704,61 @@ impl Diagnostic {
         }
     }
 
+    pub fn unused_variable(variable_name: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::Warning {
+            message: format!("Unused variable '{:}'", variable_name),
+            range: location,
+            err_no: ErrNo::lint__unused_variable,
+        }
+    }
+
+    pub fn unused_input(variable_name: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::Warning {
+            message: format!("Unused input '{:}'", variable_name),
+            range: location,
+            err_no: ErrNo::lint__unused_input,
+        }
+    }
+
+    pub fn assignment_without_effect(reason: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::Warning {
+            message: format!("Assignment without effect: {:}", reason),
+            range: location,
+            err_no: ErrNo::lint__assignment_without_effect,
+        }
+    }
+
+    pub fn shadowed_global(variable_name: &str, location: SourceRange) -> Diagnostic {
+        Diagnostic::Warning {
+            message: format!(
+                "Variable '{:}' shadows the global variable '{:}'",
+                variable_name, variable_name
+            ),
+            range: location,
+            err_no: ErrNo::lint__shadowed_global,
+        }
+    }
+
+    pub fn implicit_narrowing(
+        source_type: &str,
+        target_type: &str,
+        location: SourceRange,
+    ) -> Diagnostic {
+        Diagnostic::Warning {
+            message: format!(
+                "Implicit narrowing conversion from '{:}' to '{:}'",
+                source_type, target_type
+            ),
+            range: location,
+            err_no: ErrNo::lint__implicit_narrowing,
+        }
+    }
+
     pub fn get_message(&self) -> &str {
         match self {
             Diagnostic::SyntaxError { message, .. } => message.as_str(),
             Diagnostic::ImprovementSuggestion { message, .. } => message.as_str(),
+            Diagnostic::Warning { message, .. } => message.as_str(),
         }
     }
 
@@ -313,6 +766,23 @@ impl Diagnostic {
         match self {
             Diagnostic::SyntaxError { range, .. } => range.clone(),
             Diagnostic::ImprovementSuggestion { range, .. } => range.clone(),
+            Diagnostic::Warning { range, .. } => range.clone(),
+        }
+    }
+
+    pub fn get_err_no(&self) -> &ErrNo {
+        match self {
+            Diagnostic::SyntaxError { err_no, .. } => err_no,
+            Diagnostic::ImprovementSuggestion { .. } => &ErrNo::undefined,
+            Diagnostic::Warning { err_no, .. } => err_no,
+        }
+    }
+
+    pub fn get_severity(&self) -> Severity {
+        match self {
+            Diagnostic::SyntaxError { .. } => Severity::Error,
+            Diagnostic::ImprovementSuggestion { .. } => Severity::Info,
+            Diagnostic::Warning { .. } => Severity::Warning,
         }
     }
 }
@@ -380,6 +850,94 @@ fn create_source_code<T: Read>(
     Ok(buffer)
 }
 
+/// The amount of debug information generated into the compiled module
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DebugLevel {
+    /// no debug information
+    None,
+    /// DWARF debug information describing the POUs, variables, datatypes and source locations
+    Full,
+}
+
+/// The runtime checks generated into the compiled module. A check calls its hook, which is either
+/// declared by the user or added as a default implementation that traps (see `stdlib::RUNTIME_CHECKS`)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RuntimeChecks {
+    /// every array access is checked against the array's bounds (`CheckBounds`)
+    pub bounds: bool,
+    /// the divisor of every integer division is checked (`CheckDivDInt`, `CheckDivLInt`)
+    pub div: bool,
+    /// every integer addition, subtraction and multiplication is checked for an overflow (`CheckOverflow`)
+    pub overflow: bool,
+    /// every dereferenced pointer is checked for null (`CheckPointer`)
+    pub null: bool,
+}
+
+impl RuntimeChecks {
+    /// returns the names of the hooks called by the enabled checks. The default
+    /// implementations report a failed check to the `CheckFailed` handler
+    pub fn get_hooks(&self) -> Vec<&'static str> {
+        let mut hooks = Vec::new();
+        if self.bounds {
+            hooks.push(typesystem::BOUNDS_CHECK_FN);
+        }
+        if self.div {
+            hooks.push(typesystem::DIV_CHECK_FN);
+            hooks.push(typesystem::DIV_CHECK_L_FN);
+        }
+        if self.overflow {
+            hooks.push(typesystem::OVERFLOW_CHECK_FN);
+        }
+        if self.null {
+            hooks.push(typesystem::POINTER_CHECK_FN);
+        }
+        if !hooks.is_empty() {
+            hooks.push(typesystem::CHECK_FAILED_FN);
+        }
+        hooks
+    }
+}
+
+/// The optimizations applied to the compiled module
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptimizationLevel {
+    /// no optimizations (`-O0`)
+    None,
+    /// basic optimizations (`-O1`)
+    Less,
+    /// the default optimizations (`-O2`)
+    Default,
+    /// all optimizations, even if they increase the code-size considerably (`-O3`)
+    Aggressive,
+    /// the default optimizations that do not increase the code-size (`-Os`)
+    Size,
+}
+
+impl OptimizationLevel {
+    /// the corresponding llvm optimization level
+    fn to_llvm(self) -> inkwell::OptimizationLevel {
+        match self {
+            OptimizationLevel::None => inkwell::OptimizationLevel::None,
+            OptimizationLevel::Less => inkwell::OptimizationLevel::Less,
+            OptimizationLevel::Default | OptimizationLevel::Size => {
+                inkwell::OptimizationLevel::Default
+            }
+            OptimizationLevel::Aggressive => inkwell::OptimizationLevel::Aggressive,
+        }
+    }
+}
+
+/// The target machine object code is generated for
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TargetOptions {
+    /// the llvm target triple, the host's triple if not provided
+    pub triple: Option<String>,
+    /// the cpu to generate code for (e.g. `cortex-a53`), a generic cpu if not provided
+    pub cpu: Option<String>,
+    /// the cpu features to enable or disable (e.g. `+neon,-crypto`), the cpu's defaults if not provided
+    pub features: Option<String>,
+}
+
 pub fn get_target_triple(triple: Option<String>) -> TargetTriple {
     triple
         .map(|it| TargetTriple::create(it.as_str()))
@@ -387,6 +945,89 @@ pub fn get_target_triple(triple: Option<String>) -> TargetTriple {
         .unwrap()
 }
 
+/// runs the llvm optimization pipeline of the given level on the given module
+pub fn optimize_module(module: &Module, optimization: OptimizationLevel) {
+    if optimization == OptimizationLevel::None {
+        return;
+    }
+
+    let pass_manager_builder = PassManagerBuilder::create();
+    pass_manager_builder.set_optimization_level(optimization.to_llvm());
+    //the inline-thresholds used by clang for the given levels
+    match optimization {
+        OptimizationLevel::Size => {
+            pass_manager_builder.set_size_level(1);
+            pass_manager_builder.set_inliner_with_threshold(75);
+        }
+        OptimizationLevel::Aggressive => pass_manager_builder.set_inliner_with_threshold(275),
+        OptimizationLevel::Default => pass_manager_builder.set_inliner_with_threshold(225),
+        _ => {}
+    }
+
+    let pass_manager = PassManager::create(());
+    pass_manager_builder.populate_module_pass_manager(&pass_manager);
+    pass_manager.run_on(module);
+}
+
+/// creates the llvm target machine for the given target options
+fn create_target_machine(
+    target: &TargetOptions,
+    optimization: OptimizationLevel,
+    reloc: RelocMode,
+) -> Result<TargetMachine, CompileError> {
+    let initialization_config = &InitializationConfig::default();
+    Target::initialize_all(initialization_config);
+
+    let triple = get_target_triple(target.triple.clone());
+    Target::from_triple(&triple)
+        .map_err(|err| err.to_string())
+        .and_then(|it| {
+            it.create_target_machine(
+                &triple,
+                target.cpu.as_deref().unwrap_or("generic"),
+                target.features.as_deref().unwrap_or(""),
+                optimization.to_llvm(),
+                reloc,
+                CodeModel::Default,
+            )
+            .ok_or_else(|| {
+                format!(
+                    "Cannot create a target machine for {}",
+                    triple.as_str().to_string_lossy()
+                )
+            })
+        })
+        .map_err(|err| CompileError::codegen_error(err, SourceRange::undefined()))
+}
+
+/// The options of a compilation
+pub struct CompileOptions<'d> {
+    /// the target machine to compile for, the host machine if no triple is provided
+    pub target: TargetOptions,
+    /// the optimizations to apply
+    pub optimization: OptimizationLevel,
+    /// the amount of debug information to generate
+    pub debug_level: DebugLevel,
+    /// the runtime checks to generate
+    pub checks: RuntimeChecks,
+    /// reports the diagnostics of the compilation
+    pub diagnostician: &'d mut Diagnostician,
+}
+
+impl<'d> CompileOptions<'d> {
+    /// creates the options of an unoptimized compilation for the host machine without debug
+    /// information and runtime checks, reporting its diagnostics to the given diagnostician
+    pub fn new(diagnostician: &'d mut Diagnostician) -> CompileOptions<'d> {
+        CompileOptions {
+            target: TargetOptions::default(),
+            optimization: OptimizationLevel::None,
+            debug_level: DebugLevel::None,
+            checks: RuntimeChecks::default(),
+            diagnostician,
+        }
+    }
+}
+
 ///
 /// Compiles the given source into an object file and saves it in output
 ///
@@ -395,32 +1036,16 @@ fn compile_to_obj<T: SourceContainer>(
     encoding: Option<&'static Encoding>,
     output: &str,
     reloc: RelocMode,
-    triple: TargetTriple,
+    options: CompileOptions,
 ) -> Result<(), CompileError> {
-    let initialization_config = &InitializationConfig::default();
-    Target::initialize_all(initialization_config);
-
-    let target = Target::from_triple(&triple).unwrap();
-    let machine = target
-        .create_target_machine(
-            &triple,
-            //TODO : Add cpu features as optionals
-            "generic", //TargetMachine::get_host_cpu_name().to_string().as_str(),
-            "",        //TargetMachine::get_host_cpu_features().to_string().as_str(),
-            //TODO Optimisation as parameter
-            inkwell::OptimizationLevel::Default,
-            reloc,
-            CodeModel::Default,
-        )
-        .unwrap();
-
+    let machine = create_target_machine(&options.target, options.optimization, reloc)?;
+    let optimization = options.optimization;
     let c = Context::create();
-    let code_generator = compile_module(&c, sources, encoding)?;
+    let code_generator = compile_module(&c, sources, encoding, options)?;
+    optimize_module(&code_generator.module, optimization);
     machine
         .write_to_file(&code_generator.module, FileType::Object, Path::new(output))
-        .unwrap();
-
-    Ok(())
+        .map_err(|err| CompileError::io_write_error(output.into(), err.to_string()))
 }
 
 /// Compiles a given source string to a static object and saves the output.
@@ -429,21 +1054,15 @@ fn compile_to_obj<T: SourceContainer>(
 ///
 /// * `sources` - the source to be compiled
 /// * `output` - the location on disk to save the output
-/// * `target` - an optional llvm target triple
-///     If not provided, the machine's triple will be used.
+/// * `options` - the target, optimizations, debug information and runtime checks of the
+///     compilation and the diagnostician reporting its diagnostics
 pub fn compile_to_static_obj<T: SourceContainer>(
     sources: Vec<T>,
     encoding: Option<&'static Encoding>,
     output: &str,
-    target: Option<String>,
+    options: CompileOptions,
 ) -> Result<(), CompileError> {
-    compile_to_obj(
-        sources,
-        encoding,
-        output,
-        RelocMode::Default,
-        get_target_triple(target),
-    )
+    compile_to_obj(sources, encoding, output, RelocMode::Default, options)
 }
 
 /// Compiles a given source string to a shared position independent object and saves the output.
@@ -452,21 +1071,15 @@ pub fn compile_to_static_obj<T: SourceContainer>(
 ///
 /// * `sources` - the source to be compiled
 /// * `output` - the location on disk to save the output
-/// * `target` - an optional llvm target triple
-///     If not provided, the machine's triple will be used.
+/// * `options` - the target, optimizations, debug information and runtime checks of the
+///     compilation and the diagnostician reporting its diagnostics
 pub fn compile_to_shared_pic_object<T: SourceContainer>(
     sources: Vec<T>,
     encoding: Option<&'static Encoding>,
     output: &str,
-    target: Option<String>,
+    options: CompileOptions,
 ) -> Result<(), CompileError> {
-    compile_to_obj(
-        sources,
-        encoding,
-        output,
-        RelocMode::PIC,
-        get_target_triple(target),
-    )
+    compile_to_obj(sources, encoding, output, RelocMode::PIC, options)
 }
 
 /// Compiles a given source string to a dynamic non PIC object and saves the output.
@@ -475,21 +1088,15 @@ pub fn compile_to_shared_pic_object<T: SourceContainer>(
 ///
 /// * `sources` - the source to be compiled
 /// * `output` - the location on disk to save the output
-/// * `target` - an optional llvm target triple
-///     If not provided, the machine's triple will be used.
+/// * `options` - the target, optimizations, debug information and runtime checks of the
+///     compilation and the diagnostician reporting its diagnostics
 pub fn compile_to_shared_object<T: SourceContainer>(
     sources: Vec<T>,
     encoding: Option<&'static Encoding>,
     output: &str,
-    target: Option<String>,
+    options: CompileOptions,
 ) -> Result<(), CompileError> {
-    compile_to_obj(
-        sources,
-        encoding,
-        output,
-        RelocMode::DynamicNoPic,
-        get_target_triple(target),
-    )
+    compile_to_obj(sources, encoding, output, RelocMode::DynamicNoPic, options)
 }
 
 ///
@@ -499,14 +1106,19 @@ pub fn compile_to_shared_object<T: SourceContainer>(
 ///
 /// * `sources` - the source to be compiled
 /// * `output` - the location on disk to save the output
+/// * `options` - the target, optimizations, debug information and runtime checks of the
+///     compilation and the diagnostician reporting its diagnostics
 pub fn compile_to_bitcode<T: SourceContainer>(
     sources: Vec<T>,
     encoding: Option<&'static Encoding>,
     output: &str,
+    options: CompileOptions,
 ) -> Result<(), CompileError> {
     let path = Path::new(output);
+    let optimization = options.optimization;
     let c = Context::create();
-    let code_generator = compile_module(&c, sources, encoding)?;
+    let code_generator = compile_module(&c, sources, encoding, options)?;
+    optimize_module(&code_generator.module, optimization);
     code_generator.module.write_bitcode_to_path(path);
     Ok(())
 }
@@ -517,13 +1129,18 @@ pub fn compile_to_bitcode<T: SourceContainer>(
 /// # Arguments
 ///
 /// * `sources` - the source to be compiled
+/// * `options` - the target, optimizations, debug information and runtime checks of the
+///     compilation and the diagnostician reporting its diagnostics
 pub fn compile_to_ir<T: SourceContainer>(
     sources: Vec<T>,
     encoding: Option<&'static Encoding>,
     output: &str,
+    options: CompileOptions,
 ) -> Result<(), CompileError> {
+    let optimization = options.optimization;
     let c = Context::create();
-    let code_gen = compile_module(&c, sources, encoding)?;
+    let code_gen = compile_module(&c, sources, encoding, options)?;
+    optimize_module(&code_gen.module, optimization);
     let ir = code_gen.module.print_to_string().to_string();
     fs::write(output, ir)
         .map_err(|err| CompileError::io_write_error(output.into(), err.to_string()))
@@ -536,17 +1153,38 @@ pub fn compile_to_ir<T: SourceContainer>(
 ///
 /// * `context` - the LLVM Context to be used for the compilation
 /// * `sources` - the source to be compiled
+/// * `options` - the target (the code is generated for its data layout), debug information and
+///     runtime checks of the compilation and the diagnostician reporting its diagnostics.
+///     The optimizations are not applied to the returned module, see `optimize_module`
 pub fn compile_module<'c, T: SourceContainer>(
     context: &'c Context,
     sources: Vec<T>,
     encoding: Option<&'static Encoding>,
+    options: CompileOptions,
 ) -> Result<codegen::CodeGen<'c>, CompileError> {
+    let CompileOptions {
+        target,
+        debug_level,
+        checks,
+        diagnostician,
+        ..
+    } = options;
     let mut full_index = Index::new();
     let id_provider = IdProvider::new();
-    let mut files: SimpleFiles<String, String> = SimpleFiles::new();
 
     let mut all_units = Vec::new();
 
+    // ### PHASE 0 ###
+    // index the builtin functions first, so POUs declared by the user replace them
+    let builtins = builtins::parse_built_ins(id_provider.clone());
+    full_index.import(index::visitor::visit(&builtins));
+    // index the standard function blocks, POUs declared by the user replace them too
+    let mut standard_library = stdlib::parse_standard_library(id_provider.clone());
+    full_index.import(index::visitor::visit(&standard_library));
+    // index the default implementations of the enabled runtime checks, hooks declared by the user replace them
+    let mut runtime_checks = stdlib::parse_runtime_checks(checks, id_provider.clone());
+    full_index.import(index::visitor::visit(&runtime_checks));
+
     // ### PHASE 1 ###
     // parse & index everything
     for container in sources {
@@ -554,11 +1192,12 @@ pub fn compile_module<'c, T: SourceContainer>(
         let e = container
             .load_source(encoding)
             .map_err(|err| CompileError::io_read_error(err, location.clone()))?;
-        let file_id = files.add(location.clone(), e.source.clone());
+        let file_id = diagnostician.register_file(location.clone(), e.source.clone());
 
         let (mut parse_result, diagnostics) =
             parser::parse(lexer::lex_with_ids(e.source.as_str(), id_provider.clone()));
 
+        diagnostician.suppress(file_id, &parse_result.suppressions);
         //pre-process the ast (create inlined types)
         ast::pre_process(&mut parse_result);
         //index the pou
@@ -566,72 +1205,179 @@ pub fn compile_module<'c, T: SourceContainer>(
         all_units.push((file_id, diagnostics, parse_result));
     }
 
+    // only generate the standard function blocks used by the program and the hooks the user did not declare
+    let user_units: Vec<&CompilationUnit> = all_units.iter().map(|(_, _, unit)| unit).collect();
+    stdlib::remove_unused_pous(&mut standard_library, &user_units, &full_index);
+    stdlib::remove_replaced_pous(&mut runtime_checks, &user_units);
+    let stdlib_file_id = diagnostician.register_file(
+        stdlib::STDLIB_LOCATION.into(),
+        stdlib::STANDARD_FUNCTION_BLOCKS.into(),
+    );
+    all_units.push((stdlib_file_id, Vec::new(), standard_library));
+    let runtime_checks_file_id = diagnostician.register_file(
+        stdlib::RUNTIME_CHECKS_LOCATION.into(),
+        stdlib::RUNTIME_CHECKS.into(),
+    );
+    all_units.push((runtime_checks_file_id, Vec::new(), runtime_checks));
+
     // ### PHASE 1.1 resolve constant literal values
     let (full_index, _unresolvables) = resolver::const_evaluator::evaluate_constants(full_index);
 
+    //validate and generate the code for the target's data layout (e.g. the size of pointers)
+    let machine = create_target_machine(&target, OptimizationLevel::None, RelocMode::Default)?;
+    let target_data = machine.get_target_data();
+    let address_size = target_data.get_pointer_byte_size(None) * 8;
+
     // ### PHASE 2 ###
     // annotation & validation everything
-    type AnnotatedAst<'a> = (&'a CompilationUnit, AnnotationMap);
+    type AnnotatedAst<'a> = (usize, &'a CompilationUnit, AnnotationMap);
     let mut annotated_units: Vec<AnnotatedAst> = Vec::new();
     for (file_id, syntax_errors, unit) in all_units.iter() {
         let annotations = TypeAnnotator::visit_unit(&full_index, unit);
 
-        let mut validator = Validator::new();
+        let mut validator = Validator::for_target(address_size);
         validator.visit_unit(&annotations, &full_index, unit);
         //log errors
-        report_diagnostics(*file_id, syntax_errors.iter(), &files)?;
-        report_diagnostics(*file_id, validator.diagnostics().iter(), &files)?;
+        diagnostician.handle(*file_id, syntax_errors)?;
+        diagnostician.handle(*file_id, &validator.diagnostics())?;
+
+        //lint the user's code only
+        if *file_id != stdlib_file_id && *file_id != runtime_checks_file_id {
+            let mut linter = Linter::new();
+            linter.visit_unit(&annotations, &full_index, unit);
+            diagnostician.handle(*file_id, &linter.diagnostics)?;
+        }
 
-        annotated_units.push((unit, annotations));
+        annotated_units.push((*file_id, unit, annotations));
     }
+    //do not generate code for invalid units, all errors of all files were reported above
+    diagnostician.check_errors().map_err(|err| {
+        diagnostician.flush();
+        err
+    })?;
 
     // ### PHASE 3 ###
     // - codegen
     let code_generator = codegen::CodeGen::new(context, "main");
-
-    for (unit, annotations) in annotated_units {
-        code_generator.generate(unit, &annotations, &full_index)?;
+    code_generator.module.set_triple(&machine.get_triple());
+    code_generator
+        .module
+        .set_data_layout(&target_data.get_data_layout());
+
+    for (file_id, unit, annotations) in annotated_units {
+        let result = match (debug_level, diagnostician.get_file(file_id)) {
+            (DebugLevel::Full, Some((file_name, source))) => code_generator
+                .generate_with_debug_info(unit, &annotations, &full_index, file_name, source),
+            _ => code_generator.generate(unit, &annotations, &full_index),
+        };
+        //report the errors of all units before aborting
+        if let Err(errors) = result {
+            let diagnostics: Vec<Diagnostic> =
+                errors.iter().map(Diagnostic::codegen_error).collect();
+            diagnostician.handle(file_id, &diagnostics)?;
+        }
     }
+    diagnostician.flush();
+    diagnostician.check_errors()?;
     Ok(code_generator)
 }
 
-fn report_diagnostics(
-    file_id: usize,
-    semantic_diagnostics: std::slice::Iter<Diagnostic>,
-    files: &SimpleFiles<String, String>,
-) -> Result<(), CompileError> {
-    for error in semantic_diagnostics {
-        let diag = diagnostic::Diagnostic::error()
-            .with_message(error.get_message())
-            .with_labels(vec![Label::primary(
-                file_id,
-                error.get_location().get_start()..error.get_location().get_end(),
-            )]);
-        let writer = StandardStream::stderr(ColorChoice::Always);
-        let config = codespan_reporting::term::Config {
-            display_style: term::DisplayStyle::Rich,
-            tab_width: 2,
-            styles: Styles::default(),
-            chars: Chars::default(),
-            start_context_lines: 5,
-            end_context_lines: 3,
-        };
+/// generates the interface of the given sources (see `formatter::format_interface`)
+///
+/// another compilation can include the interface to use the object compiled from the sources.
+/// The standard function blocks used by the sources are declared too, so the including
+/// compilation does not generate them a second time. The sources are expected to be valid,
+/// their syntax errors are not reported.
+pub fn generate_interface<T: SourceContainer>(
+    sources: Vec<T>,
+    encoding: Option<&'static Encoding>,
+) -> Result<String, CompileError> {
+    let id_provider = IdProvider::new();
+    let mut full_index = Index::new();
+    let mut standard_library = stdlib::parse_standard_library(id_provider.clone());
+    full_index.import(index::visitor::visit(&standard_library));
 
-        term::emit(&mut writer.lock(), &config, files, &diag).map_err(|err| {
-            CompileError::codegen_error(
-                format!("Cannot print errors {:#?}", err),
-                SourceRange::undefined(),
-            )
-        })?;
+    let mut interfaces = Vec::new();
+    let mut units = Vec::new();
+    for container in sources {
+        let location: String = container.get_location().into();
+        let source = container
+            .load_source(encoding)
+            .map_err(|err| CompileError::io_read_error(location, err))?;
+        let (mut unit, _) = parser::parse(lexer::lex_with_ids(
+            source.source.as_str(),
+            id_provider.clone(),
+        ));
+        //the interface is printed from the unit as it was written, without the inlined types
+        interfaces.push(formatter::format_interface(&unit, &source.source));
+        ast::pre_process(&mut unit);
+        full_index.import(index::visitor::visit(&unit));
+        units.push(unit);
     }
-    Ok(())
+
+    //declare the standard function blocks generated into the object
+    let user_units: Vec<&CompilationUnit> = units.iter().collect();
+    stdlib::remove_unused_pous(&mut standard_library, &user_units, &full_index);
+    let (mut library, _) = parser::parse(lexer::lex_with_ids(
+        stdlib::STANDARD_FUNCTION_BLOCKS,
+        id_provider,
+    ));
+    let is_generated = |name: &str| {
+        !name.eq_ignore_ascii_case(stdlib::GET_TIME)
+            && standard_library
+                .units
+                .iter()
+                .any(|it| it.name.eq_ignore_ascii_case(name))
+    };
+    library.units.retain(|it| is_generated(&it.name));
+    library.implementations.retain(|it| is_generated(&it.name));
+    if !library.units.is_empty() {
+        interfaces.push(formatter::format_interface(
+            &library,
+            stdlib::STANDARD_FUNCTION_BLOCKS,
+        ));
+    }
+    Ok(interfaces.join("\n"))
+}
+
+/// formats the given sources, returns every loaded source with its formatted source-code
+///
+/// sources with syntax errors cannot be formatted, their errors are reported to the diagnostician
+pub fn format_sources<T: SourceContainer>(
+    sources: Vec<T>,
+    encoding: Option<&'static Encoding>,
+    diagnostician: &mut Diagnostician,
+) -> Result<Vec<(SourceCode, String)>, CompileError> {
+    let mut formatted_sources = Vec::new();
+    for container in sources {
+        let location: String = container.get_location().into();
+        let source = container
+            .load_source(encoding)
+            .map_err(|err| CompileError::io_read_error(location.clone(), err))?;
+        let file_id = diagnostician.register_file(location, source.source.clone());
+        match formatter::format(&source.source) {
+            Ok(formatted) => formatted_sources.push((source, formatted)),
+            Err(errors) => diagnostician.handle(file_id, &errors)?,
+        }
+    }
+    diagnostician.flush();
+    diagnostician.check_errors()?;
+    Ok(formatted_sources)
 }
 
 #[cfg(test)]
 mod tests {
     use inkwell::targets::TargetMachine;
 
-    use crate::{create_source_code, get_target_triple};
+    use inkwell::context::Context;
+
+    use crate::{
+        compile_error::CompileError,
+        compile_module, create_source_code,
+        diagnostician::{Diagnostician, ErrorFormat},
+        format_sources, generate_interface, get_target_triple, optimize_module, stdlib,
+        CompileOptions, ErrNo, OptimizationLevel, RuntimeChecks, Severity, SourceCode,
+    };
 
     #[test]
     fn test_get_target_triple() {
@@ -657,6 +1403,319 @@ mod tests {
         assert_eq!(triple.as_str().to_str().unwrap(), "x86_64-pc-linux-gnu");
     }
 
+    #[test]
+    fn optimizations_are_applied_to_the_module() {
+        let compile_optimized = |optimization| {
+            let source = SourceCode {
+                path: "optimization.st".into(),
+                source: "FUNCTION foo : DINT foo := 1 + 2; END_FUNCTION".into(),
+            };
+            let context = Context::create();
+            let code_gen = compile_module(
+                &context,
+                vec![source],
+                None,
+                CompileOptions::new(&mut Diagnostician::default()),
+            )
+            .unwrap();
+            optimize_module(&code_gen.module, optimization);
+            code_gen.module.print_to_string().to_string()
+        };
+
+        //the return value is stored on the stack without optimizations
+        assert!(!compile_optimized(OptimizationLevel::None).contains("ret i32 3"));
+        assert!(compile_optimized(OptimizationLevel::Less).contains("ret i32 3"));
+        assert!(compile_optimized(OptimizationLevel::Size).contains("ret i32 3"));
+    }
+
+    #[test]
+    fn no_optimizations_leave_the_module_unchanged() {
+        let source = SourceCode {
+            path: "optimization.st".into(),
+            source: "FUNCTION foo : DINT foo := 1 + 2; END_FUNCTION".into(),
+        };
+        let context = Context::create();
+        let code_gen = compile_module(
+            &context,
+            vec![source],
+            None,
+            CompileOptions::new(&mut Diagnostician::default()),
+        )
+        .unwrap();
+        let generated = code_gen.module.print_to_string().to_string();
+
+        optimize_module(&code_gen.module, OptimizationLevel::None);
+
+        assert_eq!(code_gen.module.print_to_string().to_string(), generated);
+    }
+
+    #[test]
+    fn reported_diagnostics_carry_their_file_and_position() {
+        let source = SourceCode {
+            path: "pointer.st".into(),
+            source: "TYPE MyPtr : POINTER TO INT; END_TYPE".into(),
+        };
+        let context = Context::create();
+        let mut diagnostician = Diagnostician::new(ErrorFormat::Json);
+        compile_module(
+            &context,
+            vec![source],
+            None,
+            CompileOptions::new(&mut diagnostician),
+        )
+        .unwrap();
+
+        let reported = diagnostician.get_reported_diagnostics();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(reported[0].file, "pointer.st");
+        assert_eq!(reported[0].diagnostic.get_severity(), Severity::Info);
+        assert_eq!(reported[0].diagnostic.get_err_no(), &ErrNo::undefined);
+        assert_eq!((reported[0].start.line, reported[0].start.column), (1, 14));
+        assert_eq!((reported[0].end.line, reported[0].end.column), (1, 21));
+    }
+
+    #[test]
+    fn errors_of_all_files_are_reported_before_aborting() {
+        let sources = vec![
+            SourceCode {
+                path: "a.st".into(),
+                source: "PROGRAM a x := 1; END_PROGRAM".into(),
+            },
+            SourceCode {
+                path: "b.st".into(),
+                source: "PROGRAM b y := 2; END_PROGRAM".into(),
+            },
+        ];
+        let context = Context::create();
+        let mut diagnostician = Diagnostician::new(ErrorFormat::Json);
+        let result = compile_module(
+            &context,
+            sources,
+            None,
+            CompileOptions::new(&mut diagnostician),
+        );
+
+        assert_eq!(result.err(), Some(CompileError::compilation_aborted(2, 0)));
+        let files: Vec<&str> = diagnostician
+            .get_reported_diagnostics()
+            .iter()
+            .map(|it| it.file.as_str())
+            .collect();
+        assert_eq!(files, vec!["a.st", "b.st"]);
+    }
+
+    #[test]
+    fn configurations_of_different_files_are_reported() {
+        let configuration = |name: &str| {
+            format!(
+                "
+                CONFIGURATION {0}
+                RESOURCE res_{0} ON PLC
+                TASK cyclic(PRIORITY := 1);
+                END_RESOURCE
+                END_CONFIGURATION
+                ",
+                name
+            )
+        };
+        let sources = vec![
+            SourceCode {
+                path: "a.st".into(),
+                source: configuration("a"),
+            },
+            SourceCode {
+                path: "b.st".into(),
+                source: configuration("b"),
+            },
+        ];
+        let context = Context::create();
+        let mut diagnostician = Diagnostician::new(ErrorFormat::Json);
+        let result = compile_module(
+            &context,
+            sources,
+            None,
+            CompileOptions::new(&mut diagnostician),
+        );
+
+        //the program has a single task table, it is generated for the first file
+        assert_eq!(result.err(), Some(CompileError::compilation_aborted(1, 0)));
+        let files: Vec<&str> = diagnostician
+            .get_reported_diagnostics()
+            .iter()
+            .map(|it| it.file.as_str())
+            .collect();
+        assert_eq!(files, vec!["b.st"]);
+    }
+
+    #[test]
+    fn an_included_interface_declares_the_library_without_generating_it() {
+        let library = SourceCode {
+            path: "lib.st".into(),
+            source: "
+                VAR_GLOBAL counter : INT; END_VAR
+                PROGRAM lib
+                VAR timer : TON; END_VAR
+                timer(IN := TRUE, PT := T#1s);
+                counter := counter + 1;
+                END_PROGRAM
+                "
+            .into(),
+        };
+        let interface = generate_interface(vec![library], None).unwrap();
+        //the used standard function blocks are part of the library's object
+        assert!(interface.contains("\n@EXTERNAL FUNCTION_BLOCK TON\n"));
+        assert!(!interface.contains("TOF"));
+        assert!(!interface.contains(stdlib::GET_TIME));
+
+        let sources = vec![
+            SourceCode {
+                path: "lib.sti".into(),
+                source: interface,
+            },
+            SourceCode {
+                path: "main.st".into(),
+                source: "
+                    PROGRAM main
+                    VAR timer : TON; END_VAR
+                    timer(IN := TRUE, PT := T#2s);
+                    lib();
+                    counter := 0;
+                    END_PROGRAM
+                    "
+                .into(),
+            },
+        ];
+        let context = Context::create();
+        let code_gen = compile_module(
+            &context,
+            sources,
+            None,
+            CompileOptions::new(&mut Diagnostician::default()),
+        )
+        .unwrap();
+        let ir = code_gen.module.print_to_string().to_string();
+        assert!(ir.contains("@counter = external global i16\n"));
+        assert!(ir.contains("@lib_instance = external global %lib_interface\n"));
+        assert!(ir.contains("declare void @lib(%lib_interface*)"));
+        assert!(ir.contains("declare void @TON(%TON_interface*)"));
+        assert!(!ir.contains("define void @TON("));
+    }
+
+    #[test]
+    fn bounds_checks_call_a_default_implementation_unless_declared() {
+        let program = SourceCode {
+            path: "main.st".into(),
+            source: "
+                PROGRAM main
+                VAR values : ARRAY[1..5] OF INT; i : INT; END_VAR
+                values[i] := 1;
+                END_PROGRAM
+                "
+            .into(),
+        };
+        let compile = |sources: Vec<SourceCode>| {
+            let context = Context::create();
+            let code_gen = compile_module(
+                &context,
+                sources,
+                None,
+                CompileOptions {
+                    checks: RuntimeChecks {
+                        bounds: true,
+                        ..RuntimeChecks::default()
+                    },
+                    ..CompileOptions::new(&mut Diagnostician::default())
+                },
+            )
+            .unwrap();
+            code_gen.module.print_to_string().to_string()
+        };
+
+        let ir = compile(vec![program.clone()]);
+        assert!(ir.contains("call i32 @CheckBounds(%CheckBounds_interface* %CheckBounds_instance)"));
+        assert!(ir.contains("call void @CheckFailed("));
+        assert!(ir.contains("call void @llvm.trap()"));
+
+        let hook = SourceCode {
+            path: "hook.st".into(),
+            source: "
+                FUNCTION CheckBounds : DINT
+                VAR_INPUT index, lower, upper : DINT; END_VAR
+                CheckBounds := LIMIT(lower, index, upper);
+                END_FUNCTION
+                "
+            .into(),
+        };
+        let ir = compile(vec![program, hook]);
+        assert_eq!(ir.matches("define i32 @CheckBounds(").count(), 1);
+        assert!(!ir.contains("call void @CheckFailed("));
+    }
+
+    #[test]
+    fn runtime_checks_add_the_default_hooks_of_the_enabled_checks() {
+        let program = SourceCode {
+            path: "main.st".into(),
+            source: "
+                PROGRAM main
+                VAR x, y : DINT; p : REF_TO DINT; END_VAR
+                x := x / y + p^;
+                END_PROGRAM
+                "
+            .into(),
+        };
+        let context = Context::create();
+        let code_gen = compile_module(
+            &context,
+            vec![program],
+            None,
+            CompileOptions {
+                checks: RuntimeChecks {
+                    div: true,
+                    null: true,
+                    ..RuntimeChecks::default()
+                },
+                ..CompileOptions::new(&mut Diagnostician::default())
+            },
+        )
+        .unwrap();
+        let ir = code_gen.module.print_to_string().to_string();
+        assert!(ir.contains("define i32 @CheckDivDInt("));
+        assert!(ir.contains("call i32 @CheckDivDInt("));
+        assert!(ir.contains("define i8* @CheckPointer("));
+        assert!(ir.contains("call i8* @CheckPointer("));
+        assert!(ir.contains("define void @CheckFailed("));
+        //the overflow- and bounds-checks are not enabled
+        assert!(!ir.contains("CheckOverflow"));
+        assert!(!ir.contains("CheckBounds"));
+        assert!(!ir.contains("with.overflow"));
+    }
+
+    #[test]
+    fn sources_with_syntax_errors_are_not_formatted() {
+        let sources = vec![
+            SourceCode {
+                path: "a.st".into(),
+                source: "program a x := 1; end_program".into(),
+            },
+            SourceCode {
+                path: "b.st".into(),
+                source: "PROGRAM b y := 2 END_PROGRAM".into(),
+            },
+        ];
+        let mut diagnostician = Diagnostician::new(ErrorFormat::Json);
+        let result = format_sources(sources.clone(), None, &mut diagnostician);
+        assert!(result.is_err());
+        //only the file with syntax errors is reported
+        let reported = diagnostician.get_reported_diagnostics();
+        assert!(!reported.is_empty());
+        assert!(reported.iter().all(|it| it.file == "b.st"));
+
+        let mut diagnostician = Diagnostician::new(ErrorFormat::Json);
+        let formatted = format_sources(vec![sources[0].clone()], None, &mut diagnostician).unwrap();
+        assert_eq!(formatted[0].0.path, "a.st");
+        assert_eq!(formatted[0].1, "PROGRAM a\n    x := 1;\nEND_PROGRAM\n");
+    }
+
     #[test]
     fn windows_encoded_file_content_read() {
         let expected = r"PROGRAM ä
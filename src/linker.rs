@@ -1,6 +1,7 @@
 // This file is based on code from the Mun Programming Language
 // https://github.com/mun-lang/mun
 
+use rusty::compile_error::CompileError;
 use std::path::{Path, PathBuf};
 
 pub struct Linker {
@@ -172,6 +173,12 @@ pub enum LinkerError {
     Path(PathBuf),
 }
 
+impl From<LinkerError> for CompileError {
+    fn from(error: LinkerError) -> Self {
+        CompileError::linker_error(error.into())
+    }
+}
+
 impl From<LinkerError> for String {
     fn from(error: LinkerError) -> Self {
         match error {
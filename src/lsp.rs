@@ -0,0 +1,336 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+//! A Structured Text Language Server
+//!
+//! `rusty-lsp` speaks the [`Language Server Protocol`] over stdin/stdout. Every change of an
+//! open document re-analyzes all open documents and publishes their diagnostics. It answers
+//! go-to-definition, hover, member-completion (after `.`) and document-symbol requests.
+//!
+//! [`Language Server Protocol`]: https://microsoft.github.io/language-server-protocol/
+use std::{collections::HashMap, error::Error};
+
+use lsp_server::{Connection, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as LspNotification, PublishDiagnostics,
+    },
+    request::{
+        Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as LspRequest,
+    },
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+    HoverProviderCapability, Location, MarkupContent, MarkupKind, NumberOrString, OneOf, Position,
+    PublishDiagnosticsParams, Range, ServerCapabilities, SymbolKind, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use rusty::{
+    analysis::{self, Analysis},
+    Severity, SourceCode,
+};
+use serde::de::DeserializeOwned;
+
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::Full)),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![".".to_string()]),
+            ..CompletionOptions::default()
+        }),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut server = Server::new();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+                let response = server.handle_request(request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                for diagnostics in server.handle_notification(notification)? {
+                    connection.sender.send(Message::Notification(diagnostics))?;
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    io_threads.join()?;
+    Ok(())
+}
+
+/// the open documents and their latest analysis
+struct Server {
+    documents: HashMap<Url, String>,
+    /// the uris of the analyzed documents, in the order they were passed to the analysis
+    analyzed_documents: Vec<Url>,
+    analysis: Analysis,
+}
+
+impl Server {
+    fn new() -> Server {
+        Server {
+            documents: HashMap::new(),
+            analyzed_documents: Vec::new(),
+            analysis: Analysis::new(&[]),
+        }
+    }
+
+    /// updates the open documents and returns the diagnostics to publish
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<Vec<Notification>, serde_json::Error> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.documents
+                    .insert(params.text_document.uri, params.text_document.text);
+            }
+            DidChangeTextDocument::METHOD => {
+                let mut params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                //the server asks for full documents, so the last change holds the whole text
+                if let Some(change) = params.content_changes.pop() {
+                    self.documents.insert(params.text_document.uri, change.text);
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                //clear the diagnostics of the closed document
+                let cleared = PublishDiagnosticsParams {
+                    uri: params.text_document.uri,
+                    diagnostics: Vec::new(),
+                    version: None,
+                };
+                self.analyze();
+                let mut notifications = self.publish_diagnostics();
+                notifications.push(Notification::new(
+                    PublishDiagnostics::METHOD.to_string(),
+                    cleared,
+                ));
+                return Ok(notifications);
+            }
+            _ => return Ok(Vec::new()),
+        }
+        self.analyze();
+        Ok(self.publish_diagnostics())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let method = request.method.clone();
+        let result =
+            match method.as_str() {
+                GotoDefinition::METHOD => parse_params(request)
+                    .map(|params| serde_json::to_value(self.definition(params))),
+                HoverRequest::METHOD => {
+                    parse_params(request).map(|params| serde_json::to_value(self.hover(params)))
+                }
+                Completion::METHOD => parse_params(request)
+                    .map(|params| serde_json::to_value(self.completion(params))),
+                DocumentSymbolRequest::METHOD => parse_params(request)
+                    .map(|params| serde_json::to_value(self.document_symbols(params))),
+                _ => {
+                    return Response::new_err(
+                        id,
+                        lsp_server::ErrorCode::MethodNotFound as i32,
+                        format!("Unsupported request '{}'", method),
+                    )
+                }
+            };
+        match result {
+            Ok(Ok(value)) => Response {
+                id,
+                result: Some(value),
+                error: None,
+            },
+            Ok(Err(err)) | Err(err) => Response::new_err(
+                id,
+                lsp_server::ErrorCode::InvalidParams as i32,
+                err.to_string(),
+            ),
+        }
+    }
+
+    /// re-analyzes all open documents
+    fn analyze(&mut self) {
+        self.analyzed_documents = self.documents.keys().cloned().collect();
+        let sources: Vec<SourceCode> = self
+            .analyzed_documents
+            .iter()
+            .map(|uri| SourceCode {
+                path: uri.to_string(),
+                source: self.documents[uri].clone(),
+            })
+            .collect();
+        self.analysis = Analysis::new(&sources);
+    }
+
+    fn publish_diagnostics(&self) -> Vec<Notification> {
+        self.analyzed_documents
+            .iter()
+            .enumerate()
+            .map(|(document, uri)| {
+                let source = &self.documents[uri];
+                let diagnostics = self
+                    .analysis
+                    .get_diagnostics(document)
+                    .iter()
+                    .map(|it| lsp_types::Diagnostic {
+                        range: to_range(
+                            source,
+                            it.get_location().get_start(),
+                            it.get_location().get_end(),
+                        ),
+                        severity: Some(match it.get_severity() {
+                            Severity::Error => DiagnosticSeverity::Error,
+                            Severity::Warning => DiagnosticSeverity::Warning,
+                            Severity::Info => DiagnosticSeverity::Information,
+                        }),
+                        code: Some(NumberOrString::String(format!("{:?}", it.get_err_no()))),
+                        source: Some("rusty".to_string()),
+                        message: it.get_message().to_string(),
+                        ..lsp_types::Diagnostic::default()
+                    })
+                    .collect();
+                Notification::new(
+                    PublishDiagnostics::METHOD.to_string(),
+                    PublishDiagnosticsParams {
+                        uri: uri.clone(),
+                        diagnostics,
+                        version: None,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// returns the position of the document with the given uri in the analysis and its source
+    fn find_document(&self, uri: &Url) -> Option<(usize, &str)> {
+        let document = self.analyzed_documents.iter().position(|it| it == uri)?;
+        Some((document, self.documents[uri].as_str()))
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let (document, source) = self.find_document(&position.text_document.uri)?;
+        let offset = to_offset(source, position.position);
+        let definition = self.analysis.find_definition(document, offset)?;
+
+        let uri = &self.analyzed_documents[definition.document];
+        let range = to_range(
+            &self.documents[uri],
+            definition.range.get_start(),
+            definition.range.get_end(),
+        );
+        Some(GotoDefinitionResponse::Scalar(Location {
+            uri: uri.clone(),
+            range,
+        }))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let (document, source) = self.find_document(&position.text_document.uri)?;
+        let hover = self
+            .analysis
+            .get_hover(document, to_offset(source, position.position))?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```st\n{}\n```", hover),
+            }),
+            range: None,
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let (document, source) = self.find_document(&position.text_document.uri)?;
+        let items = self
+            .analysis
+            .complete_members(document, to_offset(source, position.position))
+            .into_iter()
+            .map(|it| CompletionItem {
+                label: it.name,
+                kind: Some(match it.kind {
+                    analysis::SymbolKind::Method => CompletionItemKind::Method,
+                    _ => CompletionItemKind::Field,
+                }),
+                detail: Some(it.type_name),
+                ..CompletionItem::default()
+            })
+            .collect();
+        Some(CompletionResponse::Array(items))
+    }
+
+    #[allow(deprecated)] //`DocumentSymbol::deprecated` has to be initialized
+    fn document_symbols(&self, params: DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
+        let (document, source) = self.find_document(&params.text_document.uri)?;
+        let symbols = self
+            .analysis
+            .get_symbols(document)
+            .into_iter()
+            .map(|it| {
+                let range = to_range(source, it.range.get_start(), it.range.get_end());
+                DocumentSymbol {
+                    name: it.name,
+                    detail: None,
+                    kind: match it.kind {
+                        analysis::SymbolKind::Program => SymbolKind::Module,
+                        analysis::SymbolKind::Function | analysis::SymbolKind::Action => {
+                            SymbolKind::Function
+                        }
+                        analysis::SymbolKind::FunctionBlock | analysis::SymbolKind::Class => {
+                            SymbolKind::Class
+                        }
+                        analysis::SymbolKind::Method => SymbolKind::Method,
+                        analysis::SymbolKind::Interface => SymbolKind::Interface,
+                        analysis::SymbolKind::Type => SymbolKind::Struct,
+                        analysis::SymbolKind::Variable => SymbolKind::Variable,
+                    },
+                    tags: None,
+                    deprecated: None,
+                    range,
+                    selection_range: range,
+                    children: None,
+                }
+            })
+            .collect();
+        Some(DocumentSymbolResponse::Nested(symbols))
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(request: Request) -> Result<T, serde_json::Error> {
+    serde_json::from_value(request.params)
+}
+
+fn to_offset(source: &str, position: Position) -> usize {
+    analysis::get_offset(source, position.line, position.character)
+}
+
+fn to_range(source: &str, start: usize, end: usize) -> Range {
+    let to_position = |offset| {
+        let (line, character) = analysis::get_line_and_column(source, offset);
+        Position { line, character }
+    };
+    Range {
+        start: to_position(start),
+        end: to_position(end),
+    }
+}
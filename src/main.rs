@@ -17,25 +17,43 @@
 //! [`ST`]: https://en.wikipedia.org/wiki/Structured_text
 //! [`IEC61131-3`]: https://en.wikipedia.org/wiki/IEC_61131-3
 //! [`IR`]: https://llvm.org/docs/LangRef.html
-use std::path::Path;
+use std::{fs, path::Path};
 
 use glob::glob;
 use rusty::{
-    cli::{CompileParameters, FormatOption, ParameterError},
+    cli::{CompileParameters, FormatOption, FormatParameters, ParameterError},
+    compile_error::CompileError,
     compile_to_bitcode, compile_to_ir, compile_to_shared_object, compile_to_shared_pic_object,
-    compile_to_static_obj, get_target_triple, FilePath,
+    compile_to_static_obj,
+    diagnostician::Diagnostician,
+    format_sources, generate_interface, get_target_triple, CompileOptions, FilePath,
 };
 mod linker;
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
+
+    //`rustyc fmt <input-files>` formats the files instead of compiling them
+    if args.get(1).map_or(false, |it| it == "fmt") {
+        args.remove(1);
+        match FormatParameters::parse(args) {
+            Ok(parameters) => {
+                if let Err(err) = main_format(parameters) {
+                    println!("Error: {}", err);
+                    std::process::exit(1);
+                }
+            }
+            Err(err) => err.exit(),
+        }
+        return;
+    }
 
     let compile_parameters: Result<CompileParameters, ParameterError> =
         CompileParameters::parse(args);
     match compile_parameters {
         Ok(cp) => {
-            if let Err(msg) = main_compile(cp) {
-                println!("Error: {}", msg);
+            if let Err(err) = main_compile(cp) {
+                println!("Error: {}", err);
                 std::process::exit(1);
             }
         }
@@ -43,70 +61,85 @@ fn main() {
     }
 }
 
-fn create_file_paths(inputs: &[String]) -> Result<Vec<FilePath>, String> {
+fn create_file_paths(inputs: &[String]) -> Result<Vec<FilePath>, CompileError> {
     let mut sources = Vec::new();
     for input in inputs {
-        let paths =
-            glob(input).map_err(|e| format!("Failed to read glob pattern: {}, ({})", input, e))?;
+        let paths = glob(input).map_err(|e| {
+            CompileError::invalid_input(format!("Failed to read glob pattern: {}, ({})", input, e))
+        })?;
 
         let source_count_before = sources.len();
         for p in paths {
-            let path = p.map_err(|err| format!("Illegal path: {:}", err))?;
+            let path =
+                p.map_err(|err| CompileError::invalid_input(format!("Illegal path: {:}", err)))?;
             sources.push(FilePath {
                 path: path.to_string_lossy().to_string(),
             });
         }
 
         if sources.len() <= source_count_before {
-            return Err(format!("No such file(s): {}", input));
+            return Err(CompileError::invalid_input(format!(
+                "No such file(s): {}",
+                input
+            )));
         }
     }
     Ok(sources)
 }
 
-fn main_compile(parameters: CompileParameters) -> Result<(), String> {
-    let sources = create_file_paths(&parameters.input)?;
+fn main_compile(parameters: CompileParameters) -> Result<(), CompileError> {
+    //the included interfaces declare what the input-files use from other objects
+    let mut sources = create_file_paths(&parameters.includes)?;
+    sources.append(&mut create_file_paths(&parameters.input)?);
 
-    let output_filename = parameters.output_name().unwrap();
+    let output_filename = parameters.output_name().ok_or_else(|| {
+        CompileError::invalid_input("Cannot derive the name of the output file".into())
+    })?;
     let encoding = parameters.encoding;
+    let mut diagnostician =
+        Diagnostician::new(parameters.error_format).with_lint_options(parameters.lint_options());
+
+    let options = CompileOptions {
+        target: parameters.target_options(),
+        optimization: parameters.optimization_level(),
+        debug_level: parameters.debug_level(),
+        checks: parameters.runtime_checks(),
+        diagnostician: &mut diagnostician,
+    };
 
     let out_format = parameters.output_format_or_default();
     match out_format {
         FormatOption::Static => {
-            compile_to_static_obj(
-                sources,
-                encoding,
-                output_filename.as_str(),
-                parameters.target.clone(),
-            )
-            .unwrap();
+            compile_to_static_obj(sources, encoding, output_filename.as_str(), options)?;
         }
         FormatOption::Shared => {
-            compile_to_shared_object(
-                sources,
-                encoding,
-                output_filename.as_str(),
-                parameters.target.clone(),
-            )
-            .unwrap();
+            compile_to_shared_object(sources, encoding, output_filename.as_str(), options)?;
         }
         FormatOption::PIC => {
-            compile_to_shared_pic_object(
-                sources,
-                encoding,
-                output_filename.as_str(),
-                parameters.target.clone(),
-            )
-            .unwrap();
+            compile_to_shared_pic_object(sources, encoding, output_filename.as_str(), options)?;
         }
         FormatOption::Bitcode => {
-            compile_to_bitcode(sources, encoding, output_filename.as_str()).unwrap();
+            compile_to_bitcode(sources, encoding, output_filename.as_str(), options)?;
         }
         FormatOption::IR => {
-            compile_to_ir(sources, encoding, &output_filename).unwrap();
+            compile_to_ir(sources, encoding, &output_filename, options)?;
         }
     }
 
+    if parameters.generate_interface {
+        let interface_filename = parameters.interface_name().ok_or_else(|| {
+            CompileError::invalid_input("Cannot derive the name of the interface file".into())
+        })?;
+        let interface = generate_interface(create_file_paths(&parameters.input)?, encoding)?;
+        //the interface is included with the same encoding as the input-files
+        let content = match encoding {
+            Some(encoding) => encoding.encode(&interface).0.into_owned(),
+            None => interface.into_bytes(),
+        };
+        fs::write(&interface_filename, content)
+            .map_err(|err| CompileError::io_write_error(interface_filename, err.to_string()))?;
+    }
+
     let linkable_formats = vec![
         FormatOption::Static,
         FormatOption::Shared,
@@ -114,7 +147,11 @@ fn main_compile(parameters: CompileParameters) -> Result<(), String> {
     ];
     if linkable_formats.contains(&out_format) && !parameters.skip_linking {
         let triple = get_target_triple(parameters.target);
-        let mut linker = linker::Linker::new(triple.as_str().to_str().unwrap())?;
+        let target = triple
+            .as_str()
+            .to_str()
+            .map_err(|err| CompileError::invalid_input(format!("Invalid target: {}", err)))?;
+        let mut linker = linker::Linker::new(target)?;
         linker
             .add_lib_path(".")
             .add_obj(Path::new(&output_filename));
@@ -135,3 +172,36 @@ fn main_compile(parameters: CompileParameters) -> Result<(), String> {
 
     Ok(())
 }
+
+fn main_format(parameters: FormatParameters) -> Result<(), CompileError> {
+    let sources = create_file_paths(&parameters.input)?;
+    let mut diagnostician = Diagnostician::new(parameters.error_format);
+    let formatted_sources = format_sources(sources, parameters.encoding, &mut diagnostician)?;
+
+    let mut unformatted_files = 0;
+    for (source, formatted) in formatted_sources {
+        if source.source == formatted {
+            continue;
+        }
+        if parameters.check {
+            println!("{} is not formatted", source.path);
+            unformatted_files += 1;
+        } else {
+            let content = match parameters.encoding {
+                Some(encoding) => encoding.encode(&formatted).0.into_owned(),
+                None => formatted.into_bytes(),
+            };
+            fs::write(&source.path, content).map_err(|err| {
+                CompileError::io_write_error(source.path.clone(), err.to_string())
+            })?;
+        }
+    }
+
+    if unformatted_files > 0 {
+        return Err(CompileError::invalid_input(format!(
+            "{} file(s) are not formatted",
+            unformatted_files
+        )));
+    }
+    Ok(())
+}
@@ -27,13 +27,17 @@ pub fn parse(mut lexer: ParseSession) -> ParsedAst {
                 //Don't reset linkage
                 continue;
             }
-            KeywordVarGlobal => unit
-                .global_vars
-                .push(parse_variable_block(&mut lexer, VariableBlockType::Global)),
-            KeywordProgram | KeywordClass | KeywordFunction | KeywordFunctionBlock => {
+            KeywordVarGlobal => unit.global_vars.push(parse_variable_block(
+                &mut lexer,
+                VariableBlockType::Global,
+                linkage,
+            )),
+            KeywordProgram | KeywordClass | KeywordFunction | KeywordFunctionBlock
+            | KeywordInterface => {
                 let params = match lexer.token {
                     KeywordProgram => (PouType::Program, KeywordEndProgram),
                     KeywordClass => (PouType::Class, KeywordEndClass),
+                    KeywordInterface => (PouType::Interface, KeywordEndInterface),
                     KeywordFunction => (PouType::Function, KeywordEndFunction),
                     _ => (PouType::FunctionBlock, KeywordEndFunctionBlock),
                 };
@@ -58,12 +62,20 @@ pub fn parse(mut lexer: ParseSession) -> ParsedAst {
                 let mut actions = parse_actions(&mut lexer, linkage, last_pou);
                 unit.implementations.append(&mut actions);
             }
+            KeywordConfiguration => {
+                let configuration = parse_configuration(&mut lexer, &mut unit.global_vars);
+                unit.configurations.push(configuration);
+            }
             KeywordType => {
-                if let Some(unit_type) = parse_type(&mut lexer) {
+                if let Some(unit_type) = parse_type(&mut lexer, linkage) {
                     unit.types.push(unit_type);
                 }
             }
-            KeywordEndActions | End => return (unit, lexer.diagnostics),
+            KeywordEndActions | End => {
+                unit.suppressions = lexer.suppressions;
+                unit.comments = lexer.comments;
+                return (unit, lexer.diagnostics);
+            }
             _ => {
                 lexer.accept_diagnostic(Diagnostic::unexpected_token_found(
                     "StartKeyword",
@@ -151,53 +163,78 @@ fn parse_pou(
         let name = parse_identifier(lexer).unwrap_or_else(|| "".to_string()); // parse POU name
 
         // TODO: Parse USING directives
-        // TODO: Parse EXTENDS specifier
-        // TODO: Parse IMPLEMENTS specifier
+        let super_class = match pou_type {
+            // classes and function blocks can extend another class or function block
+            PouType::Class | PouType::FunctionBlock if lexer.allow(&KeywordExtends) => {
+                parse_identifier(lexer)
+            }
+            _ => None,
+        };
+        let interfaces = match pou_type {
+            // classes and function blocks can implement a list of interfaces
+            PouType::Class | PouType::FunctionBlock if lexer.allow(&KeywordImplements) => {
+                parse_identifier_list(lexer)
+            }
+            _ => vec![],
+        };
 
-        let return_type = if pou_type != PouType::Class {
+        let return_type = if pou_type != PouType::Class && pou_type != PouType::Interface {
             // parse an optional return type
             parse_return_type(lexer, &pou_type)
         } else {
-            // classes do not have a return type
+            // classes and interfaces do not have a return type
             None
         };
 
         // parse variable declarations. note that var in/out/inout
         // blocks are not allowed inside of class declarations.
+        // interfaces do not declare any variables.
         let mut variable_blocks = vec![];
         let allowed_var_types = match pou_type {
             PouType::Class => vec![KeywordVar],
+            PouType::Interface => vec![],
             _ => vec![
                 KeywordVar,
                 KeywordVarInput,
                 KeywordVarOutput,
                 KeywordVarInOut,
+                KeywordVarTemp,
             ],
         };
         while allowed_var_types.contains(&lexer.token) {
             variable_blocks.push(parse_variable_block(
                 lexer,
                 parse_variable_block_type(&lexer.token),
+                LinkageType::Internal,
             ));
         }
 
         let mut impl_pous = vec![];
         let mut implementations = vec![];
-        if pou_type == PouType::Class || pou_type == PouType::FunctionBlock {
+        if matches!(
+            pou_type,
+            PouType::Class | PouType::FunctionBlock | PouType::Interface
+        ) {
             // classes and function blocks can have methods. methods consist of a Pou part
             // and an implementation part. That's why we get another (Pou, Implementation)
             // tuple out of parse_method() that has to be added to the list of Pous and
             // implementations. Note that function blocks have to start with the method
             // declarations before their implementation.
+            // an interface's methods are prototypes only, so they are treated as external
+            let method_linkage = if pou_type == PouType::Interface {
+                LinkageType::External
+            } else {
+                linkage
+            };
             while lexer.token == KeywordMethod {
-                if let Some((pou, implementation)) = parse_method(lexer, &name, linkage) {
+                if let Some((pou, implementation)) = parse_method(lexer, &name, method_linkage) {
                     impl_pous.push(pou);
                     implementations.push(implementation);
                 }
             }
         }
-        if pou_type != PouType::Class {
-            // a class may not contain an implementation
+        if pou_type != PouType::Class && pou_type != PouType::Interface {
+            // a class or an interface may not contain an implementation
             implementations.push(parse_implementation(
                 lexer,
                 linkage,
@@ -214,6 +251,9 @@ fn parse_pou(
             return_type,
             location: SourceRange::new(start..lexer.range().end),
             poly_mode,
+            super_class,
+            interfaces,
+            linkage,
         }];
         pous.append(&mut impl_pous);
 
@@ -316,6 +356,7 @@ fn parse_method(
             variable_blocks.push(parse_variable_block(
                 lexer,
                 parse_variable_block_type(&lexer.token),
+                LinkageType::Internal,
             ));
         }
 
@@ -347,12 +388,16 @@ fn parse_method(
                 return_type,
                 location: SourceRange::new(method_start..method_end),
                 poly_mode,
+                super_class: None,
+                interfaces: vec![],
+                linkage,
             },
             implementation,
         ))
     })
 }
 
+/// parses an optional access modifier, members and methods without one are PUBLIC
 fn parse_access_modifier(lexer: &mut ParseSession) -> AccessModifier {
     if lexer.allow(&KeywordAccessPublic) {
         AccessModifier::Public
@@ -363,10 +408,145 @@ fn parse_access_modifier(lexer: &mut ParseSession) -> AccessModifier {
     } else if lexer.allow(&KeywordAccessInternal) {
         AccessModifier::Internal
     } else {
-        AccessModifier::Protected
+        AccessModifier::Public
     }
 }
 
+/// parses a CONFIGURATION
+///
+/// the global variables declared in the configuration and its resources are added
+/// to the given `global_vars`
+fn parse_configuration(
+    lexer: &mut ParseSession,
+    global_vars: &mut Vec<VariableBlock>,
+) -> Configuration {
+    let start = lexer.range().start;
+    lexer.advance(); //consume CONFIGURATION
+    parse_any_in_region(lexer, vec![KeywordEndConfiguration], |lexer| {
+        let name = parse_identifier(lexer).unwrap_or_else(|| "".to_string());
+        let mut resources = vec![];
+        loop {
+            match lexer.token {
+                KeywordVarGlobal => global_vars.push(parse_variable_block(
+                    lexer,
+                    VariableBlockType::Global,
+                    LinkageType::Internal,
+                )),
+                KeywordResource => resources.push(parse_resource(lexer, global_vars)),
+                _ => break,
+            }
+        }
+        Configuration {
+            name,
+            resources,
+            location: SourceRange::new(start..lexer.range().end),
+        }
+    })
+}
+
+/// parses a RESOURCE of a configuration, e.g. `RESOURCE r ON PLC ... END_RESOURCE`
+fn parse_resource(lexer: &mut ParseSession, global_vars: &mut Vec<VariableBlock>) -> Resource {
+    let start = lexer.range().start;
+    lexer.advance(); //consume RESOURCE
+    parse_any_in_region(lexer, vec![KeywordEndResource], |lexer| {
+        let name = parse_identifier(lexer).unwrap_or_else(|| "".to_string());
+        lexer.consume_or_report(KeywordOn);
+        let target = parse_identifier(lexer).unwrap_or_else(|| "".to_string());
+        let mut tasks = vec![];
+        let mut programs = vec![];
+        loop {
+            match lexer.token {
+                KeywordVarGlobal => global_vars.push(parse_variable_block(
+                    lexer,
+                    VariableBlockType::Global,
+                    LinkageType::Internal,
+                )),
+                KeywordTask => tasks.push(parse_task(lexer)),
+                KeywordProgram => programs.push(parse_program_configuration(lexer)),
+                _ => break,
+            }
+        }
+        Resource {
+            name,
+            target,
+            tasks,
+            programs,
+            location: SourceRange::new(start..lexer.range().end),
+        }
+    })
+}
+
+/// parses a TASK of a resource, e.g. `TASK t(INTERVAL := T#10ms, PRIORITY := 1);`
+fn parse_task(lexer: &mut ParseSession) -> Task {
+    let start = lexer.range().start;
+    lexer.advance(); //consume TASK
+    parse_any_in_region(lexer, vec![KeywordSemicolon], |lexer| {
+        let name = parse_identifier(lexer).unwrap_or_else(|| "".to_string());
+        let mut interval = None;
+        let mut priority = None;
+        lexer.consume_or_report(KeywordParensOpen);
+        let properties = parse_expression(lexer);
+        lexer.consume_or_report(KeywordParensClose);
+        for property in flatten_expression_list(&properties) {
+            let value = match property {
+                AstStatement::Assignment { left, right, .. } => match left.as_ref() {
+                    AstStatement::Reference { name, .. }
+                        if name.eq_ignore_ascii_case("INTERVAL") =>
+                    {
+                        Some((&mut interval, right))
+                    }
+                    AstStatement::Reference { name, .. }
+                        if name.eq_ignore_ascii_case("PRIORITY") =>
+                    {
+                        Some((&mut priority, right))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some((target, value)) = value {
+                *target = Some(value.as_ref().clone());
+            } else {
+                let location = property.get_location();
+                let found = lexer.slice_region(location.to_range()).to_string();
+                lexer.accept_diagnostic(Diagnostic::unexpected_token_found(
+                    "INTERVAL or PRIORITY",
+                    found.as_str(),
+                    location,
+                ));
+            }
+        }
+        Task {
+            name,
+            interval,
+            priority,
+            location: SourceRange::new(start..lexer.last_range.end),
+        }
+    })
+}
+
+/// parses a PROGRAM instance of a resource, e.g. `PROGRAM p WITH t : MyProg;`
+fn parse_program_configuration(lexer: &mut ParseSession) -> ProgramConfiguration {
+    let start = lexer.range().start;
+    lexer.advance(); //consume PROGRAM
+    parse_any_in_region(lexer, vec![KeywordSemicolon], |lexer| {
+        let instance_name = parse_identifier(lexer).unwrap_or_else(|| "".to_string());
+        let task_name = if lexer.allow(&KeywordWith) {
+            parse_identifier(lexer)
+        } else {
+            None
+        };
+        lexer.consume_or_report(KeywordColon);
+        let type_name = parse_identifier(lexer).unwrap_or_else(|| "".to_string());
+        ProgramConfiguration {
+            instance_name,
+            task_name,
+            type_name,
+            location: SourceRange::new(start..lexer.last_range.end),
+        }
+    })
+}
+
 /// parse identifier and advance if successful
 fn parse_identifier(lexer: &mut ParseSession) -> Option<String> {
     let pou_name = lexer.slice().to_string();
@@ -383,6 +563,19 @@ fn parse_identifier(lexer: &mut ParseSession) -> Option<String> {
     }
 }
 
+/// parse a comma-separated list of identifiers (e.g. `I1, I2, I3`)
+fn parse_identifier_list(lexer: &mut ParseSession) -> Vec<String> {
+    let mut identifiers = vec![];
+    loop {
+        if let Some(identifier) = parse_identifier(lexer) {
+            identifiers.push(identifier);
+        }
+        if !lexer.allow(&KeywordComma) {
+            return identifiers;
+        }
+    }
+}
+
 fn parse_implementation(
     lexer: &mut ParseSession,
     linkage: LinkageType,
@@ -449,7 +642,7 @@ fn parse_action(
 }
 
 // TYPE ... END_TYPE
-fn parse_type(lexer: &mut ParseSession) -> Option<UserTypeDeclaration> {
+fn parse_type(lexer: &mut ParseSession, linkage: LinkageType) -> Option<UserTypeDeclaration> {
     lexer.advance(); // consume the TYPE
     let start = lexer.location().get_start();
     let name = lexer.slice_and_advance();
@@ -464,6 +657,7 @@ fn parse_type(lexer: &mut ParseSession) -> Option<UserTypeDeclaration> {
             data_type,
             initializer,
             location: (start..end).into(),
+            linkage,
         })
     } else {
         None
@@ -542,9 +736,17 @@ fn parse_data_type_definition(
         } else {
             lexer.advance();
         }
-        parse_pointer_definition(lexer, name, start_pos)
+        parse_pointer_definition(lexer, name, start_pos, false)
     } else if lexer.allow(&KeywordRef) {
-        parse_pointer_definition(lexer, name, lexer.last_range.start)
+        parse_pointer_definition(lexer, name, lexer.last_range.start, false)
+    } else if lexer.allow(&KeywordReference) {
+        let start_pos = lexer.last_range.start;
+        if let Err(diag) = lexer.expect(KeywordTo) {
+            lexer.accept_diagnostic(diag);
+        } else {
+            lexer.advance();
+        }
+        parse_pointer_definition(lexer, name, start_pos, true)
     } else if lexer.allow(&KeywordParensOpen) {
         parse_enum_type_definition(lexer, name)
     } else if lexer.token == KeywordString || lexer.token == KeywordWideString {
@@ -566,6 +768,7 @@ fn parse_pointer_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
     start_pos: usize,
+    auto_deref: bool,
 ) -> Option<(DataTypeDeclaration, Option<AstStatement>)> {
     parse_data_type_definition(lexer, None).map(|(decl, initializer)| {
         (
@@ -573,6 +776,7 @@ fn parse_pointer_definition(
                 data_type: DataType::PointerType {
                     name,
                     referenced_type: Box::new(decl),
+                    auto_deref,
                 },
                 location: (start_pos..lexer.last_range.end).into(),
             },
@@ -725,7 +929,11 @@ fn parse_array_type_definition(
         expect_token!(lexer, KeywordSquareParensOpen, None);
         lexer.advance();
 
-        let range_statement = parse_expression(lexer);
+        let range_statement = if lexer.token == OperatorMultiplication {
+            parse_variable_length_dimensions(lexer)
+        } else {
+            parse_expression(lexer)
+        };
 
         expect_token!(lexer, KeywordSquareParensClose, None);
         lexer.advance();
@@ -750,6 +958,40 @@ fn parse_array_type_definition(
     })
 }
 
+/// parses the `*, *` dimensions of a variable-length array (`ARRAY[*, *] OF INT`)
+/// every `*` results in an EmptyStatement, multiple dimensions in an ExpressionList
+fn parse_variable_length_dimensions(lexer: &mut ParseSession) -> AstStatement {
+    let mut dimensions = vec![];
+    loop {
+        dimensions.push(AstStatement::EmptyStatement {
+            location: lexer.location(),
+            id: lexer.next_id(),
+        });
+        expect_token!(
+            lexer,
+            OperatorMultiplication,
+            AstStatement::ExpressionList {
+                expressions: dimensions,
+                id: lexer.next_id(),
+            }
+        );
+        lexer.advance();
+        if lexer.token == KeywordComma {
+            lexer.advance();
+        } else {
+            break;
+        }
+    }
+    if dimensions.len() == 1 {
+        dimensions.remove(0)
+    } else {
+        AstStatement::ExpressionList {
+            expressions: dimensions,
+            id: lexer.next_id(),
+        }
+    }
+}
+
 /// parse a body and recovers until the given `end_keywords`
 fn parse_body_in_region(lexer: &mut ParseSession, end_keywords: Vec<Token>) -> Vec<AstStatement> {
     parse_any_in_region(lexer, end_keywords, |lexer| parse_body_standalone(lexer))
@@ -827,6 +1069,7 @@ fn parse_variable_block_type(block_type: &Token) -> VariableBlockType {
 fn parse_variable_block(
     lexer: &mut ParseSession,
     variable_block_type: VariableBlockType,
+    linkage: LinkageType,
 ) -> VariableBlock {
     let location = lexer.location();
     //Consume the type keyword
@@ -834,8 +1077,15 @@ fn parse_variable_block(
 
     let constant = lexer.allow(&KeywordConstant);
 
-    let retain = lexer.allow(&KeywordRetain);
-    lexer.allow(&KeywordNonRetain);
+    //PERSISTENT variables are retained as well
+    let mut retain = false;
+    while matches!(
+        lexer.token,
+        KeywordRetain | KeywordPersistent | KeywordNonRetain
+    ) {
+        retain |= lexer.token != KeywordNonRetain;
+        lexer.advance();
+    }
 
     let access = parse_access_modifier(lexer);
 
@@ -849,6 +1099,7 @@ fn parse_variable_block(
         variables,
         variable_block_type,
         location,
+        linkage,
     }
 }
 
@@ -869,7 +1120,7 @@ fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
         let identifier_end = location.get_end();
         var_names.push((lexer.slice_and_advance(), location));
 
-        if lexer.token == KeywordColon {
+        if lexer.token == KeywordColon || lexer.token == KeywordAt {
             break;
         }
 
@@ -882,6 +1133,13 @@ fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
         }
     }
 
+    // located variables declare their address before the data type
+    let address = if lexer.allow(&KeywordAt) {
+        parse_hardware_address(lexer)
+    } else {
+        None
+    };
+
     // colon has to come before the data type
     if !lexer.allow(&KeywordColon) {
         lexer.accept_diagnostic(Diagnostic::missing_token(
@@ -899,8 +1157,51 @@ fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
                 data_type: data_type.clone(),
                 location,
                 initializer: initializer.clone(),
+                address: address.clone(),
             });
         }
     }
     variables
 }
+
+/// parses the address of a located variable, e.g. `%IX1.2` or `%QW4`
+fn parse_hardware_address(lexer: &mut ParseSession) -> Option<HardwareAddress> {
+    if lexer.token != HardwareAccess {
+        lexer.accept_diagnostic(Diagnostic::unexpected_token_found(
+            "hardware address",
+            lexer.slice(),
+            lexer.location(),
+        ));
+        return None;
+    }
+    let location = lexer.location();
+    let address = lexer.slice_and_advance();
+    //the address starts with '%' followed by the area, an optional size and the numbers
+    let mut chars = address.chars().skip(1).peekable();
+    let area = match chars.next().map(|it| it.to_ascii_uppercase()) {
+        Some('I') => HardwareAccessType::Input,
+        Some('Q') => HardwareAccessType::Output,
+        _ => HardwareAccessType::Memory,
+    };
+    let access = match chars.peek().map(|it| it.to_ascii_uppercase()) {
+        Some('B') => DirectAccessType::Byte,
+        Some('W') => DirectAccessType::Word,
+        Some('D') => DirectAccessType::DWord,
+        Some('L') => DirectAccessType::LWord,
+        _ => DirectAccessType::Bit,
+    };
+    if chars.peek().map_or(false, char::is_ascii_alphabetic) {
+        chars.next();
+    }
+    let address = chars
+        .collect::<String>()
+        .split('.')
+        .map(|it| it.parse().unwrap_or_default())
+        .collect();
+    Some(HardwareAddress {
+        area,
+        access,
+        address,
+        location,
+    })
+}
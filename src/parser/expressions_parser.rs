@@ -289,6 +289,13 @@ fn parse_leaf_expression(lexer: &mut ParseSession) -> AstStatement {
                     right: Box::new(parse_range_statement(lexer)),
                     id: lexer.next_id(),
                 }
+            } else if lexer.token == KeywordRefAssignment {
+                lexer.advance();
+                AstStatement::RefAssignment {
+                    left: Box::new(statement),
+                    right: Box::new(parse_range_statement(lexer)),
+                    id: lexer.next_id(),
+                }
             } else {
                 statement
             }
@@ -61,7 +61,7 @@ fn method_with_defaults_can_be_parsed() {
     let method = &unit.implementations[0];
 
     assert_eq!(method_pou.name, "MyClass.testMethod");
-    assert_eq!(method.access, Some(AccessModifier::Protected));
+    assert_eq!(method.access, Some(AccessModifier::Public));
     assert_eq!(method_pou.poly_mode, Some(PolymorphismMode::None));
     assert_eq!(method_pou.return_type, None);
     assert_eq!(method.overriding, false);
@@ -153,7 +153,7 @@ fn class_with_var_default_block() {
 
     assert_eq!(vblock.retain, false);
     assert_eq!(vblock.constant, false);
-    assert_eq!(vblock.access, AccessModifier::Protected);
+    assert_eq!(vblock.access, AccessModifier::Public);
     assert_eq!(vblock.variable_block_type, VariableBlockType::Local);
 }
 
@@ -207,7 +207,7 @@ fn method_with_var_block() {
 
     assert_eq!(vblock.retain, false);
     assert_eq!(vblock.constant, false);
-    assert_eq!(vblock.access, AccessModifier::Protected);
+    assert_eq!(vblock.access, AccessModifier::Public);
     assert_eq!(vblock.variable_block_type, VariableBlockType::Temp);
 }
 
@@ -295,7 +295,7 @@ fn fb_two_methods_can_be_parsed() {
 
     let method2 = &unit.implementations[1];
     assert_eq!(method2.name, "MyNewFb.otherMethod");
-    assert_eq!(method2.access, Some(AccessModifier::Protected));
+    assert_eq!(method2.access, Some(AccessModifier::Public));
 }
 
 #[test]
@@ -326,3 +326,93 @@ fn fb_method_with_return_type_can_be_parsed() {
     assert_ne!(method_pou.return_type, None);
     assert_eq!(method.overriding, true);
 }
+
+#[test]
+fn class_with_super_class_can_be_parsed() {
+    let lexer = lex("CLASS FINAL MyClass EXTENDS MyBaseClass END_CLASS");
+    let unit = parse(lexer).0;
+
+    let class = &unit.units[0];
+    assert_eq!(class.pou_type, PouType::Class);
+
+    assert_eq!(class.name, "MyClass");
+    assert_eq!(class.poly_mode, Some(PolymorphismMode::Final));
+    assert_eq!(class.super_class, Some("MyBaseClass".into()));
+}
+
+#[test]
+fn fb_with_super_class_can_be_parsed() {
+    let lexer = lex(r#"
+        FUNCTION_BLOCK MyFb EXTENDS MyBaseFb
+            VAR_INPUT x : INT; END_VAR
+        END_FUNCTION_BLOCK
+    "#);
+    let (unit, diagnostics) = parse(lexer);
+
+    let fb = &unit.units[0];
+    assert_eq!(fb.pou_type, PouType::FunctionBlock);
+    assert_eq!(fb.super_class, Some("MyBaseFb".into()));
+    assert_eq!(fb.variable_blocks.len(), 1);
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn class_without_super_class_has_no_super_class() {
+    let lexer = lex("CLASS MyClass END_CLASS");
+    let unit = parse(lexer).0;
+
+    assert_eq!(unit.units[0].super_class, None);
+}
+
+#[test]
+fn interface_with_methods_can_be_parsed() {
+    let lexer = lex(r#"
+        INTERFACE IShape
+            METHOD area : INT END_METHOD
+            METHOD scale
+                VAR_INPUT factor : INT; END_VAR
+            END_METHOD
+        END_INTERFACE
+    "#);
+    let (unit, diagnostics) = parse(lexer);
+    assert_eq!(diagnostics, vec![]);
+
+    let interface = &unit.units[0];
+    assert_eq!(interface.pou_type, PouType::Interface);
+    assert_eq!(interface.name, "IShape");
+    assert_eq!(interface.poly_mode, None);
+
+    let area = &unit.units[1];
+    assert_eq!(area.name, "IShape.area");
+    assert_eq!(
+        area.pou_type,
+        PouType::Method {
+            owner_class: "IShape".into()
+        }
+    );
+    assert_ne!(area.return_type, None);
+    assert_eq!(unit.units[2].name, "IShape.scale");
+    assert_eq!(unit.units[2].variable_blocks.len(), 1);
+
+    //the methods are prototypes only
+    assert_eq!(unit.implementations.len(), 2);
+    assert_eq!(unit.implementations[0].linkage, LinkageType::External);
+    assert_eq!(unit.implementations[1].linkage, LinkageType::External);
+}
+
+#[test]
+fn class_implementing_interfaces_can_be_parsed() {
+    let lexer = lex(r#"
+        CLASS MyClass EXTENDS MyBase IMPLEMENTS I1, I2 END_CLASS
+        FUNCTION_BLOCK MyFb IMPLEMENTS I3 END_FUNCTION_BLOCK
+    "#);
+    let (unit, diagnostics) = parse(lexer);
+    assert_eq!(diagnostics, vec![]);
+
+    let class = &unit.units[0];
+    assert_eq!(class.super_class, Some("MyBase".into()));
+    assert_eq!(class.interfaces, vec!["I1".to_string(), "I2".to_string()]);
+
+    let fb = &unit.units[1];
+    assert_eq!(fb.interfaces, vec!["I3".to_string()]);
+}
@@ -0,0 +1,101 @@
+use crate::{
+    ast::{AstStatement, ProgramConfiguration, SourceRange, VariableBlockType},
+    parser::{parse, tests::lex},
+    Diagnostic,
+};
+use pretty_assertions::*;
+
+#[test]
+fn configuration_with_resources_tasks_and_programs_can_be_parsed() {
+    let lexer = lex("
+        CONFIGURATION config
+            VAR_GLOBAL x : INT; END_VAR
+            RESOURCE res ON PLC
+                VAR_GLOBAL y : INT; END_VAR
+                TASK fast(INTERVAL := T#10ms, PRIORITY := 1);
+                TASK slow(PRIORITY := 2);
+                PROGRAM p1 WITH fast : MyProg;
+                PROGRAM p2 : MyProg;
+            END_RESOURCE
+        END_CONFIGURATION
+        ");
+    let (result, diagnostics) = parse(lexer);
+    assert_eq!(diagnostics, vec![]);
+
+    let configuration = &result.configurations[0];
+    assert_eq!(configuration.name, "config");
+
+    //the global variables of the configuration and its resource are global variables
+    assert_eq!(result.global_vars.len(), 2);
+    assert_eq!(
+        result.global_vars[0].variable_block_type,
+        VariableBlockType::Global
+    );
+    assert_eq!(result.global_vars[0].variables[0].name, "x");
+    assert_eq!(result.global_vars[1].variables[0].name, "y");
+
+    let resource = &configuration.resources[0];
+    assert_eq!(resource.name, "res");
+    assert_eq!(resource.target, "PLC");
+
+    let fast = &resource.tasks[0];
+    assert_eq!(fast.name, "fast");
+    assert!(matches!(
+        fast.interval,
+        Some(AstStatement::LiteralTime { .. })
+    ));
+    assert!(matches!(
+        fast.priority,
+        Some(AstStatement::LiteralInteger { value: 1, .. })
+    ));
+    let slow = &resource.tasks[1];
+    assert_eq!(slow.name, "slow");
+    assert_eq!(slow.interval, None);
+    assert!(matches!(
+        slow.priority,
+        Some(AstStatement::LiteralInteger { value: 2, .. })
+    ));
+
+    assert_eq!(
+        resource.programs,
+        vec![
+            ProgramConfiguration {
+                instance_name: "p1".into(),
+                task_name: Some("fast".into()),
+                type_name: "MyProg".into(),
+                location: SourceRange::new(266..295),
+            },
+            ProgramConfiguration {
+                instance_name: "p2".into(),
+                task_name: None,
+                type_name: "MyProg".into(),
+                location: SourceRange::new(313..332),
+            },
+        ]
+    );
+}
+
+#[test]
+fn unknown_task_properties_are_reported() {
+    let lexer = lex("
+        CONFIGURATION config
+            RESOURCE res ON PLC
+                TASK t(INTERVAL := T#10ms, SINGLE := x);
+            END_RESOURCE
+        END_CONFIGURATION
+        ");
+    let (result, diagnostics) = parse(lexer);
+
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::unexpected_token_found(
+            "INTERVAL or PRIORITY",
+            "SINGLE := x",
+            (105..116).into()
+        )]
+    );
+    let task = &result.configurations[0].resources[0].tasks[0];
+    assert_eq!(task.name, "t");
+    assert!(task.interval.is_some());
+    assert_eq!(task.priority, None);
+}
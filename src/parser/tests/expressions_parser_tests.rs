@@ -1,6 +1,7 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use crate::ast::{
-    AstStatement, DataType, DataTypeDeclaration, DirectAccessType, Operator, Pou, SourceRange,
+    AstStatement, DataType, DataTypeDeclaration, DirectAccessType, LinkageType, Operator, Pou,
+    SourceRange,
 };
 use crate::parser::parse;
 use crate::parser::tests::{literal_int, ref_to};
@@ -1367,6 +1368,32 @@ fn assignment_to_null() {
     assert_eq!(ast_string, expected_ast);
 }
 
+#[test]
+fn ref_assignment_test() {
+    let lexer = super::lex(
+        "
+        PROGRAM exp 
+        r REF= x;
+        END_PROGRAM
+        ",
+    );
+    let result = parse(lexer).0;
+
+    let prg = &result.implementations[0];
+    let statement = &prg.statements[0];
+
+    let ast_string = format!("{:#?}", statement);
+    let expected_ast = r#"RefAssignment {
+    left: Reference {
+        name: "r",
+    },
+    right: Reference {
+        name: "x",
+    },
+}"#;
+    assert_eq!(ast_string, expected_ast);
+}
+
 #[test]
 fn pointer_address_test() {
     let lexer = super::lex(
@@ -2601,6 +2628,8 @@ fn sized_string_as_function_return() {
     let expected = Pou {
         name: "foo".into(),
         poly_mode: None,
+        super_class: None,
+        interfaces: vec![],
         pou_type: crate::ast::PouType::Function,
         return_type: Some(DataTypeDeclaration::DataTypeDefinition {
             data_type: DataType::StringType {
@@ -2616,6 +2645,7 @@ fn sized_string_as_function_return() {
         }),
         variable_blocks: vec![],
         location: SourceRange::undefined(),
+        linkage: LinkageType::Internal,
     };
 
     assert_eq!(format!("{:?}", ast.units[0]), format!("{:?}", expected));
@@ -2634,6 +2664,8 @@ fn array_type_as_function_return() {
     let expected = Pou {
         name: "foo".into(),
         poly_mode: None,
+        super_class: None,
+        interfaces: vec![],
         pou_type: crate::ast::PouType::Function,
         return_type: Some(DataTypeDeclaration::DataTypeDefinition {
             data_type: DataType::ArrayType {
@@ -2660,6 +2692,7 @@ fn array_type_as_function_return() {
         }),
         variable_blocks: vec![],
         location: SourceRange::undefined(),
+        linkage: LinkageType::Internal,
     };
 
     assert_eq!(format!("{:?}", ast.units[0]), format!("{:?}", expected));
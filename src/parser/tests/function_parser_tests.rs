@@ -33,6 +33,27 @@ fn simple_foo_function_block_can_be_parsed() {
     assert!(prg.return_type.is_none());
 }
 
+#[test]
+fn temp_variables_of_functions_and_function_blocks_can_be_parsed() {
+    let lexer = lex("
+        FUNCTION foo : INT VAR_TEMP x : INT; END_VAR END_FUNCTION
+        FUNCTION_BLOCK bar VAR_INPUT y : INT; END_VAR VAR_TEMP z : INT; END_VAR END_FUNCTION_BLOCK
+        ");
+    let (result, diagnostics) = parse(lexer);
+
+    assert_eq!(diagnostics, vec![]);
+    let foo = &result.units[0];
+    assert_eq!(
+        foo.variable_blocks[0].variable_block_type,
+        VariableBlockType::Temp
+    );
+    let bar = &result.units[1];
+    assert_eq!(
+        bar.variable_blocks[1].variable_block_type,
+        VariableBlockType::Temp
+    );
+}
+
 #[test]
 fn a_function_with_varargs_can_be_parsed() {
     let lexer = lex("FUNCTION foo : INT VAR_INPUT x : INT; y : ...; END_VAR END_FUNCTION");
@@ -124,7 +145,7 @@ fn varargs_parameters_can_be_parsed() {
         }),
         variable_blocks: vec![VariableBlock {
             constant: false,
-            access: AccessModifier::Protected,
+            access: AccessModifier::Public,
             retain: false,
             variable_block_type: VariableBlockType::Input,
             location: SourceRange::undefined(),
@@ -139,6 +160,7 @@ fn varargs_parameters_can_be_parsed() {
                     },
                     initializer: None,
                     location: SourceRange::undefined(),
+                    address: None,
                 },
                 Variable {
                     name: "args2".into(),
@@ -155,11 +177,16 @@ fn varargs_parameters_can_be_parsed() {
                     },
                     initializer: None,
                     location: SourceRange::undefined(),
+                    address: None,
                 },
             ],
+            linkage: LinkageType::Internal,
         }],
         location: SourceRange::undefined(),
         poly_mode: None,
+        super_class: None,
+        interfaces: vec![],
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:#?}", expected), format!("{:#?}", x).as_str());
 }
@@ -26,6 +26,25 @@ fn programs_can_be_external() {
     assert_eq!(LinkageType::External, implementation.linkage);
 }
 
+#[test]
+fn global_variables_and_types_can_be_external() {
+    let lexer = lex("
+        @EXTERNAL VAR_GLOBAL x : INT; END_VAR
+        @EXTERNAL TYPE MyEnum : (red, green); END_TYPE
+        @EXTERNAL FUNCTION foo : INT END_FUNCTION
+        VAR_GLOBAL y : INT; END_VAR
+        TYPE MyStruct : STRUCT a : INT; END_STRUCT END_TYPE
+        ");
+    let parse_result = parse(lexer).0;
+
+    assert_eq!(LinkageType::External, parse_result.global_vars[0].linkage);
+    assert_eq!(LinkageType::External, parse_result.types[0].linkage);
+    assert_eq!(LinkageType::External, parse_result.units[0].linkage);
+    //the linkage only applies to the next element
+    assert_eq!(LinkageType::Internal, parse_result.global_vars[1].linkage);
+    assert_eq!(LinkageType::Internal, parse_result.types[1].linkage);
+}
+
 #[test]
 fn exponent_literals_parsed_as_variables() {
     let lexer = super::lex(
@@ -46,6 +65,8 @@ fn exponent_literals_parsed_as_variables() {
         name: "E1".into(),
         pou_type: PouType::Function,
         poly_mode: None,
+        super_class: None,
+        interfaces: vec![],
         return_type: Some(DataTypeDeclaration::DataTypeReference {
             referenced_type: "E2".into(),
             location: SourceRange::undefined(),
@@ -64,9 +85,12 @@ fn exponent_literals_parsed_as_variables() {
                 },
                 initializer: None,
                 location: SourceRange::undefined(),
+                address: None,
             }],
+            linkage: LinkageType::Internal,
         }],
         location: SourceRange::undefined(),
+        linkage: LinkageType::Internal,
     };
     assert_eq!(format!("{:#?}", expected), format!("{:#?}", pou).as_str());
     let implementation = &parse_result.implementations[0];
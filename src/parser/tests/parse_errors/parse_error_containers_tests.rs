@@ -258,7 +258,7 @@ fn unclosed_var_container() {
             "{:#?}",
             VariableBlock {
                 constant: false,
-                access: AccessModifier::Protected,
+                access: AccessModifier::Public,
                 retain: false,
                 variable_block_type: VariableBlockType::Local,
                 location: SourceRange::undefined(),
@@ -270,7 +270,9 @@ fn unclosed_var_container() {
                     },
                     initializer: None,
                     location: SourceRange::undefined(),
-                }]
+                    address: None,
+                }],
+                linkage: LinkageType::Internal,
             }
         )
     );
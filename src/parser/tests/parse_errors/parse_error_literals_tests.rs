@@ -168,6 +168,7 @@ fn string_with_round_parens_can_be_parsed() {
                 },
                 initializer: None,
                 location: (18..42).into(),
+                linkage: LinkageType::Internal,
             },
             UserTypeDeclaration {
                 data_type: DataType::StringType {
@@ -186,6 +187,7 @@ fn string_with_round_parens_can_be_parsed() {
                     id: 0,
                 }),
                 location: SourceRange::undefined(),
+                linkage: LinkageType::Internal,
             },
             UserTypeDeclaration {
                 data_type: DataType::StringType {
@@ -199,6 +201,7 @@ fn string_with_round_parens_can_be_parsed() {
                 },
                 initializer: None,
                 location: SourceRange::undefined(),
+                linkage: LinkageType::Internal,
             }
         ]
     );
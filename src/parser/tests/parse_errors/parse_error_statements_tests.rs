@@ -272,7 +272,7 @@ fn invalid_variable_name_error_recovery() {
             "{:#?}",
             VariableBlock {
                 constant: false,
-                access: AccessModifier::Protected,
+                access: AccessModifier::Public,
                 retain: false,
                 location: SourceRange::undefined(),
                 variables: vec![Variable {
@@ -283,8 +283,10 @@ fn invalid_variable_name_error_recovery() {
                     },
                     initializer: None,
                     location: SourceRange::undefined(),
+                    address: None,
                 },],
                 variable_block_type: VariableBlockType::Local,
+                linkage: LinkageType::Internal,
             }
         )
     );
@@ -1031,9 +1033,11 @@ fn pointer_type_without_to_test() {
                 referenced_type: "INT".to_string(),
                 location: SourceRange::undefined(),
             }),
+            auto_deref: false,
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(
         format!("{:#?}", expected),
@@ -1067,9 +1071,11 @@ fn pointer_type_with_wrong_keyword_to_test() {
                 referenced_type: "tu".to_string(),
                 location: SourceRange::undefined(),
             }),
+            auto_deref: false,
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(
         format!("{:#?}", expected),
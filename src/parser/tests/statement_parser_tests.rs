@@ -155,6 +155,7 @@ fn inline_enum_declaration_can_be_parsed() {
         },
         initializer: None,
         location: SourceRange::undefined(),
+        address: None,
     };
     let expected_ast = format!("{:#?}", &v);
     assert_eq!(ast_string, expected_ast);
@@ -33,6 +33,7 @@ fn simple_struct_type_can_be_parsed() {
                         },
                         initializer: None,
                         location: SourceRange::undefined(),
+                        address: None,
                     },
                     Variable {
                         name: "Two".to_string(),
@@ -42,6 +43,7 @@ fn simple_struct_type_can_be_parsed() {
                         },
                         initializer: None,
                         location: SourceRange::undefined(),
+                        address: None,
                     },
                     Variable {
                         name: "Three".to_string(),
@@ -51,11 +53,13 @@ fn simple_struct_type_can_be_parsed() {
                         },
                         initializer: None,
                         location: SourceRange::undefined(),
+                        address: None,
                     },
                 ),
             },
             initializer: None,
             location: SourceRange::undefined(),
+            linkage: LinkageType::Internal,
         }
     );
     assert_eq!(ast_string, expected_ast);
@@ -77,6 +81,7 @@ fn simple_enum_type_can_be_parsed() {
         },
         initializer: None,
         location: SourceRange::undefined(),
+        linkage: LinkageType::Internal,
     };
     let expected_string = format!("{:#?}", epxtected_ast);
     assert_eq!(ast_string, expected_string);
@@ -101,6 +106,7 @@ fn type_alias_can_be_parsed() {
             },
             initializer: None,
             location: SourceRange::undefined(),
+            linkage: LinkageType::Internal,
         }
     );
 
@@ -140,6 +146,48 @@ fn array_type_can_be_parsed_test() {
             },
             initializer: None,
             location: SourceRange::undefined(),
+            linkage: LinkageType::Internal,
+        }
+    );
+
+    assert_eq!(ast_string, expected_ast);
+}
+
+#[test]
+fn variable_length_array_type_can_be_parsed_test() {
+    let (result, diagnostics) = parse(lex(r#"
+            TYPE Matrix : ARRAY[*, *] OF INT; END_TYPE
+            "#));
+
+    assert_eq!(diagnostics, vec![]);
+    let ast_string = format!("{:#?}", &result.types[0]);
+
+    let expected_ast = format!(
+        "{:#?}",
+        &UserTypeDeclaration {
+            data_type: DataType::ArrayType {
+                name: Some("Matrix".to_string()),
+                bounds: AstStatement::ExpressionList {
+                    expressions: vec![
+                        AstStatement::EmptyStatement {
+                            location: SourceRange::undefined(),
+                            id: 0,
+                        },
+                        AstStatement::EmptyStatement {
+                            location: SourceRange::undefined(),
+                            id: 0,
+                        },
+                    ],
+                    id: 0,
+                },
+                referenced_type: Box::new(DataTypeDeclaration::DataTypeReference {
+                    referenced_type: "INT".to_string(),
+                    location: SourceRange::undefined(),
+                }),
+            },
+            initializer: None,
+            location: SourceRange::undefined(),
+            linkage: LinkageType::Internal,
         }
     );
 
@@ -170,6 +218,7 @@ fn string_type_can_be_parsed_test() {
                 },
                 initializer: None,
                 location: SourceRange::undefined(),
+                linkage: LinkageType::Internal,
             },
             UserTypeDeclaration {
                 data_type: DataType::StringType {
@@ -188,6 +237,7 @@ fn string_type_can_be_parsed_test() {
                     id: 0,
                 }),
                 location: SourceRange::undefined(),
+                linkage: LinkageType::Internal,
             }
         ]
     );
@@ -217,6 +267,7 @@ fn wide_string_type_can_be_parsed_test() {
             },
             initializer: None,
             location: SourceRange::undefined(),
+            linkage: LinkageType::Internal,
         }
     );
 
@@ -257,6 +308,7 @@ fn subrangetype_can_be_parsed() {
         },
         initializer: None,
         location: (0..0).into(),
+        address: None,
     };
     assert_eq!(format!("{:#?}", expected), format!("{:#?}", x).as_str());
 }
@@ -320,9 +372,11 @@ fn pointer_type_test() {
                 referenced_type: "INT".to_string(),
                 location: SourceRange::undefined(),
             }),
+            auto_deref: false,
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(
         format!("{:#?}", expected),
@@ -351,9 +405,39 @@ fn ref_type_test() {
                 referenced_type: "INT".to_string(),
                 location: SourceRange::undefined(),
             }),
+            auto_deref: false,
+        },
+        location: SourceRange::undefined(),
+        initializer: None,
+        linkage: LinkageType::Internal,
+    };
+    assert_eq!(
+        format!("{:#?}", expected),
+        format!("{:#?}", reference_type).as_str()
+    );
+    assert_eq!(diagnostics.len(), 0)
+}
+
+#[test]
+fn reference_to_type_test() {
+    let (result, diagnostics) = parse(lex(r#"
+        TYPE SampleReference :
+            REFERENCE TO INT;
+        END_TYPE 
+        "#));
+    let reference_type = &result.types[0];
+    let expected = UserTypeDeclaration {
+        data_type: DataType::PointerType {
+            name: Some("SampleReference".into()),
+            referenced_type: Box::new(DataTypeDeclaration::DataTypeReference {
+                referenced_type: "INT".to_string(),
+                location: SourceRange::undefined(),
+            }),
+            auto_deref: true,
         },
         location: SourceRange::undefined(),
         initializer: None,
+        linkage: LinkageType::Internal,
     };
     assert_eq!(
         format!("{:#?}", expected),
@@ -380,11 +464,13 @@ fn global_pointer_declaration() {
                     referenced_type: "INT".to_string(),
                     location: SourceRange::undefined(),
                 }),
+                auto_deref: false,
             },
             location: SourceRange::undefined(),
         },
         initializer: None,
         location: (0..0).into(),
+        address: None,
     };
     assert_eq!(
         format!("{:#?}", expected),
@@ -400,11 +486,13 @@ fn global_pointer_declaration() {
                     referenced_type: "INT".to_string(),
                     location: SourceRange::undefined(),
                 }),
+                auto_deref: false,
             },
             location: SourceRange::undefined(),
         },
         initializer: None,
         location: (0..0).into(),
+        address: None,
     };
     assert_eq!(
         format!("{:#?}", expected),
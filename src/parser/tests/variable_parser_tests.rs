@@ -1,4 +1,7 @@
-use crate::parser::{parse, tests::lex};
+use crate::{
+    parser::{parse, tests::lex},
+    Diagnostic,
+};
 
 #[test]
 fn empty_global_vars_can_be_parsed() {
@@ -131,3 +134,80 @@ fn two_global_vars_can_be_parsed() {
 ]"#;
     assert_eq!(ast_string, expected_ast)
 }
+
+#[test]
+fn located_variables_can_be_parsed() {
+    let lexer = lex("VAR_GLOBAL x AT %IX1.2 : BOOL; y AT %QW4 : WORD; z AT %md10 : DINT; END_VAR");
+    let (result, diagnostics) = parse(lexer);
+    assert_eq!(diagnostics, vec![]);
+
+    let vars = &result.global_vars[0]; //globar_vars
+    let ast_string = format!("{:#?}", vars);
+    let expected_ast = r#"VariableBlock {
+    variables: [
+        Variable {
+            name: "x",
+            data_type: DataTypeReference {
+                referenced_type: "BOOL",
+            },
+            address: Some(
+                %IX1.2,
+            ),
+        },
+        Variable {
+            name: "y",
+            data_type: DataTypeReference {
+                referenced_type: "WORD",
+            },
+            address: Some(
+                %QW4,
+            ),
+        },
+        Variable {
+            name: "z",
+            data_type: DataTypeReference {
+                referenced_type: "DINT",
+            },
+            address: Some(
+                %MD10,
+            ),
+        },
+    ],
+    variable_block_type: Global,
+}"#;
+    assert_eq!(ast_string, expected_ast)
+}
+
+#[test]
+fn located_variables_without_an_address_report_an_error() {
+    let lexer = lex("VAR_GLOBAL x AT : BOOL; END_VAR");
+    let (result, diagnostics) = parse(lexer);
+
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::unexpected_token_found(
+            "hardware address",
+            ":",
+            (16..17).into()
+        )]
+    );
+    assert_eq!(result.global_vars[0].variables[0].address, None);
+}
+
+#[test]
+fn persistent_blocks_are_retained() {
+    let lexer = lex("
+        VAR_GLOBAL PERSISTENT x : INT; END_VAR
+        VAR_GLOBAL RETAIN PERSISTENT y : INT; END_VAR
+        VAR_GLOBAL NON_RETAIN z : INT; END_VAR
+        ");
+    let (result, diagnostics) = parse(lexer);
+    assert_eq!(diagnostics, vec![]);
+
+    let retained = result
+        .global_vars
+        .iter()
+        .map(|it| it.retain)
+        .collect::<Vec<_>>();
+    assert_eq!(retained, vec![true, true, false]);
+}
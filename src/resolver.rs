@@ -14,11 +14,12 @@ use crate::{
         AstId, AstStatement, CompilationUnit, DataType, DataTypeDeclaration, Operator, Pou,
         UserTypeDeclaration, Variable,
     },
+    builtins,
     index::{ImplementationIndexEntry, ImplementationType, Index, VariableIndexEntry},
     typesystem::{
-        self, get_bigger_type_borrow, DataTypeInformation, BOOL_TYPE, BYTE_TYPE,
-        DATE_AND_TIME_TYPE, DATE_TYPE, DINT_TYPE, DWORD_TYPE, LINT_TYPE, REAL_TYPE, STRING_TYPE,
-        TIME_OF_DAY_TYPE, TIME_TYPE, VOID_TYPE, WORD_TYPE, WSTRING_TYPE,
+        self, get_bigger_type_borrow, DataTypeInformation, StringEncoding, TypeNature, BOOL_TYPE,
+        BYTE_TYPE, DATE_AND_TIME_TYPE, DATE_TYPE, DINT_TYPE, DWORD_TYPE, LINT_TYPE, LWORD_TYPE,
+        REAL_TYPE, STRING_TYPE, TIME_OF_DAY_TYPE, TIME_TYPE, VOID_TYPE, WORD_TYPE, WSTRING_TYPE,
     },
 };
 
@@ -190,6 +191,14 @@ impl AnnotationMap {
         self.type_map.get(&s.get_id())
     }
 
+    /// returns the qualified names of all variables referenced by the annotated statements
+    pub fn get_referenced_variables(&self) -> impl Iterator<Item = &str> {
+        self.type_map.values().filter_map(|it| match it {
+            StatementAnnotation::Variable { qualified_name, .. } => Some(qualified_name.as_str()),
+            _ => None,
+        })
+    }
+
     pub fn has_type_annotation(&self, id: &usize) -> bool {
         self.type_map.contains_key(id)
     }
@@ -372,12 +381,17 @@ impl<'i> TypeAnnotator<'i> {
                 reference, access, ..
             } => {
                 visit_all_statements!(self, ctx, reference, access);
-                let array_type = self
-                    .annotation_map
-                    .get_type_or_void(reference, self.index)
-                    .get_type_information();
+                //VAR_IN_OUT arrays are accessed through their auto-deref pointer
+                let array_type = self.get_value_type(
+                    self.annotation_map
+                        .get_type_or_void(reference, self.index)
+                        .get_type_information(),
+                );
                 if let DataTypeInformation::Array {
                     inner_type_name, ..
+                }
+                | DataTypeInformation::VariableLengthArray {
+                    inner_type_name, ..
                 } = array_type
                 {
                     let t = self
@@ -438,23 +452,43 @@ impl<'i> TypeAnnotator<'i> {
                             resulting_type: DWORD_TYPE.into(),
                         },
                     ),
+                    crate::ast::DirectAccessType::LWord => self.annotation_map.annotate(
+                        statement,
+                        StatementAnnotation::Value {
+                            resulting_type: LWORD_TYPE.into(),
+                        },
+                    ),
                 }
             }
-            AstStatement::BinaryExpression { left, right, .. } => {
+            AstStatement::BinaryExpression {
+                left,
+                right,
+                operator,
+                ..
+            } => {
                 visit_all_statements!(self, ctx, left, right);
-                let left = &self
-                    .annotation_map
-                    .get_type_or_void(left, self.index)
-                    .get_type_information();
-                let right = &self
-                    .annotation_map
-                    .get_type_or_void(right, self.index)
-                    .get_type_information();
+                //references and VAR_IN_OUTs are dereferenced automatically
+                let left = self.get_value_type(
+                    self.annotation_map
+                        .get_type_or_void(left, self.index)
+                        .get_type_information(),
+                );
+                let right = self.get_value_type(
+                    self.annotation_map
+                        .get_type_or_void(right, self.index)
+                        .get_type_information(),
+                );
 
                 if left.is_numerical() && right.is_numerical() {
                     let bigger_name = get_bigger_type_borrow(left, right, self.index).get_name();
                     self.annotation_map
                         .annotate(statement, StatementAnnotation::expression(bigger_name));
+                } else if let Some(pointer) = get_pointer_arithmetic_type(operator, left, right) {
+                    //pointer +/- integer results in a pointer of the same type
+                    self.annotation_map.annotate(
+                        statement,
+                        StatementAnnotation::expression(pointer.get_name()),
+                    );
                 }
             }
             AstStatement::UnaryExpression {
@@ -496,9 +530,30 @@ impl<'i> TypeAnnotator<'i> {
                                     format!("{}.{}", qualifier, name).as_str(),
                                     self.index,
                                 )
+                                // 4th try - look for a method inherited from a super class
+                                .or_else(|| {
+                                    self.index
+                                        .find_method(qualifier, name)
+                                        .map(|it| to_function_annotation(it, self.index))
+                                })
                             },
                             |v| Some(to_variable_annotation(v, self.index, ctx.constant)),
                         )
+                } else if name.eq_ignore_ascii_case("SUPER") {
+                    // SUPER is a pointer to the super class of the current class/function block
+                    ctx.pou
+                        .and_then(|pou_name| {
+                            self.index
+                                .find_implementation(pou_name)
+                                .and_then(ImplementationIndexEntry::get_associated_class_name)
+                                .map(String::as_str)
+                                .or(Some(pou_name))
+                        })
+                        .and_then(|class_name| self.index.find_pou(class_name))
+                        .filter(|pou| pou.get_super_class().is_some())
+                        .map(|pou| {
+                            StatementAnnotation::expression(&pou.get_super_pointer_type_name())
+                        })
                 } else {
                     // if we see no qualifier, we try some strategies ...
                     ctx.pou
@@ -541,7 +596,12 @@ impl<'i> TypeAnnotator<'i> {
                             let call_name = class_name
                                 .map(|it| format!("{}.{}", it, name))
                                 .unwrap_or_else(|| name.into());
-                            find_implementation_annotation(&call_name, self.index)
+                            find_implementation_annotation(&call_name, self.index).or_else(|| {
+                                // ... maybe it's a method inherited from a super class
+                                class_name
+                                    .and_then(|it| self.index.find_method(it, name))
+                                    .map(|it| to_function_annotation(it, self.index))
+                            })
                         })
                         .or_else(|| {
                             // ... last option is a global variable, where we ignore the current pou's name as a qualifier
@@ -578,6 +638,18 @@ impl<'i> TypeAnnotator<'i> {
                             }
                         })
                         .unwrap_or_else(|| (VOID_TYPE, false));
+                    //members are accessed through references and VAR_IN_OUTs (auto-deref pointers)
+                    let qualifier = match self.index.get_type_information_or_void(qualifier) {
+                        DataTypeInformation::Pointer {
+                            inner_type_name,
+                            auto_deref: true,
+                            ..
+                        } => self
+                            .index
+                            .get_effective_type_by_name(inner_type_name)
+                            .get_name(),
+                        _ => qualifier,
+                    };
                     let mut new_ctx = ctx.with_qualifier(qualifier.to_string());
                     new_ctx.constant = constant;
                     ctx = new_ctx;
@@ -605,6 +677,9 @@ impl<'i> TypeAnnotator<'i> {
                     self.visit_statement(ctx, left);
                 }
             }
+            AstStatement::RefAssignment { left, right, .. } => {
+                visit_all_statements!(self, ctx, left, right);
+            }
             AstStatement::OutputAssignment { left, right, .. } => {
                 visit_all_statements!(self, ctx, left, right);
                 if let Some(lhs) = ctx.call {
@@ -631,7 +706,8 @@ impl<'i> TypeAnnotator<'i> {
                             StatementAnnotation::Program { qualified_name } => {
                                 Some(qualified_name.clone())
                             }
-                            StatementAnnotation::Variable { resulting_type, .. } => {
+                            StatementAnnotation::Variable { resulting_type, .. }
+                            | StatementAnnotation::Value { resulting_type } => {
                                 //lets see if this is a FB
                                 if let Some(implementation) =
                                     self.index.find_implementation(resulting_type.as_str())
@@ -655,15 +731,22 @@ impl<'i> TypeAnnotator<'i> {
                     self.visit_statement(&ctx, s);
                 }
 
-                if let Some(StatementAnnotation::Function { return_type, .. }) =
-                    self.annotation_map.get(operator)
+                if let Some(StatementAnnotation::Function {
+                    return_type,
+                    qualified_name,
+                }) = self.annotation_map.get(operator)
                 {
-                    if let Some(return_type) = self
-                        .index
-                        .find_type(return_type)
-                        .and_then(|it| self.index.find_effective_type(it))
-                        .map(|it| it.get_name())
-                    {
+                    let return_type =
+                        self.index
+                            .find_type(return_type)
+                            .and_then(|it| self.index.find_effective_type(it))
+                            .and_then(|it| match it.get_type_information() {
+                                //the return type of a generic builtin function depends on its arguments
+                                DataTypeInformation::Generic { name, nature } => self
+                                    .resolve_generic_type(qualified_name, name, nature, parameters),
+                                _ => Some(it.get_name()),
+                            });
+                    if let Some(return_type) = return_type {
                         self.annotation_map
                             .annotate(statement, StatementAnnotation::expression(return_type));
                     }
@@ -703,6 +786,100 @@ impl<'i> TypeAnnotator<'i> {
         }
     }
 
+    /// returns the type of the values stored in a variable of the given type, the variables of
+    /// an auto-deref pointer type (references and VAR_IN_OUTs) store values of its inner type
+    fn get_value_type(&self, data_type: &'i DataTypeInformation) -> &'i DataTypeInformation {
+        match data_type {
+            DataTypeInformation::Pointer {
+                inner_type_name,
+                auto_deref: true,
+                ..
+            } => self
+                .index
+                .get_effective_type_by_name(inner_type_name)
+                .get_type_information(),
+            _ => data_type,
+        }
+    }
+
+    /// returns the actual type of the given generic type in a call to the given builtin function:
+    /// the bigger type of all arguments passed to parameters of this generic type
+    /// (integers passed to an `ANY_REAL` parameter result in a REAL or LREAL,
+    /// strings result in at least a default sized STRING or WSTRING)
+    fn resolve_generic_type(
+        &self,
+        builtin: &str,
+        generic_name: &str,
+        nature: &TypeNature,
+        parameters: &Option<AstStatement>,
+    ) -> Option<&'i str> {
+        let mut argument_types: Vec<&typesystem::DataType> =
+            builtins::get_arguments(self.index, builtin, parameters)
+                .into_iter()
+                .filter(|(type_name, _)| type_name.eq_ignore_ascii_case(generic_name))
+                .filter_map(|(_, argument)| self.annotation_map.get_type(argument, self.index))
+                .map(|it| self.index.find_effective_type(it).unwrap_or(it))
+                .collect();
+        match nature {
+            TypeNature::Real => argument_types.extend(self.index.find_type(REAL_TYPE)),
+            TypeNature::String => {
+                let is_wide = argument_types.iter().any(|it| {
+                    matches!(
+                        it.get_type_information(),
+                        DataTypeInformation::String {
+                            encoding: StringEncoding::Utf16,
+                            ..
+                        }
+                    )
+                });
+                let default_string = if is_wide { WSTRING_TYPE } else { STRING_TYPE };
+                argument_types.extend(self.index.find_type(default_string));
+            }
+            _ => {}
+        }
+
+        let is_number = |it: &DataTypeInformation| {
+            matches!(
+                it,
+                DataTypeInformation::Integer { .. } | DataTypeInformation::Float { .. }
+            )
+        };
+        let string_size = |it: &DataTypeInformation| match it {
+            DataTypeInformation::String { size, .. } => size.as_int_value(self.index).ok(),
+            _ => None,
+        };
+        let is_same_encoding = |left: &DataTypeInformation, right: &DataTypeInformation| {
+            matches!(
+                (left, right),
+                (
+                    DataTypeInformation::String { encoding: l, .. },
+                    DataTypeInformation::String { encoding: r, .. },
+                ) if l == r
+            )
+        };
+        let mut argument_types = argument_types.into_iter();
+        let first = argument_types.next()?;
+        Some(
+            argument_types
+                .fold(first, |bigger, it| {
+                    let (bigger_info, info) =
+                        (bigger.get_type_information(), it.get_type_information());
+                    if is_number(bigger_info) && is_number(info) {
+                        let bigger_name =
+                            get_bigger_type_borrow(bigger_info, info, self.index).get_name();
+                        self.index.find_type(bigger_name).unwrap_or(bigger)
+                    } else if string_size(info) > string_size(bigger_info)
+                        && is_same_encoding(info, bigger_info)
+                    {
+                        it
+                    } else {
+                        bigger
+                    }
+                })
+                .get_name(),
+        )
+    }
+
     /// annotate a literal statement
     fn visit_statement_literals(&mut self, ctx: &VisitorContext, statement: &AstStatement) {
         match statement {
@@ -820,6 +997,29 @@ fn get_int_type_name_for(value: i128) -> &'static str {
     }
 }
 
+/// returns the pointer type of the given pointer arithmetic (`pointer +/- integer` or
+/// `integer + pointer`), None if the operands are no pointer and integer
+fn get_pointer_arithmetic_type<'t>(
+    operator: &Operator,
+    left: &'t DataTypeInformation,
+    right: &'t DataTypeInformation,
+) -> Option<&'t DataTypeInformation> {
+    let is_pointer = |it: &DataTypeInformation| {
+        matches!(
+            it,
+            DataTypeInformation::Pointer {
+                auto_deref: false,
+                ..
+            }
+        )
+    };
+    match operator {
+        Operator::Plus | Operator::Minus if is_pointer(left) && right.is_int() => Some(left),
+        Operator::Plus if left.is_int() && is_pointer(right) => Some(right),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod resolver_tests {
     use super::get_int_type_name_for;
@@ -2,6 +2,7 @@ use std::collections::VecDeque;
 
 use crate::{
     ast::{AstStatement, Operator, SourceRange},
+    builtins::{self, Conversion},
     index::{
         const_expressions::{ConstExpression, ConstId},
         Index,
@@ -22,6 +23,29 @@ macro_rules! cannot_eval_error {
     };
 }
 
+/// casts the given integer or real value to the integer type with the given signedness and size
+/// using rust's `as`, so integers are truncated to the type's size while reals are saturated to
+/// the type's range. Evaluates to `None` if there is no integer type with the given size
+macro_rules! cast_to_integer_type {
+    ($value:expr, $signed:expr, $size:expr) => {{
+        const SIGNED: bool = true;
+        const UNSIGNED: bool = false;
+        match ($signed, $size) {
+            //signed
+            (SIGNED, SINT_SIZE) => Some(($value as NativeSintType) as i128),
+            (SIGNED, INT_SIZE) => Some(($value as NativeIntType) as i128),
+            (SIGNED, DINT_SIZE) => Some(($value as NativeDintType) as i128),
+            (SIGNED, LINT_SIZE) => Some(($value as NativeLintType) as i128),
+            //unsigned
+            (UNSIGNED, SINT_SIZE) => Some(($value as NativeByteType) as i128),
+            (UNSIGNED, INT_SIZE) => Some(($value as NativeWordType) as i128),
+            (UNSIGNED, DINT_SIZE) => Some(($value as NativeDwordType) as i128),
+            (UNSIGNED, LINT_SIZE) => Some(($value as NativeLwordType) as i128),
+            _ => None,
+        }
+    }};
+}
+
 macro_rules! arithmetic_expression {
     ($left:expr, $op:tt, $right:expr, $op_text:expr) => {
         match ($left, $right) {
@@ -498,11 +522,207 @@ pub fn evaluate(
             }
             _ => return Err(format!("Cannot resolve constant Minus {:?}", value)),
         },
+        AstStatement::CallStatement {
+            operator,
+            parameters,
+            ..
+        } => match operator.as_ref() {
+            AstStatement::Reference { name, .. } if index.is_builtin(name) => {
+                if let Some(conversion) = builtins::get_conversion(name) {
+                    evaluate_conversion(name, conversion, parameters, scope, index)?
+                } else {
+                    return Err(format!("Cannot resolve constant: {:#?}", initial));
+                }
+            }
+            _ => return Err(format!("Cannot resolve constant: {:#?}", initial)),
+        },
         _ => return Err(format!("Cannot resolve constant: {:#?}", initial)),
     };
     Ok(literal)
 }
 
+/// evaluates a call to a builtin conversion function with a constant argument
+/// (e.g. `INT_TO_REAL(3)`)
+/// - returns None if the argument cannot be resolved yet
+fn evaluate_conversion(
+    builtin: &str,
+    conversion: Conversion,
+    parameters: &Option<AstStatement>,
+    scope: Option<&str>,
+    index: &Index,
+) -> Result<Option<AstStatement>, String> {
+    let (parameter_type, argument) = builtins::get_arguments(index, builtin, parameters)
+        .first()
+        .copied()
+        .ok_or_else(|| format!("Missing argument in call to {:}", builtin))?;
+    let target_type = index
+        .find_return_type(builtin)
+        .and_then(|it| index.find_effective_type(it))
+        .map(DataType::get_type_information)
+        .ok_or_else(|| format!("Cannot resolve return type of {:}", builtin))?;
+    //`<FROM>_TO_<TO>` declares the source type, `TO_<TO>` takes the type of the referenced variable
+    let source_type = index
+        .find_effective_type(parameter_type)
+        .map(DataType::get_type_information)
+        .filter(|it| !it.is_generic())
+        .or_else(|| match argument {
+            AstStatement::Reference { name, .. } => index
+                .find_variable(scope, std::slice::from_ref(&name.as_str()))
+                .and_then(|it| index.find_effective_type(it.get_type_name()))
+                .map(DataType::get_type_information),
+            _ => None,
+        });
+
+    evaluate(argument, scope, index)?
+        .map(|literal| convert_literal(conversion, literal, source_type, target_type))
+        .transpose()
+}
+
+/// converts the given literal to the given type following the semantics of the
+/// builtin conversion functions (see `builtins::Conversion`). A literal of an unknown
+/// source type is converted like a value of the target type
+fn convert_literal(
+    conversion: Conversion,
+    literal: AstStatement,
+    source_type: Option<&DataTypeInformation>,
+    target_type: &DataTypeInformation,
+) -> Result<AstStatement, String> {
+    let (id, location) = (literal.get_id(), literal.get_location());
+    let parse_real = |value: &str| value.parse::<f64>().map_err(|err| err.to_string());
+
+    let converted = match (&literal, target_type) {
+        (AstStatement::LiteralReal { value, .. }, _) if conversion == Conversion::Round => {
+            AstStatement::LiteralReal {
+                value: format!("{:}", parse_real(value)?.round()),
+                id,
+                location,
+            }
+        }
+        (AstStatement::LiteralInteger { .. }, _) if conversion == Conversion::Round => literal,
+        (AstStatement::LiteralInteger { value, .. }, _) if target_type.is_bool() => {
+            AstStatement::LiteralBool {
+                value: *value != 0,
+                id,
+                location,
+            }
+        }
+        (AstStatement::LiteralReal { value, .. }, _) if target_type.is_bool() => {
+            AstStatement::LiteralBool {
+                value: parse_real(value)? != 0_f64,
+                id,
+                location,
+            }
+        }
+        (AstStatement::LiteralBool { .. }, _) if target_type.is_bool() => literal,
+        (AstStatement::LiteralBool { value, .. }, DataTypeInformation::Integer { .. }) => {
+            AstStatement::LiteralInteger {
+                value: *value as i128,
+                id,
+                location,
+            }
+        }
+        (AstStatement::LiteralBool { value, .. }, DataTypeInformation::Float { .. }) => {
+            AstStatement::LiteralReal {
+                value: if *value { "1" } else { "0" }.to_string(),
+                id,
+                location,
+            }
+        }
+        (
+            AstStatement::LiteralInteger { value, .. },
+            DataTypeInformation::Integer { signed, size, .. },
+        ) => {
+            let source_type = source_type.unwrap_or(target_type);
+            let converted = if builtins::is_saturating_conversion(source_type, target_type) {
+                saturate_integer(*value, *signed, *size)
+            } else {
+                cast_integer(*value, *signed, *size)
+            };
+            AstStatement::LiteralInteger {
+                value: converted.ok_or_else(|| {
+                    format!("Cannot convert {:} to {:}", value, target_type.get_name())
+                })?,
+                id,
+                location,
+            }
+        }
+        (
+            AstStatement::LiteralReal { value, .. },
+            DataTypeInformation::Integer { signed, size, .. },
+        ) => {
+            let value = parse_real(value)?;
+            let value = if conversion == Conversion::Truncate {
+                value.trunc()
+            } else {
+                value.round()
+            };
+            AstStatement::LiteralInteger {
+                value: saturate_real(value, *signed, *size).ok_or_else(|| {
+                    format!("Cannot convert {:} to {:}", value, target_type.get_name())
+                })?,
+                id,
+                location,
+            }
+        }
+        (AstStatement::LiteralInteger { value, .. }, DataTypeInformation::Float { .. }) => {
+            AstStatement::LiteralReal {
+                value: format!("{:}", value),
+                id,
+                location,
+            }
+        }
+        (AstStatement::LiteralReal { .. }, DataTypeInformation::Float { .. }) => literal,
+        (
+            AstStatement::LiteralInteger { value, .. },
+            DataTypeInformation::String { encoding, .. },
+        ) => AstStatement::LiteralString {
+            value: format!("{:}", value),
+            is_wide: *encoding == StringEncoding::Utf16,
+            id,
+            location,
+        },
+        (AstStatement::LiteralBool { value, .. }, DataTypeInformation::String { encoding, .. }) => {
+            AstStatement::LiteralString {
+                value: if *value { "TRUE" } else { "FALSE" }.to_string(),
+                is_wide: *encoding == StringEncoding::Utf16,
+                id,
+                location,
+            }
+        }
+        _ => {
+            return Err(format!(
+                "Cannot resolve constant conversion of {:?} to {:}",
+                literal,
+                target_type.get_name()
+            ))
+        }
+    };
+    Ok(converted)
+}
+
+/// truncates the given value to the range of the integer type with the given size
+/// (e.g. -1 is 255 as a BYTE)
+fn cast_integer(value: i128, signed: bool, size: u32) -> Option<i128> {
+    cast_to_integer_type!(value, signed, size)
+}
+
+/// limits the given value to the range of the integer type with the given size
+/// (e.g. 300 is 127 as a SINT)
+fn saturate_integer(value: i128, signed: bool, size: u32) -> Option<i128> {
+    let (min, max) = match (signed, size) {
+        (true, 1..=64) => (-(1_i128 << (size - 1)), (1_i128 << (size - 1)) - 1),
+        (false, 1..=64) => (0, (1_i128 << size) - 1),
+        _ => return None,
+    };
+    Some(value.max(min).min(max))
+}
+
+/// converts the given real to the integer type with the given size,
+/// values out of the type's range are saturated, NaN is converted to 0
+fn saturate_real(value: f64, signed: bool, size: u32) -> Option<i128> {
+    cast_to_integer_type!(value, signed, size)
+}
+
 /// attempts to resolve the inital value of this reference's target
 /// may return Ok(None) if the variable's initial value can not be
 /// resolved yet
@@ -556,26 +776,12 @@ fn get_cast_statement_literal(
                 })
                 .transpose()?;
             if let Some(value) = evaluated_initial {
-                const SIGNED: bool = true;
-                const UNSIGNED: bool = false;
-                let value: i128 = match (signed, size) {
-                    //signed
-                    (SIGNED, SINT_SIZE) => (value as NativeSintType) as i128,
-                    (SIGNED, INT_SIZE) => (value as NativeIntType) as i128,
-                    (SIGNED, DINT_SIZE) => (value as NativeDintType) as i128,
-                    (SIGNED, LINT_SIZE) => (value as NativeLintType) as i128,
-                    //unsigned
-                    (UNSIGNED, SINT_SIZE) => (value as NativeByteType) as i128,
-                    (UNSIGNED, INT_SIZE) => (value as NativeWordType) as i128,
-                    (UNSIGNED, DINT_SIZE) => (value as NativeDwordType) as i128,
-                    (UNSIGNED, LINT_SIZE) => (value as NativeLwordType) as i128,
-                    _ => {
-                        return Err(format!(
-                            "Cannot resolve constant: {:}#{:?}",
-                            type_name, cast_statement
-                        ))
-                    }
-                };
+                let value = cast_integer(value, signed, size).ok_or_else(|| {
+                    format!(
+                        "Cannot resolve constant: {:}#{:?}",
+                        type_name, cast_statement
+                    )
+                })?;
                 Ok(AstStatement::LiteralInteger {
                     value,
                     id: cast_statement.get_id(),
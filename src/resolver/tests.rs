@@ -1,7 +1,8 @@
 use crate::{
     ast::{self, CompilationUnit},
+    builtins,
     index::{self, Index},
-    lexer::lex,
+    lexer::{lex, IdProvider},
 };
 
 use super::{AnnotationMap, TypeAnnotator};
@@ -19,7 +20,8 @@ fn parse(src: &str) -> (CompilationUnit, Index) {
     let (mut unit, _) = crate::parser::parse(lex(src));
 
     ast::pre_process(&mut unit);
-    let index = index::visitor::visit(&unit);
+    let mut index = index::visitor::visit(&builtins::parse_built_ins(IdProvider::new()));
+    index.import(index::visitor::visit(&unit));
     (unit, index)
 }
 
@@ -1164,3 +1164,131 @@ fn const_string_initializers_should_be_converted() {
         })
     );
 }
+
+#[test]
+fn integer_conversions_saturate_at_compile_time() {
+    // GIVEN conversions of integers out of the target's range
+    let (_, index) = parse(
+        "VAR_GLOBAL CONSTANT
+            x : DINT := 1000;
+            w : DWORD := 16#1234;
+            a : SINT := DINT_TO_SINT(300);
+            b : USINT := INT_TO_USINT(-5);
+            c : UDINT := DINT_TO_UDINT(-1);
+            d : SINT := TO_SINT(x);
+            e : BYTE := DINT_TO_BYTE(300);
+            f : BYTE := TO_BYTE(w);
+            g : WSTRING := INT_TO_WSTRING(42);
+        END_VAR
+        ",
+    );
+
+    // WHEN compile-time evaluation is applied
+    let (index, unresolvable) = evaluate_constants(index);
+
+    // THEN all should be resolved
+    debug_assert_eq!(EMPTY, unresolvable);
+
+    // AND integers are saturated while bit-strings keep the lower bits
+    debug_assert_eq!(
+        &create_int_literal(127),
+        find_connstant_value(&index, "a").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(0),
+        find_connstant_value(&index, "b").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(0),
+        find_connstant_value(&index, "c").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(127),
+        find_connstant_value(&index, "d").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(44),
+        find_connstant_value(&index, "e").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(0x34),
+        find_connstant_value(&index, "f").unwrap()
+    );
+    debug_assert_eq!(
+        &create_string_literal("42", true),
+        find_connstant_value(&index, "g").unwrap()
+    );
+}
+
+#[test]
+fn conversion_functions_are_evaluated_at_compile_time() {
+    // GIVEN some conversion functions of literals used as initializers
+    let (_, index) = parse(
+        "VAR_GLOBAL CONSTANT
+            a : REAL := INT_TO_REAL(3);
+            b : INT := REAL_TO_INT(2.5);
+            c : INT := REAL_TO_INT(-2.5);
+            d : DINT := REAL_TRUNC_DINT(-2.7);
+            e : SINT := LREAL_TO_SINT(1000.0);
+            f : BYTE := INT_TO_BYTE(-1);
+            g : BOOL := DINT_TO_BOOL(7);
+            h : INT := BOOL_TO_INT(TRUE);
+            i : STRING := DINT_TO_STRING(42);
+            j : LREAL := ROUND(2.5);
+            k : INT := TO_INT(a);
+        END_VAR
+        ",
+    );
+
+    // WHEN compile-time evaluation is applied
+    let (index, unresolvable) = evaluate_constants(index);
+
+    // THEN all should be resolved
+    debug_assert_eq!(EMPTY, unresolvable);
+
+    // AND the conversions follow IEC 61131-3 (rounding, truncation and saturation)
+    debug_assert_eq!(
+        &create_real_literal(3.0),
+        find_connstant_value(&index, "a").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(3),
+        find_connstant_value(&index, "b").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(-3),
+        find_connstant_value(&index, "c").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(-2),
+        find_connstant_value(&index, "d").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(127),
+        find_connstant_value(&index, "e").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(255),
+        find_connstant_value(&index, "f").unwrap()
+    );
+    debug_assert_eq!(
+        &create_bool_literal(true),
+        find_connstant_value(&index, "g").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(1),
+        find_connstant_value(&index, "h").unwrap()
+    );
+    debug_assert_eq!(
+        &create_string_literal("42", false),
+        find_connstant_value(&index, "i").unwrap()
+    );
+    debug_assert_eq!(
+        &create_real_literal(3.0),
+        find_connstant_value(&index, "j").unwrap()
+    );
+    debug_assert_eq!(
+        &create_int_literal(3),
+        find_connstant_value(&index, "k").unwrap()
+    );
+}
@@ -348,6 +348,41 @@ fn pointer_expressions_resolve_types() {
 
     assert_eq!(format!("{:?}", expected_types), format!("{:?}", type_names));
 }
+#[test]
+fn pointer_arithmetic_resolves_to_the_pointer_type() {
+    let (unit, index) = parse(
+        "PROGRAM PRG
+            VAR
+                i : REF_TO INT;
+                a : MyIntRef;
+                x : INT;
+            END_VAR
+
+            i + 1;
+            2 + i;
+            i - x;
+            a - 1;
+            ADR(x);
+            SIZEOF(x);
+        END_PROGRAM
+
+        TYPE MyIntRef: REF_TO INT; END_TYPE
+        ",
+    );
+    let annotations = annotate(&unit, &index);
+    let statements = &unit.implementations[0].statements;
+
+    let expected_types = vec![
+        "__PRG_i", "__PRG_i", "__PRG_i", "MyIntRef", "LWORD", "ULINT",
+    ];
+    let type_names: Vec<&str> = statements
+        .iter()
+        .map(|s| annotations.get_type_or_void(s, &index).get_name())
+        .collect();
+
+    assert_eq!(format!("{:?}", expected_types), format!("{:?}", type_names));
+}
+
 #[test]
 fn array_expressions_resolve_types() {
     let (unit, index) = parse(
@@ -1098,6 +1133,92 @@ fn method_references_are_resolved() {
     }
 }
 
+#[test]
+fn inherited_references_are_resolved() {
+    let (unit, index) = parse(
+        "
+        CLASS base
+        VAR x : INT; END_VAR
+        METHOD foo : INT
+        END_METHOD
+        END_CLASS
+
+        CLASS derived EXTENDS base
+        METHOD bar
+            x;
+            SUPER^.foo();
+        END_METHOD
+        END_CLASS
+
+        FUNCTION buz : INT
+        VAR d : derived; END_VAR
+        d.x;
+        d.foo();
+        END_FUNCTION
+        ",
+    );
+
+    let annotations = annotate(&unit, &index);
+
+    //x inherited from base
+    let x_reference = &unit.implementations[1].statements[0];
+    assert_eq!(
+        Some(&StatementAnnotation::Variable {
+            qualified_name: "derived.x".into(),
+            resulting_type: "INT".into(),
+            constant: false
+        }),
+        annotations.get(x_reference)
+    );
+
+    //SUPER^.foo()
+    let super_call = &unit.implementations[1].statements[1];
+    if let AstStatement::CallStatement { operator, .. } = super_call {
+        if let AstStatement::QualifiedReference { elements, .. } = operator.as_ref() {
+            assert_eq!(
+                Some(&StatementAnnotation::expression("base")),
+                annotations.get(&elements[0])
+            );
+        } else {
+            panic!("Unexpcted statemet : {:?}", operator);
+        }
+        assert_eq!(
+            Some(&StatementAnnotation::Function {
+                return_type: "INT".into(),
+                qualified_name: "base.foo".into(),
+            }),
+            annotations.get(operator)
+        );
+    } else {
+        panic!("Unexpcted statemet : {:?}", super_call);
+    }
+
+    //d.x
+    let qualified_x = &unit.implementations[2].statements[0];
+    assert_eq!(
+        Some(&StatementAnnotation::Variable {
+            qualified_name: "derived.x".into(),
+            resulting_type: "INT".into(),
+            constant: false
+        }),
+        annotations.get(qualified_x)
+    );
+
+    //d.foo()
+    let method_call = &unit.implementations[2].statements[1];
+    if let AstStatement::CallStatement { operator, .. } = method_call {
+        assert_eq!(
+            Some(&StatementAnnotation::Function {
+                return_type: "INT".into(),
+                qualified_name: "base.foo".into(),
+            }),
+            annotations.get(operator)
+        );
+    } else {
+        panic!("Unexpcted statemet : {:?}", method_call);
+    }
+}
+
 #[test]
 fn bitaccess_is_resolved() {
     let (unit, index) = parse(
@@ -1388,3 +1509,77 @@ fn const_flag_is_calculated_when_resolving_enum_literals() {
         format!("{:?}", actual_consts)
     );
 }
+
+#[test]
+fn calls_to_builtin_functions_resolve_to_the_type_of_their_arguments() {
+    //GIVEN calls to generic builtin functions
+    let (unit, index) = parse(
+        "
+        PROGRAM PRG
+            VAR
+                i : INT;
+                l : LINT;
+                b : BYTE;
+                r : REAL;
+                lr : LREAL;
+            END_VAR
+            ABS(i);
+            ABS(lr);
+            MAX(i, l);
+            MIN(r, lr, i);
+            SQRT(i);
+            SQRT(lr);
+            SHL(b, i);
+            ROR(IN := b, N := 3);
+            SEL(TRUE, r, r);
+            LIMIT(i, l, i);
+        END_PROGRAM
+        ",
+    );
+
+    //WHEN the AST is annotated
+    let annotations = annotate(&unit, &index);
+    let statements = &unit.implementations[0].statements;
+
+    //THEN the calls resolve to the bigger type of the generic arguments
+    let expected_types = vec![
+        "INT", "LREAL", "LINT", "LREAL", "REAL", "LREAL", "BYTE", "BYTE", "REAL", "LINT",
+    ];
+    let type_names: Vec<&str> = statements
+        .iter()
+        .map(|s| annotations.get_type_or_void(s, &index).get_name())
+        .collect();
+    assert_eq!(format!("{:?}", expected_types), format!("{:?}", type_names));
+}
+
+#[test]
+fn calls_to_string_functions_resolve_to_the_type_of_their_arguments() {
+    //GIVEN calls to builtin string functions
+    let (unit, index) = parse(
+        "
+        PROGRAM PRG
+            VAR
+                s : STRING[10];
+                w : WSTRING;
+            END_VAR
+            LEN(s);
+            LEFT(s, 3);
+            CONCAT(s, 'abc');
+            CONCAT(w, w);
+            FIND(w, w);
+        END_PROGRAM
+        ",
+    );
+
+    //WHEN the AST is annotated
+    let annotations = annotate(&unit, &index);
+    let statements = &unit.implementations[0].statements;
+
+    //THEN LEN and FIND resolve to INT, the others to a string of the arguments' encoding
+    let expected_types = vec!["INT", "STRING", "STRING", "WSTRING", "INT"];
+    let type_names: Vec<&str> = statements
+        .iter()
+        .map(|s| annotations.get_type_or_void(s, &index).get_name())
+        .collect();
+    assert_eq!(format!("{:?}", expected_types), format!("{:?}", type_names));
+}
@@ -0,0 +1,183 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+//! the standard function blocks of the IEC 61131-3 standard library
+//!
+//! the function blocks (timers, counters, edge detectors and bistables) are implemented in
+//! structured text (see `stdlib/function_blocks.st`). The library is indexed before the user's
+//! sources, so POUs declared by the user replace the standard ones. Only the function blocks
+//! that are instantiated by the user's program are generated.
+//!
+//! The timers read the current time through the external function `__get_time()` that has
+//! to be provided by the runtime.
+//!
+//! The default implementations of the runtime checks' hooks (e.g. `CheckBounds`) are
+//! implemented in structured text too (see `stdlib/runtime_checks.st`). They are only added
+//! to a compilation if the corresponding check is enabled and the user did not declare the hook.
+use std::collections::HashSet;
+
+use crate::{
+    ast::{self, CompilationUnit},
+    index::Index,
+    lexer::{self, IdProvider},
+    parser,
+    typesystem::DataTypeInformation,
+    RuntimeChecks,
+};
+
+/// the name of the function that returns the current time of the runtime
+pub const GET_TIME: &str = "__get_time";
+
+/// the location reported for the standard library's source
+pub const STDLIB_LOCATION: &str = "<standard library>";
+
+pub const STANDARD_FUNCTION_BLOCKS: &str = include_str!("stdlib/function_blocks.st");
+
+/// the location reported for the default implementations of the runtime checks
+pub const RUNTIME_CHECKS_LOCATION: &str = "<runtime checks>";
+
+pub const RUNTIME_CHECKS: &str = include_str!("stdlib/runtime_checks.st");
+
+/// parses the standard function blocks
+pub fn parse_standard_library(id_provider: IdProvider) -> CompilationUnit {
+    let (mut unit, _) = parser::parse(lexer::lex_with_ids(STANDARD_FUNCTION_BLOCKS, id_provider));
+    ast::pre_process(&mut unit);
+    unit
+}
+
+/// removes all function blocks from the standard library that are not instantiated
+/// in the given index, or that were replaced by a POU of one of the given user-units
+pub fn remove_unused_pous(
+    library: &mut CompilationUnit,
+    units: &[&CompilationUnit],
+    index: &Index,
+) {
+    let declared_by_user = get_declared_pous(units);
+    let instantiated: HashSet<String> = index
+        .get_all_variable_entries()
+        .map(|variable| get_instantiated_type(index, variable.get_type_name()))
+        .collect();
+    let is_used = |name: &str| {
+        let name = name.to_lowercase();
+        //the time-source is an external function and always kept
+        name == GET_TIME || (instantiated.contains(&name) && !declared_by_user.contains(&name))
+    };
+
+    library.units.retain(|pou| is_used(&pou.name));
+    library
+        .implementations
+        .retain(|implementation| is_used(&implementation.name));
+}
+
+/// parses the default implementations of the hooks of the given runtime checks
+pub fn parse_runtime_checks(checks: RuntimeChecks, id_provider: IdProvider) -> CompilationUnit {
+    let (mut unit, _) = parser::parse(lexer::lex_with_ids(RUNTIME_CHECKS, id_provider));
+    let hooks = checks.get_hooks();
+    let is_enabled = |name: &str| hooks.iter().any(|hook| hook.eq_ignore_ascii_case(name));
+    unit.units.retain(|pou| is_enabled(&pou.name));
+    unit.implementations
+        .retain(|implementation| is_enabled(&implementation.name));
+    ast::pre_process(&mut unit);
+    unit
+}
+
+/// removes all POUs from the given library that were replaced by a POU of one of the given user-units
+pub fn remove_replaced_pous(library: &mut CompilationUnit, units: &[&CompilationUnit]) {
+    let declared_by_user = get_declared_pous(units);
+    library
+        .units
+        .retain(|pou| !declared_by_user.contains(&pou.name.to_lowercase()));
+    library
+        .implementations
+        .retain(|implementation| !declared_by_user.contains(&implementation.name.to_lowercase()));
+}
+
+/// returns the lowercase names of all POUs declared in the given units
+fn get_declared_pous(units: &[&CompilationUnit]) -> HashSet<String> {
+    units
+        .iter()
+        .flat_map(|unit| unit.units.iter())
+        .map(|pou| pou.name.to_lowercase())
+        .collect()
+}
+
+/// returns the lowercase name of the type instantiated by a variable of the given type,
+/// for arrays this is the name of the array's inner type. Pointers, references and VAR_IN_OUTs
+/// call the instance they point to, so they need the pointed-to type as well
+fn get_instantiated_type(index: &Index, type_name: &str) -> String {
+    match index
+        .find_effective_type_by_name(type_name)
+        .map(|it| it.get_type_information())
+    {
+        Some(DataTypeInformation::Array {
+            inner_type_name, ..
+        })
+        | Some(DataTypeInformation::VariableLengthArray {
+            inner_type_name, ..
+        })
+        | Some(DataTypeInformation::Pointer {
+            inner_type_name, ..
+        }) => get_instantiated_type(index, inner_type_name),
+        _ => type_name.to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::{self, IdProvider},
+        parser,
+        typesystem::RUNTIME_CHECK_FNS,
+    };
+
+    use super::{RUNTIME_CHECKS, STANDARD_FUNCTION_BLOCKS};
+
+    #[test]
+    fn standard_function_blocks_parse_without_diagnostics() {
+        let (unit, diagnostics) = parser::parse(lexer::lex_with_ids(
+            STANDARD_FUNCTION_BLOCKS,
+            IdProvider::new(),
+        ));
+
+        assert_eq!(diagnostics, vec![]);
+        let pous: Vec<&str> = unit.units.iter().map(|it| it.name.as_str()).collect();
+        assert_eq!(
+            pous,
+            vec![
+                "__get_time",
+                "SR",
+                "RS",
+                "R_TRIG",
+                "F_TRIG",
+                "CTU",
+                "CTD",
+                "CTUD",
+                "CTU_DINT",
+                "CTD_DINT",
+                "CTUD_DINT",
+                "CTU_UDINT",
+                "CTD_UDINT",
+                "CTUD_UDINT",
+                "TP",
+                "TON",
+                "TOF"
+            ]
+        );
+    }
+
+    #[test]
+    fn runtime_checks_parse_without_diagnostics() {
+        let (unit, diagnostics) =
+            parser::parse(lexer::lex_with_ids(RUNTIME_CHECKS, IdProvider::new()));
+
+        assert_eq!(diagnostics, vec![]);
+        //every hook has a default implementation
+        for hook in RUNTIME_CHECK_FNS.iter() {
+            assert!(
+                unit.units
+                    .iter()
+                    .any(|it| it.name.eq_ignore_ascii_case(hook)),
+                "missing default implementation of {}",
+                hook
+            );
+        }
+    }
+}
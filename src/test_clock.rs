@@ -0,0 +1,55 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+//! a deterministic time source for the standard timers
+//!
+//! the timers read the time through the external function `__get_time` (see `stdlib`).
+//! A program executed by a JIT can map it to the test clock, whose time only changes when it is
+//! set, so the behaviour of the timers can be tested step by step.
+//!
+//! The clock's time is kept per thread, so tests running in parallel don't affect each other.
+use std::cell::Cell;
+
+use inkwell::{execution_engine::ExecutionEngine, module::Module};
+
+use crate::stdlib::GET_TIME;
+
+thread_local! {
+    static TIME: Cell<i64> = Cell::new(0);
+}
+
+/// returns the time of the test clock in nanoseconds, the implementation of `__get_time`
+pub extern "C" fn get_time() -> i64 {
+    TIME.with(|it| it.get())
+}
+
+/// sets the time of the test clock in nanoseconds
+pub fn set_time(nanos: i64) {
+    TIME.with(|it| it.set(nanos));
+}
+
+/// advances the time of the test clock by the given nanoseconds
+pub fn advance(nanos: i64) {
+    TIME.with(|it| it.set(it.get() + nanos));
+}
+
+/// maps `__get_time` of the given module to the test clock, modules that don't instantiate a
+/// timer don't declare `__get_time` and are left as they are
+pub fn map_to(module: &Module, exec_engine: &ExecutionEngine) {
+    if let Some(get_time_function) = module.get_function(GET_TIME) {
+        exec_engine.add_global_mapping(&get_time_function, get_time as usize);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{advance, get_time, set_time};
+
+    #[test]
+    fn the_time_only_changes_when_it_is_set_or_advanced() {
+        set_time(100);
+        assert_eq!(100, get_time());
+        assert_eq!(100, get_time());
+
+        advance(50);
+        assert_eq!(150, get_time());
+    }
+}
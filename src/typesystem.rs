@@ -12,6 +12,21 @@ pub const RANGE_CHECK_S_FN: &str = "CheckRangeSigned";
 pub const RANGE_CHECK_LS_FN: &str = "CheckLRangeSigned";
 pub const RANGE_CHECK_U_FN: &str = "CheckRangeUnsigned";
 pub const RANGE_CHECK_LU_FN: &str = "CheckLRangeUnsigned";
+pub const BOUNDS_CHECK_FN: &str = "CheckBounds";
+pub const DIV_CHECK_FN: &str = "CheckDivDInt";
+pub const DIV_CHECK_L_FN: &str = "CheckDivLInt";
+pub const OVERFLOW_CHECK_FN: &str = "CheckOverflow";
+pub const POINTER_CHECK_FN: &str = "CheckPointer";
+pub const CHECK_FAILED_FN: &str = "CheckFailed";
+/// the hooks of the runtime checks, no checks are generated into the hooks themselves
+pub const RUNTIME_CHECK_FNS: [&str; 6] = [
+    BOUNDS_CHECK_FN,
+    DIV_CHECK_FN,
+    DIV_CHECK_L_FN,
+    OVERFLOW_CHECK_FN,
+    POINTER_CHECK_FN,
+    CHECK_FAILED_FN,
+];
 
 pub type NativeSintType = i8;
 pub type NativeIntType = i16;
@@ -63,6 +78,13 @@ pub const WSTRING_TYPE: &str = "WSTRING";
 
 pub const VOID_TYPE: &str = "VOID";
 
+pub const ANY_TYPE: &str = "ANY";
+pub const ANY_NUM_TYPE: &str = "ANY_NUM";
+pub const ANY_REAL_TYPE: &str = "ANY_REAL";
+pub const ANY_INT_TYPE: &str = "ANY_INT";
+pub const ANY_BIT_TYPE: &str = "ANY_BIT";
+pub const ANY_STRING_TYPE: &str = "ANY_STRING";
+
 #[derive(Debug, PartialEq)]
 pub struct DataType {
     pub name: String,
@@ -141,6 +163,31 @@ impl TypeSize {
     }
 }
 
+/// the type-nature of a generic type. Generic types are only used to declare
+/// the parameters of builtin functions (e.g. `ABS(IN : ANY_NUM) : ANY_NUM`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypeNature {
+    Any,
+    Num,
+    Real,
+    Int,
+    Bit,
+    String,
+}
+
+impl TypeNature {
+    /// returns true if the given type belongs to this type-nature
+    pub fn derives(&self, data_type: &DataTypeInformation) -> bool {
+        match self {
+            TypeNature::Any => !matches!(data_type, DataTypeInformation::Void),
+            // integers are implicitly converted to reals
+            TypeNature::Num | TypeNature::Real => data_type.is_numerical(),
+            TypeNature::Int | TypeNature::Bit => data_type.is_int(),
+            TypeNature::String => data_type.is_string(),
+        }
+    }
+}
+
 /// indicates where this Struct origins from.
 #[derive(Debug, Clone, PartialEq)]
 pub enum StructSource {
@@ -161,6 +208,13 @@ pub enum DataTypeInformation {
         inner_type_name: String,
         dimensions: Vec<Dimension>,
     },
+    /// an `ARRAY[*] OF ...` whose bounds are only known at runtime. It is passed as a
+    /// descriptor holding a pointer to the first element and the bounds of every dimension
+    VariableLengthArray {
+        name: String,
+        inner_type_name: String,
+        dimension_count: usize,
+    },
     Pointer {
         name: String,
         inner_type_name: String,
@@ -192,6 +246,10 @@ pub enum DataTypeInformation {
         name: String,
         referenced_type: String,
     },
+    Generic {
+        name: String,
+        nature: TypeNature,
+    },
     Void,
 }
 
@@ -200,6 +258,7 @@ impl DataTypeInformation {
         match self {
             DataTypeInformation::Struct { name, .. } => name,
             DataTypeInformation::Array { name, .. } => name,
+            DataTypeInformation::VariableLengthArray { name, .. } => name,
             DataTypeInformation::Pointer { name, .. } => name,
             DataTypeInformation::Integer { name, .. } => name,
             DataTypeInformation::Float { name, .. } => name,
@@ -215,6 +274,7 @@ impl DataTypeInformation {
             DataTypeInformation::Void => "VOID",
             DataTypeInformation::Alias { name, .. } => name,
             DataTypeInformation::Enum { name, .. } => name,
+            DataTypeInformation::Generic { name, .. } => name,
         }
     }
 
@@ -247,6 +307,47 @@ impl DataTypeInformation {
         )
     }
 
+    pub fn is_bool(&self) -> bool {
+        matches!(
+            self,
+            DataTypeInformation::Integer {
+                size: BOOL_SIZE,
+                ..
+            }
+        )
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, DataTypeInformation::String { .. })
+    }
+
+    /// returns true for the bit-strings BYTE, WORD, DWORD and LWORD
+    pub fn is_bit_string(&self) -> bool {
+        [BYTE_TYPE, WORD_TYPE, DWORD_TYPE, LWORD_TYPE]
+            .iter()
+            .any(|it| self.get_name().eq_ignore_ascii_case(it))
+    }
+
+    /// returns true for DATE, TIME, DATE_AND_TIME and TIME_OF_DAY (or their short forms)
+    pub fn is_date_or_time(&self) -> bool {
+        [
+            DATE_TYPE,
+            SHORT_DATE_TYPE,
+            TIME_TYPE,
+            SHORT_TIME_TYPE,
+            DATE_AND_TIME_TYPE,
+            SHORT_DATE_AND_TIME_TYPE,
+            TIME_OF_DAY_TYPE,
+            SHORT_TIME_OF_DAY_TYPE,
+        ]
+        .iter()
+        .any(|it| self.get_name().eq_ignore_ascii_case(it))
+    }
+
+    pub fn is_generic(&self) -> bool {
+        matches!(self, DataTypeInformation::Generic { .. })
+    }
+
     pub fn is_variadic(&self) -> bool {
         matches!(
             self,
@@ -276,11 +377,13 @@ impl DataTypeInformation {
             DataTypeInformation::String { .. } => unimplemented!("string"),
             DataTypeInformation::Struct { .. } => 0, //TODO : Should we fill in the struct members here for size calculation or save the struct size.
             DataTypeInformation::Array { .. } => unimplemented!("array"), //Propably length * inner type size
+            DataTypeInformation::VariableLengthArray { .. } => unimplemented!("array"),
             DataTypeInformation::Pointer { .. } => unimplemented!("pointer"),
             DataTypeInformation::SubRange { .. } => unimplemented!("subrange"),
             DataTypeInformation::Alias { .. } => unimplemented!("alias"),
             DataTypeInformation::Void => 0,
             DataTypeInformation::Enum { .. } => DINT_SIZE,
+            DataTypeInformation::Generic { .. } => unimplemented!("generic"),
         }
     }
 }
@@ -523,9 +626,26 @@ pub fn get_builtin_types() -> Vec<DataType> {
                 referenced_type: TIME_TYPE.into(),
             },
         },
+        new_generic_type(ANY_TYPE, TypeNature::Any),
+        new_generic_type(ANY_NUM_TYPE, TypeNature::Num),
+        new_generic_type(ANY_REAL_TYPE, TypeNature::Real),
+        new_generic_type(ANY_INT_TYPE, TypeNature::Int),
+        new_generic_type(ANY_BIT_TYPE, TypeNature::Bit),
+        new_generic_type(ANY_STRING_TYPE, TypeNature::String),
     ]
 }
 
+fn new_generic_type(name: &str, nature: TypeNature) -> DataType {
+    DataType {
+        name: name.into(),
+        initial_value: None,
+        information: DataTypeInformation::Generic {
+            name: name.into(),
+            nature,
+        },
+    }
+}
+
 pub fn new_string_information(len: u32) -> DataTypeInformation {
     DataTypeInformation::String {
         size: TypeSize::from_literal(len),
@@ -13,10 +13,13 @@ use self::{
     variable_validator::VariableValidator,
 };
 
+mod linter;
 mod pou_validator;
 mod stmt_validator;
 mod variable_validator;
 
+pub use linter::Linter;
+
 #[cfg(test)]
 mod tests;
 
@@ -35,6 +38,8 @@ pub struct ValidationContext<'s> {
     ast_annotation: &'s AnnotationMap,
     index: &'s Index,
     qualifier: Option<&'s str>,
+    /// the size of an address of the target in bits
+    address_size: u32,
 }
 
 pub struct Validator {
@@ -42,14 +47,22 @@ pub struct Validator {
     pou_validator: PouValidator,
     variable_validator: VariableValidator,
     stmt_validator: StatementValidator,
+    address_size: u32,
 }
 
 impl Validator {
+    /// creates a validator for a target with 64 bit addresses
     pub fn new() -> Validator {
+        Validator::for_target(64)
+    }
+
+    /// creates a validator for a target whose addresses have the given size in bits
+    pub fn for_target(address_size: u32) -> Validator {
         Validator {
             pou_validator: PouValidator::new(),
             variable_validator: VariableValidator::new(),
             stmt_validator: StatementValidator::new(),
+            address_size,
         }
     }
 
@@ -74,6 +87,7 @@ impl Validator {
                     ast_annotation: annotations,
                     index,
                     qualifier: Some(pou.name.as_str()),
+                    address_size: self.address_size,
                 },
             );
         }
@@ -82,6 +96,7 @@ impl Validator {
             ast_annotation: annotations,
             index,
             qualifier: None,
+            address_size: self.address_size,
         };
         for t in &unit.types {
             self.visit_user_type_declaration(t, no_context);
@@ -91,15 +106,22 @@ impl Validator {
             self.visit_variable_container(no_context, gv);
         }
 
+        for configuration in &unit.configurations {
+            self.pou_validator
+                .validate_configuration(configuration, no_context);
+        }
+
         for i in &unit.implementations {
             let context = ValidationContext {
                 ast_annotation: annotations,
                 index,
                 qualifier: Some(i.name.as_str()),
+                address_size: self.address_size,
             };
             i.statements
                 .iter()
                 .for_each(|s| self.visit_statement(s, &context));
+            self.stmt_validator.validate_reference_bindings(i, &context);
         }
     }
 
@@ -112,7 +134,7 @@ impl Validator {
     }
 
     pub fn visit_pou(&mut self, pou: &Pou, context: &ValidationContext) {
-        self.pou_validator.validate_pou(pou);
+        self.pou_validator.validate_pou(pou, context);
 
         for block in &pou.variable_blocks {
             self.visit_variable_container(context, block);
@@ -211,6 +233,10 @@ impl Validator {
                 self.visit_statement(left, context);
                 self.visit_statement(right, context);
             }
+            AstStatement::RefAssignment { left, right, .. } => {
+                self.visit_statement(left, context);
+                self.visit_statement(right, context);
+            }
             AstStatement::CallStatement {
                 parameters,
                 operator,
@@ -0,0 +1,270 @@
+use std::collections::HashSet;
+
+use crate::{
+    ast::{AstStatement, CompilationUnit, LinkageType, Operator, Pou, PouType, VariableBlockType},
+    index::Index,
+    resolver::AnnotationMap,
+    typesystem::{self, DataTypeInformation},
+    Diagnostic,
+};
+
+/// reports lints: valid code that most likely does not do what was intended
+///
+/// lints are reported as warnings, the checks are independent of the `Validator`'s checks
+pub struct Linter {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Linter {
+    pub fn new() -> Linter {
+        Linter {
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub fn visit_unit(
+        &mut self,
+        annotations: &AnnotationMap,
+        index: &Index,
+        unit: &CompilationUnit,
+    ) {
+        let referenced_variables: HashSet<String> = annotations
+            .get_referenced_variables()
+            .map(|it| it.to_lowercase())
+            .collect();
+
+        //external POUs are only declared here, e.g. by an included interface file
+        for pou in unit
+            .units
+            .iter()
+            .filter(|it| it.linkage == LinkageType::Internal)
+        {
+            let has_implementation = unit.implementations.iter().any(|it| {
+                it.linkage == LinkageType::Internal && it.name.eq_ignore_ascii_case(&pou.name)
+            });
+            if has_implementation {
+                self.validate_unused_variables(pou, &referenced_variables);
+            }
+            self.validate_shadowed_globals(pou, index);
+        }
+
+        for implementation in &unit.implementations {
+            self.visit_body(&implementation.statements, annotations, index);
+        }
+    }
+
+    /// reports the inputs and private variables of the given pou that are never referenced
+    fn validate_unused_variables(&mut self, pou: &Pou, referenced_variables: &HashSet<String>) {
+        //the VARs of programs and function blocks are part of their instance and may be
+        //accessed from the outside, the VARs of functions and methods only live during a call
+        let has_private_locals = matches!(pou.pou_type, PouType::Function | PouType::Method { .. });
+
+        for block in &pou.variable_blocks {
+            let is_input = block.variable_block_type == VariableBlockType::Input;
+            let is_private = match block.variable_block_type {
+                VariableBlockType::Temp => true,
+                VariableBlockType::Local => has_private_locals,
+                _ => false,
+            };
+            if !is_input && !is_private {
+                continue;
+            }
+
+            for variable in &block.variables {
+                let qualified_name = format!("{}.{}", pou.name, variable.name).to_lowercase();
+                if referenced_variables.contains(&qualified_name) {
+                    continue;
+                }
+                if is_input {
+                    self.diagnostics.push(Diagnostic::unused_input(
+                        variable.name.as_str(),
+                        variable.location.clone(),
+                    ));
+                } else {
+                    self.diagnostics.push(Diagnostic::unused_variable(
+                        variable.name.as_str(),
+                        variable.location.clone(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// reports the variables of the given pou that hide a global variable with the same name
+    fn validate_shadowed_globals(&mut self, pou: &Pou, index: &Index) {
+        let globals = index.get_globals();
+        pou.variable_blocks
+            .iter()
+            .flat_map(|it| it.variables.iter())
+            .filter(|it| globals.contains_key(&it.name.to_lowercase()))
+            .for_each(|it| {
+                self.diagnostics.push(Diagnostic::shadowed_global(
+                    it.name.as_str(),
+                    it.location.clone(),
+                ))
+            });
+    }
+
+    /// visits the statements of a body and the bodies nested in its control-statements
+    fn visit_body(
+        &mut self,
+        statements: &[AstStatement],
+        annotations: &AnnotationMap,
+        index: &Index,
+    ) {
+        for statement in statements {
+            match statement {
+                AstStatement::Assignment { left, right, .. } => {
+                    self.validate_assignment(left, right, statement, annotations, index);
+                }
+                AstStatement::BinaryExpression {
+                    operator: Operator::Equal,
+                    ..
+                } => {
+                    self.diagnostics.push(Diagnostic::assignment_without_effect(
+                        "the result of the comparison is not used, did you mean ':='?",
+                        statement.get_location(),
+                    ));
+                }
+                AstStatement::IfStatement {
+                    blocks, else_block, ..
+                } => {
+                    blocks
+                        .iter()
+                        .for_each(|b| self.visit_body(&b.body, annotations, index));
+                    self.visit_body(else_block, annotations, index);
+                }
+                AstStatement::CaseStatement {
+                    case_blocks,
+                    else_block,
+                    ..
+                } => {
+                    case_blocks
+                        .iter()
+                        .for_each(|b| self.visit_body(&b.body, annotations, index));
+                    self.visit_body(else_block, annotations, index);
+                }
+                AstStatement::ForLoopStatement { body, .. }
+                | AstStatement::WhileLoopStatement { body, .. }
+                | AstStatement::RepeatLoopStatement { body, .. } => {
+                    self.visit_body(body, annotations, index);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn validate_assignment(
+        &mut self,
+        left: &AstStatement,
+        right: &AstStatement,
+        assignment: &AstStatement,
+        annotations: &AnnotationMap,
+        index: &Index,
+    ) {
+        if let (Some(target), Some(source)) = (get_reference_name(left), get_reference_name(right))
+        {
+            if target == source {
+                self.diagnostics.push(Diagnostic::assignment_without_effect(
+                    "the variable is assigned to itself",
+                    assignment.get_location(),
+                ));
+                return;
+            }
+        }
+
+        let target_type = annotations
+            .get_type_or_void(left, index)
+            .get_type_information();
+        if let Some(source_type) = get_expression_type(right, annotations, index) {
+            if is_narrowing(source_type, target_type) {
+                self.diagnostics.push(Diagnostic::implicit_narrowing(
+                    source_type.get_name(),
+                    target_type.get_name(),
+                    right.get_location(),
+                ));
+            }
+        }
+    }
+}
+
+/// returns the lowercase name of a (qualified) reference like `a.b.c`
+fn get_reference_name(statement: &AstStatement) -> Option<String> {
+    match statement {
+        AstStatement::Reference { name, .. } => Some(name.to_lowercase()),
+        AstStatement::QualifiedReference { elements, .. } => elements
+            .iter()
+            .map(get_reference_name)
+            .collect::<Option<Vec<_>>>()
+            .map(|it| it.join(".")),
+        _ => None,
+    }
+}
+
+/// returns the type the given expression is evaluated with
+///
+/// literals adapt to the type they are assigned to, so they are ignored. comparisons
+/// result in a BOOL and cannot narrow, `None` is returned for both.
+fn get_expression_type<'i>(
+    statement: &AstStatement,
+    annotations: &AnnotationMap,
+    index: &'i Index,
+) -> Option<&'i DataTypeInformation> {
+    match statement {
+        AstStatement::LiteralInteger { .. } | AstStatement::LiteralReal { .. } => None,
+        AstStatement::UnaryExpression {
+            operator: Operator::Minus,
+            value,
+            ..
+        } => get_expression_type(value, annotations, index),
+        AstStatement::BinaryExpression {
+            operator:
+                Operator::Equal
+                | Operator::NotEqual
+                | Operator::Less
+                | Operator::Greater
+                | Operator::LessOrEqual
+                | Operator::GreaterOrEqual,
+            ..
+        } => None,
+        AstStatement::BinaryExpression { left, right, .. } => {
+            match (
+                get_expression_type(left, annotations, index),
+                get_expression_type(right, annotations, index),
+            ) {
+                (Some(left), Some(right)) if left.is_numerical() && right.is_numerical() => {
+                    Some(typesystem::get_bigger_type_borrow(left, right, index))
+                }
+                (Some(left), Some(_)) => Some(left),
+                (left, right) => left.or(right),
+            }
+        }
+        _ => annotations
+            .get_type(statement, index)
+            .map(|it| it.get_type_information()),
+    }
+}
+
+/// returns true if assigning a value of the source type to the target type may lose information
+fn is_narrowing(source: &DataTypeInformation, target: &DataTypeInformation) -> bool {
+    match (source, target) {
+        (
+            DataTypeInformation::Integer {
+                size: source_size, ..
+            },
+            DataTypeInformation::Integer {
+                size: target_size, ..
+            },
+        )
+        | (
+            DataTypeInformation::Float {
+                size: source_size, ..
+            },
+            DataTypeInformation::Float {
+                size: target_size, ..
+            },
+        ) => source_size > target_size,
+        (DataTypeInformation::Float { .. }, DataTypeInformation::Integer { .. }) => true,
+        _ => false,
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{ast::Pou, Diagnostic};
+use crate::{
+    ast::{Configuration, Pou, PouType},
+    index::{ImplementationIndexEntry, Index, PouIndexEntry, VariableType},
+    Diagnostic,
+};
+
+use super::ValidationContext;
 
 /// validates POUs
 pub struct PouValidator {
@@ -12,5 +18,236 @@ impl PouValidator {
         }
     }
 
-    pub fn validate_pou(&mut self, _pou: &Pou) {}
+    pub fn validate_pou(&mut self, pou: &Pou, context: &ValidationContext) {
+        if let Some(super_class) = &pou.super_class {
+            self.validate_super_class(pou, super_class, context);
+        }
+        for interface in &pou.interfaces {
+            self.validate_interface(pou, interface, context);
+        }
+        if let PouType::Method { owner_class } = &pou.pou_type {
+            self.validate_interface_method_signature(pou, owner_class, context);
+            self.validate_overridden_method(pou, owner_class, context);
+        }
+        if matches!(pou.pou_type, PouType::Class | PouType::FunctionBlock) {
+            self.validate_abstract_methods(pou, context);
+        }
+    }
+
+    /// validates that the program instances of a configuration's resources
+    /// refer to PROGRAMs and to TASKs declared in their resource
+    pub fn validate_configuration(
+        &mut self,
+        configuration: &Configuration,
+        context: &ValidationContext,
+    ) {
+        for resource in &configuration.resources {
+            for program in &resource.programs {
+                let is_program = context
+                    .index
+                    .find_pou(&program.type_name)
+                    .map_or(false, |it| it.get_pou_type() == &PouType::Program);
+                if !is_program {
+                    self.diagnostics.push(Diagnostic::invalid_program_instance(
+                        program.type_name.as_str(),
+                        program.location.clone(),
+                    ));
+                }
+
+                if let Some(task_name) = &program.task_name {
+                    if !resource
+                        .tasks
+                        .iter()
+                        .any(|it| it.name.eq_ignore_ascii_case(task_name))
+                    {
+                        self.diagnostics.push(Diagnostic::unknown_task(
+                            task_name.as_str(),
+                            program.location.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// validates that an OVERRIDE method overrides a method of a super class
+    /// and that no method overrides a FINAL method
+    fn validate_overridden_method(
+        &mut self,
+        method: &Pou,
+        owner_class: &str,
+        context: &ValidationContext,
+    ) {
+        let index = context.index;
+        let method_name = method.name.rsplit('.').next().unwrap_or_default();
+        let overridden_method = index
+            .find_super_class(owner_class)
+            .and_then(|it| index.find_method(it, method_name));
+        match overridden_method {
+            Some(overridden_method) => {
+                if index
+                    .find_pou(overridden_method.get_call_name())
+                    .map(PouIndexEntry::is_final)
+                    .unwrap_or(false)
+                {
+                    self.diagnostics.push(Diagnostic::cannot_override_final(
+                        method.name.as_str(),
+                        overridden_method.get_call_name(),
+                        method.location.clone(),
+                    ));
+                }
+            }
+            None if index
+                .find_implementation(&method.name)
+                .map(ImplementationIndexEntry::is_overriding)
+                .unwrap_or(false) =>
+            {
+                self.diagnostics.push(Diagnostic::invalid_override(
+                    method.name.as_str(),
+                    method.location.clone(),
+                ))
+            }
+            None => {}
+        }
+    }
+
+    /// validates that a class, which is not ABSTRACT itself, implements all ABSTRACT methods
+    fn validate_abstract_methods(&mut self, pou: &Pou, context: &ValidationContext) {
+        let index = context.index;
+        if index
+            .find_pou(&pou.name)
+            .map(PouIndexEntry::is_abstract)
+            .unwrap_or(true)
+        {
+            return;
+        }
+        for method in index.find_method_table(&pou.name) {
+            if index
+                .find_pou(method.get_call_name())
+                .map(PouIndexEntry::is_abstract)
+                .unwrap_or(false)
+            {
+                self.diagnostics
+                    .push(Diagnostic::unimplemented_abstract_method(
+                        pou.name.as_str(),
+                        method.get_call_name(),
+                        pou.location.clone(),
+                    ));
+            }
+        }
+    }
+
+    /// validates that the given pou implements all methods of the given interface
+    fn validate_interface(&mut self, pou: &Pou, interface: &str, context: &ValidationContext) {
+        match context.index.find_pou(interface) {
+            Some(it) if it.is_interface() => {
+                for method in context.index.find_declared_methods(interface) {
+                    let method_name = method.get_name().rsplit('.').next().unwrap_or_default();
+                    if context.index.find_method(&pou.name, method_name).is_none() {
+                        self.diagnostics.push(Diagnostic::missing_interface_method(
+                            pou.name.as_str(),
+                            interface,
+                            method_name,
+                            pou.location.clone(),
+                        ));
+                    }
+                }
+            }
+            Some(_) => self.diagnostics.push(Diagnostic::invalid_interface(
+                pou.name.as_str(),
+                interface,
+                pou.location.clone(),
+            )),
+            None => self.diagnostics.push(Diagnostic::unrseolved_reference(
+                interface,
+                pou.location.clone(),
+            )),
+        }
+    }
+
+    /// validates that the given method matches the declarations of the same method
+    /// in all interfaces implemented by its owner class
+    fn validate_interface_method_signature(
+        &mut self,
+        method: &Pou,
+        owner_class: &str,
+        context: &ValidationContext,
+    ) {
+        let index = context.index;
+        let method_name = method.name.rsplit('.').next().unwrap_or_default();
+        for interface in index.find_implemented_interfaces(owner_class) {
+            let interface_method = format!("{}.{}", interface, method_name);
+            if index.find_pou(&interface_method).is_some()
+                && get_signature(index, &method.name) != get_signature(index, &interface_method)
+            {
+                self.diagnostics.push(Diagnostic::interface_method_mismatch(
+                    method.name.as_str(),
+                    interface_method.as_str(),
+                    method.location.clone(),
+                ));
+            }
+        }
+    }
+
+    /// validates the POU named in the EXTENDS clause of the given pou
+    fn validate_super_class(&mut self, pou: &Pou, super_class: &str, context: &ValidationContext) {
+        if let Some(super_pou) = context.index.find_pou(super_class) {
+            let compatible = matches!(
+                (&pou.pou_type, super_pou.get_pou_type()),
+                (PouType::Class, PouType::Class) | (PouType::FunctionBlock, PouType::FunctionBlock)
+            );
+            if !compatible {
+                self.diagnostics.push(Diagnostic::invalid_super_class(
+                    pou.name.as_str(),
+                    super_class,
+                    pou.location.clone(),
+                ));
+            } else if super_pou.is_final() {
+                self.diagnostics.push(Diagnostic::cannot_extend_final(
+                    pou.name.as_str(),
+                    super_class,
+                    pou.location.clone(),
+                ));
+            } else if context
+                .index
+                .is_same_or_derived_class(super_class, pou.name.as_str())
+            {
+                self.diagnostics.push(Diagnostic::cyclic_inheritance(
+                    pou.name.as_str(),
+                    super_class,
+                    pou.location.clone(),
+                ));
+            }
+        } else {
+            self.diagnostics.push(Diagnostic::unrseolved_reference(
+                super_class,
+                pou.location.clone(),
+            ));
+        }
+    }
+}
+
+/// returns the parameters and the return variable of the given pou as a list of
+/// (name, variable type, effective datatype) tuples
+fn get_signature(index: &Index, pou_name: &str) -> Vec<(String, VariableType, String)> {
+    index
+        .find_local_members(pou_name)
+        .into_iter()
+        .filter(|it| {
+            !matches!(
+                it.get_variable_type(),
+                VariableType::Local | VariableType::Temp
+            )
+        })
+        .map(|it| {
+            (
+                it.get_name().to_lowercase(),
+                *it.get_variable_type(),
+                index
+                    .get_effective_type_by_name(it.get_type_name())
+                    .get_name()
+                    .to_lowercase(),
+            )
+        })
+        .collect()
 }
@@ -2,12 +2,17 @@ use std::{convert::TryInto, mem::discriminant};
 
 use super::ValidationContext;
 use crate::{
-    ast::{AstStatement, DirectAccessType, SourceRange},
+    ast::{
+        flatten_expression_list, get_children, AccessModifier, AstStatement, DirectAccessType,
+        Implementation, Operator, PouType, SourceRange,
+    },
+    builtins,
+    index::{ImplementationType, Index, VariableType},
     resolver::StatementAnnotation,
     typesystem::{
-        DataTypeInformation, BOOL_TYPE, DATE_AND_TIME_TYPE, DATE_TYPE, DINT_TYPE, INT_TYPE,
-        LINT_TYPE, LREAL_TYPE, SINT_TYPE, STRING_TYPE, TIME_OF_DAY_TYPE, TIME_TYPE, UDINT_TYPE,
-        UINT_TYPE, ULINT_TYPE, USINT_TYPE, VOID_TYPE, WSTRING_TYPE,
+        DataTypeInformation, Dimension, StructSource, BOOL_TYPE, DATE_AND_TIME_TYPE, DATE_TYPE,
+        DINT_TYPE, INT_TYPE, LINT_TYPE, LREAL_TYPE, SINT_TYPE, STRING_TYPE, TIME_OF_DAY_TYPE,
+        TIME_TYPE, UDINT_TYPE, UINT_TYPE, ULINT_TYPE, USINT_TYPE, VOID_TYPE, WSTRING_TYPE,
     },
     Diagnostic,
 };
@@ -21,6 +26,9 @@ macro_rules! is_covered_by {
     };
 }
 
+/// the size of an address returned by ADR in bits, it is an LWORD on every target
+const ADR_SIZE: u32 = 64;
+
 pub struct StatementValidator {
     pub diagnostics: Vec<Diagnostic>,
 }
@@ -38,6 +46,7 @@ impl StatementValidator {
                 name, location, id, ..
             } => {
                 self.validate_reference(id, name, location, context);
+                self.validate_member_access(statement, name, location, context);
             }
             AstStatement::CastStatement {
                 location,
@@ -89,7 +98,54 @@ impl StatementValidator {
                     }
                 }
             }
-            AstStatement::Assignment { left, .. } => {
+            AstStatement::CallStatement {
+                operator,
+                parameters,
+                ..
+            } => {
+                self.validate_builtin_call(operator, parameters, context);
+                self.validate_call_arguments(operator, parameters, context);
+            }
+            AstStatement::IfStatement { blocks, .. } => {
+                blocks
+                    .iter()
+                    .for_each(|b| self.validate_condition(b.condition.as_ref(), context));
+            }
+            AstStatement::WhileLoopStatement { condition, .. }
+            | AstStatement::RepeatLoopStatement { condition, .. } => {
+                self.validate_condition(condition, context);
+            }
+            AstStatement::Assignment { left, right, .. } => {
+                let target_type = get_value_type_of(left, context);
+                let source_type = get_value_type_of(right, context);
+                if let Some(interface) = get_interface_name(target_type, context.index) {
+                    //only instances of classes and function blocks implementing it are assignable
+                    if !is_assignable(target_type, source_type, context) {
+                        self.diagnostics.push(Diagnostic::interface_not_implemented(
+                            get_pou_name(source_type).unwrap_or_else(|| source_type.get_name()),
+                            interface,
+                            right.get_location(),
+                        ));
+                    }
+                } else if is_in_out_argument(left, context) {
+                    //a VAR_IN_OUT references the argument, so the types have to match
+                    if !is_referenceable(target_type, source_type, context) {
+                        self.diagnostics.push(Diagnostic::incompatible_assignment(
+                            get_pou_name(source_type).unwrap_or_else(|| source_type.get_name()),
+                            get_pou_name(target_type).unwrap_or_else(|| target_type.get_name()),
+                            right.get_location(),
+                        ));
+                    }
+                } else if !is_assignable_address(target_type, right, context)
+                    .unwrap_or_else(|| is_assignable(target_type, source_type, context))
+                {
+                    self.diagnostics.push(Diagnostic::incompatible_assignment(
+                        get_pou_name(source_type).unwrap_or_else(|| source_type.get_name()),
+                        get_pou_name(target_type).unwrap_or_else(|| target_type.get_name()),
+                        right.get_location(),
+                    ));
+                }
+
                 // check if we assign to a constant variable
                 if let Some(StatementAnnotation::Variable {
                     constant: true,
@@ -103,10 +159,309 @@ impl StatementValidator {
                     ));
                 }
             }
+            AstStatement::RefAssignment { left, right, .. } => {
+                self.validate_ref_assignment(left, right, context);
+            }
             _ => (),
         }
     }
 
+    /// validates that the temporary references of the given implementation are bound with
+    /// `REF=` before they are used on every path through the implementation. A reference bound in
+    /// an IF or a CASE is only bound after it if every branch (including a missing ELSE) binds it,
+    /// a reference bound in a loop is not bound after it, the loop may not be executed at all
+    ///
+    /// only references that are unbound at the start of every call are checked (VAR_TEMP and the
+    /// variables of functions and methods), other references may still be bound from a previous call
+    pub fn validate_reference_bindings(
+        &mut self,
+        implementation: &Implementation,
+        context: &ValidationContext,
+    ) {
+        let is_stateless_pou = matches!(
+            implementation.pou_type,
+            PouType::Function | PouType::Method { .. }
+        );
+        let mut unbound_references: Vec<&str> = context
+            .index
+            .find_local_members(&implementation.name)
+            .into_iter()
+            .filter(|it| {
+                it.is_temp() || (is_stateless_pou && it.get_variable_type() == &VariableType::Local)
+            })
+            .filter(|it| {
+                is_reference(
+                    context
+                        .index
+                        .get_effective_type_by_name(it.get_type_name())
+                        .get_type_information(),
+                )
+            })
+            .map(|it| it.get_qualified_name())
+            .collect();
+        let mut reported_references = Vec::new();
+        self.validate_bound_statements(
+            &implementation.statements,
+            unbound_references,
+            &mut reported_references,
+            context,
+        );
+    }
+
+    /// validates the given statements one after another starting with the given unbound
+    /// references, returns the references that are still unbound after the statements
+    fn validate_bound_statements<'r>(
+        &mut self,
+        statements: &[AstStatement],
+        mut unbound_references: Vec<&'r str>,
+        reported_references: &mut Vec<&'r str>,
+        context: &ValidationContext,
+    ) -> Vec<&'r str> {
+        for statement in statements {
+            if unbound_references.is_empty() {
+                break;
+            }
+            self.validate_bound_references(
+                statement,
+                &mut unbound_references,
+                reported_references,
+                context,
+            );
+        }
+        unbound_references
+    }
+
+    /// reports the first use of every reference in `unbound_references`, references are removed
+    /// from the list once they are bound or reported. Reported references are collected in
+    /// `reported_references`, so they are reported only once on all paths
+    fn validate_bound_references<'r>(
+        &mut self,
+        statement: &AstStatement,
+        unbound_references: &mut Vec<&'r str>,
+        reported_references: &mut Vec<&'r str>,
+        context: &ValidationContext,
+    ) {
+        match statement {
+            AstStatement::Reference { name, location, .. } => {
+                if let Some(position) = find_variable(statement, unbound_references, context) {
+                    self.diagnostics
+                        .push(Diagnostic::unbound_reference(name, location.clone()));
+                    reported_references.push(unbound_references.remove(position));
+                }
+            }
+            AstStatement::RefAssignment { left, right, .. } => {
+                self.validate_bound_references(
+                    right,
+                    unbound_references,
+                    reported_references,
+                    context,
+                );
+                if let Some(position) = find_variable(left, unbound_references, context) {
+                    unbound_references.remove(position);
+                } else {
+                    self.validate_bound_references(
+                        left,
+                        unbound_references,
+                        reported_references,
+                        context,
+                    );
+                }
+            }
+            //__ISVALIDREF checks the reference itself
+            AstStatement::CallStatement { operator, .. }
+                if is_builtin_call(operator, builtins::IS_VALID_REF, context) => {}
+            AstStatement::IfStatement {
+                blocks, else_block, ..
+            } => {
+                let mut branches = Vec::with_capacity(blocks.len() + 1);
+                for block in blocks {
+                    //a condition is only evaluated if the conditions before it are false
+                    self.validate_bound_references(
+                        &block.condition,
+                        unbound_references,
+                        reported_references,
+                        context,
+                    );
+                    branches.push(self.validate_bound_statements(
+                        &block.body,
+                        unbound_references.clone(),
+                        reported_references,
+                        context,
+                    ));
+                }
+                //without an ELSE, the ELSE-branch leaves the references as they are
+                branches.push(self.validate_bound_statements(
+                    else_block,
+                    unbound_references.clone(),
+                    reported_references,
+                    context,
+                ));
+                *unbound_references = merge_branches(branches, reported_references);
+            }
+            AstStatement::CaseStatement {
+                selector,
+                case_blocks,
+                else_block,
+                ..
+            } => {
+                self.validate_bound_references(
+                    selector,
+                    unbound_references,
+                    reported_references,
+                    context,
+                );
+                let mut branches = Vec::with_capacity(case_blocks.len() + 1);
+                for block in case_blocks {
+                    self.validate_bound_references(
+                        &block.condition,
+                        unbound_references,
+                        reported_references,
+                        context,
+                    );
+                    branches.push(self.validate_bound_statements(
+                        &block.body,
+                        unbound_references.clone(),
+                        reported_references,
+                        context,
+                    ));
+                }
+                branches.push(self.validate_bound_statements(
+                    else_block,
+                    unbound_references.clone(),
+                    reported_references,
+                    context,
+                ));
+                *unbound_references = merge_branches(branches, reported_references);
+            }
+            //the body of a loop may not be executed at all, so it binds no references
+            AstStatement::ForLoopStatement {
+                counter,
+                start,
+                end,
+                by_step,
+                body,
+                ..
+            } => {
+                let header = vec![counter.as_ref(), start.as_ref(), end.as_ref()];
+                for statement in header.into_iter().chain(by_step.as_deref()) {
+                    self.validate_bound_references(
+                        statement,
+                        unbound_references,
+                        reported_references,
+                        context,
+                    );
+                }
+                self.validate_bound_statements(
+                    body,
+                    unbound_references.clone(),
+                    reported_references,
+                    context,
+                );
+                unbound_references.retain(|it| !reported_references.contains(it));
+            }
+            AstStatement::WhileLoopStatement {
+                condition, body, ..
+            } => {
+                self.validate_bound_references(
+                    condition,
+                    unbound_references,
+                    reported_references,
+                    context,
+                );
+                self.validate_bound_statements(
+                    body,
+                    unbound_references.clone(),
+                    reported_references,
+                    context,
+                );
+                unbound_references.retain(|it| !reported_references.contains(it));
+            }
+            AstStatement::RepeatLoopStatement {
+                condition, body, ..
+            } => {
+                let mut body_references = self.validate_bound_statements(
+                    body,
+                    unbound_references.clone(),
+                    reported_references,
+                    context,
+                );
+                self.validate_bound_references(
+                    condition,
+                    &mut body_references,
+                    reported_references,
+                    context,
+                );
+                unbound_references.retain(|it| !reported_references.contains(it));
+            }
+            _ => get_children(statement).into_iter().for_each(|it| {
+                self.validate_bound_references(it, unbound_references, reported_references, context)
+            }),
+        }
+    }
+
+    /// validates that `left REF= right` binds a reference to a variable of the referenced type
+    fn validate_ref_assignment(
+        &mut self,
+        left: &AstStatement,
+        right: &AstStatement,
+        context: &ValidationContext,
+    ) {
+        let index = context.index;
+        let reference_type = context
+            .ast_annotation
+            .get_type_or_void(left, index)
+            .get_type_information();
+        let reference_type = index
+            .find_effective_type_information(reference_type)
+            .unwrap_or(reference_type);
+        let inner_type_name = match reference_type {
+            DataTypeInformation::Pointer {
+                inner_type_name,
+                auto_deref: true,
+                ..
+            } => inner_type_name,
+            //unresolved references are reported elsewhere
+            DataTypeInformation::Void => return,
+            _ => {
+                self.diagnostics.push(Diagnostic::invalid_reference_usage(
+                    &format!("'{}' is no reference", reference_type.get_name()),
+                    left.get_location(),
+                ));
+                return;
+            }
+        };
+
+        //a reference is bound to a variable or to the address of REF(..)
+        let is_address = is_variable(right)
+            || is_builtin_call(right, builtins::REF, context)
+            || matches!(
+                right,
+                AstStatement::LiteralNull { .. }
+                    | AstStatement::UnaryExpression {
+                        operator: Operator::Address,
+                        ..
+                    }
+            );
+        if !is_address {
+            self.diagnostics.push(Diagnostic::invalid_reference_usage(
+                "only variables can be referenced",
+                right.get_location(),
+            ));
+            return;
+        }
+        let target_type = index
+            .get_effective_type_by_name(inner_type_name)
+            .get_type_information();
+        let source_type = get_value_type_of(right, context);
+        if !is_referenceable(target_type, source_type, context) {
+            self.diagnostics.push(Diagnostic::incompatible_assignment(
+                source_type.get_name(),
+                target_type.get_name(),
+                right.get_location(),
+            ));
+        }
+    }
+
     fn validate_access_index(
         &mut self,
         context: &ValidationContext,
@@ -156,6 +511,307 @@ impl StatementValidator {
         }
     }
 
+    /// validates that the arguments passed to a builtin function's generic parameters
+    /// match the parameter's type-nature (e.g. no REAL is passed to an ANY_INT parameter)
+    fn validate_builtin_call(
+        &mut self,
+        operator: &AstStatement,
+        parameters: &Option<AstStatement>,
+        context: &ValidationContext,
+    ) {
+        let index = context.index;
+        let builtin = match context.ast_annotation.get(operator) {
+            Some(StatementAnnotation::Function { qualified_name, .. })
+                if index.is_builtin(qualified_name) =>
+            {
+                qualified_name
+            }
+            _ => return,
+        };
+        for (parameter_type, argument) in builtins::get_arguments(index, builtin, parameters) {
+            //REF and ADR take the address of a variable, __ISVALIDREF checks a reference
+            let takes_address = builtin.eq_ignore_ascii_case(builtins::REF)
+                || builtin.eq_ignore_ascii_case(builtins::ADR);
+            if takes_address && !is_variable(argument) {
+                self.diagnostics.push(Diagnostic::invalid_reference_usage(
+                    "only variables can be referenced",
+                    argument.get_location(),
+                ));
+            } else if builtin.eq_ignore_ascii_case(builtins::IS_VALID_REF) {
+                let argument_type = context
+                    .ast_annotation
+                    .get_type_or_void(argument, index)
+                    .get_type_information();
+                if !is_reference(
+                    index
+                        .find_effective_type_information(argument_type)
+                        .unwrap_or(argument_type),
+                ) {
+                    self.diagnostics.push(Diagnostic::invalid_reference_usage(
+                        &format!("'{}' is no reference", argument_type.get_name()),
+                        argument.get_location(),
+                    ));
+                }
+            }
+
+            if let Some(DataTypeInformation::Generic { name, nature }) = index
+                .find_type(parameter_type)
+                .map(|it| it.get_type_information())
+            {
+                let argument_type = context
+                    .ast_annotation
+                    .get_type_or_void(argument, index)
+                    .get_type_information();
+                let argument_type = index
+                    .find_effective_type_information(argument_type)
+                    .unwrap_or(argument_type);
+                //unresolved arguments are reported elsewhere
+                let is_unresolved = matches!(argument_type, DataTypeInformation::Void);
+                if !is_unresolved && !nature.derives(argument_type) {
+                    self.diagnostics.push(Diagnostic::invalid_type_nature(
+                        argument_type.get_name(),
+                        name,
+                        argument.get_location(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// validates the positional arguments and the output assignments (`x => y`) of a call
+    /// to a function, function block, program or method
+    ///
+    /// named arguments (`x := y`) are validated like any other assignment
+    fn validate_call_arguments(
+        &mut self,
+        operator: &AstStatement,
+        parameters: &Option<AstStatement>,
+        context: &ValidationContext,
+    ) {
+        let index = context.index;
+        let pou_name = match get_called_pou(operator, context) {
+            Some(pou_name) if !index.is_builtin(pou_name) => pou_name,
+            _ => return,
+        };
+        let arguments = parameters
+            .as_ref()
+            .map(flatten_expression_list)
+            .unwrap_or_default();
+        let is_variadic = index
+            .find_type(pou_name)
+            .map(|it| it.get_type_information().is_variadic())
+            .unwrap_or(false);
+        let parameter_count = index
+            .find_local_members(pou_name)
+            .iter()
+            .filter(|it| {
+                matches!(
+                    it.get_variable_type(),
+                    VariableType::Input | VariableType::InOut | VariableType::Output
+                )
+            })
+            .count();
+        if !is_variadic && arguments.len() > parameter_count {
+            self.diagnostics.push(Diagnostic::too_many_arguments(
+                pou_name,
+                parameter_count,
+                arguments.len(),
+                arguments[parameter_count].get_location(),
+            ));
+        }
+
+        for (position, argument) in arguments.into_iter().enumerate() {
+            match argument {
+                AstStatement::Assignment { .. } | AstStatement::EmptyStatement { .. } => {}
+                AstStatement::OutputAssignment { left, right, .. } => {
+                    self.validate_output_assignment(pou_name, left, right, context);
+                }
+                _ => {
+                    if let Some(parameter) =
+                        index.find_declared_parameter(pou_name, position as u32)
+                    {
+                        let parameter_type = get_value_type(
+                            index
+                                .get_effective_type_by_name(parameter.get_type_name())
+                                .get_type_information(),
+                            index,
+                        );
+                        let argument_type = get_value_type_of(argument, context);
+                        //a VAR_IN_OUT references the argument, so the types have to match
+                        let is_compatible = if parameter.get_variable_type() == &VariableType::InOut
+                        {
+                            is_referenceable(parameter_type, argument_type, context)
+                        } else {
+                            is_assignable_address(parameter_type, argument, context).unwrap_or_else(
+                                || is_assignable(parameter_type, argument_type, context),
+                            )
+                        };
+                        if !is_compatible {
+                            self.diagnostics.push(Diagnostic::incompatible_argument(
+                                get_pou_name(argument_type)
+                                    .unwrap_or_else(|| argument_type.get_name()),
+                                parameter.get_name(),
+                                get_pou_name(parameter_type)
+                                    .unwrap_or_else(|| parameter_type.get_name()),
+                                argument.get_location(),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// validates that `left => right` assigns an output parameter to a variable of a compatible type
+    fn validate_output_assignment(
+        &mut self,
+        pou_name: &str,
+        left: &AstStatement,
+        right: &AstStatement,
+        context: &ValidationContext,
+    ) {
+        let index = context.index;
+        let parameter = match left {
+            AstStatement::Reference { name, .. } => index.find_member(pou_name, name),
+            _ => None,
+        };
+        if let Some(parameter) = parameter {
+            if parameter.get_variable_type() != &VariableType::Output {
+                self.diagnostics.push(Diagnostic::invalid_output_assignment(
+                    &format!("'{:}' is not an output", parameter.get_name()),
+                    left.get_location(),
+                ));
+                return;
+            }
+        }
+
+        if !matches!(
+            right,
+            AstStatement::Reference { .. }
+                | AstStatement::QualifiedReference { .. }
+                | AstStatement::ArrayAccess { .. }
+        ) {
+            self.diagnostics.push(Diagnostic::invalid_output_assignment(
+                "an output can only be assigned to a variable",
+                right.get_location(),
+            ));
+            return;
+        }
+
+        let output_type = get_value_type_of(left, context);
+        let target_type = get_value_type_of(right, context);
+        if !is_assignable(target_type, output_type, context) {
+            self.diagnostics.push(Diagnostic::incompatible_assignment(
+                output_type.get_name(),
+                target_type.get_name(),
+                right.get_location(),
+            ));
+        }
+    }
+
+    /// validates that the condition of an IF, WHILE or REPEAT statement is a BOOL
+    fn validate_condition(&mut self, condition: &AstStatement, context: &ValidationContext) {
+        if !is_bool_expression(condition, context) {
+            let condition_type = get_value_type_of(condition, context);
+            self.diagnostics.push(Diagnostic::invalid_condition(
+                condition_type.get_name(),
+                condition.get_location(),
+            ));
+        }
+    }
+
+    /// validates that a PRIVATE member of a class or function block is only accessed from within
+    /// its declaring pou and a PROTECTED member only from within its declaring pou or the pous
+    /// derived from it (methods and actions count as part of their pou)
+    fn validate_member_access(
+        &mut self,
+        statement: &AstStatement,
+        ref_name: &str,
+        location: &SourceRange,
+        context: &ValidationContext,
+    ) {
+        let index = context.index;
+        let member = match context.ast_annotation.get(statement) {
+            Some(StatementAnnotation::Variable { qualified_name, .. }) => {
+                let mut segments = qualified_name.rsplitn(2, '.');
+                let member_name = segments.next().unwrap_or_default();
+                segments
+                    .next()
+                    .filter(|container| {
+                        index
+                            .find_pou(container)
+                            .filter(|it| {
+                                matches!(it.get_pou_type(), PouType::Class | PouType::FunctionBlock)
+                            })
+                            .is_some()
+                    })
+                    .and_then(|container| {
+                        index
+                            .find_member(container, member_name)
+                            .filter(|it| it.is_local())
+                            .map(|it| {
+                                (
+                                    index.find_declaring_class(container, member_name),
+                                    *it.get_access(),
+                                )
+                            })
+                    })
+            }
+            Some(StatementAnnotation::Function { qualified_name, .. }) => index
+                .find_implementation(qualified_name)
+                .filter(|it| it.get_implementation_type() == &ImplementationType::Method)
+                .and_then(|it| {
+                    it.get_associated_class_name()
+                        .filter(|class| {
+                            index
+                                .find_pou(class)
+                                .filter(|it| !it.is_interface())
+                                .is_some()
+                        })
+                        .map(|class| (class.as_str(), *it.get_access()))
+                }),
+            _ => None,
+        };
+
+        if let Some((declaring_class, access)) = member {
+            let accessing_class = context.qualifier.and_then(|qualifier| {
+                index
+                    .find_implementation(qualifier)
+                    .and_then(|it| match it.get_implementation_type() {
+                        //an action runs on the instance of its pou
+                        ImplementationType::Action => Some(it.get_type_name()),
+                        _ => it.get_associated_class_name().map(|it| it.as_str()),
+                    })
+                    .or(Some(qualifier))
+                    .filter(|it| {
+                        index
+                            .find_pou(it)
+                            .filter(|it| {
+                                matches!(it.get_pou_type(), PouType::Class | PouType::FunctionBlock)
+                            })
+                            .is_some()
+                    })
+            });
+
+            let accessible = match access {
+                AccessModifier::Public | AccessModifier::Internal => true,
+                AccessModifier::Private => accessing_class
+                    .filter(|it| it.eq_ignore_ascii_case(declaring_class))
+                    .is_some(),
+                AccessModifier::Protected => accessing_class
+                    .filter(|it| index.is_same_or_derived_class(it, declaring_class))
+                    .is_some(),
+            };
+            if !accessible {
+                self.diagnostics.push(Diagnostic::illegal_access(
+                    ref_name,
+                    &access,
+                    location.clone(),
+                ));
+            }
+        }
+    }
+
     /// validates a literal statement with a dedicated type-prefix (e.g. INT#3)
     ///
     /// checks whether ...
@@ -269,6 +925,442 @@ impl StatementValidator {
     }
 }
 
+/// returns the name of the function, function block, program or method called by the given operator
+fn get_called_pou<'i>(operator: &AstStatement, context: &ValidationContext<'i>) -> Option<&'i str> {
+    let index = context.index;
+    let pou_name = match context.ast_annotation.get(operator)? {
+        StatementAnnotation::Function { qualified_name, .. }
+        | StatementAnnotation::Program { qualified_name } => qualified_name.as_str(),
+        StatementAnnotation::Variable { resulting_type, .. } => resulting_type.as_str(),
+        _ => return None,
+    };
+    index
+        .find_implementation(pou_name)
+        .map(|it| it.get_call_name())
+}
+
+/// returns the position of the variable the given statement refers to in the given
+/// qualified names or None if it refers to none of them
+fn find_variable(
+    statement: &AstStatement,
+    qualified_names: &[&str],
+    context: &ValidationContext,
+) -> Option<usize> {
+    match context.ast_annotation.get(statement) {
+        Some(StatementAnnotation::Variable { qualified_name, .. }) => qualified_names
+            .iter()
+            .position(|it| *it == qualified_name.as_str()),
+        _ => None,
+    }
+}
+
+/// merges the unbound references at the end of the branches of an IF or a CASE: a reference is
+/// unbound after the statement if it is unbound at the end of any branch and was not reported yet
+fn merge_branches<'r>(
+    branches: Vec<Vec<&'r str>>,
+    reported_references: &[&'r str],
+) -> Vec<&'r str> {
+    let mut unbound_references: Vec<&str> = branches
+        .into_iter()
+        .flatten()
+        .filter(|it| !reported_references.contains(it))
+        .collect();
+    unbound_references.sort_unstable();
+    unbound_references.dedup();
+    unbound_references
+}
+
+/// returns true if the given statement is a call to the given builtin function
+fn is_builtin_call(statement: &AstStatement, builtin: &str, context: &ValidationContext) -> bool {
+    let operator = match statement {
+        AstStatement::CallStatement { operator, .. } => operator.as_ref(),
+        _ => statement,
+    };
+    matches!(
+        context.ast_annotation.get(operator),
+        Some(StatementAnnotation::Function { qualified_name, .. })
+            if qualified_name.eq_ignore_ascii_case(builtin)
+    )
+}
+
+/// returns true if the given statement refers to a variable (or a part of it) that can be referenced
+fn is_variable(statement: &AstStatement) -> bool {
+    matches!(
+        statement,
+        AstStatement::Reference { .. }
+            | AstStatement::QualifiedReference { .. }
+            | AstStatement::ArrayAccess { .. }
+            | AstStatement::PointerAccess { .. }
+    )
+}
+
+/// returns true if the given type is a reference (`REFERENCE TO`) or a VAR_IN_OUT's pointer
+fn is_reference(data_type: &DataTypeInformation) -> bool {
+    matches!(
+        data_type,
+        DataTypeInformation::Pointer {
+            auto_deref: true,
+            ..
+        }
+    )
+}
+
+/// returns the type of values stored in a variable of the given type, variables with an
+/// auto-deref pointer type (e.g. VAR_IN_OUT) store values of the pointer's inner type
+fn get_value_type<'i>(
+    data_type: &'i DataTypeInformation,
+    index: &'i Index,
+) -> &'i DataTypeInformation {
+    let data_type = index
+        .find_effective_type_information(data_type)
+        .unwrap_or(data_type);
+    match data_type {
+        DataTypeInformation::Pointer {
+            inner_type_name,
+            auto_deref: true,
+            ..
+        } => index
+            .get_effective_type_by_name(inner_type_name)
+            .get_type_information(),
+        _ => data_type,
+    }
+}
+
+/// returns the type of the value the given statement evaluates to
+fn get_value_type_of<'i>(
+    statement: &AstStatement,
+    context: &ValidationContext<'i>,
+) -> &'i DataTypeInformation {
+    get_value_type(
+        context
+            .ast_annotation
+            .get_type_or_void(statement, context.index)
+            .get_type_information(),
+        context.index,
+    )
+}
+
+/// returns true if a value of the source type can be assigned to a variable of the target type
+///
+/// numbers convert implicitly, strings need the same encoding, structs and function block
+/// instances need the same type, unresolved and generic types are ignored
+fn is_assignable(
+    target: &DataTypeInformation,
+    source: &DataTypeInformation,
+    context: &ValidationContext,
+) -> bool {
+    let index = context.index;
+    match (target, source) {
+        (DataTypeInformation::Void, _)
+        | (_, DataTypeInformation::Void)
+        | (DataTypeInformation::Generic { .. }, _)
+        | (_, DataTypeInformation::Generic { .. }) => true,
+        (target, source) if target.is_numerical() && source.is_numerical() => true,
+        (
+            DataTypeInformation::String {
+                encoding: target_encoding,
+                ..
+            },
+            DataTypeInformation::String {
+                encoding: source_encoding,
+                ..
+            },
+        ) => target_encoding == source_encoding,
+        //an interface takes the instances of all classes and function blocks implementing it
+        (DataTypeInformation::Struct { .. }, DataTypeInformation::Struct { .. })
+            if get_interface_name(target, index).is_some() =>
+        {
+            let interface = get_pou_name(target).unwrap_or_default();
+            get_pou_name(source).map_or(false, |source_pou| {
+                source_pou.eq_ignore_ascii_case(interface)
+                    || index
+                        .find_implemented_interfaces(source_pou)
+                        .iter()
+                        .any(|it| it.eq_ignore_ascii_case(interface))
+            })
+        }
+        (
+            DataTypeInformation::Struct {
+                name: target_name, ..
+            },
+            DataTypeInformation::Struct {
+                name: source_name, ..
+            },
+        ) => target_name.eq_ignore_ascii_case(source_name),
+        //an `ARRAY[*]` takes every array of the same element type and number of dimensions
+        (DataTypeInformation::VariableLengthArray { .. }, _) => {
+            get_array_shape(target, index).is_some()
+                && get_array_shape(target, index) == get_array_shape(source, index)
+        }
+        //arrays need the same element type and the same length in every dimension
+        (
+            DataTypeInformation::Array {
+                inner_type_name: target_inner,
+                dimensions: target_dimensions,
+                ..
+            },
+            DataTypeInformation::Array {
+                inner_type_name: source_inner,
+                dimensions: source_dimensions,
+                ..
+            },
+        ) => {
+            let get_lengths = |dimensions: &[Dimension]| {
+                dimensions
+                    .iter()
+                    .map(|it| it.get_length(index).ok())
+                    .collect::<Vec<Option<u32>>>()
+            };
+            let target_lengths = get_lengths(target_dimensions);
+            let source_lengths = get_lengths(source_dimensions);
+            //lengths that cannot be evaluated are reported elsewhere
+            target_lengths.len() == source_lengths.len()
+                && target_lengths
+                    .iter()
+                    .zip(source_lengths.iter())
+                    .all(|(target, source)| {
+                        target.is_none() || source.is_none() || target == source
+                    })
+                && is_same_inner_type(target_inner, source_inner, context)
+        }
+        (
+            DataTypeInformation::Pointer {
+                inner_type_name: target_inner,
+                ..
+            },
+            DataTypeInformation::Pointer {
+                inner_type_name: source_inner,
+                ..
+            },
+        ) => is_referenceable(
+            index
+                .get_effective_type_by_name(target_inner)
+                .get_type_information(),
+            index
+                .get_effective_type_by_name(source_inner)
+                .get_type_information(),
+            context,
+        ),
+        //pointers convert to integers as wide as an address of the target, addresses from ADR
+        //are LWORDs on every target
+        (DataTypeInformation::Pointer { .. }, DataTypeInformation::Integer { size, .. })
+        | (DataTypeInformation::Integer { size, .. }, DataTypeInformation::Pointer { .. }) => {
+            *size == context.address_size || *size == ADR_SIZE
+        }
+        _ => false,
+    }
+}
+
+/// returns the name of the POU whose instances have the given type, None if it is no POU's type.
+/// The struct of a POU's instance is named `<pou>_interface`
+fn get_pou_name(data_type: &DataTypeInformation) -> Option<&str> {
+    match data_type {
+        DataTypeInformation::Struct {
+            name,
+            source: StructSource::Pou(..),
+            ..
+        } => name.strip_suffix("_interface"),
+        _ => None,
+    }
+}
+
+/// returns the name of the interface if the given type is an interface
+fn get_interface_name<'i>(data_type: &'i DataTypeInformation, index: &Index) -> Option<&'i str> {
+    get_pou_name(data_type).filter(|it| {
+        index
+            .find_pou(it)
+            .filter(|pou| pou.is_interface())
+            .is_some()
+    })
+}
+
+/// returns whether a pointer to the given address expression (`&x` or `REF(x)`) can be
+/// assigned to a variable of the target type, None if the source is no address expression.
+/// Address expressions are annotated with the type of the variable they point to, so they are
+/// checked against the target's inner type
+fn is_assignable_address(
+    target: &DataTypeInformation,
+    source: &AstStatement,
+    context: &ValidationContext,
+) -> Option<bool> {
+    let is_address = matches!(
+        source,
+        AstStatement::UnaryExpression {
+            operator: Operator::Address,
+            ..
+        }
+    ) || is_builtin_call(source, builtins::REF, context);
+    if !is_address {
+        return None;
+    }
+    let index = context.index;
+    Some(match target {
+        DataTypeInformation::Pointer {
+            inner_type_name,
+            auto_deref: false,
+            ..
+        } => is_referenceable(
+            index
+                .get_effective_type_by_name(inner_type_name)
+                .get_type_information(),
+            get_value_type_of(source, context),
+            context,
+        ),
+        DataTypeInformation::Void => true,
+        _ => false,
+    })
+}
+
+/// returns true if a variable of the source type can be referenced as the target type
+/// (VAR_IN_OUT arguments, pointers and references).
+///
+/// an instance of a derived class or function block starts with the members of its base,
+/// so it can be referenced as its base. It cannot be assigned by value though, the base
+/// is smaller than the derived instance.
+fn is_referenceable(
+    target: &DataTypeInformation,
+    source: &DataTypeInformation,
+    context: &ValidationContext,
+) -> bool {
+    let index = context.index;
+    match (get_pou_name(target), get_pou_name(source)) {
+        (Some(target_pou), Some(source_pou)) if get_interface_name(target, index).is_none() => {
+            index.is_same_or_derived_class(source_pou, target_pou)
+        }
+        _ => is_same_type(target, source, context),
+    }
+}
+
+/// returns true if the given assignment passes an argument to a VAR_IN_OUT parameter of a
+/// called POU, e.g. `foo(x := y)`
+fn is_in_out_argument(left: &AstStatement, context: &ValidationContext) -> bool {
+    match context.ast_annotation.get(left) {
+        Some(StatementAnnotation::Variable { qualified_name, .. }) => qualified_name
+            .rsplit_once('.')
+            .filter(|(pou, _)| {
+                context
+                    .qualifier
+                    .map_or(true, |it| !it.eq_ignore_ascii_case(pou))
+            })
+            .and_then(|(pou, name)| context.index.find_member(pou, name))
+            .map_or(false, |it| it.get_variable_type() == &VariableType::InOut),
+        _ => false,
+    }
+}
+
+/// returns true if the given element types of two arrays or pointers are the same type
+fn is_same_inner_type(target: &str, source: &str, context: &ValidationContext) -> bool {
+    let index = context.index;
+    is_same_type(
+        index
+            .get_effective_type_by_name(target)
+            .get_type_information(),
+        index
+            .get_effective_type_by_name(source)
+            .get_type_information(),
+        context,
+    )
+}
+
+/// returns true if both types are the same type, unresolved and generic types are ignored
+fn is_same_type(
+    target: &DataTypeInformation,
+    source: &DataTypeInformation,
+    context: &ValidationContext,
+) -> bool {
+    let index = context.index;
+    match (target, source) {
+        (DataTypeInformation::Void, _)
+        | (_, DataTypeInformation::Void)
+        | (DataTypeInformation::Generic { .. }, _)
+        | (_, DataTypeInformation::Generic { .. }) => true,
+        //an interface reference has another layout than the instances implementing it
+        (DataTypeInformation::Struct { .. }, _) if get_interface_name(target, index).is_some() => {
+            target.get_name().eq_ignore_ascii_case(source.get_name())
+        }
+        //a string is referenced with its length, so it has to match as well
+        (
+            DataTypeInformation::String {
+                size: target_size,
+                encoding: target_encoding,
+            },
+            DataTypeInformation::String {
+                size: source_size,
+                encoding: source_encoding,
+            },
+        ) => {
+            //lengths that cannot be evaluated are reported elsewhere
+            let target_length = target_size.as_int_value(index).ok();
+            let source_length = source_size.as_int_value(index).ok();
+            target_encoding == source_encoding
+                && (target_length.is_none()
+                    || source_length.is_none()
+                    || target_length == source_length)
+        }
+        //compound types of different (inline) declarations share the same layout
+        (DataTypeInformation::VariableLengthArray { .. }, _)
+        | (DataTypeInformation::Array { .. }, DataTypeInformation::Array { .. })
+        | (DataTypeInformation::Struct { .. }, DataTypeInformation::Struct { .. })
+        | (DataTypeInformation::Pointer { .. }, DataTypeInformation::Pointer { .. }) => {
+            is_assignable(target, source, context)
+        }
+        _ => target.get_name().eq_ignore_ascii_case(source.get_name()),
+    }
+}
+
+/// returns the (lower case) element type and the number of dimensions of an array
+fn get_array_shape(data_type: &DataTypeInformation, index: &Index) -> Option<(String, usize)> {
+    let (inner_type_name, dimension_count) = match data_type {
+        DataTypeInformation::Array {
+            inner_type_name,
+            dimensions,
+            ..
+        } => (inner_type_name, dimensions.len()),
+        DataTypeInformation::VariableLengthArray {
+            inner_type_name,
+            dimension_count,
+            ..
+        } => (inner_type_name, *dimension_count),
+        _ => return None,
+    };
+    let inner_type = index.get_effective_type_by_name(inner_type_name);
+    Some((inner_type.get_name().to_lowercase(), dimension_count))
+}
+
+/// returns true if the given expression results in a BOOL
+///
+/// comparisons are annotated with the type of their operands, so they are checked syntactically
+fn is_bool_expression(expression: &AstStatement, context: &ValidationContext) -> bool {
+    match expression {
+        AstStatement::BinaryExpression {
+            operator:
+                Operator::Equal
+                | Operator::NotEqual
+                | Operator::Less
+                | Operator::Greater
+                | Operator::LessOrEqual
+                | Operator::GreaterOrEqual,
+            ..
+        } => true,
+        AstStatement::BinaryExpression {
+            operator: Operator::And | Operator::Or | Operator::Xor,
+            left,
+            right,
+            ..
+        } => is_bool_expression(left, context) && is_bool_expression(right, context),
+        AstStatement::UnaryExpression {
+            operator: Operator::Not,
+            value,
+            ..
+        } => is_bool_expression(value, context),
+        _ => {
+            let expression_type = get_value_type_of(expression, context);
+            matches!(expression_type, DataTypeInformation::Void)
+                || expression_type.get_name() == BOOL_TYPE
+        }
+    }
+}
+
 fn is_date_or_time_type(cast_type: &crate::typesystem::DataTypeInformation) -> bool {
     return cast_type.get_name() == DATE_TYPE
         || cast_type.get_name() == DATE_AND_TIME_TYPE
@@ -1,32 +1,52 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
-use super::Validator;
+use super::{Linter, Validator};
 use crate::{
-    ast,
+    ast::{self, CompilationUnit},
+    builtins,
     index::{self, Index},
-    lexer::lex,
+    lexer::{lex, IdProvider},
     parser::parse,
     resolver::{self, TypeAnnotator},
     Diagnostic,
 };
 
 mod bitaccess_validation_test;
+mod lint_tests;
 mod literals_validation_tests;
+mod pou_validation_tests;
 mod reference_resolve_tests;
 mod variable_validation_tests;
 
 mod statement_validation_tests;
 
 pub fn parse_and_validate(src: &str) -> Vec<Diagnostic> {
-    let mut idx = Index::new();
-    let (mut ast, _) = parse(lex(src));
-    ast::pre_process(&mut ast);
-    idx.import(index::visitor::visit(&ast));
-
-    let (idx, _) = resolver::const_evaluator::evaluate_constants(idx);
+    parse_and_validate_for_target(src, 64)
+}
 
+pub fn parse_and_validate_for_target(src: &str, address_size: u32) -> Vec<Diagnostic> {
+    let (idx, ast) = parse_and_index(src);
     let annotations = TypeAnnotator::visit_unit(&idx, &ast);
 
-    let mut validator = Validator::new();
+    let mut validator = Validator::for_target(address_size);
     validator.visit_unit(&annotations, &idx, &ast);
     validator.diagnostics()
 }
+
+pub fn parse_and_lint(src: &str) -> Vec<Diagnostic> {
+    let (idx, ast) = parse_and_index(src);
+    let annotations = TypeAnnotator::visit_unit(&idx, &ast);
+
+    let mut linter = Linter::new();
+    linter.visit_unit(&annotations, &idx, &ast);
+    linter.diagnostics
+}
+
+fn parse_and_index(src: &str) -> (Index, CompilationUnit) {
+    let mut idx = index::visitor::visit(&builtins::parse_built_ins(IdProvider::new()));
+    let (mut ast, _) = parse(lex(src));
+    ast::pre_process(&mut ast);
+    idx.import(index::visitor::visit(&ast));
+
+    let (idx, _) = resolver::const_evaluator::evaluate_constants(idx);
+    (idx, ast)
+}
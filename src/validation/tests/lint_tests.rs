@@ -0,0 +1,182 @@
+use crate::{validation::tests::parse_and_lint, Diagnostic};
+
+#[test]
+fn unused_variables_and_inputs_are_reported() {
+    let diagnostics = parse_and_lint(
+        "
+        FUNCTION foo : INT
+            VAR_INPUT
+                a : INT;
+                b : INT;
+            END_VAR
+            VAR
+                c : INT;
+                d : INT;
+            END_VAR
+            VAR_TEMP
+                e : INT;
+            END_VAR
+            foo := a + c;
+        END_FUNCTION
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::unused_input("b", (91..92).into()),
+            Diagnostic::unused_variable("d", (177..178).into()),
+            Diagnostic::unused_variable("e", (243..244).into()),
+        ]
+    );
+}
+
+#[test]
+fn only_private_variables_of_programs_and_function_blocks_are_reported_as_unused() {
+    let diagnostics = parse_and_lint(
+        "
+        PROGRAM prg
+            VAR
+                x : INT;
+            END_VAR
+        END_PROGRAM
+
+        FUNCTION_BLOCK fb
+            VAR_INPUT
+                in : INT;
+            END_VAR
+            VAR
+                y : INT;
+            END_VAR
+            VAR_TEMP
+                t : INT;
+            END_VAR
+        END_FUNCTION_BLOCK
+
+        ACTIONS fb
+            ACTION act
+                y := in;
+            END_ACTION
+        END_ACTIONS
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::unused_variable("t", (295..296).into())]
+    );
+}
+
+#[test]
+fn unused_inputs_of_external_functions_are_not_reported() {
+    let diagnostics = parse_and_lint(
+        "
+        @EXTERNAL FUNCTION ext : INT
+            VAR_INPUT
+                a : INT;
+            END_VAR
+        END_FUNCTION
+       ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn assignments_without_effect_are_reported() {
+    let diagnostics = parse_and_lint(
+        "
+        PROGRAM prg
+            VAR
+                x : INT;
+                y : INT;
+            END_VAR
+            x := x;
+            x := y;
+            x = 3;
+            IF x > 0 THEN
+                x = 4;
+            END_IF
+        END_PROGRAM
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::assignment_without_effect(
+                "the variable is assigned to itself",
+                (119..125).into()
+            ),
+            Diagnostic::assignment_without_effect(
+                "the result of the comparison is not used, did you mean ':='?",
+                (159..164).into()
+            ),
+            Diagnostic::assignment_without_effect(
+                "the result of the comparison is not used, did you mean ':='?",
+                (208..213).into()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn variables_shadowing_globals_are_reported() {
+    let diagnostics = parse_and_lint(
+        "
+        VAR_GLOBAL
+            g : INT;
+        END_VAR
+
+        PROGRAM prg
+            VAR
+                g : INT;
+                h : INT;
+            END_VAR
+            g := h;
+        END_PROGRAM
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::shadowed_global("g", (110..111).into())]
+    );
+}
+
+#[test]
+fn implicit_narrowing_conversions_are_reported() {
+    let diagnostics = parse_and_lint(
+        "
+        PROGRAM prg
+            VAR
+                s : SINT;
+                i : INT;
+                d : DINT;
+                r : REAL;
+                l : LREAL;
+                b : BOOL;
+            END_VAR
+            s := i;
+            i := s;
+            i := 500;
+            s := s + 1;
+            i := d + s;
+            r := l;
+            l := r;
+            i := r;
+            b := i > 3;
+        END_PROGRAM
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::implicit_narrowing("INT", "SINT", (230..231).into()),
+            Diagnostic::implicit_narrowing("DINT", "INT", (316..321).into()),
+            Diagnostic::implicit_narrowing("LREAL", "REAL", (340..341).into()),
+            Diagnostic::implicit_narrowing("REAL", "INT", (380..381).into()),
+        ]
+    );
+}
@@ -0,0 +1,202 @@
+use crate::{validation::tests::parse_and_validate, Diagnostic};
+
+#[test]
+fn invalid_super_classes_are_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        CLASS FINAL base END_CLASS
+        CLASS derived EXTENDS base END_CLASS
+        FUNCTION_BLOCK fb EXTENDS base END_FUNCTION_BLOCK
+        CLASS cls EXTENDS unknown END_CLASS
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::cannot_extend_final("derived", "base", (44..80).into()),
+            Diagnostic::invalid_super_class("fb", "base", (89..138).into()),
+            Diagnostic::unrseolved_reference("unknown", (147..182).into()),
+        ]
+    );
+}
+
+#[test]
+fn valid_super_classes_are_not_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        CLASS base END_CLASS
+        CLASS derived EXTENDS base END_CLASS
+        FUNCTION_BLOCK base_fb END_FUNCTION_BLOCK
+        FUNCTION_BLOCK fb EXTENDS base_fb END_FUNCTION_BLOCK
+       ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn cyclic_inheritance_is_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        CLASS a EXTENDS b END_CLASS
+        CLASS b EXTENDS a END_CLASS
+        CLASS c EXTENDS c END_CLASS
+        CLASS d EXTENDS a END_CLASS
+        FUNCTION_BLOCK fb1 EXTENDS fb2 END_FUNCTION_BLOCK
+        FUNCTION_BLOCK fb2 EXTENDS fb1 END_FUNCTION_BLOCK
+       ",
+    );
+
+    // d extends a class of a cycle, but is not part of the cycle itself
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::cyclic_inheritance("a", "b", (9..36).into()),
+            Diagnostic::cyclic_inheritance("b", "a", (45..72).into()),
+            Diagnostic::cyclic_inheritance("c", "c", (81..108).into()),
+            Diagnostic::cyclic_inheritance("fb1", "fb2", (153..202).into()),
+            Diagnostic::cyclic_inheritance("fb2", "fb1", (211..260).into()),
+        ]
+    );
+}
+
+#[test]
+fn missing_and_mismatching_interface_methods_are_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        INTERFACE I1
+            METHOD foo : INT
+                VAR_INPUT x : INT; END_VAR
+            END_METHOD
+            METHOD bar END_METHOD
+        END_INTERFACE
+
+        CLASS cls IMPLEMENTS I1, unknown, base
+            METHOD foo : DINT
+                VAR_INPUT x : INT; END_VAR
+            END_METHOD
+        END_CLASS
+
+        CLASS base END_CLASS
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::missing_interface_method("cls", "I1", "bar", (182..334).into()),
+            Diagnostic::unrseolved_reference("unknown", (182..334).into()),
+            Diagnostic::invalid_interface("cls", "base", (182..334).into()),
+            Diagnostic::interface_method_mismatch("cls.foo", "I1.foo", (233..316).into()),
+        ]
+    );
+}
+
+#[test]
+fn inherited_interface_methods_are_accepted() {
+    let diagnostics = parse_and_validate(
+        "
+        INTERFACE I1
+            METHOD foo : INT
+                VAR_INPUT x : INT; END_VAR
+            END_METHOD
+        END_INTERFACE
+
+        CLASS base
+            METHOD foo : INT
+                VAR_INPUT x : INT; END_VAR
+            END_METHOD
+        END_CLASS
+
+        CLASS derived EXTENDS base IMPLEMENTS I1
+        END_CLASS
+       ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn invalid_overrides_are_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        CLASS base
+            METHOD foo END_METHOD
+            METHOD FINAL bar END_METHOD
+        END_CLASS
+
+        CLASS derived EXTENDS base
+            METHOD OVERRIDE foo END_METHOD
+            METHOD OVERRIDE bar END_METHOD
+            METHOD OVERRIDE baz END_METHOD
+        END_CLASS
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::cannot_override_final("derived.bar", "base.bar", (203..233).into()),
+            Diagnostic::invalid_override("derived.baz", (246..276).into()),
+        ]
+    );
+}
+
+#[test]
+fn abstract_classes_and_methods_are_validated() {
+    let diagnostics = parse_and_validate(
+        "
+        CLASS ABSTRACT shape
+            METHOD ABSTRACT area : INT END_METHOD
+            METHOD ABSTRACT name : INT END_METHOD
+        END_CLASS
+
+        CLASS square EXTENDS shape
+            METHOD OVERRIDE area : INT END_METHOD
+        END_CLASS
+
+        FUNCTION main : INT
+            VAR
+                s : shape;
+                q : square;
+            END_VAR
+            VAR_IN_OUT r : shape; END_VAR
+        END_FUNCTION
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::unimplemented_abstract_method("square", "shape.name", (157..251).into()),
+            Diagnostic::abstract_instance("shape", (313..314).into()),
+        ]
+    );
+}
+
+#[test]
+fn program_instances_of_configurations_are_validated() {
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM prg END_PROGRAM
+        FUNCTION_BLOCK fb END_FUNCTION_BLOCK
+        CONFIGURATION config
+            RESOURCE res ON PLC
+                TASK t(INTERVAL := T#10ms);
+                PROGRAM p1 WITH t : prg;
+                PROGRAM p2 WITH unknown : prg;
+                PROGRAM p3 WITH t : fb;
+            END_RESOURCE
+        END_CONFIGURATION
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::unknown_task("unknown", (240..269).into()),
+            Diagnostic::invalid_program_instance("fb", (287..309).into()),
+        ]
+    );
+}
@@ -1,4 +1,4 @@
-use crate::{validation::tests::parse_and_validate, Diagnostic};
+use crate::{ast::AccessModifier, validation::tests::parse_and_validate, Diagnostic};
 
 /// tests wheter simple local and global variables can be resolved and
 /// errors are reported properly
@@ -235,3 +235,96 @@ fn resolve_function_members_via_qualifier() {
         ]
     );
 }
+
+#[test]
+fn illegal_access_to_private_and_protected_members_is_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        CLASS base
+            VAR PRIVATE p : INT; END_VAR
+            VAR PROTECTED q : INT; END_VAR
+            VAR PUBLIC r : INT; END_VAR
+
+            METHOD PRIVATE foo END_METHOD
+
+            METHOD PROTECTED bar
+                p := q + r; (* ok *)
+                foo();      (* ok *)
+            END_METHOD
+        END_CLASS
+
+        CLASS derived EXTENDS base
+            METHOD baz
+                q := r;     (* ok *)
+                bar();      (* ok *)
+                p := 1;     (* not ok *)
+                foo();      (* not ok *)
+            END_METHOD
+        END_CLASS
+
+        PROGRAM prg
+            VAR d : derived; END_VAR
+            d.r := 1;       (* ok *)
+            d.q := 1;       (* not ok *)
+            d.bar();        (* not ok *)
+        END_PROGRAM
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::illegal_access("p", &AccessModifier::Private, (485..486).into()),
+            Diagnostic::illegal_access("foo", &AccessModifier::Private, (526..529).into()),
+            Diagnostic::illegal_access("q", &AccessModifier::Protected, (701..702).into()),
+            Diagnostic::illegal_access("bar", &AccessModifier::Protected, (742..745).into()),
+        ]
+    );
+}
+
+#[test]
+fn illegal_access_to_function_block_members_is_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION_BLOCK base
+            VAR PRIVATE p : INT; END_VAR
+            VAR PROTECTED q : INT; END_VAR
+            VAR s : INT; END_VAR
+            VAR PUBLIC r : INT; END_VAR
+
+            METHOD PRIVATE foo END_METHOD
+
+            p := q + r; (* ok *)
+            foo();      (* ok *)
+        END_FUNCTION_BLOCK
+
+        FUNCTION_BLOCK derived EXTENDS base
+            METHOD PROTECTED bar
+                q := r;     (* ok *)
+                foo();      (* not ok *)
+            END_METHOD
+
+            p := 1;         (* not ok *)
+        END_FUNCTION_BLOCK
+
+        PROGRAM prg
+            VAR d : derived; END_VAR
+            d.r := 1;       (* ok *)
+            d.s := 1;       (* ok *)
+            d.q := 1;       (* not ok *)
+            d.bar();        (* not ok *)
+        END_PROGRAM
+       
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::illegal_access("foo", &AccessModifier::Private, (454..457).into()),
+            Diagnostic::illegal_access("p", &AccessModifier::Private, (515..516).into()),
+            Diagnostic::illegal_access("q", &AccessModifier::Protected, (717..718).into()),
+            Diagnostic::illegal_access("bar", &AccessModifier::Protected, (758..761).into()),
+        ]
+    );
+}
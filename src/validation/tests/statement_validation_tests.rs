@@ -1,4 +1,7 @@
-use crate::{validation::tests::parse_and_validate, Diagnostic};
+use crate::{
+    validation::tests::{parse_and_validate, parse_and_validate_for_target},
+    Diagnostic,
+};
 
 #[test]
 fn assignment_to_constants_result_in_an_error() {
@@ -75,3 +78,727 @@ fn assignment_to_enum_literals_results_in_error() {
         ]
     );
 }
+
+#[test]
+fn arguments_of_builtin_functions_must_match_the_generic_type_nature() {
+    // GIVEN calls to builtin functions with arguments of the wrong type nature
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM prg
+            VAR
+                i : INT;
+                r : REAL;
+                s : STRING;
+            END_VAR
+
+            SQRT(i);
+            SHL(r, 2);
+            SHL(i, r);
+            ABS(s);
+        END_PROGRAM
+      ",
+    );
+
+    // THEN the arguments not matching the generic parameters are reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::invalid_type_nature("REAL", "ANY_BIT", (174..175).into()),
+            Diagnostic::invalid_type_nature("REAL", "ANY_INT", (200..201).into()),
+            Diagnostic::invalid_type_nature("STRING", "ANY_NUM", (220..221).into()),
+        ]
+    );
+}
+
+#[test]
+fn arguments_of_string_functions_must_be_strings() {
+    // GIVEN calls to string functions with arguments of the wrong type nature
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM prg
+            VAR
+                i : INT;
+                s : STRING;
+            END_VAR
+
+            LEN(i);
+            LEFT(s, s);
+            CONCAT(s, i);
+        END_PROGRAM
+      ",
+    );
+
+    // THEN the arguments not matching the generic parameters are reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::invalid_type_nature("INT", "ANY_STRING", (127..128).into()),
+            Diagnostic::invalid_type_nature("STRING", "ANY_INT", (148..149).into()),
+            Diagnostic::invalid_type_nature("INT", "ANY_STRING", (177..178).into()),
+        ]
+    );
+}
+
+#[test]
+fn assignments_of_incompatible_types_result_in_an_error() {
+    // GIVEN assignments between numbers, strings and structs
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        TYPE MyStruct : STRUCT
+            a : DINT;
+        END_STRUCT END_TYPE
+
+        PROGRAM prg
+            VAR
+                i : INT;
+                r : REAL;
+                s : STRING;
+                ws : WSTRING;
+                st : MyStruct;
+                st2 : MyStruct;
+            END_VAR
+
+            i := r;     (* ok *)
+            st := st2;  (* ok *)
+            i := s;
+            s := i;
+            ws := s;
+            st := i;
+        END_PROGRAM
+      ",
+    );
+
+    // THEN every incompatible assignment is reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::incompatible_assignment("STRING", "INT", (395..396).into()),
+            Diagnostic::incompatible_assignment("INT", "STRING", (415..416).into()),
+            Diagnostic::incompatible_assignment("STRING", "WSTRING", (436..437).into()),
+            Diagnostic::incompatible_assignment("INT", "MyStruct", (457..458).into()),
+        ]
+    );
+}
+
+#[test]
+fn instances_are_assigned_to_their_own_class_and_referenced_as_their_base_class() {
+    // GIVEN assignments, arguments and references of instances of classes and function blocks
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        CLASS base END_CLASS
+        CLASS derived EXTENDS base END_CLASS
+        CLASS other END_CLASS
+        FUNCTION_BLOCK fb END_FUNCTION_BLOCK
+
+        FUNCTION by_value : INT
+            VAR_INPUT x : base; END_VAR
+        END_FUNCTION
+
+        FUNCTION by_reference : INT
+            VAR_IN_OUT x : base; END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+            VAR
+                b : base;
+                d : derived;
+                o : other;
+                f : fb;
+                pb : REF_TO base;
+            END_VAR
+
+            by_reference(d);        (* ok *)
+            by_reference(x := d);   (* ok *)
+            pb := &d;               (* ok *)
+            pb := REF(d);           (* ok *)
+            b := d;
+            d := b;
+            b := o;
+            f := d;
+            by_value(d);
+            by_value(x := d);
+        END_PROGRAM
+      ",
+    );
+
+    // THEN a derived instance can only be referenced as its base class, it does not fit into
+    // a variable of its base class
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::incompatible_assignment("derived", "base", (738..739).into()),
+            Diagnostic::incompatible_assignment("base", "derived", (758..759).into()),
+            Diagnostic::incompatible_assignment("other", "base", (778..779).into()),
+            Diagnostic::incompatible_assignment("derived", "fb", (798..799).into()),
+            Diagnostic::incompatible_argument("derived", "x", "base", (822..823).into()),
+            Diagnostic::incompatible_assignment("derived", "base", (852..853).into()),
+        ]
+    );
+}
+
+#[test]
+fn only_instances_implementing_an_interface_can_be_assigned_to_it() {
+    // GIVEN assignments of instances, interfaces and numbers to an interface
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        INTERFACE ICounter
+            METHOD next : INT END_METHOD
+        END_INTERFACE
+
+        INTERFACE IOther END_INTERFACE
+
+        CLASS Up IMPLEMENTS ICounter
+            METHOD next : INT END_METHOD
+        END_CLASS
+
+        CLASS Derived EXTENDS Up END_CLASS
+
+        FUNCTION_BLOCK fb IMPLEMENTS ICounter
+            METHOD next : INT END_METHOD
+        END_FUNCTION_BLOCK
+
+        CLASS Plain END_CLASS
+
+        FUNCTION count : INT
+            VAR_INPUT counter : ICounter; END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+            VAR
+                c, c2 : ICounter;
+                o : IOther;
+                up : Up;
+                d : Derived;
+                f : fb;
+                p : Plain;
+                i : INT;
+            END_VAR
+
+            c := up;    (* ok *)
+            c := d;     (* ok *)
+            c := f;     (* ok *)
+            c := c2;    (* ok *)
+            count(up);  (* ok *)
+            c := p;
+            c := i;
+            c := o;
+        END_PROGRAM
+      ",
+    );
+
+    // THEN everything that does not implement the interface is reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::interface_not_implemented("Plain", "ICounter", (951..952).into()),
+            Diagnostic::interface_not_implemented("INT", "ICounter", (971..972).into()),
+            Diagnostic::interface_not_implemented("IOther", "ICounter", (991..992).into()),
+        ]
+    );
+}
+
+#[test]
+fn call_arguments_must_match_the_declared_parameters() {
+    // GIVEN calls with arguments of the wrong type and with too many arguments
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        TYPE MyStruct : STRUCT
+            a : DINT;
+        END_STRUCT END_TYPE
+
+        FUNCTION foo : INT
+            VAR_INPUT
+                a : DINT;
+                b : STRING;
+            END_VAR
+        END_FUNCTION
+
+        FUNCTION bar : INT
+            VAR_IN_OUT
+                x : DINT;
+            END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+            VAR
+                i : INT;
+                d : DINT;
+                s : STRING;
+                st : MyStruct;
+            END_VAR
+
+            foo(i, s);          (* ok *)
+            bar(d);             (* ok *)
+            foo(st, s);
+            foo(a := st, b := s);
+            foo(i, s, i);
+            bar(i);
+        END_PROGRAM
+      ",
+    );
+
+    // THEN the wrong arguments and the first surplus argument are reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::incompatible_argument("MyStruct", "a", "DINT", (611..613).into()),
+            Diagnostic::incompatible_assignment("MyStruct", "DINT", (640..642).into()),
+            Diagnostic::too_many_arguments("foo", 2, 3, (675..676).into()),
+            Diagnostic::incompatible_argument("INT", "x", "DINT", (695..696).into()),
+        ]
+    );
+}
+
+#[test]
+fn in_out_strings_must_have_the_same_length_and_encoding() {
+    // GIVEN strings of other lengths and encodings passed to a VAR_IN_OUT string
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION foo : INT
+            VAR_IN_OUT
+                s : STRING[80];
+            END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+            VAR
+                long : STRING[80];
+                short : STRING[10];
+                wide : WSTRING[80];
+            END_VAR
+
+            foo(long);  (* ok *)
+            foo(short);
+            foo(wide);
+        END_PROGRAM
+      ",
+    );
+
+    // THEN they are reported, the callee would write beyond the shorter string
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::incompatible_argument("__prg_short", "s", "__foo_s", (338..343).into()),
+            Diagnostic::incompatible_argument("__prg_wide", "s", "__foo_s", (362..366).into()),
+        ]
+    );
+}
+
+#[test]
+fn arrays_and_pointers_must_match_their_element_types() {
+    // GIVEN assignments and arguments of arrays, pointers and addresses
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION takes_ints : INT
+            VAR_IN_OUT
+                x : ARRAY[0..1] OF INT;
+            END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+            VAR
+                ints : ARRAY[0..1] OF INT;
+                other_ints : ARRAY[1..2] OF INT;
+                long_ints : ARRAY[0..9] OF INT;
+                strings : ARRAY[0..9] OF STRING;
+                s : STRING;
+                i : INT;
+                r : REAL;
+                l : LWORD;
+                pi : REF_TO INT;
+                pr : REF_TO REAL;
+            END_VAR
+
+            ints := other_ints;     (* ok *)
+            takes_ints(other_ints); (* ok *)
+            pi := &i;               (* ok *)
+            pi := REF(i);           (* ok *)
+            l := pi;                (* ok *)
+            pi := l;                (* ok *)
+            takes_ints(strings);
+            ints := long_ints;
+            ints := strings;
+            s := ints;
+            pi := pr;
+            pi := &r;
+            i := pi;
+        END_PROGRAM
+      ",
+    );
+
+    // THEN arrays of other lengths or element types, pointers to other types and pointers
+    // assigned to integers smaller than an address are reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::incompatible_argument(
+                "__prg_strings",
+                "x",
+                "__takes_ints_x",
+                (852..859).into()
+            ),
+            Diagnostic::incompatible_assignment("__prg_long_ints", "__prg_ints", (882..891).into()),
+            Diagnostic::incompatible_assignment("__prg_strings", "__prg_ints", (913..920).into()),
+            Diagnostic::incompatible_assignment("__prg_ints", "STRING", (939..943).into()),
+            Diagnostic::incompatible_assignment("__prg_pr", "__prg_pi", (963..965).into()),
+            Diagnostic::incompatible_assignment("REAL", "__prg_pi", (985..987).into()),
+            Diagnostic::incompatible_assignment("__prg_pi", "INT", (1006..1008).into()),
+        ]
+    );
+}
+
+#[test]
+fn pointers_convert_to_integers_as_wide_as_an_address_of_the_target() {
+    // GIVEN pointers assigned to and from integers on a target with 32 bit addresses
+    // WHEN it is validated
+    let diagnostics = parse_and_validate_for_target(
+        "
+        PROGRAM prg
+            VAR
+                i : INT;
+                d : DWORD;
+                l : LWORD;
+                pi : REF_TO INT;
+            END_VAR
+
+            d := pi;        (* ok *)
+            pi := d;        (* ok *)
+            pi := ADR(i);   (* ok *)
+            l := pi;        (* ok *)
+            i := pi;
+        END_PROGRAM
+      ",
+        32,
+    );
+
+    // THEN integers of the target's address size and LWORDs (addresses of ADR) are accepted
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::incompatible_assignment(
+            "__prg_pi",
+            "INT",
+            (335..337).into()
+        )]
+    );
+}
+
+#[test]
+fn output_assignments_must_assign_outputs_to_compatible_variables() {
+    // GIVEN output assignments from inputs, to literals and to variables of the wrong type
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION_BLOCK fb
+            VAR_INPUT
+                x : INT;
+            END_VAR
+            VAR_OUTPUT
+                out : INT;
+            END_VAR
+        END_FUNCTION_BLOCK
+
+        PROGRAM prg
+            VAR
+                i : INT;
+                s : STRING;
+                inst : fb;
+            END_VAR
+
+            inst(out => i); (* ok *)
+            inst(x => i);
+            inst(out => 5);
+            inst(out => s);
+        END_PROGRAM
+      ",
+    );
+
+    // THEN every invalid output assignment is reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::invalid_output_assignment("'x' is not an output", (383..384).into()),
+            Diagnostic::invalid_output_assignment(
+                "an output can only be assigned to a variable",
+                (416..417).into()
+            ),
+            Diagnostic::incompatible_assignment("INT", "STRING", (444..445).into()),
+        ]
+    );
+}
+
+#[test]
+fn conditions_must_be_bool() {
+    // GIVEN IF, WHILE and REPEAT statements with BOOL and non-BOOL conditions
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM prg
+            VAR
+                i : INT;
+                b : BOOL;
+                s : STRING;
+            END_VAR
+
+            IF b AND i > 1 THEN i := 0; END_IF      (* ok *)
+            WHILE NOT b DO i := 0; END_WHILE        (* ok *)
+            IF i THEN i := 0; ELSIF s THEN i := 1; END_IF
+            WHILE i - i DO i := 0; END_WHILE
+            REPEAT i := 0; UNTIL s END_REPEAT
+        END_PROGRAM
+      ",
+    );
+
+    // THEN every non-BOOL condition is reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::invalid_condition("INT", (274..275).into()),
+            Diagnostic::invalid_condition("STRING", (295..296).into()),
+            Diagnostic::invalid_condition("INT", (335..340).into()),
+            Diagnostic::invalid_condition("STRING", (395..396).into()),
+        ]
+    );
+}
+
+#[test]
+fn arrays_passed_to_variable_length_arrays_must_match_their_element_type_and_dimensions() {
+    // GIVEN arrays of different sizes, element types and dimensions passed to an ARRAY[*]
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION sum : DINT
+            VAR_IN_OUT
+                values : ARRAY[*] OF DINT;
+            END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+            VAR
+                a : ARRAY[1..3] OF DINT;
+                b : ARRAY[0..9] OF DINT;
+                c : ARRAY[1..3] OF INT;
+                d : ARRAY[1..2, 1..2] OF DINT;
+            END_VAR
+
+            sum(a);             (* ok *)
+            sum(values := b);   (* ok *)
+            sum(c);
+            sum(d);
+        END_PROGRAM
+      ",
+    );
+
+    // THEN only the arrays of another element type or dimension are reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::incompatible_argument(
+                "__prg_c",
+                "values",
+                "__sum_values",
+                (461..462).into()
+            ),
+            Diagnostic::incompatible_argument(
+                "__prg_d",
+                "values",
+                "__sum_values",
+                (481..482).into()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn references_are_bound_to_variables_of_the_referenced_type() {
+    // GIVEN references bound to variables, addresses and values
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM prg
+            VAR
+                i : INT;
+                d : DINT;
+                p : REF_TO INT;
+                r : REFERENCE TO INT;
+            END_VAR
+
+            r REF= i;           (* ok *)
+            r REF= REF(i);      (* ok *)
+            r REF= p^;          (* ok *)
+            r REF= d;
+            r REF= 5;
+            i REF= i;
+            REF(5);
+            __ISVALIDREF(i);
+        END_PROGRAM
+      ",
+    );
+
+    // THEN bindings to values, to other types and of non-references are reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::incompatible_assignment("DINT", "INT", (321..322).into()),
+            Diagnostic::invalid_reference_usage(
+                "only variables can be referenced",
+                (343..344).into()
+            ),
+            Diagnostic::invalid_reference_usage("'INT' is no reference", (358..359).into()),
+            Diagnostic::invalid_reference_usage(
+                "only variables can be referenced",
+                (384..385).into()
+            ),
+            Diagnostic::invalid_reference_usage("'INT' is no reference", (413..414).into()),
+        ]
+    );
+}
+
+#[test]
+fn addresses_can_only_be_taken_of_variables() {
+    // GIVEN addresses of variables and of a literal
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM prg
+            VAR
+                i : INT;
+                a : ARRAY[0..3] OF INT;
+                p : REF_TO INT;
+            END_VAR
+
+            p := ADR(i);        (* ok *)
+            p := ADR(a[1]);     (* ok *)
+            p := ADR(5);
+            SIZEOF(a);          (* ok *)
+        END_PROGRAM
+      ",
+    );
+
+    // THEN only the address of the literal is reported
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::invalid_reference_usage(
+            "only variables can be referenced",
+            (258..259).into()
+        )]
+    );
+}
+
+#[test]
+fn temporary_references_must_be_bound_before_they_are_used() {
+    // GIVEN references of a function and temporary references of a program
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION foo : INT
+            VAR
+                x : INT;
+                a : REFERENCE TO INT;
+                b : REFERENCE TO INT;
+                c : REFERENCE TO INT;
+            END_VAR
+            VAR_TEMP
+                t : REFERENCE TO INT;
+            END_VAR
+
+            IF __ISVALIDREF(a) THEN     (* ok *)
+                foo := 1;
+            END_IF
+            a REF= x;
+            foo := a;                   (* ok *)
+            b := 2;
+            foo := b + c;
+            c REF= x;
+            t REF= t;
+        END_FUNCTION
+
+        PROGRAM prg
+            VAR
+                r : REFERENCE TO INT;
+            END_VAR
+            VAR_TEMP
+                t : REFERENCE TO INT;
+            END_VAR
+
+            r := 1;     (* ok, r may be bound by a previous call *)
+            t := 1;
+        END_PROGRAM
+      ",
+    );
+
+    // THEN the first use of every reference before its REF= binding is reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::unbound_reference("b", (460..461).into()),
+            Diagnostic::unbound_reference("c", (491..492).into()),
+            Diagnostic::unbound_reference("t", (535..536).into()),
+            Diagnostic::unbound_reference("t", (814..815).into()),
+        ]
+    );
+}
+
+#[test]
+fn references_must_be_bound_on_every_path_before_they_are_used() {
+    // GIVEN references bound in some or all branches of IFs and CASEs and in loops
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION foo : INT
+            VAR
+                x : INT;
+                c : BOOL;
+                r : REFERENCE TO INT;
+                both : REFERENCE TO INT;
+                all_cases : REFERENCE TO INT;
+                one_case : REFERENCE TO INT;
+                looped : REFERENCE TO INT;
+            END_VAR
+
+            IF c THEN
+                r REF= x;
+            END_IF
+            r := 1;
+            IF c THEN
+                both REF= x;
+            ELSE
+                both REF= x;
+            END_IF
+            both := 1;                  (* ok *)
+            CASE foo OF
+                1: all_cases REF= x;
+            ELSE
+                all_cases REF= x;
+            END_CASE
+            all_cases := 1;             (* ok *)
+            CASE foo OF
+                1: one_case REF= x;
+            END_CASE
+            one_case := 1;
+            WHILE c DO
+                looped REF= x;
+                looped := 1;            (* ok *)
+            END_WHILE
+            looped := 1;
+        END_FUNCTION
+      ",
+    );
+
+    // THEN references that are unbound on one of the paths are reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::unbound_reference("r", (408..409).into()),
+            Diagnostic::unbound_reference("one_case", (856..864).into()),
+            Diagnostic::unbound_reference("looped", (1008..1014).into()),
+        ]
+    );
+}
@@ -172,3 +172,166 @@ fn constant_fb_instances_are_illegal() {
         ]
     );
 }
+
+#[test]
+fn invalid_located_variables_are_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        VAR_GLOBAL
+            a AT %IX1.2 : BOOL;
+            b AT %QW4 : WORD;
+            c AT %IX1 : BOOL;
+            d AT %MB1.2 : BYTE;
+            e AT %IX1.8 : BOOL;
+            f AT %IW2 : DINT;
+            g AT %QX0.0 : INT;
+        END_VAR
+
+        FUNCTION_BLOCK fb
+        VAR_INPUT
+            i AT %IB0 : BYTE;
+        END_VAR
+        VAR
+            j AT %MD0 : DWORD := 1;
+        END_VAR
+        END_FUNCTION_BLOCK
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::invalid_located_variable(
+                "i",
+                "only VAR and VAR_GLOBAL variables can be located",
+                (310..311).into()
+            ),
+            Diagnostic::invalid_located_variable(
+                "j",
+                "located variables cannot be initialized",
+                (368..369).into()
+            ),
+            Diagnostic::invalid_hardware_address("%IX1", (99..103).into()),
+            Diagnostic::invalid_hardware_address("%MB1.2", (129..135).into()),
+            Diagnostic::invalid_hardware_address("%IX1.8", (161..167).into()),
+            Diagnostic::incompatible_hardware_address("%IW2", "DINT", (193..197).into()),
+            Diagnostic::incompatible_hardware_address("%QX0.0", "INT", (223..229).into()),
+        ]
+    );
+}
+
+#[test]
+fn only_elementary_types_can_be_located() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE MyByte : BYTE; END_TYPE
+
+        VAR_GLOBAL
+            a AT %IB0 : MyByte;
+            x AT %IB1 : STRING;
+            y AT %ID0 : ARRAY[0..3] OF BYTE;
+        END_VAR
+       ",
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::incompatible_hardware_address("%IB1", "STRING", (107..111).into()),
+            Diagnostic::incompatible_hardware_address("%ID0", "__global_y", (139..143).into()),
+        ]
+    );
+}
+
+#[test]
+fn invalid_retain_blocks_are_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        VAR_GLOBAL RETAIN
+            a : INT;
+        END_VAR
+
+        FUNCTION_BLOCK fb
+        VAR_INPUT RETAIN
+            b : INT;
+        END_VAR
+        VAR_IN_OUT RETAIN
+            c : INT;
+        END_VAR
+        VAR_TEMP RETAIN
+            d : INT;
+        END_VAR
+        END_FUNCTION_BLOCK
+
+        FUNCTION foo : INT
+        VAR RETAIN
+            e : INT;
+        END_VAR
+        END_FUNCTION
+       ",
+    );
+
+    // THEN VAR_IN_OUT, VAR_TEMP and the variables of functions are reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::invalid_retain_block((161..171).into()), // VAR_IN_OUT
+            Diagnostic::invalid_retain_block((224..232).into()), // VAR_TEMP
+            Diagnostic::invalid_retain_block((340..343).into()), // FUNCTION's VAR
+        ]
+    );
+}
+
+#[test]
+fn variable_length_arrays_are_only_allowed_for_var_in_out() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION foo : INT
+        VAR_IN_OUT
+            a : ARRAY[*] OF INT;
+        END_VAR
+        VAR_INPUT
+            b : ARRAY[*] OF INT;
+        END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+            c : ARRAY[*, *] OF INT;
+        END_VAR
+        END_PROGRAM
+       ",
+    );
+
+    // THEN every ARRAY[*] but the VAR_IN_OUT is reported
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::invalid_variable_length_array("b", (126..127).into()),
+            Diagnostic::invalid_variable_length_array("c", (229..230).into()),
+        ]
+    );
+}
+
+#[test]
+fn references_cannot_be_initialized() {
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM prg
+        VAR
+            a : REFERENCE TO INT;
+            b : REFERENCE TO INT := 5;
+        END_VAR
+        END_PROGRAM
+       ",
+    );
+
+    // THEN the initialized reference is reported
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::invalid_reference_usage(
+            "references cannot be initialized, bind them with REF=",
+            (79..80).into()
+        )]
+    );
+}
@@ -1,9 +1,9 @@
 use crate::{
     ast::{
-        DataType, DataTypeDeclaration, PouType, SourceRange, Variable, VariableBlock,
-        VariableBlockType,
+        DataType, DataTypeDeclaration, DirectAccessType, HardwareAddress, PouType, SourceRange,
+        Variable, VariableBlock, VariableBlockType,
     },
-    index::{const_expressions::ConstExpression, Index},
+    index::{const_expressions::ConstExpression, Index, PouIndexEntry},
     typesystem::{DataTypeInformation, StructSource},
     Diagnostic,
 };
@@ -34,11 +34,167 @@ impl VariableValidator {
                 .push(Diagnostic::invalid_constant_block(block.location.clone()))
         }
 
+        if block.retain {
+            self.validate_retain_block(block, context);
+        }
+
         for variable in &block.variables {
+            //a VAR_IN_OUT only references an instance
+            if block.variable_block_type != VariableBlockType::InOut {
+                self.validate_instance_type(variable, context);
+                self.validate_fixed_length(variable, context);
+                self.validate_reference_initializer(variable, context);
+            }
+            if let Some(address) = &variable.address {
+                self.validate_located_variable(block, variable, address, context);
+            }
             self.validate_variable(variable, context);
         }
     }
 
+    /// validates that a RETAIN block is declared in a block whose variables outlive a call
+    fn validate_retain_block(&mut self, block: &VariableBlock, context: &ValidationContext) {
+        //the variables of functions and methods only live during a call
+        let is_stateless_pou = context
+            .qualifier
+            .and_then(|it| context.index.find_pou(it))
+            .map(|it| {
+                matches!(
+                    it.get_pou_type(),
+                    PouType::Function | PouType::Method { .. }
+                )
+            })
+            .unwrap_or(false);
+        if is_stateless_pou
+            || matches!(
+                block.variable_block_type,
+                VariableBlockType::Temp | VariableBlockType::InOut
+            )
+        {
+            self.diagnostics
+                .push(Diagnostic::invalid_retain_block(block.location.clone()))
+        }
+    }
+
+    /// validates that a located variable is declared in a VAR or VAR_GLOBAL block
+    /// and that its address is valid for its datatype
+    fn validate_located_variable(
+        &mut self,
+        block: &VariableBlock,
+        variable: &Variable,
+        address: &HardwareAddress,
+        context: &ValidationContext,
+    ) {
+        if !matches!(
+            block.variable_block_type,
+            VariableBlockType::Global | VariableBlockType::Local
+        ) {
+            self.diagnostics.push(Diagnostic::invalid_located_variable(
+                variable.name.as_str(),
+                "only VAR and VAR_GLOBAL variables can be located",
+                variable.location.clone(),
+            ));
+        }
+        if variable.initializer.is_some() {
+            self.diagnostics.push(Diagnostic::invalid_located_variable(
+                variable.name.as_str(),
+                "located variables cannot be initialized",
+                variable.location.clone(),
+            ));
+        }
+
+        //bits are addressed by byte and bit, all other sizes by their index
+        let is_valid_address = match address.access {
+            DirectAccessType::Bit => address.address.len() == 2 && address.get_bit_offset() < 8,
+            _ => address.address.len() == 1,
+        };
+        if !is_valid_address {
+            self.diagnostics.push(Diagnostic::invalid_hardware_address(
+                address.to_string().as_str(),
+                address.location.clone(),
+            ));
+        }
+
+        if let Some(data_type) = variable
+            .data_type
+            .get_name()
+            .and_then(|it| context.index.find_effective_type_by_name(it))
+        {
+            //only elementary types can be located, their size must match the address
+            let size = match data_type.get_type_information() {
+                it @ DataTypeInformation::Integer { .. }
+                | it @ DataTypeInformation::Float { .. }
+                | it @ DataTypeInformation::Enum { .. } => Some(it.get_size() as u64),
+                _ => None,
+            };
+            if size != Some(address.access.get_bit_width()) {
+                self.diagnostics
+                    .push(Diagnostic::incompatible_hardware_address(
+                        address.to_string().as_str(),
+                        data_type.get_name(),
+                        address.location.clone(),
+                    ));
+            }
+        }
+    }
+
+    /// validates that the given variable does not declare an instance of an ABSTRACT class
+    fn validate_instance_type(&mut self, variable: &Variable, context: &ValidationContext) {
+        if let DataTypeDeclaration::DataTypeReference {
+            referenced_type, ..
+        } = &variable.data_type
+        {
+            if context
+                .index
+                .find_pou(referenced_type)
+                .map(PouIndexEntry::is_abstract)
+                .unwrap_or(false)
+            {
+                self.diagnostics.push(Diagnostic::abstract_instance(
+                    referenced_type,
+                    variable.location.clone(),
+                ));
+            }
+        }
+    }
+
+    /// validates that the given variable is not a variable-length array (`ARRAY[*]`), only
+    /// VAR_IN_OUT parameters can reference an array whose bounds are not known at compile time
+    fn validate_fixed_length(&mut self, variable: &Variable, context: &ValidationContext) {
+        if let Some(DataTypeInformation::VariableLengthArray { .. }) = variable
+            .data_type
+            .get_name()
+            .and_then(|it| context.index.find_effective_type_by_name(it))
+            .map(|it| it.get_type_information())
+        {
+            self.diagnostics
+                .push(Diagnostic::invalid_variable_length_array(
+                    variable.name.as_str(),
+                    variable.location.clone(),
+                ));
+        }
+    }
+
+    /// validates that the given reference (`REFERENCE TO`) has no initializer,
+    /// references are bound with `REF=`
+    fn validate_reference_initializer(&mut self, variable: &Variable, context: &ValidationContext) {
+        if let Some(DataTypeInformation::Pointer {
+            auto_deref: true, ..
+        }) = variable
+            .data_type
+            .get_name()
+            .and_then(|it| context.index.find_effective_type_by_name(it))
+            .map(|it| it.get_type_information())
+        {
+            if variable.initializer.is_some() {
+                self.diagnostics.push(Diagnostic::invalid_reference_usage(
+                    "references cannot be initialized, bind them with REF=",
+                    variable.location.clone(),
+                ));
+            }
+        }
+    }
+
     pub fn validate_variable(&mut self, variable: &Variable, context: &ValidationContext) {
         if let Some(v_entry) = context
             .qualifier
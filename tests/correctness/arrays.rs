@@ -210,3 +210,76 @@ fn cube_array_assignments2() {
         }
     }
 }
+
+#[allow(dead_code)]
+#[repr(C)]
+struct VariableLengthType {
+    small: i32,
+    large: i32,
+    weighted: i32,
+    cleared: i32,
+}
+
+#[test]
+fn arrays_of_any_size_can_be_passed_to_variable_length_arrays() {
+    let function = r"
+            FUNCTION total : DINT
+            VAR_IN_OUT
+                values : ARRAY[*] OF DINT;
+            END_VAR
+            VAR
+                i : DINT;
+            END_VAR
+            FOR i := LOWER_BOUND(values, 1) TO UPPER_BOUND(values, 1) DO
+                total := total + values[i];
+                values[i] := 0;
+            END_FOR
+            END_FUNCTION
+
+            FUNCTION weighted_total : DINT
+            VAR_IN_OUT
+                values : ARRAY[*, *] OF DINT;
+            END_VAR
+            VAR
+                i : DINT;
+                j : DINT;
+            END_VAR
+            FOR i := LOWER_BOUND(values, 1) TO UPPER_BOUND(values, 1) DO
+                FOR j := LOWER_BOUND(values, 2) TO UPPER_BOUND(values, 2) DO
+                    weighted_total := weighted_total + values[i, j] * i * j;
+                END_FOR
+            END_FOR
+            END_FUNCTION
+
+            PROGRAM main
+            VAR
+                small : DINT;
+                large : DINT;
+                weighted : DINT;
+                cleared : DINT;
+                a : ARRAY[1..3] OF DINT := [1, 2, 3];
+                b : ARRAY[-5..5] OF DINT := [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1];
+                m : ARRAY[0..1, 1..3] OF DINT := [1, 2, 3, 4, 5, 6];
+            END_VAR
+            small := total(a);
+            large := total(b);
+            weighted := weighted_total(m);
+            cleared := total(a);
+            END_PROGRAM
+            ";
+
+    let mut maintype = VariableLengthType {
+        small: 0,
+        large: 0,
+        weighted: 0,
+        cleared: 0,
+    };
+
+    compile_and_run::<_, i32>(function.to_string(), &mut maintype);
+    assert_eq!(6, maintype.small);
+    assert_eq!(11, maintype.large);
+    //only the second row has a weight: 4*1*1 + 5*1*2 + 6*1*3
+    assert_eq!(32, maintype.weighted);
+    //the first call cleared the elements of a
+    assert_eq!(0, maintype.cleared);
+}
@@ -58,3 +58,348 @@ fn class_reference_in_pou() {
     );
     assert_eq!(res, 10);
 }
+
+#[test]
+fn inherited_members_and_methods_can_be_used() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MyDerived {
+        vtable: *const u8,
+        x: i16,
+        y: i16,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        cl: MyDerived,
+        x: i16,
+    }
+
+    let source = "
+        CLASS MyBase
+            VAR
+                x : INT;
+            END_VAR
+
+            METHOD inc : INT
+                x := x + 1;
+                inc := x;
+            END_METHOD
+        END_CLASS
+
+        CLASS MyDerived EXTENDS MyBase
+            VAR
+                y : INT;
+            END_VAR
+
+            METHOD incBoth : INT
+                y := y + 10;
+                incBoth := SUPER^.inc() + y;
+            END_METHOD
+        END_CLASS
+
+        FUNCTION main : DINT
+        VAR
+          cl : MyDerived;
+          x : INT := 0;
+        END_VAR
+        cl.x := 1;
+        x := cl.inc();
+        x := x + cl.incBoth();
+        main := x + cl.x;
+        END_FUNCTION
+        "
+    .into();
+
+    let res: i32 = compile_and_run(
+        source,
+        &mut MainType {
+            cl: MyDerived {
+                vtable: std::ptr::null(),
+                x: 0,
+                y: 0,
+            },
+            x: 0,
+        },
+    );
+    assert_eq!(res, 18);
+}
+
+#[test]
+fn super_function_block_body_can_be_called() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct DerivedFb {
+        a: i16,
+        r: i16,
+        b: i16,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        fb: DerivedFb,
+    }
+
+    let source = "
+        FUNCTION_BLOCK BaseFb
+            VAR_INPUT a : INT; END_VAR
+            VAR_OUTPUT r : INT; END_VAR
+            r := a * 2;
+        END_FUNCTION_BLOCK
+
+        FUNCTION_BLOCK DerivedFb EXTENDS BaseFb
+            VAR_INPUT b : INT; END_VAR
+            SUPER^();
+            r := r + b;
+        END_FUNCTION_BLOCK
+
+        FUNCTION main : DINT
+        VAR
+          fb : DerivedFb;
+        END_VAR
+        fb(a := 3, b := 4);
+        main := fb.r;
+        END_FUNCTION
+        "
+    .into();
+
+    let res: i32 = compile_and_run(
+        source,
+        &mut MainType {
+            fb: DerivedFb { a: 0, r: 0, b: 0 },
+        },
+    );
+    assert_eq!(res, 10);
+}
+
+#[test]
+fn overridden_methods_are_dispatched_to_the_most_derived_class() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Shape {
+        vtable: *const u8,
+        size: i16,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        sq: Shape,
+        tr: Shape,
+        x: i16,
+    }
+
+    let source = "
+        CLASS ABSTRACT Shape
+            VAR size : INT; END_VAR
+
+            METHOD ABSTRACT area : INT
+            END_METHOD
+
+            METHOD describe : INT
+                describe := area() * 10 + sides();
+            END_METHOD
+
+            METHOD sides : INT
+                sides := 0;
+            END_METHOD
+        END_CLASS
+
+        CLASS Square EXTENDS Shape
+            METHOD OVERRIDE area : INT
+                area := size * size;
+            END_METHOD
+
+            METHOD OVERRIDE sides : INT
+                sides := 4;
+            END_METHOD
+        END_CLASS
+
+        CLASS Triangle EXTENDS Shape
+            METHOD OVERRIDE area : INT
+                area := size * size / 2;
+            END_METHOD
+
+            METHOD FINAL OVERRIDE sides : INT
+                sides := 3;
+            END_METHOD
+        END_CLASS
+
+        FUNCTION area_of : INT
+            VAR_IN_OUT s : Shape; END_VAR
+            area_of := s.area();
+        END_FUNCTION
+
+        FUNCTION main : DINT
+        VAR
+            sq : Square;
+            tr : Triangle;
+            x : INT;
+        END_VAR
+        sq.size := 3;
+        tr.size := 4;
+        x := area_of(sq);
+        x := x + area_of(tr);
+        x := x + tr.describe();
+        x := x + sq.describe();
+        main := x;
+        END_FUNCTION
+        "
+    .into();
+
+    let res: i32 = compile_and_run(
+        source,
+        &mut MainType {
+            sq: Shape {
+                vtable: std::ptr::null(),
+                size: 0,
+            },
+            tr: Shape {
+                vtable: std::ptr::null(),
+                size: 0,
+            },
+            x: 0,
+        },
+    );
+    //9 + 8 + (8 * 10 + 3) + (9 * 10 + 4)
+    assert_eq!(res, 194);
+}
+
+#[test]
+fn interface_methods_are_dispatched_to_the_referenced_instance() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Counter {
+        vtable: *const u8,
+        value: i16,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct CounterReference {
+        instance: *const u8,
+        vtable: *const u8,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        up: Counter,
+        down: Counter,
+        counter: CounterReference,
+        x: i16,
+    }
+
+    let source = "
+        INTERFACE ICounter
+            METHOD next : INT
+                VAR_INPUT step : INT; END_VAR
+            END_METHOD
+        END_INTERFACE
+
+        FUNCTION_BLOCK Up IMPLEMENTS ICounter
+            VAR value : INT; END_VAR
+            METHOD next : INT
+                VAR_INPUT step : INT; END_VAR
+                value := value + step;
+                next := value;
+            END_METHOD
+        END_FUNCTION_BLOCK
+
+        CLASS Down IMPLEMENTS ICounter
+            VAR value : INT; END_VAR
+            METHOD next : INT
+                VAR_INPUT step : INT; END_VAR
+                value := value - step;
+                next := value;
+            END_METHOD
+        END_CLASS
+
+        FUNCTION count : INT
+            VAR_INPUT counter : ICounter; END_VAR
+            count := counter.next(step := 2);
+        END_FUNCTION
+
+        FUNCTION main : DINT
+        VAR
+            up : Up;
+            down : Down;
+            counter : ICounter;
+            x : INT;
+        END_VAR
+        counter := up;
+        x := counter.next(3);
+        x := x + count(up);
+        x := x + count(down);
+        counter := down;
+        x := x + counter.next(1);
+        main := x * 10 + up.value;
+        END_FUNCTION
+        "
+    .into();
+
+    let res: i32 = compile_and_run(
+        source,
+        &mut MainType {
+            up: Counter {
+                vtable: std::ptr::null(),
+                value: 0,
+            },
+            down: Counter {
+                vtable: std::ptr::null(),
+                value: 0,
+            },
+            counter: CounterReference {
+                instance: std::ptr::null(),
+                vtable: std::ptr::null(),
+            },
+            x: 0,
+        },
+    );
+    assert_eq!(res, 35);
+}
+
+#[test]
+fn derived_instances_can_be_referenced_as_their_base_class() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        count: i16,
+        extra: i16,
+    }
+
+    let source = "
+        CLASS Base
+            VAR PUBLIC count : INT; END_VAR
+        END_CLASS
+
+        CLASS Derived EXTENDS Base
+            VAR PUBLIC extra : INT; END_VAR
+        END_CLASS
+
+        FUNCTION increment : INT
+            VAR_IN_OUT b : Base; END_VAR
+            b.count := b.count + 1;
+        END_FUNCTION
+
+        FUNCTION main : DINT
+        VAR
+            d : Derived;
+            r : REFERENCE TO Base;
+        END_VAR
+        d.extra := 100;
+        increment(d);
+        increment(b := d);
+        r REF= d;
+        r.count := r.count + 10;
+        main := d.count + d.extra;
+        END_FUNCTION
+        "
+    .into();
+
+    let res: i32 = compile_and_run(source, &mut MainType { count: 0, extra: 0 });
+    //the members of the derived instance are left untouched
+    assert_eq!(res, 112);
+}
@@ -0,0 +1,81 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+type CycleFunction = unsafe extern "C" fn();
+
+#[repr(C)]
+struct MainType {
+    trace: i32,
+    fast_count: i32,
+    slow_count: i32,
+}
+
+/// calls the given task's cycle function
+fn run_cycle(exec_engine: &ExecutionEngine, name: &str) {
+    unsafe {
+        let cycle: JitFunction<CycleFunction> = exec_engine.get_function(name).unwrap();
+        cycle.call();
+    }
+}
+
+#[test]
+fn cycle_functions_call_the_program_instances_of_their_task_in_order() {
+    let src = "
+        PROGRAM first
+            trace := trace * 10 + 1;
+        END_PROGRAM
+
+        PROGRAM second
+            trace := trace * 10 + 2;
+        END_PROGRAM
+
+        PROGRAM counter
+        VAR
+            count : DINT;
+        END_VAR
+            count := count + 1;
+        END_PROGRAM
+
+        PROGRAM main
+        VAR
+            trace_result : DINT;
+            fast_count : DINT;
+            slow_count : DINT;
+        END_VAR
+            trace_result := trace;
+            fast_count := fast_counter.count;
+            slow_count := slow_counter.count;
+        END_PROGRAM
+
+        CONFIGURATION config
+            VAR_GLOBAL
+                trace : DINT;
+            END_VAR
+            RESOURCE res ON PLC
+                TASK fast(INTERVAL := T#10ms, PRIORITY := 1);
+                TASK slow(INTERVAL := T#100ms, PRIORITY := 2);
+                PROGRAM second WITH fast : second;
+                PROGRAM first WITH fast : first;
+                PROGRAM fast_counter WITH fast : counter;
+                PROGRAM slow_counter WITH slow : counter;
+            END_RESOURCE
+        END_CONFIGURATION
+    ";
+
+    let context: Context = Context::create();
+    let exec_engine = compile(&context, src.to_string());
+    run_cycle(&exec_engine, "__cycle_res_fast");
+    run_cycle(&exec_engine, "__cycle_res_fast");
+    run_cycle(&exec_engine, "__cycle_res_fast");
+    run_cycle(&exec_engine, "__cycle_res_slow");
+
+    let mut main = MainType {
+        trace: 0,
+        fast_count: 0,
+        slow_count: 0,
+    };
+    run::<_, ()>(&exec_engine, "main", &mut main);
+    assert_eq!(main.trace, 212121);
+    assert_eq!(main.fast_count, 3);
+    assert_eq!(main.slow_count, 1);
+}
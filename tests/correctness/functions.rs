@@ -435,3 +435,57 @@ fn inouts_behave_like_pointers() {
     assert_eq!(8, interface.p2);
     assert_eq!(9, interface.p3);
 }
+
+#[test]
+fn temp_variables_are_initialized_on_every_call() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct FooType {
+        i: i16,
+        o: i16,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        f: FooType,
+        a: i16,
+        b: i16,
+    }
+    let function = r#"
+    FUNCTION_BLOCK foo
+    VAR_INPUT
+        i : INT;
+    END_VAR
+    VAR_OUTPUT
+        o : INT;
+    END_VAR
+    VAR_TEMP
+        t : INT := 10;
+    END_VAR
+    t := t + i;
+    o := t;
+    END_FUNCTION_BLOCK
+
+    PROGRAM main
+    VAR
+        f : foo;
+        a : INT;
+        b : INT;
+    END_VAR
+    f(1);
+    a := f.o;
+    f(2);
+    b := f.o;
+    END_PROGRAM
+    "#;
+
+    let mut interface = MainType {
+        f: FooType { i: 0, o: 0 },
+        a: 0,
+        b: 0,
+    };
+    compile_and_run::<_, i32>(function.to_string(), &mut interface);
+    assert_eq!(11, interface.a);
+    assert_eq!(12, interface.b);
+}
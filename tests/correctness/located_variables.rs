@@ -0,0 +1,65 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+#[derive(Default)]
+#[repr(C)]
+struct MainType {
+    local_word: u16,
+    bit0: bool,
+    bit3: bool,
+    in_value: u8,
+    low_byte: u8,
+    high_byte: u8,
+    memory: u16,
+}
+
+#[test]
+fn located_variables_share_their_process_image() {
+    let src = "
+        VAR_GLOBAL
+            in_byte AT %IB0 : BYTE;
+            in_bit0 AT %IX0.0 : BOOL;
+            in_bit1 AT %IX0.1 : BOOL;
+            in_bit3 AT %IX0.3 : BOOL;
+            out_word AT %QW1 : WORD;
+            out_low AT %QB2 : BYTE;
+            out_high AT %QB3 : BYTE;
+            global_word AT %MW0 : WORD;
+        END_VAR
+
+        PROGRAM main
+        VAR
+            local_word AT %MW0 : WORD;
+            bit0 : BOOL;
+            bit3 : BOOL;
+            in_value : BYTE;
+            low_byte : BYTE;
+            high_byte : BYTE;
+            memory : WORD;
+        END_VAR
+            in_byte := 2#0000_1001;
+            bit0 := in_bit0;
+            bit3 := in_bit3;
+
+            in_bit0 := FALSE;
+            in_bit1 := TRUE;
+            in_value := in_byte;
+
+            out_word := 16#1234;
+            low_byte := out_low;
+            high_byte := out_high;
+
+            global_word := 42;
+            memory := local_word;
+        END_PROGRAM
+    ";
+
+    let mut main = MainType::default();
+    compile_and_run::<_, i32>(src.to_string(), &mut main);
+    assert!(main.bit0);
+    assert!(main.bit3);
+    assert_eq!(main.in_value, 0b0000_1010);
+    assert_eq!(main.low_byte, 0x34);
+    assert_eq!(main.high_byte, 0x12);
+    assert_eq!(main.memory, 42);
+}
@@ -42,3 +42,81 @@ END_FUNCTION
 
     assert_eq!(36, res);
 }
+
+#[test]
+fn references_alias_the_variables_they_are_bound_to() {
+    let function = r"
+TYPE MyStruct: STRUCT  x: DINT; y: DINT; END_STRUCT END_TYPE
+
+FUNCTION main : DINT
+	main := foo();
+END_FUNCTION
+
+FUNCTION foo : DINT
+VAR
+				a, b : DINT;
+				s : MyStruct;
+				r : REFERENCE TO DINT;
+				rs : REFERENCE TO MyStruct;
+END_VAR
+IF __ISVALIDREF(r) THEN
+	foo := -1;
+	RETURN;
+END_IF
+r REF= a;
+r := 2;
+b := r + a;
+r REF= REF(b);
+r := r * 10;
+rs REF= s;
+rs.x := b;
+rs.y := rs.x + a;
+IF __ISVALIDREF(rs) THEN
+	foo := s.y;
+END_IF
+END_FUNCTION
+ ";
+
+    let mut maintype = MainType {};
+
+    let res: i32 = compile_and_run(function.to_string(), &mut maintype);
+
+    assert_eq!(42, res);
+}
+
+#[test]
+fn pointers_are_moved_by_whole_elements() {
+    let function = r"
+TYPE MyStruct: STRUCT  x: DINT; y: BYTE; END_STRUCT END_TYPE
+
+FUNCTION main : DINT
+	main := foo();
+END_FUNCTION
+
+FUNCTION foo : DINT
+VAR
+				buffer : ARRAY[1..4] OF DINT;
+				p : REF_TO DINT;
+				s : MyStruct;
+				i : INT;
+END_VAR
+IF ADR(buffer[2]) <> ADR(buffer) + SIZEOF(buffer[1]) THEN
+	foo := -1;
+	RETURN;
+END_IF
+p := ADR(buffer);
+FOR i := 1 TO 4 DO
+	p^ := i * 10;
+	p := p + 1;
+END_FOR
+p := p - 2;
+foo := p^ + SIZEOF(buffer) + SIZEOF(s);
+END_FUNCTION
+ ";
+
+    let mut maintype = MainType {};
+
+    let res: i32 = compile_and_run(function.to_string(), &mut maintype);
+
+    assert_eq!(54, res);
+}
@@ -0,0 +1,461 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+const MILLIS: i64 = 1_000_000;
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug)]
+struct TimersType {
+    input: bool,
+    ton_q: bool,
+    ton_et: i64,
+    tof_q: bool,
+    tof_et: i64,
+    tp_q: bool,
+    tp_et: i64,
+}
+
+#[test]
+fn timers() {
+    let prog = "
+    VAR_GLOBAL
+        ton_inst : TON;
+        tof_inst : TOF;
+        tp_inst : TP;
+    END_VAR
+
+    PROGRAM main
+    VAR
+        input : BOOL;
+        ton_q : BOOL;
+        ton_et : TIME;
+        tof_q : BOOL;
+        tof_et : TIME;
+        tp_q : BOOL;
+        tp_et : TIME;
+    END_VAR
+    ton_inst(IN := input, PT := T#100ms);
+    ton_q := ton_inst.Q;
+    ton_et := ton_inst.ET;
+    tof_inst(IN := input, PT := T#100ms);
+    tof_q := tof_inst.Q;
+    tof_et := tof_inst.ET;
+    tp_inst(IN := input, PT := T#100ms);
+    tp_q := tp_inst.Q;
+    tp_et := tp_inst.ET;
+    END_PROGRAM
+    ";
+    let context = Context::create();
+    let exec_engine = compile(&context, prog.to_string());
+    let mut main_type = TimersType {
+        input: true,
+        ..TimersType::default()
+    };
+
+    //IN rises at 0ms
+    test_clock::set_time(0);
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((false, 0), (main_type.ton_q, main_type.ton_et));
+    assert_eq!((true, 0), (main_type.tof_q, main_type.tof_et));
+    assert_eq!((true, 0), (main_type.tp_q, main_type.tp_et));
+
+    test_clock::set_time(50 * MILLIS);
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((false, 50 * MILLIS), (main_type.ton_q, main_type.ton_et));
+    assert_eq!((true, 50 * MILLIS), (main_type.tp_q, main_type.tp_et));
+
+    //PT elapsed
+    test_clock::set_time(120 * MILLIS);
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((true, 100 * MILLIS), (main_type.ton_q, main_type.ton_et));
+    assert_eq!((false, 100 * MILLIS), (main_type.tp_q, main_type.tp_et));
+
+    //IN falls at 150ms
+    main_type.input = false;
+    test_clock::set_time(150 * MILLIS);
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((false, 0), (main_type.ton_q, main_type.ton_et));
+    assert_eq!((true, 0), (main_type.tof_q, main_type.tof_et));
+    assert_eq!((false, 0), (main_type.tp_q, main_type.tp_et));
+
+    test_clock::set_time(200 * MILLIS);
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((true, 50 * MILLIS), (main_type.tof_q, main_type.tof_et));
+
+    test_clock::set_time(300 * MILLIS);
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((false, 100 * MILLIS), (main_type.tof_q, main_type.tof_et));
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug)]
+struct CountersType {
+    clk: bool,
+    down: bool,
+    reset: bool,
+    load: bool,
+    ctu_q: bool,
+    ctu_cv: i16,
+    ctd_q: bool,
+    ctd_cv: i16,
+    ctud_qu: bool,
+    ctud_qd: bool,
+    ctud_cv: i16,
+    r_trig_q: bool,
+    f_trig_q: bool,
+}
+
+#[test]
+fn counters_and_edge_detectors() {
+    let prog = "
+    VAR_GLOBAL
+        ctu_inst : CTU;
+        ctd_inst : CTD;
+        ctud_inst : CTUD;
+        r_trig_inst : R_TRIG;
+        f_trig_inst : F_TRIG;
+    END_VAR
+
+    PROGRAM main
+    VAR
+        clk : BOOL;
+        down : BOOL;
+        reset : BOOL;
+        load : BOOL;
+        ctu_q : BOOL;
+        ctu_cv : INT;
+        ctd_q : BOOL;
+        ctd_cv : INT;
+        ctud_qu : BOOL;
+        ctud_qd : BOOL;
+        ctud_cv : INT;
+        r_trig_q : BOOL;
+        f_trig_q : BOOL;
+    END_VAR
+    ctu_inst(CU := clk, R := reset, PV := 2);
+    ctu_q := ctu_inst.Q;
+    ctu_cv := ctu_inst.CV;
+    ctd_inst(CD := clk, LD := load, PV := 2);
+    ctd_q := ctd_inst.Q;
+    ctd_cv := ctd_inst.CV;
+    ctud_inst(CU := clk, CD := down, R := reset, LD := load, PV := 4);
+    ctud_qu := ctud_inst.QU;
+    ctud_qd := ctud_inst.QD;
+    ctud_cv := ctud_inst.CV;
+    r_trig_inst(CLK := clk);
+    r_trig_q := r_trig_inst.Q;
+    f_trig_inst(CLK := clk);
+    f_trig_q := f_trig_inst.Q;
+    END_PROGRAM
+    ";
+    let context = Context::create();
+    let exec_engine = compile(&context, prog.to_string());
+    let mut main_type = CountersType {
+        load: true,
+        ..CountersType::default()
+    };
+
+    //load the preset values
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((false, 0), (main_type.ctu_q, main_type.ctu_cv));
+    assert_eq!((false, 2), (main_type.ctd_q, main_type.ctd_cv));
+    assert_eq!(4, main_type.ctud_cv);
+    assert_eq!((false, false), (main_type.r_trig_q, main_type.f_trig_q));
+
+    //rising edge
+    main_type.load = false;
+    main_type.clk = true;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((false, 1), (main_type.ctu_q, main_type.ctu_cv));
+    assert_eq!((false, 1), (main_type.ctd_q, main_type.ctd_cv));
+    assert_eq!(
+        (true, false, 5),
+        (main_type.ctud_qu, main_type.ctud_qd, main_type.ctud_cv)
+    );
+    assert_eq!((true, false), (main_type.r_trig_q, main_type.f_trig_q));
+
+    //no edge, nothing is counted
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!(1, main_type.ctu_cv);
+    assert_eq!(1, main_type.ctd_cv);
+    assert_eq!(5, main_type.ctud_cv);
+    assert_eq!((false, false), (main_type.r_trig_q, main_type.f_trig_q));
+
+    //falling edge
+    main_type.clk = false;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((false, true), (main_type.r_trig_q, main_type.f_trig_q));
+
+    //second rising edge reaches the preset values
+    main_type.clk = true;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((true, 2), (main_type.ctu_q, main_type.ctu_cv));
+    assert_eq!((true, 0), (main_type.ctd_q, main_type.ctd_cv));
+
+    //count down
+    main_type.clk = false;
+    main_type.down = true;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!(5, main_type.ctud_cv);
+
+    //reset
+    main_type.down = false;
+    main_type.reset = true;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((false, 0), (main_type.ctu_q, main_type.ctu_cv));
+    assert_eq!(
+        (false, true, 0),
+        (main_type.ctud_qu, main_type.ctud_qd, main_type.ctud_cv)
+    );
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug)]
+struct BistablesType {
+    set: bool,
+    reset: bool,
+    sr_q: bool,
+    rs_q: bool,
+}
+
+#[test]
+fn bistables() {
+    let prog = "
+    VAR_GLOBAL
+        sr_inst : SR;
+        rs_inst : RS;
+    END_VAR
+
+    PROGRAM main
+    VAR
+        set : BOOL;
+        reset : BOOL;
+        sr_q : BOOL;
+        rs_q : BOOL;
+    END_VAR
+    sr_inst(S1 := set, R := reset);
+    sr_q := sr_inst.Q1;
+    rs_inst(S := set, R1 := reset);
+    rs_q := rs_inst.Q1;
+    END_PROGRAM
+    ";
+    let context = Context::create();
+    let exec_engine = compile(&context, prog.to_string());
+    let mut main_type = BistablesType::default();
+
+    //set
+    main_type.set = true;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((true, true), (main_type.sr_q, main_type.rs_q));
+
+    //the state is kept
+    main_type.set = false;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((true, true), (main_type.sr_q, main_type.rs_q));
+
+    //set and reset: SR is dominated by set, RS by reset
+    main_type.set = true;
+    main_type.reset = true;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((true, false), (main_type.sr_q, main_type.rs_q));
+
+    //reset
+    main_type.set = false;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!((false, false), (main_type.sr_q, main_type.rs_q));
+}
+
+#[test]
+fn function_blocks_declared_by_the_user_replace_standard_function_blocks() {
+    let prog = "
+    FUNCTION_BLOCK TON
+    VAR_OUTPUT
+        Q : DINT;
+    END_VAR
+        Q := 42;
+    END_FUNCTION_BLOCK
+
+    FUNCTION main : DINT
+    VAR
+        timer : TON;
+    END_VAR
+        timer();
+        main := timer.Q;
+    END_FUNCTION
+    ";
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct UserTon {
+        q: i32,
+    }
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        timer: UserTon,
+    }
+
+    let res: i32 = compile_and_run(
+        prog.to_string(),
+        &mut MainType {
+            timer: UserTon { q: 0 },
+        },
+    );
+    assert_eq!(42, res);
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug)]
+struct WideCountersType {
+    clk: bool,
+    load: bool,
+    ctd_dint_q: bool,
+    ctd_dint_cv: i32,
+    ctd_udint_q: bool,
+    ctd_udint_cv: u32,
+    ctud_udint_qu: bool,
+    ctud_udint_cv: u32,
+}
+
+#[test]
+fn counters_with_dint_and_udint_values() {
+    let prog = "
+    VAR_GLOBAL
+        ctd_dint_inst : CTD_DINT;
+        ctd_udint_inst : CTD_UDINT;
+        ctud_udint_inst : CTUD_UDINT;
+    END_VAR
+
+    PROGRAM main
+    VAR
+        clk : BOOL;
+        load : BOOL;
+        ctd_dint_q : BOOL;
+        ctd_dint_cv : DINT;
+        ctd_udint_q : BOOL;
+        ctd_udint_cv : UDINT;
+        ctud_udint_qu : BOOL;
+        ctud_udint_cv : UDINT;
+    END_VAR
+    ctd_dint_inst(CD := clk, LD := load, PV := 100000);
+    ctd_dint_q := ctd_dint_inst.Q;
+    ctd_dint_cv := ctd_dint_inst.CV;
+    ctd_udint_inst(CD := clk, LD := load, PV := 0);
+    ctd_udint_q := ctd_udint_inst.Q;
+    ctd_udint_cv := ctd_udint_inst.CV;
+    ctud_udint_inst(CU := clk, CD := FALSE, R := FALSE, LD := load, PV := 4294967295);
+    ctud_udint_qu := ctud_udint_inst.QU;
+    ctud_udint_cv := ctud_udint_inst.CV;
+    END_PROGRAM
+    ";
+    let context = Context::create();
+    let exec_engine = compile(&context, prog.to_string());
+    let mut main_type = WideCountersType {
+        load: true,
+        ..WideCountersType::default()
+    };
+
+    //load the preset values, they don't fit into an INT
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!(
+        (false, 100000),
+        (main_type.ctd_dint_q, main_type.ctd_dint_cv)
+    );
+    assert_eq!((true, 0), (main_type.ctd_udint_q, main_type.ctd_udint_cv));
+    assert_eq!(
+        (true, u32::MAX),
+        (main_type.ctud_udint_qu, main_type.ctud_udint_cv)
+    );
+
+    //rising edge, the UDINT counters stop at their limits
+    main_type.load = false;
+    main_type.clk = true;
+    run::<_, i32>(&exec_engine, "main", &mut main_type);
+    assert_eq!(
+        (false, 99999),
+        (main_type.ctd_dint_q, main_type.ctd_dint_cv)
+    );
+    assert_eq!((true, 0), (main_type.ctd_udint_q, main_type.ctd_udint_cv));
+    assert_eq!(
+        (true, u32::MAX),
+        (main_type.ctud_udint_qu, main_type.ctud_udint_cv)
+    );
+}
+
+#[test]
+fn only_instantiated_function_blocks_are_generated() {
+    let prog = "
+    PROGRAM main
+    VAR
+        timer : TP;
+    END_VAR
+    END_PROGRAM
+    ";
+    let context = Context::create();
+    let source = SourceCode {
+        path: "external_test.st".to_string(),
+        source: prog.to_string(),
+    };
+    let code_gen = compile_module(
+        &context,
+        vec![source],
+        None,
+        CompileOptions::new(&mut Diagnostician::default()),
+    )
+    .unwrap();
+
+    let is_generated = |name: &str| {
+        code_gen
+            .module
+            .get_function(name)
+            .map_or(false, |it| it.count_basic_blocks() > 0)
+    };
+    assert!(is_generated("TP"));
+    assert!(!is_generated("TON"));
+    assert!(!is_generated("CTU"));
+}
+
+#[test]
+fn function_blocks_called_through_pointers_are_generated() {
+    let prog = "
+    FUNCTION start : INT
+    VAR_IN_OUT
+        timer : TON;
+    END_VAR
+    timer(IN := TRUE, PT := T#1s);
+    END_FUNCTION
+
+    PROGRAM main
+    VAR
+        counter : REF_TO CTU;
+        off_delay : REFERENCE TO TOF;
+    END_VAR
+    END_PROGRAM
+    ";
+    let context = Context::create();
+    let source = SourceCode {
+        path: "external_test.st".to_string(),
+        source: prog.to_string(),
+    };
+    let code_gen = compile_module(
+        &context,
+        vec![source],
+        None,
+        CompileOptions::new(&mut Diagnostician::default()),
+    )
+    .unwrap();
+
+    let is_generated = |name: &str| {
+        code_gen
+            .module
+            .get_function(name)
+            .map_or(false, |it| it.count_basic_blocks() > 0)
+    };
+    assert!(is_generated("TON"));
+    assert!(is_generated("CTU"));
+    assert!(is_generated("TOF"));
+    assert!(!is_generated("TP"));
+}
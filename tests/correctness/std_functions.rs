@@ -0,0 +1,688 @@
+// Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug)]
+struct NumericType {
+    abs_int: i16,
+    abs_real: f32,
+    sqrt_real: f32,
+    sqrt_int: f32,
+    ln_lreal: f64,
+    log_lreal: f64,
+    exp_real: f32,
+    sin_lreal: f64,
+    cos_lreal: f64,
+    tan_lreal: f64,
+}
+
+#[test]
+fn numeric_functions() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        abs_int : INT;
+        abs_real : REAL;
+        sqrt_real : REAL;
+        sqrt_int : REAL;
+        ln_lreal : LREAL;
+        log_lreal : LREAL;
+        exp_real : REAL;
+        sin_lreal : LREAL;
+        cos_lreal : LREAL;
+        tan_lreal : LREAL;
+    END_VAR
+    VAR_TEMP
+        i : INT := -7;
+        r : REAL := -2.5;
+    END_VAR
+    abs_int := ABS(i);
+    abs_real := ABS(r);
+    sqrt_real := SQRT(REAL#6.25);
+    sqrt_int := SQRT(16);
+    ln_lreal := LN(LREAL#1.0);
+    log_lreal := LOG(LREAL#1000.0);
+    exp_real := EXP(REAL#0.0);
+    sin_lreal := SIN(LREAL#0.0);
+    cos_lreal := COS(LREAL#0.0);
+    tan_lreal := TAN(LREAL#0.0);
+    END_FUNCTION
+    ";
+    let mut main_type = NumericType::default();
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(7, main_type.abs_int);
+    assert_almost_eq!(2.5, main_type.abs_real, f32::EPSILON);
+    assert_almost_eq!(2.5, main_type.sqrt_real, f32::EPSILON);
+    assert_almost_eq!(4.0, main_type.sqrt_int, f32::EPSILON);
+    assert_almost_eq!(0.0, main_type.ln_lreal, f64::EPSILON);
+    assert_almost_eq!(3.0, main_type.log_lreal, f64::EPSILON);
+    assert_almost_eq!(1.0, main_type.exp_real, f32::EPSILON);
+    assert_almost_eq!(0.0, main_type.sin_lreal, f64::EPSILON);
+    assert_almost_eq!(1.0, main_type.cos_lreal, f64::EPSILON);
+    assert_almost_eq!(0.0, main_type.tan_lreal, f64::EPSILON);
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug, PartialEq)]
+struct BitShiftType {
+    shl_byte: u8,
+    shr_byte: u8,
+    rol_byte: u8,
+    ror_word: u16,
+    shl_dword: u32,
+}
+
+#[test]
+fn bit_shift_functions() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        shl_byte : BYTE;
+        shr_byte : BYTE;
+        rol_byte : BYTE;
+        ror_word : WORD;
+        shl_dword : DWORD;
+    END_VAR
+    VAR_TEMP
+        b : BYTE := 2#1000_0001;
+    END_VAR
+    shl_byte := SHL(b, 1);
+    shr_byte := SHR(b, 1);
+    rol_byte := ROL(b, 1);
+    ror_word := ROR(IN := WORD#16#0001, N := 4);
+    shl_dword := SHL(DWORD#1, 31);
+    END_FUNCTION
+    ";
+    let mut main_type = BitShiftType::default();
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(
+        BitShiftType {
+            shl_byte: 0b0000_0010,
+            shr_byte: 0b0100_0000,
+            rol_byte: 0b0000_0011,
+            ror_word: 0x1000,
+            shl_dword: 0x8000_0000,
+        },
+        main_type
+    );
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug, PartialEq)]
+struct ShiftOutOfRangeType {
+    shl_width: u8,
+    shl_wider: u8,
+    shr_width: u8,
+    shr_wider: u16,
+    rol_width: u8,
+    rol_wider: u8,
+    ror_width: u16,
+    ror_wider: u16,
+}
+
+#[test]
+fn bit_shifts_by_the_bit_width_or_more() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        shl_width : BYTE;
+        shl_wider : BYTE;
+        shr_width : BYTE;
+        shr_wider : WORD;
+        rol_width : BYTE;
+        rol_wider : BYTE;
+        ror_width : WORD;
+        ror_wider : WORD;
+    END_VAR
+    VAR_TEMP
+        b : BYTE := 2#1000_0001;
+        n : DINT := 257;
+    END_VAR
+    shl_width := SHL(b, 8);
+    shl_wider := SHL(b, n);
+    shr_width := SHR(b, 8);
+    shr_wider := SHR(WORD#16#FFFF, 17);
+    rol_width := ROL(b, 8);
+    rol_wider := ROL(b, 9);
+    ror_width := ROR(WORD#16#0001, 16);
+    ror_wider := ROR(WORD#16#0001, 20);
+    END_FUNCTION
+    ";
+    let mut main_type = ShiftOutOfRangeType::default();
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(
+        ShiftOutOfRangeType {
+            shl_width: 0,
+            shl_wider: 0,
+            shr_width: 0,
+            shr_wider: 0,
+            rol_width: 0b1000_0001,
+            rol_wider: 0b0000_0011,
+            ror_width: 0x0001,
+            ror_wider: 0x1000,
+        },
+        main_type
+    );
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug, PartialEq)]
+struct SelectionType {
+    sel_false: i32,
+    sel_true: i32,
+    mux: i32,
+    max_int: i32,
+    min_int: i64,
+    max_real: f64,
+    limit_low: i16,
+    limit_high: i16,
+    limit_in: i16,
+    move_int: i16,
+}
+
+#[test]
+fn selection_functions() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        sel_false : DINT;
+        sel_true : DINT;
+        mux : DINT;
+        max_int : DINT;
+        min_int : LINT;
+        max_real : LREAL;
+        limit_low : INT;
+        limit_high : INT;
+        limit_in : INT;
+        move_int : INT;
+    END_VAR
+    VAR_TEMP
+        i : INT := 4;
+        l : LINT := -10;
+    END_VAR
+    sel_false := SEL(FALSE, 1, 2);
+    sel_true := SEL(TRUE, 1, 2);
+    mux := MUX(2, 10, 20, 30, 40);
+    max_int := MAX(3, 9, i, -1);
+    min_int := MIN(i, l, 7);
+    max_real := MAX(i, LREAL#4.5);
+    limit_low := LIMIT(INT#5, i, INT#8);
+    limit_high := LIMIT(INT#0, i, INT#2);
+    limit_in := LIMIT(INT#0, i, INT#8);
+    move_int := MOVE(i);
+    END_FUNCTION
+    ";
+    let mut main_type = SelectionType::default();
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(
+        SelectionType {
+            sel_false: 1,
+            sel_true: 2,
+            mux: 30,
+            max_int: 9,
+            min_int: -10,
+            max_real: 4.5,
+            limit_low: 5,
+            limit_high: 2,
+            limit_in: 4,
+            move_int: 4,
+        },
+        main_type
+    );
+}
+
+#[test]
+fn functions_declared_by_the_user_replace_builtin_functions() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {}
+
+    let prog = "
+    FUNCTION ABS : DINT
+    VAR_INPUT
+        x : DINT;
+    END_VAR
+        ABS := 42;
+    END_FUNCTION
+
+    FUNCTION main : DINT
+        main := ABS(-3);
+    END_FUNCTION
+    ";
+
+    let res: i32 = compile_and_run(prog.to_string(), &mut MainType {});
+    assert_eq!(42, res);
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug, PartialEq)]
+struct ConversionType {
+    int_to_real: f32,
+    real_to_int_up: i16,
+    real_to_int_down: i16,
+    real_to_int_negative: i16,
+    real_to_sint_saturated: i8,
+    lreal_to_byte_saturated: u8,
+    real_trunc_dint: i32,
+    trunc_value: i32,
+    round_value: f64,
+    dint_to_bool: bool,
+    bool_to_int: i16,
+    dword_to_byte: u8,
+    to_lint: i64,
+}
+
+#[test]
+fn conversion_functions() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        int_to_real : REAL;
+        real_to_int_up : INT;
+        real_to_int_down : INT;
+        real_to_int_negative : INT;
+        real_to_sint_saturated : SINT;
+        lreal_to_byte_saturated : BYTE;
+        real_trunc_dint : DINT;
+        trunc_value : DINT;
+        round_value : LREAL;
+        dint_to_bool : BOOL;
+        bool_to_int : INT;
+        dword_to_byte : BYTE;
+        to_lint : LINT;
+    END_VAR
+    VAR_TEMP
+        i : INT := 7;
+        r : REAL := 2.5;
+        lr : LREAL := -1000.7;
+        d : DINT := 2;
+        b : BOOL := TRUE;
+        dw : DWORD := 16#1234_5678;
+    END_VAR
+    int_to_real := INT_TO_REAL(i);
+    real_to_int_up := REAL_TO_INT(r);
+    real_to_int_down := REAL_TO_INT(REAL#2.4);
+    real_to_int_negative := REAL_TO_INT(-r);
+    real_to_sint_saturated := REAL_TO_SINT(REAL#1000.0);
+    lreal_to_byte_saturated := LREAL_TO_BYTE(lr);
+    real_trunc_dint := REAL_TRUNC_DINT(REAL#2.9);
+    trunc_value := TRUNC(lr);
+    round_value := ROUND(lr);
+    dint_to_bool := DINT_TO_BOOL(d);
+    bool_to_int := BOOL_TO_INT(b);
+    dword_to_byte := DWORD_TO_BYTE(dw);
+    to_lint := TO_LINT(lr);
+    END_FUNCTION
+    ";
+    let mut main_type = ConversionType::default();
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(
+        ConversionType {
+            int_to_real: 7.0,
+            real_to_int_up: 3,
+            real_to_int_down: 2,
+            real_to_int_negative: -3,
+            real_to_sint_saturated: 127,
+            lreal_to_byte_saturated: 0,
+            real_trunc_dint: 2,
+            trunc_value: -1000,
+            round_value: -1001.0,
+            dint_to_bool: true,
+            bool_to_int: 1,
+            dword_to_byte: 0x78,
+            to_lint: -1001,
+        },
+        main_type
+    );
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct StringConversionType {
+    int_to_string: [u8; 81],
+    real_to_string: [u8; 81],
+    bool_to_string: [u8; 81],
+    string_to_dint: i32,
+    string_to_lreal: f64,
+    string_to_bool: bool,
+}
+
+#[test]
+fn string_conversion_functions() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        int_to_string : STRING;
+        real_to_string : STRING;
+        bool_to_string : STRING;
+        string_to_dint : DINT;
+        string_to_lreal : LREAL;
+        string_to_bool : BOOL;
+    END_VAR
+    VAR_TEMP
+        i : INT := -42;
+        s : STRING := '1234';
+    END_VAR
+    int_to_string := INT_TO_STRING(i);
+    real_to_string := REAL_TO_STRING(REAL#2.5);
+    bool_to_string := BOOL_TO_STRING(TRUE);
+    string_to_dint := STRING_TO_DINT(s);
+    string_to_lreal := STRING_TO_LREAL('0.25');
+    string_to_bool := STRING_TO_BOOL('true');
+    END_FUNCTION
+    ";
+    let mut main_type = StringConversionType {
+        int_to_string: [0; 81],
+        real_to_string: [0; 81],
+        bool_to_string: [0; 81],
+        string_to_dint: 0,
+        string_to_lreal: 0.0,
+        string_to_bool: false,
+    };
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(b"-42\0", &main_type.int_to_string[0..4]);
+    assert_eq!(b"2.5\0", &main_type.real_to_string[0..4]);
+    assert_eq!(b"TRUE\0", &main_type.bool_to_string[0..5]);
+    assert_eq!(1234, main_type.string_to_dint);
+    assert_almost_eq!(0.25, main_type.string_to_lreal, f64::EPSILON);
+    assert!(main_type.string_to_bool);
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct RealToStringType {
+    real_to_string: [u8; 81],
+    lreal_to_string: [u8; 81],
+}
+
+#[test]
+fn reals_are_converted_to_strings_with_all_significant_digits() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        real_to_string : STRING;
+        lreal_to_string : STRING;
+    END_VAR
+    real_to_string := REAL_TO_STRING(REAL#0.1);
+    lreal_to_string := LREAL_TO_STRING(LREAL#0.1);
+    END_FUNCTION
+    ";
+    let mut main_type = RealToStringType {
+        real_to_string: [0; 81],
+        lreal_to_string: [0; 81],
+    };
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(b"0.100000001\0", &main_type.real_to_string[0..12]);
+    assert_eq!(b"0.10000000000000001\0", &main_type.lreal_to_string[0..20]);
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default, Debug, PartialEq)]
+struct SaturatingConversionType {
+    dint_to_sint: i8,
+    int_to_usint: u8,
+    dint_to_udint: u32,
+    udint_to_dint: i32,
+    dint_to_byte: u8,
+    string_to_sint: i8,
+}
+
+#[test]
+fn integer_conversions_saturate_at_the_target_range() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        dint_to_sint : SINT;
+        int_to_usint : USINT;
+        dint_to_udint : UDINT;
+        udint_to_dint : DINT;
+        dint_to_byte : BYTE;
+        string_to_sint : SINT;
+    END_VAR
+    VAR_TEMP
+        d : DINT := 300;
+        i : INT := -5;
+        m : DINT := -1;
+        u : UDINT := 4000000000;
+        s : STRING := '300';
+    END_VAR
+    dint_to_sint := DINT_TO_SINT(d);
+    int_to_usint := INT_TO_USINT(i);
+    dint_to_udint := DINT_TO_UDINT(m);
+    udint_to_dint := UDINT_TO_DINT(u);
+    dint_to_byte := DINT_TO_BYTE(d);
+    string_to_sint := STRING_TO_SINT(s);
+    END_FUNCTION
+    ";
+    let mut main_type = SaturatingConversionType::default();
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(
+        SaturatingConversionType {
+            dint_to_sint: 127,
+            int_to_usint: 0,
+            dint_to_udint: 0,
+            udint_to_dint: i32::MAX,
+            dint_to_byte: 44,
+            string_to_sint: 127,
+        },
+        main_type
+    );
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct WideStringConversionType {
+    string_to_wstring: [u16; 81],
+    wstring_to_string: [u8; 81],
+    int_to_wstring: [u16; 81],
+    wstring_to_dint: i32,
+}
+
+#[test]
+fn strings_are_transcoded_between_utf8_and_utf16() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        string_to_wstring : WSTRING;
+        wstring_to_string : STRING;
+        int_to_wstring : WSTRING;
+        wstring_to_dint : DINT;
+    END_VAR
+    string_to_wstring := STRING_TO_WSTRING('Hä😀');
+    wstring_to_string := WSTRING_TO_STRING(\"Hä😀\");
+    int_to_wstring := INT_TO_WSTRING(INT#-42);
+    wstring_to_dint := WSTRING_TO_DINT(\"1234\");
+    END_FUNCTION
+    ";
+    let mut main_type = WideStringConversionType {
+        string_to_wstring: [0; 81],
+        wstring_to_string: [0; 81],
+        int_to_wstring: [0; 81],
+        wstring_to_dint: 0,
+    };
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    let expected: Vec<u16> = "Hä😀\0".encode_utf16().collect();
+    assert_eq!(&expected[..], &main_type.string_to_wstring[0..5]);
+    assert_eq!("Hä😀\0".as_bytes(), &main_type.wstring_to_string[0..8]);
+    let expected: Vec<u16> = "-42\0".encode_utf16().collect();
+    assert_eq!(&expected[..], &main_type.int_to_wstring[0..4]);
+    assert_eq!(1234, main_type.wstring_to_dint);
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct DateTimeStringConversionType {
+    string_to_time: i64,
+    string_to_negative_time: i64,
+    string_to_date: i64,
+    string_to_date_and_time: i64,
+    string_to_time_of_day: i64,
+    time_to_string: [u8; 81],
+    zero_time_to_string: [u8; 81],
+    negative_time_to_string: [u8; 81],
+    date_to_string: [u8; 81],
+    date_and_time_to_string: [u8; 81],
+    time_of_day_to_string: [u8; 81],
+}
+
+#[test]
+fn dates_and_times_are_converted_from_and_to_strings() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        string_to_time : TIME;
+        string_to_negative_time : TIME;
+        string_to_date : DATE;
+        string_to_date_and_time : DATE_AND_TIME;
+        string_to_time_of_day : TIME_OF_DAY;
+        time_to_string : STRING;
+        zero_time_to_string : STRING;
+        negative_time_to_string : STRING;
+        date_to_string : STRING;
+        date_and_time_to_string : STRING;
+        time_of_day_to_string : STRING;
+    END_VAR
+    string_to_time := STRING_TO_TIME('T#1h30m');
+    string_to_negative_time := STRING_TO_TIME('-2m3.5s');
+    string_to_date := STRING_TO_DATE('D#2021-08-31');
+    string_to_date_and_time := STRING_TO_DATE_AND_TIME('2000-02-29-12:30:15.25');
+    string_to_time_of_day := STRING_TO_TIME_OF_DAY('TOD#12:30:15.25');
+    time_to_string := TIME_TO_STRING(T#1h30m);
+    zero_time_to_string := TIME_TO_STRING(T#0s);
+    negative_time_to_string := TIME_TO_STRING(T#-1.5s);
+    date_to_string := DATE_TO_STRING(D#2021-08-31);
+    date_and_time_to_string := DATE_AND_TIME_TO_STRING(DT#2000-02-29-12:30:15.250);
+    time_of_day_to_string := TIME_OF_DAY_TO_STRING(TOD#08:05:03.007);
+    END_FUNCTION
+    ";
+    let mut main_type = DateTimeStringConversionType {
+        string_to_time: 0,
+        string_to_negative_time: 0,
+        string_to_date: 0,
+        string_to_date_and_time: 0,
+        string_to_time_of_day: 0,
+        time_to_string: [0; 81],
+        zero_time_to_string: [0; 81],
+        negative_time_to_string: [0; 81],
+        date_to_string: [0; 81],
+        date_and_time_to_string: [0; 81],
+        time_of_day_to_string: [0; 81],
+    };
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(5_400_000_000_000, main_type.string_to_time);
+    assert_eq!(-123_500_000_000, main_type.string_to_negative_time);
+    assert_eq!(1_630_368_000_000, main_type.string_to_date);
+    assert_eq!(951_827_415_250, main_type.string_to_date_and_time);
+    assert_eq!(45_015_250, main_type.string_to_time_of_day);
+    assert_eq!(b"T#1h30m\0", &main_type.time_to_string[0..8]);
+    assert_eq!(b"T#0s\0", &main_type.zero_time_to_string[0..5]);
+    assert_eq!(b"T#-1s500ms\0", &main_type.negative_time_to_string[0..11]);
+    assert_eq!(b"D#2021-08-31\0", &main_type.date_to_string[0..13]);
+    assert_eq!(
+        b"DT#2000-02-29-12:30:15.250\0",
+        &main_type.date_and_time_to_string[0..27]
+    );
+    assert_eq!(
+        b"TOD#08:05:03.007\0",
+        &main_type.time_of_day_to_string[0..17]
+    );
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct StringFunctionsType {
+    len: i16,
+    left: [u8; 81],
+    right: [u8; 81],
+    mid: [u8; 81],
+    concat: [u8; 81],
+    insert: [u8; 81],
+    delete: [u8; 81],
+    replace: [u8; 81],
+    find: i16,
+    find_missing: i16,
+    truncated: [u8; 6],
+    wide_left: [u16; 81],
+}
+
+#[test]
+fn string_functions() {
+    let prog = "
+    FUNCTION main : DINT
+    VAR
+        len : INT;
+        left : STRING;
+        right : STRING;
+        mid : STRING;
+        concat : STRING;
+        insert : STRING;
+        delete : STRING;
+        replace : STRING;
+        find : INT;
+        find_missing : INT;
+        truncated : STRING[5];
+        wide_left : WSTRING;
+    END_VAR
+    VAR_TEMP
+        s : STRING := 'Hello World';
+        w : WSTRING := \"Hello\";
+    END_VAR
+    len := LEN(s);
+    left := LEFT(s, 5);
+    right := RIGHT(s, 5);
+    mid := MID(s, 3, 2);
+    concat := CONCAT('a', 'b', 'c');
+    insert := INSERT(s, 'XY', 5);
+    delete := DELETE(s, 6, 6);
+    replace := REPLACE(s, 'there', 5, 7);
+    find := FIND(s, 'World');
+    find_missing := FIND(s, 'world');
+    truncated := CONCAT(s, s);
+    wide_left := LEFT(w, 2);
+    END_FUNCTION
+    ";
+    let mut main_type = StringFunctionsType {
+        len: 0,
+        left: [0; 81],
+        right: [0; 81],
+        mid: [0; 81],
+        concat: [0; 81],
+        insert: [0; 81],
+        delete: [0; 81],
+        replace: [0; 81],
+        find: 0,
+        find_missing: 0,
+        truncated: [0; 6],
+        wide_left: [0; 81],
+    };
+
+    compile_and_run::<_, i32>(prog.to_string(), &mut main_type);
+    assert_eq!(11, main_type.len);
+    assert_eq!(b"Hello\0", &main_type.left[0..6]);
+    assert_eq!(b"World\0", &main_type.right[0..6]);
+    assert_eq!(b"ell\0", &main_type.mid[0..4]);
+    assert_eq!(b"abc\0", &main_type.concat[0..4]);
+    assert_eq!(b"HelloXY World\0", &main_type.insert[0..14]);
+    assert_eq!(b"Hello\0", &main_type.delete[0..6]);
+    assert_eq!(b"Hello there\0", &main_type.replace[0..12]);
+    assert_eq!(7, main_type.find);
+    assert_eq!(0, main_type.find_missing);
+    assert_eq!(b"Hello\0", &main_type.truncated);
+    assert_eq!([72, 101, 0], &main_type.wide_left[0..3]);
+}
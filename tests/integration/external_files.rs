@@ -5,25 +5,80 @@ use std::{env, fs, path::PathBuf};
 use encoding_rs::Encoding;
 use rusty::{
     compile_to_bitcode, compile_to_ir, compile_to_shared_object, compile_to_shared_pic_object,
-    compile_to_static_obj, FilePath,
+    compile_to_static_obj, diagnostician::Diagnostician, CompileOptions, DebugLevel, FilePath,
+    OptimizationLevel,
 };
 
-fn compile_all(name: &str, encoding: Option<&'static Encoding>) {
+fn compile_all(
+    name: &str,
+    encoding: Option<&'static Encoding>,
+    optimization: OptimizationLevel,
+    debug_level: DebugLevel,
+) {
     let path = get_file(name);
     let mut out = env::temp_dir();
     let out_name = format!("{}.out", &name);
     out.push(out_name);
     let out = out.into_os_string().into_string().unwrap();
-    compile_to_ir(vec![FilePath { path: path.clone() }], encoding, &out).unwrap();
+    compile_to_ir(
+        vec![FilePath { path: path.clone() }],
+        encoding,
+        &out,
+        CompileOptions {
+            optimization,
+            debug_level,
+            ..CompileOptions::new(&mut Diagnostician::default())
+        },
+    )
+    .unwrap();
     fs::remove_file(&out).unwrap();
-    compile_to_bitcode(vec![FilePath { path: path.clone() }], encoding, &out).unwrap();
+    compile_to_bitcode(
+        vec![FilePath { path: path.clone() }],
+        encoding,
+        &out,
+        CompileOptions {
+            optimization,
+            debug_level,
+            ..CompileOptions::new(&mut Diagnostician::default())
+        },
+    )
+    .unwrap();
     fs::remove_file(&out).unwrap();
-    compile_to_shared_object(vec![FilePath { path: path.clone() }], encoding, &out, None).unwrap();
+    compile_to_shared_object(
+        vec![FilePath { path: path.clone() }],
+        encoding,
+        &out,
+        CompileOptions {
+            optimization,
+            debug_level,
+            ..CompileOptions::new(&mut Diagnostician::default())
+        },
+    )
+    .unwrap();
     fs::remove_file(&out).unwrap();
-    compile_to_shared_pic_object(vec![FilePath { path: path.clone() }], encoding, &out, None)
-        .unwrap();
+    compile_to_shared_pic_object(
+        vec![FilePath { path: path.clone() }],
+        encoding,
+        &out,
+        CompileOptions {
+            optimization,
+            debug_level,
+            ..CompileOptions::new(&mut Diagnostician::default())
+        },
+    )
+    .unwrap();
     fs::remove_file(&out).unwrap();
-    compile_to_static_obj(vec![FilePath { path: path }], encoding, &out, None).unwrap();
+    compile_to_static_obj(
+        vec![FilePath { path: path }],
+        encoding,
+        &out,
+        CompileOptions {
+            optimization,
+            debug_level,
+            ..CompileOptions::new(&mut Diagnostician::default())
+        },
+    )
+    .unwrap();
     fs::remove_file(&out).unwrap();
 }
 
@@ -41,11 +96,69 @@ fn get_file(name: &str) -> String {
 
 #[test]
 fn compile_external_file() {
-    compile_all("test_file.st", None);
+    compile_all(
+        "test_file.st",
+        None,
+        OptimizationLevel::Default,
+        DebugLevel::None,
+    );
+}
+
+#[test]
+fn compile_external_file_with_optimizations() {
+    for optimization in &[
+        OptimizationLevel::None,
+        OptimizationLevel::Less,
+        OptimizationLevel::Aggressive,
+        OptimizationLevel::Size,
+    ] {
+        compile_all("test_file.st", None, *optimization, DebugLevel::None);
+    }
+}
+
+#[test]
+fn compile_external_file_with_debug_information() {
+    compile_all(
+        "test_file.st",
+        None,
+        OptimizationLevel::None,
+        DebugLevel::Full,
+    );
+
+    let mut out = env::temp_dir();
+    out.push("test_file_debug.ir");
+    let out = out.into_os_string().into_string().unwrap();
+    compile_to_ir(
+        vec![FilePath {
+            path: get_file("test_file.st"),
+        }],
+        None,
+        &out,
+        CompileOptions {
+            debug_level: DebugLevel::Full,
+            ..CompileOptions::new(&mut Diagnostician::default())
+        },
+    )
+    .unwrap();
+    let ir = fs::read_to_string(&out).unwrap();
+    fs::remove_file(&out).unwrap();
+
+    assert!(ir.contains(r#"!DIFile(filename: "test_file.st""#));
+    assert!(ir.contains(r#"!DISubprogram(name: "a""#));
 }
 
 #[test]
 fn compile_external_file_with_encoding() {
-    compile_all("encoding_utf_16.st", None);
-    compile_all("encoding_win.st", Some(encoding_rs::WINDOWS_1252));
+    compile_all(
+        "encoding_utf_16.st",
+        None,
+        OptimizationLevel::Default,
+        DebugLevel::None,
+    );
+    compile_all(
+        "encoding_win.st",
+        Some(encoding_rs::WINDOWS_1252),
+        OptimizationLevel::Default,
+        DebugLevel::None,
+    );
 }
@@ -1,6 +1,7 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use inkwell::context::Context;
 use inkwell::execution_engine::{ExecutionEngine, JitFunction};
+use rusty::diagnostician::Diagnostician;
 use rusty::*;
 
 type MainFunction<T, U> = unsafe extern "C" fn(*mut T) -> U;
@@ -9,6 +10,7 @@ mod correctness {
     mod arrays;
     mod bitaccess;
     mod classes;
+    mod configurations;
     mod control_flow;
     mod custom_datatypes;
     mod datatypes;
@@ -16,7 +18,10 @@ mod correctness {
     mod functions;
     mod global_variables;
     mod initial_values;
+    mod located_variables;
     mod pointers;
+    mod std_function_blocks;
+    mod std_functions;
     mod sub_range_types;
     mod sums;
 }
@@ -55,12 +60,21 @@ pub fn compile(context: &Context, source: String) -> ExecutionEngine {
         path: "external_test.st".to_string(),
         source,
     };
-    let code_gen = compile_module(context, vec![source], None).unwrap();
+    let code_gen = compile_module(
+        context,
+        vec![source],
+        None,
+        CompileOptions::new(&mut Diagnostician::default()),
+    )
+    .unwrap();
     println!("{}", code_gen.module.print_to_string());
-    code_gen
+    let exec_engine = code_gen
         .module
         .create_jit_execution_engine(inkwell::OptimizationLevel::None)
-        .unwrap()
+        .unwrap();
+    //the standard timers read the time from the deterministic test-clock
+    test_clock::map_to(&code_gen.module, &exec_engine);
+    exec_engine
 }
 
 pub fn compile_and_run<T, U>(source: String, params: &mut T) -> U {